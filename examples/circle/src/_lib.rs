@@ -120,9 +120,10 @@ pub extern "C" fn rust_special_key_down(key: u32) -> bool {
             .unwrap()
             .key_down(None, Some(TheKeyCode::Return), &mut CTX.lock().unwrap())
     } else if key == KEY_DELETE {
+        // The delete key of Mac keyboards deletes backwards.
         APP.lock()
             .unwrap()
-            .key_down(None, Some(TheKeyCode::Delete), &mut CTX.lock().unwrap())
+            .key_down(None, Some(TheKeyCode::Backspace), &mut CTX.lock().unwrap())
     } else if key == KEY_UP {
         APP.lock()
             .unwrap()
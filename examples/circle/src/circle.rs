@@ -12,12 +12,12 @@ impl TheTrait for Circle {
 
     /// Draw a circle in the middle of the window
     fn draw(&mut self, pixels: &mut [u8], ctx: &mut TheContext) {
-        ctx.draw.rect(
-            pixels,
-            &(0, 0, ctx.width, ctx.height),
-            ctx.width,
-            &[0, 0, 0, 255],
-        );
+        // The frame is allocated at physical resolution, clear all of it
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[0, 0, 0, 255]);
+        }
+
+        // The drawing functions take logical coordinates and strides and scale them to the frame
         ctx.draw.circle(
             pixels,
             &(ctx.width / 2 - 100, ctx.height / 2 - 100, 200, 200),
@@ -0,0 +1,321 @@
+use std::sync::mpsc::Receiver;
+use theframework::prelude::*;
+
+pub struct CodeDemo {
+    editor: TheCodeEditor,
+    compiler: TheCompiler,
+    source: TheCodeSource,
+
+    debugger: TheCodeDebugger,
+    debug_sandbox: TheCodeSandbox,
+    debug_module_id: Uuid,
+    pause: Option<TheDebugPause>,
+
+    event_receiver: Option<Receiver<TheEvent>>,
+}
+
+impl CodeDemo {
+    /// Creates the sample grid, which is equivalent to:
+    ///   a = 2 + 3 * 4
+    ///   b = a - 4
+    fn sample_grid() -> TheCodeGrid {
+        use TheCodeAtom::*;
+
+        let mut grid = TheCodeGrid::new();
+        grid.name = "Sample".to_string();
+
+        grid.insert_atom(
+            (0, 0),
+            LocalSet("a".to_string(), TheValueAssignment::Assign),
+        );
+        grid.insert_atom((1, 0), Assignment(TheValueAssignment::Assign));
+        grid.insert_atom((2, 0), Value(TheValue::Int(2)));
+        grid.insert_atom((3, 0), Add);
+        grid.insert_atom((4, 0), Value(TheValue::Int(3)));
+        grid.insert_atom((5, 0), Multiply);
+        grid.insert_atom((6, 0), Value(TheValue::Int(4)));
+
+        grid.insert_atom(
+            (0, 2),
+            LocalSet("b".to_string(), TheValueAssignment::Assign),
+        );
+        grid.insert_atom((1, 2), Assignment(TheValueAssignment::Assign));
+        grid.insert_atom((2, 2), LocalGet("a".to_string()));
+        grid.insert_atom((3, 2), Subtract);
+        grid.insert_atom((4, 2), Value(TheValue::Int(4)));
+
+        grid
+    }
+
+    /// Compiles the grid of the editor and runs its bytecode in a sandbox. The debug values of the
+    /// run are shown in the editor.
+    fn compile_and_run(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
+        let mut grid = self.editor.get_codegrid(ui);
+
+        let text = match self.compiler.compile(&mut grid) {
+            Ok(mut module) => {
+                let mut sandbox = TheCodeSandbox::new();
+                sandbox.debug_mode = true;
+                print!("{}", module.chunk.disassemble());
+                module.execute_vm(&mut sandbox);
+
+                let debug_module = sandbox.get_module_debug_module(module.id);
+
+                let mut values: Vec<_> = debug_module.values.iter().collect();
+                values.sort_by_key(|(loc, _)| (loc.1, loc.0));
+                for (loc, (_, value)) in values {
+                    println!("{:?}: {}", loc, value.describe());
+                }
+
+                self.editor.set_debug_module(debug_module, ui);
+                "Compiled and executed.".to_string()
+            }
+            Err(err) => {
+                grid.add_message(
+                    err.location,
+                    TheCodeGridMessage::new(TheCodeGridMessageType::Error, err.message.clone()),
+                );
+                self.editor.set_codegrid(grid, ui);
+                format!("Error at {:?}: {}", err.location, err.message)
+            }
+        };
+
+        self.set_status(text, ui, ctx);
+    }
+
+    /// Compiles the grid of the editor and starts debugging it, pausing at the first atom.
+    fn start_debugging(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
+        let mut grid = self.editor.get_codegrid(ui);
+
+        match self.compiler.compile(&mut grid) {
+            Ok(module) => {
+                self.debug_sandbox = TheCodeSandbox::new();
+                self.debug_sandbox.debug_mode = true;
+                self.debug_module_id = module.id;
+                self.debugger.start(module, &mut self.debug_sandbox);
+                self.debug_step(TheDebugStep::StepInto, ui, ctx);
+            }
+            Err(err) => {
+                let text = format!("Error at {:?}: {}", err.location, err.message);
+                self.set_status(text, ui, ctx);
+            }
+        }
+    }
+
+    /// Continues the debugger and shows the new state.
+    fn debug_step(&mut self, step: TheDebugStep, ui: &mut TheUI, ctx: &mut TheContext) {
+        let text = match self.debugger.step(step, &mut self.debug_sandbox) {
+            TheDebugState::Paused(pause) => {
+                if let Some(tree) = ui.get_tree_layout("Debug Variables") {
+                    pause.snapshot.apply_to_tree_layout(tree);
+                }
+                let debug_module = self.debug_sandbox.get_module_debug_module(pause.module_id);
+                self.editor.set_debug_module(debug_module, ui);
+
+                let text = format!("{:?} at {:?}.", pause.reason, pause.location);
+                self.pause = Some(pause);
+                text
+            }
+            TheDebugState::Finished(_) => {
+                let debug_module = self
+                    .debug_sandbox
+                    .get_module_debug_module(self.debug_module_id);
+                self.editor.set_debug_module(debug_module, ui);
+                self.pause = None;
+                "Finished.".to_string()
+            }
+            TheDebugState::Idle | TheDebugState::Running => "Not debugging.".to_string(),
+        };
+
+        self.editor
+            .set_debug_location(self.pause.as_ref(), &self.debugger, ui);
+        self.set_status(text, ui, ctx);
+    }
+
+    /// Toggles the breakpoint at the selected cell of the editor.
+    fn toggle_breakpoint(&mut self, ui: &mut TheUI) {
+        let mut selection = None;
+        if let Some(layout) = ui.get_code_layout("Code Editor") {
+            if let Some(code_view) = layout.code_view_mut().as_code_view() {
+                selection = code_view.selection().map(|s| (code_view.codegrid().id, s));
+            }
+        }
+
+        if let Some((codegrid_id, position)) = selection {
+            self.debugger.toggle_breakpoint(codegrid_id, position);
+            self.editor
+                .set_debug_location(self.pause.as_ref(), &self.debugger, ui);
+        }
+    }
+
+    /// Converts the text of the source editor into the grid of the code editor.
+    fn apply_source(&mut self, text: &str, ui: &mut TheUI, ctx: &mut TheContext) {
+        match self.source.compile(text) {
+            Ok(mut grid) => {
+                // Keep the identity of the edited grid.
+                let current = self.editor.get_codegrid(ui);
+                grid.id = current.id;
+                grid.name = current.name;
+                self.editor.set_codegrid(grid, ui);
+            }
+            Err(err) => {
+                let text = format!(
+                    "Source error at line {}, column {}: {}",
+                    err.location.1 + 1,
+                    err.location.0 + 1,
+                    err.message
+                );
+                self.set_status(text, ui, ctx);
+            }
+        }
+    }
+
+    /// Writes the grid of the code editor into the source editor.
+    fn update_source(&mut self, grid: &TheCodeGrid, ui: &mut TheUI) {
+        if let Ok(text) = TheCodeSource::to_source(grid) {
+            if let Some(edit) = ui.get_text_area_edit("Code Source") {
+                edit.set_text(text);
+            }
+        }
+    }
+
+    fn set_status(&mut self, text: String, ui: &mut TheUI, ctx: &mut TheContext) {
+        println!("{}", text);
+        if let Some(widget) = ui.get_text("Code Grid Status") {
+            widget.set_text(text);
+        }
+        ctx.ui.relayout = true;
+    }
+}
+
+impl TheTrait for CodeDemo {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            editor: TheCodeEditor::new(),
+            compiler: TheCompiler::new(),
+            source: TheCodeSource::new(),
+
+            debugger: TheCodeDebugger::new(),
+            debug_sandbox: TheCodeSandbox::new(),
+            debug_module_id: Uuid::nil(),
+            pause: None,
+
+            event_receiver: None,
+        }
+    }
+
+    fn window_title(&self) -> String {
+        "Code".to_string()
+    }
+
+    /// Everything is drawn by TheUI, which reports its damage.
+    fn reports_damage(&self) -> bool {
+        true
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
+        let mut bundle = TheCodeBundle::new();
+        let grid = CodeDemo::sample_grid();
+        bundle.insert_grid(grid.clone());
+
+        let mut canvas = self.editor.build_canvas(ctx);
+        canvas.set_left(self.editor.set_bundle(bundle, ctx, 180, None));
+
+        // The text source of the grid, edits are applied to the grid.
+        let mut source_canvas = TheCanvas::new();
+        let mut source_edit = TheTextAreaEdit::new(TheId::named("Code Source"));
+        source_edit.limiter_mut().set_max_width(330);
+        source_edit.limiter_mut().set_max_height(i32::MAX);
+        source_edit.display_line_number(true);
+        source_canvas.set_widget(source_edit);
+
+        // The debugger controls and the variables of the paused module.
+        let mut debug_canvas = TheCanvas::new();
+
+        let mut debug_toolbar_canvas = TheCanvas::new();
+        let mut toolbar_hlayout = TheHLayout::new(TheId::named("Debug Toolbar Layout"));
+        toolbar_hlayout.set_background_color(None);
+        toolbar_hlayout.set_margin(Vec4::new(5, 2, 5, 2));
+        for (name, text) in [
+            ("Debug Breakpoint", "Break"),
+            ("Debug Start", "Debug"),
+            ("Debug Step Over", "Over"),
+            ("Debug Step Into", "Into"),
+            ("Debug Step Out", "Out"),
+            ("Debug Continue", "Run"),
+        ] {
+            let mut button = TheTraybarButton::new(TheId::named(name));
+            button.set_text(text.to_string());
+            toolbar_hlayout.add_widget(Box::new(button));
+        }
+        toolbar_hlayout.limiter_mut().set_max_height(27);
+        debug_toolbar_canvas.set_layout(toolbar_hlayout);
+        debug_toolbar_canvas.set_widget(TheTraybar::new(TheId::empty()));
+
+        let mut tree_layout = TheTreeLayout::new(TheId::named("Debug Variables"));
+        tree_layout.limiter_mut().set_max_size(Vec2::new(330, 220));
+
+        debug_canvas.set_top(debug_toolbar_canvas);
+        debug_canvas.set_layout(tree_layout);
+        debug_canvas.top_is_expanding = false;
+        source_canvas.set_bottom(debug_canvas);
+
+        canvas.set_right(source_canvas);
+
+        ui.canvas = canvas;
+
+        self.update_source(&grid, ui);
+        self.editor.set_codegrid(grid, ui);
+
+        self.event_receiver = Some(ui.add_state_listener("Main".into()));
+    }
+
+    fn update_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) -> bool {
+        let mut redraw = false;
+
+        if let Some(receiver) = &mut self.event_receiver {
+            let events: Vec<TheEvent> = receiver.try_iter().collect();
+            for event in events {
+                if self.editor.handle_event(&event, ui, ctx) {
+                    redraw = true;
+                }
+
+                match &event {
+                    TheEvent::StateChanged(id, TheWidgetState::Clicked) if id.name == "Compile" => {
+                        self.compile_and_run(ui, ctx);
+                        redraw = true;
+                    }
+                    TheEvent::StateChanged(id, TheWidgetState::Clicked)
+                        if id.name.starts_with("Debug ") =>
+                    {
+                        match id.name.as_str() {
+                            "Debug Breakpoint" => self.toggle_breakpoint(ui),
+                            "Debug Start" => self.start_debugging(ui, ctx),
+                            "Debug Step Over" => self.debug_step(TheDebugStep::StepOver, ui, ctx),
+                            "Debug Step Into" => self.debug_step(TheDebugStep::StepInto, ui, ctx),
+                            "Debug Step Out" => self.debug_step(TheDebugStep::StepOut, ui, ctx),
+                            _ => self.debug_step(TheDebugStep::Continue, ui, ctx),
+                        }
+                        redraw = true;
+                    }
+                    TheEvent::ValueChanged(id, TheValue::Text(text))
+                        if id.name == "Code Source" =>
+                    {
+                        self.apply_source(text, ui, ctx);
+                        redraw = true;
+                    }
+                    TheEvent::CodeEditorChanged(_, grid) => {
+                        self.update_source(grid, ui);
+                        redraw = true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        redraw
+    }
+}
@@ -0,0 +1,11 @@
+use theframework::*;
+
+pub mod code;
+use crate::code::CodeDemo;
+
+fn main() {
+    let demo = CodeDemo::new();
+    let app = TheApp::new();
+
+    () = app.run(Box::new(demo));
+}
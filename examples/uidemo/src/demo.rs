@@ -28,6 +28,11 @@ impl TheTrait for UIDemo {
         "UIDemo".to_string()
     }
 
+    /// Everything is drawn by TheUI, which reports its damage.
+    fn reports_damage(&self) -> bool {
+        true
+    }
+
     fn init_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
         // Menubar
         let mut top_canvas = TheCanvas::new();
@@ -3,6 +3,7 @@ pub mod thecolor;
 pub mod thecontext;
 pub mod thedim;
 pub mod thedraw2d;
+pub mod theheadlessapp;
 pub mod thenodecanvas;
 pub mod thepalette;
 pub mod thergbabuffer;
@@ -14,6 +15,9 @@ pub mod thewinitapp;
 #[cfg(feature = "ui")]
 pub mod theui;
 
+#[cfg(feature = "code")]
+pub mod thecode;
+
 #[cfg(feature = "log")]
 pub mod thelogger;
 
@@ -35,17 +39,81 @@ use rust_embed::RustEmbed;
 pub struct Embedded;
 pub use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TheKeyCode {
     Escape,
     Return,
+    /// Forward delete, removes the character after the cursor.
     Delete,
+    /// Removes the character before the cursor.
+    Backspace,
+    Insert,
     Up,
     Right,
     Down,
     Left,
+    Home,
+    End,
+    PageUp,
+    PageDown,
     Space,
     Tab,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    /// The numpad keys. Keys which produce a character (with NumLock on) or Enter are sent as the
+    /// character or Return first and then as their numpad key code.
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
+}
+
+impl TheKeyCode {
+    /// Returns true for the keys which delete text, Delete and Backspace.
+    pub fn is_delete(&self) -> bool {
+        matches!(self, TheKeyCode::Delete | TheKeyCode::Backspace)
+    }
+
+    /// Returns true for the function keys F1 to F12.
+    pub fn is_function_key(&self) -> bool {
+        matches!(
+            self,
+            TheKeyCode::F1
+                | TheKeyCode::F2
+                | TheKeyCode::F3
+                | TheKeyCode::F4
+                | TheKeyCode::F5
+                | TheKeyCode::F6
+                | TheKeyCode::F7
+                | TheKeyCode::F8
+                | TheKeyCode::F9
+                | TheKeyCode::F10
+                | TheKeyCode::F11
+                | TheKeyCode::F12
+        )
+    }
 }
 
 use ::serde::de::{self, Deserializer};
@@ -94,9 +162,11 @@ pub mod prelude {
     pub use crate::thecolor::TheColor;
     pub use crate::thecontext::TheContext;
     pub use crate::thecontext::TheCursorIcon;
+    pub use crate::thecontext::TheDamage;
     pub use crate::thedraw2d::{
         TheDraw2D, TheFontPreference, TheFontSettings, TheHorizontalAlign, TheVerticalAlign,
     };
+    pub use crate::theheadlessapp::{TheHeadlessApp, TheHeadlessEvent};
     pub use crate::thenodecanvas::{TheNode, TheNodeCanvas, TheNodeTerminal};
     pub use crate::thepalette::ThePalette;
     pub use crate::thetime::TheTime;
@@ -110,8 +180,8 @@ pub mod prelude {
     #[cfg(feature = "ui")]
     pub use crate::theui::prelude::*;
 
-    //#[cfg(feature = "code")]
-    //pub use crate::thecode::prelude::*;
+    #[cfg(feature = "code")]
+    pub use crate::thecode::prelude::*;
 
     #[cfg(feature = "log")]
     pub use crate::thelogger::setup_logger;
@@ -29,4 +29,15 @@ impl TheApp {
         #[cfg(feature = "winit_app")]
         run_winit_app(self.args, app);
     }
+
+    /// Runs the app without a window at the given size. The returned runner drives the app
+    /// and gives access to the rendered frames.
+    pub fn run_headless(
+        self,
+        app: Box<dyn crate::TheTrait>,
+        width: usize,
+        height: usize,
+    ) -> TheHeadlessApp {
+        TheHeadlessApp::with_args(width, height, self.args, app)
+    }
 }
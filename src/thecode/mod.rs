@@ -2,6 +2,8 @@
 
 pub mod thecodeatom;
 pub mod thecodebundle;
+pub mod thecodechunk;
+pub mod thecodedebugger;
 pub mod thecodeeditor;
 pub mod thecodefunction;
 pub mod thecodegrid;
@@ -10,11 +12,15 @@ pub mod thecodenode;
 pub mod thecodeobject;
 pub mod thecodepackage;
 pub mod thecodesandbox;
+pub mod thecodesource;
+pub mod thecodevm;
 pub mod thecompiler;
 
 pub mod prelude {
     pub use crate::thecode::thecodeatom::{TheCodeAtom, TheCodeAtomKind};
     pub use crate::thecode::thecodebundle::TheCodeBundle;
+    pub use crate::thecode::thecodechunk::{TheCodeChunk, TheCodeOp};
+    pub use crate::thecode::thecodedebugger::*;
     pub use crate::thecode::thecodeeditor::{TheCodeEditor, TheExternalCode};
     pub use crate::thecode::thecodefunction::TheCodeFunction;
     pub use crate::thecode::thecodegrid::{
@@ -25,5 +31,7 @@ pub mod prelude {
     pub use crate::thecode::thecodeobject::TheCodeObject;
     pub use crate::thecode::thecodepackage::TheCodePackage;
     pub use crate::thecode::thecodesandbox::*;
+    pub use crate::thecode::thecodesource::TheCodeSource;
+    pub use crate::thecode::thecodevm::{TheCodeVM, TheCodeVMStep};
     pub use crate::thecode::thecompiler::{TheCompiler, TheCompilerContext, TheCompilerError};
 }
@@ -62,7 +62,7 @@ impl TheCodeAtom {
     }
 
     pub fn to_node(&self, ctx: &mut TheCompilerContext) -> Option<TheCodeNode> {
-        match self {
+        let node = match self {
             TheCodeAtom::Comparison(op) => {
                 let call: TheCodeNodeCall =
                     |stack: &mut Vec<TheValue>,
@@ -1099,7 +1099,12 @@ impl TheCodeAtom {
                 ))
             }
             _ => None,
-        }
+        };
+
+        node.map(|mut node| {
+            node.atom = Some(self.clone());
+            node
+        })
     }
 
     pub fn to_sdf(&self, dim: TheDim, zoom: f32) -> TheSDF {
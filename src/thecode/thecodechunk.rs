@@ -0,0 +1,182 @@
+use crate::prelude::*;
+
+/// A single instruction of TheCodeVM.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TheCodeOp {
+    /// Push the constant with the given index.
+    Constant(u32),
+    /// Push the local variable with the given name index.
+    GetLocal(u32),
+    /// Pop a value and assign it to the local variable with the given name index.
+    SetLocal(u32),
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulus,
+    /// Remember the stack height before the right side of a comparison is evaluated.
+    Mark,
+    /// Pop the right side, reset the stack to the last mark, pop the left side and push the
+    /// result of the comparison as a Bool.
+    Compare(TheValueComparison),
+    /// Pop a Bool and jump to the given instruction if it is false.
+    JumpIfFalse(u32),
+    /// Mark the location of the instruction as executed (only in debug mode).
+    Executed,
+    /// Open a block which ends at the given EndBlock instruction. A block works on its own part
+    /// of the stack, the same way a sub function of a node does.
+    BeginBlock(u32),
+    /// Close the innermost block and drop its values from the stack.
+    EndBlock,
+    /// Leave the innermost block, or the chunk if no block is open.
+    Return,
+    /// Call the module referenced by the node with the given index.
+    CallModule(u32),
+    /// Call the node with the given index. Used by all atoms without a dedicated instruction.
+    Node(u32),
+}
+
+/// TheCodeChunk is the bytecode of a compiled TheCodeFunction. It is created by lowering the
+/// nodes of the function and executed by TheCodeVM.
+#[derive(Clone, Debug, Default)]
+pub struct TheCodeChunk {
+    pub code: Vec<TheCodeOp>,
+    /// The grid location of each instruction.
+    pub locations: Vec<(u16, u16)>,
+    pub constants: Vec<TheValue>,
+    pub names: Vec<String>,
+    pub nodes: Vec<TheCodeNode>,
+}
+
+impl TheCodeChunk {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lowers the nodes of the given function to bytecode.
+    pub fn from_function(function: &TheCodeFunction) -> Self {
+        let mut chunk = Self::new();
+        chunk.lower(function);
+        chunk
+    }
+
+    /// Returns true if the chunk has no instructions.
+    pub fn is_empty(&self) -> bool {
+        self.code.is_empty()
+    }
+
+    /// Adds an instruction and returns its index.
+    fn emit(&mut self, op: TheCodeOp, location: (u16, u16)) -> usize {
+        self.code.push(op);
+        self.locations.push(location);
+        self.code.len() - 1
+    }
+
+    fn add_constant(&mut self, value: &TheValue) -> u32 {
+        if let Some(index) = self.constants.iter().position(|c| c == value) {
+            index as u32
+        } else {
+            self.constants.push(value.clone());
+            self.constants.len() as u32 - 1
+        }
+    }
+
+    fn add_name(&mut self, name: &str) -> u32 {
+        if let Some(index) = self.names.iter().position(|n| n == name) {
+            index as u32
+        } else {
+            self.names.push(name.to_string());
+            self.names.len() as u32 - 1
+        }
+    }
+
+    fn add_node(&mut self, node: &TheCodeNode) -> u32 {
+        self.nodes.push(node.clone());
+        self.nodes.len() as u32 - 1
+    }
+
+    fn lower(&mut self, function: &TheCodeFunction) {
+        use TheCodeOp::*;
+
+        for node in &function.nodes {
+            let location = node.data.location;
+
+            match &node.atom {
+                Some(TheCodeAtom::Value(value)) => {
+                    let index = self.add_constant(value);
+                    self.emit(Constant(index), location);
+                }
+                Some(TheCodeAtom::LocalGet(name)) => {
+                    let index = self.add_name(name);
+                    self.emit(GetLocal(index), location);
+                }
+                Some(TheCodeAtom::LocalSet(name, TheValueAssignment::Assign)) => {
+                    let index = self.add_name(name);
+                    self.emit(SetLocal(index), location);
+                }
+                Some(TheCodeAtom::Add) => _ = self.emit(Add, location),
+                Some(TheCodeAtom::Subtract) => _ = self.emit(Subtract, location),
+                Some(TheCodeAtom::Multiply) => _ = self.emit(Multiply, location),
+                Some(TheCodeAtom::Divide) => _ = self.emit(Divide, location),
+                Some(TheCodeAtom::Modulus) => _ = self.emit(Modulus, location),
+                Some(TheCodeAtom::Comparison(op)) => {
+                    // The left side is already on the stack.
+                    self.emit(Mark, location);
+                    if let Some(right) = node.data.sub_functions.first() {
+                        self.lower(right);
+                    }
+                    self.emit(Compare(*op), location);
+                    let jump = self.emit(JumpIfFalse(0), location);
+
+                    if let Some(block) = node.data.sub_functions.get(1) {
+                        self.emit(Executed, location);
+                        let begin = self.emit(BeginBlock(0), location);
+                        self.lower(block);
+                        let end = self.emit(EndBlock, location);
+                        self.code[begin] = BeginBlock(end as u32);
+                    }
+
+                    self.code[jump] = JumpIfFalse(self.code.len() as u32);
+                }
+                Some(TheCodeAtom::Return) => _ = self.emit(Return, location),
+                Some(TheCodeAtom::ModuleCall(_, _, _, _)) => {
+                    let index = self.add_node(node);
+                    self.emit(CallModule(index), location);
+                }
+                _ => {
+                    let index = self.add_node(node);
+                    self.emit(Node(index), location);
+                }
+            }
+        }
+    }
+
+    /// Returns a human readable listing of the instructions.
+    pub fn disassemble(&self) -> String {
+        let mut text = String::new();
+
+        for (index, op) in self.code.iter().enumerate() {
+            let location = self.locations[index];
+            let operand = match op {
+                TheCodeOp::Constant(i) => format!(" {}", self.constants[*i as usize].describe()),
+                TheCodeOp::GetLocal(i) | TheCodeOp::SetLocal(i) => {
+                    format!(" {}", self.names[*i as usize])
+                }
+                TheCodeOp::Node(i) | TheCodeOp::CallModule(i) => {
+                    if let Some(atom) = &self.nodes[*i as usize].atom {
+                        format!(" {}", atom.describe())
+                    } else {
+                        String::new()
+                    }
+                }
+                _ => String::new(),
+            };
+            text += &format!(
+                "{:04} ({:>3}, {:>3}) {:?}{}\n",
+                index, location.0, location.1, op, operand
+            );
+        }
+
+        text
+    }
+}
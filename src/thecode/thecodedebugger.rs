@@ -0,0 +1,450 @@
+use crate::prelude::*;
+
+/// How the debugger continues from a pause. Steps work on grid lines (statements).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TheDebugStep {
+    /// Run until the next breakpoint.
+    Continue,
+    /// Run until the next line of the current module or of a calling module.
+    StepOver,
+    /// Run until the next line, entering called modules.
+    StepInto,
+    /// Run until the current module returned to its caller.
+    StepOut,
+}
+
+/// Why the debugger paused.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TheDebugPauseReason {
+    Breakpoint,
+    Step,
+    Pause,
+}
+
+/// The state of the debugger after it ran.
+#[derive(Clone, Debug)]
+pub enum TheDebugState {
+    /// No module is being debugged.
+    Idle,
+    /// The instruction budget is used up, the module is still running.
+    Running,
+    Paused(TheDebugPause),
+    /// The module finished with the given stack.
+    Finished(Vec<TheValue>),
+}
+
+/// The location the debugger paused at, together with the state of the sandbox.
+#[derive(Clone, Debug)]
+pub struct TheDebugPause {
+    pub reason: TheDebugPauseReason,
+    pub module_id: Uuid,
+    pub codegrid_id: Uuid,
+    /// The grid location of the next atom to be executed.
+    pub location: (u16, u16),
+    /// The amount of modules on the debugger call stack.
+    pub depth: usize,
+    pub snapshot: TheDebugSnapshot,
+}
+
+/// The locals of a single module on the call stack.
+#[derive(Clone, Debug)]
+pub struct TheDebugFrameSnapshot {
+    pub module_name: String,
+    pub module_id: Uuid,
+    pub codegrid_id: Uuid,
+    pub location: (u16, u16),
+    /// The local variables sorted by name.
+    pub locals: Vec<(String, TheValue)>,
+}
+
+/// A copy of the call stack, locals and object values at a pause point.
+#[derive(Clone, Debug, Default)]
+pub struct TheDebugSnapshot {
+    /// The frames of the call stack, the innermost frame is last.
+    pub frames: Vec<TheDebugFrameSnapshot>,
+    /// The objects of the sandbox. Aliased objects (like "self") come first under their alias.
+    pub objects: Vec<(String, TheCodeObject)>,
+}
+
+impl TheDebugSnapshot {
+    /// Creates a snapshot of the debugger frames and the sandbox objects.
+    fn new(frames: &[TheDebugFrame], sandbox: &TheCodeSandbox) -> Self {
+        let mut snapshot = Self::default();
+
+        // The debugger frames are the innermost functions on the sandbox call stack.
+        let offset = sandbox.call_stack.len().saturating_sub(frames.len());
+        for (index, frame) in frames.iter().enumerate() {
+            let mut locals: FxHashMap<String, TheValue> = FxHashMap::default();
+            if let Some(function) = sandbox.call_stack.get(offset + index) {
+                for scope in &function.local {
+                    for (name, value) in &scope.values {
+                        locals.insert(name.clone(), value.clone());
+                    }
+                }
+            }
+            let mut locals: Vec<(String, TheValue)> = locals.into_iter().collect();
+            locals.sort_by(|a, b| a.0.cmp(&b.0));
+
+            snapshot.frames.push(TheDebugFrameSnapshot {
+                module_name: frame.module.name.clone(),
+                module_id: frame.module.id,
+                codegrid_id: frame.module.codegrid_id,
+                location: frame.location(),
+                locals,
+            });
+        }
+
+        let mut aliases: Vec<(&String, &Uuid)> = sandbox.aliases.iter().collect();
+        aliases.sort();
+        for (alias, id) in aliases {
+            if let Some(object) = sandbox
+                .objects
+                .get(id)
+                .or_else(|| sandbox.items.get(id))
+                .or_else(|| sandbox.areas.get(id))
+            {
+                snapshot.objects.push((alias.clone(), object.clone()));
+            }
+        }
+
+        let mut objects: Vec<(String, TheCodeObject)> = sandbox
+            .objects
+            .values()
+            .chain(sandbox.items.values())
+            .chain(sandbox.areas.values())
+            .map(|object| {
+                let name = object
+                    .get(&"name".to_string())
+                    .map(|name| name.describe())
+                    .unwrap_or_else(|| object.id.to_string());
+                (name, object.clone())
+            })
+            .collect();
+        objects.sort_by(|a, b| a.0.cmp(&b.0));
+        snapshot.objects.extend(objects);
+
+        snapshot
+    }
+
+    /// Replaces the content of the tree layout with the call stack and the objects of the
+    /// snapshot. The innermost frame is shown first and open.
+    pub fn apply_to_tree_layout(&self, layout: &mut dyn TheTreeLayoutTrait) {
+        let root = layout.get_root();
+        root.childs.clear();
+        root.widgets.clear();
+
+        for (index, frame) in self.frames.iter().enumerate().rev() {
+            let mut node = TheTreeNode::new(TheId::named_with_id(
+                &format!(
+                    "{} ({}, {})",
+                    frame.module_name, frame.location.0, frame.location.1
+                ),
+                frame.module_id,
+            ));
+            node.set_open(index + 1 == self.frames.len());
+            for (name, value) in &frame.locals {
+                node.add_widget(Box::new(Self::tree_item(name, value)));
+            }
+            root.add_child(node);
+        }
+
+        let mut objects_node = TheTreeNode::new(TheId::named("Objects"));
+        for (name, object) in &self.objects {
+            let mut node = TheTreeNode::new(TheId::named(name));
+            let mut values: Vec<(&String, &TheValue)> = object.values.iter().collect();
+            values.sort_by(|a, b| a.0.cmp(b.0));
+            for (name, value) in values {
+                node.add_widget(Box::new(Self::tree_item(name, value)));
+            }
+            objects_node.add_child(node);
+        }
+        root.add_child(objects_node);
+    }
+
+    fn tree_item(name: &str, value: &TheValue) -> TheTreeItem {
+        let mut item = TheTreeItem::new(TheId::named(name));
+        item.set_text(name.to_string());
+        item.add_value_column(150, TheValue::Text(value.describe()));
+        item
+    }
+}
+
+/// A module executing in the debugger.
+#[derive(Clone, Debug)]
+struct TheDebugFrame {
+    module: TheCodeModule,
+    vm: TheCodeVM,
+    /// The location of the last executed instruction.
+    last_location: Option<(u16, u16)>,
+    /// The locations already executed on the current line, so that a breakpoint only pauses once
+    /// per line, even if the instructions of an atom are not consecutive.
+    visited: FxHashSet<(u16, u16)>,
+}
+
+impl TheDebugFrame {
+    /// The location of the next instruction.
+    fn location(&self) -> (u16, u16) {
+        self.module
+            .chunk
+            .locations
+            .get(self.vm.ip())
+            .copied()
+            .or(self.last_location)
+            .unwrap_or((0, 0))
+    }
+}
+
+/// TheCodeDebugger executes a module on TheCodeVM instruction by instruction. It supports
+/// breakpoints on grid positions, stepping over, into and out of module calls and pausing.
+///
+/// A breakpoint pauses before the atom at its position is executed, positions of atoms which do
+/// not execute (like assignment operators) never pause.
+#[derive(Clone, Debug, Default)]
+pub struct TheCodeDebugger {
+    /// The breakpoints as (codegrid id, position).
+    breakpoints: FxHashSet<(Uuid, (u16, u16))>,
+    frames: Vec<TheDebugFrame>,
+    pause_requested: bool,
+    /// True if the debugger is paused, the instruction at the pause point executes on resume.
+    paused: bool,
+    /// The maximum amount of instructions per call to resume() or step().
+    budget: Option<usize>,
+}
+
+impl TheCodeDebugger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a breakpoint at the given position of the codegrid.
+    pub fn set_breakpoint(&mut self, codegrid_id: Uuid, position: (u16, u16)) {
+        self.breakpoints.insert((codegrid_id, position));
+    }
+
+    /// Removes the breakpoint at the given position of the codegrid.
+    pub fn remove_breakpoint(&mut self, codegrid_id: Uuid, position: (u16, u16)) {
+        self.breakpoints.remove(&(codegrid_id, position));
+    }
+
+    /// Toggles the breakpoint at the given position, returns true if the breakpoint is now set.
+    pub fn toggle_breakpoint(&mut self, codegrid_id: Uuid, position: (u16, u16)) -> bool {
+        if self.breakpoints.remove(&(codegrid_id, position)) {
+            false
+        } else {
+            self.breakpoints.insert((codegrid_id, position));
+            true
+        }
+    }
+
+    /// Returns true if a breakpoint is set at the given position of the codegrid.
+    pub fn has_breakpoint(&self, codegrid_id: Uuid, position: (u16, u16)) -> bool {
+        self.breakpoints.contains(&(codegrid_id, position))
+    }
+
+    /// Returns the breakpoint positions of the given codegrid.
+    pub fn breakpoints(&self, codegrid_id: Uuid) -> Vec<(u16, u16)> {
+        let mut positions: Vec<(u16, u16)> = self
+            .breakpoints
+            .iter()
+            .filter(|(id, _)| *id == codegrid_id)
+            .map(|(_, position)| *position)
+            .collect();
+        positions.sort_by_key(|p| (p.1, p.0));
+        positions
+    }
+
+    /// Removes all breakpoints.
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Limits the amount of instructions executed per call to resume() or step(). When the
+    /// budget is used up the debugger returns TheDebugState::Running, which allows an app to
+    /// run the module over several frames and to pause it from the UI.
+    pub fn set_instruction_budget(&mut self, budget: Option<usize>) {
+        self.budget = budget;
+    }
+
+    /// Returns true if a module is being debugged.
+    pub fn is_active(&self) -> bool {
+        !self.frames.is_empty()
+    }
+
+    /// The current depth of the debugger call stack.
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Starts debugging the module. Call resume() or step() to execute it.
+    pub fn start(&mut self, module: TheCodeModule, sandbox: &mut TheCodeSandbox) {
+        self.stop(sandbox);
+        self.pause_requested = false;
+        self.paused = false;
+        self.enter(module, sandbox);
+    }
+
+    /// Aborts the debugged module.
+    pub fn stop(&mut self, sandbox: &mut TheCodeSandbox) {
+        self.paused = false;
+        while !self.frames.is_empty() {
+            self.frames.pop();
+            sandbox.call_stack.pop();
+            sandbox.pop_current_module();
+        }
+    }
+
+    /// Requests a pause, the debugger pauses before the next instruction it executes.
+    pub fn pause(&mut self) {
+        self.pause_requested = true;
+    }
+
+    /// Runs until a breakpoint is hit or the module finished.
+    pub fn resume(&mut self, sandbox: &mut TheCodeSandbox) -> TheDebugState {
+        self.step(TheDebugStep::Continue, sandbox)
+    }
+
+    /// Runs until the given step is completed, a breakpoint is hit or the module finished.
+    pub fn step(&mut self, step: TheDebugStep, sandbox: &mut TheCodeSandbox) -> TheDebugState {
+        let Some(frame) = self.frames.last() else {
+            return TheDebugState::Idle;
+        };
+
+        let start_depth = self.frames.len();
+        let start_line = frame.last_location.map(|_| frame.location().1);
+
+        let mut executed = 0;
+        // Never pause on the instruction we resume from.
+        let mut skip = std::mem::take(&mut self.paused);
+
+        loop {
+            let depth = self.frames.len();
+            let Some(frame) = self.frames.last_mut() else {
+                return TheDebugState::Idle;
+            };
+
+            if frame.vm.ip() >= frame.module.chunk.code.len() {
+                let rc = self.leave(sandbox);
+                if self.frames.is_empty() {
+                    return TheDebugState::Finished(rc);
+                }
+                continue;
+            }
+
+            let location = frame.location();
+            let entering = !frame.visited.contains(&location);
+
+            if !skip && entering {
+                let codegrid_id = frame.module.codegrid_id;
+
+                let step_done = match step {
+                    TheDebugStep::Continue => false,
+                    TheDebugStep::StepInto => {
+                        depth != start_depth || start_line != Some(location.1)
+                    }
+                    TheDebugStep::StepOver => {
+                        depth < start_depth
+                            || (depth == start_depth && start_line != Some(location.1))
+                    }
+                    TheDebugStep::StepOut => depth < start_depth,
+                };
+
+                let reason = if self.pause_requested {
+                    Some(TheDebugPauseReason::Pause)
+                } else if self.breakpoints.contains(&(codegrid_id, location)) {
+                    Some(TheDebugPauseReason::Breakpoint)
+                } else if step_done {
+                    Some(TheDebugPauseReason::Step)
+                } else {
+                    None
+                };
+
+                if let Some(reason) = reason {
+                    self.pause_requested = false;
+                    self.paused = true;
+                    return TheDebugState::Paused(self.pause_info(reason, sandbox));
+                }
+            }
+
+            if let Some(budget) = self.budget {
+                if executed >= budget {
+                    return TheDebugState::Running;
+                }
+            }
+
+            skip = false;
+            executed += 1;
+
+            let Some(frame) = self.frames.last_mut() else {
+                return TheDebugState::Idle;
+            };
+            if frame.last_location.map(|l| l.1) != Some(location.1) {
+                frame.visited.clear();
+            }
+            frame.visited.insert(location);
+            frame.last_location = Some(location);
+            match frame.vm.step(&mut frame.module.chunk, sandbox) {
+                TheCodeVMStep::Continue | TheCodeVMStep::Finished => {}
+                TheCodeVMStep::Call(module) => {
+                    self.enter(*module, sandbox);
+                }
+            }
+        }
+    }
+
+    /// Returns the pause information for the current location.
+    fn pause_info(&self, reason: TheDebugPauseReason, sandbox: &TheCodeSandbox) -> TheDebugPause {
+        let frame = &self.frames[self.frames.len() - 1];
+        TheDebugPause {
+            reason,
+            module_id: frame.module.id,
+            codegrid_id: frame.module.codegrid_id,
+            location: frame.location(),
+            depth: self.frames.len(),
+            snapshot: TheDebugSnapshot::new(&self.frames, sandbox),
+        }
+    }
+
+    /// Returns a snapshot of the current state, None if no module is being debugged.
+    pub fn snapshot(&self, sandbox: &TheCodeSandbox) -> Option<TheDebugSnapshot> {
+        if self.frames.is_empty() {
+            None
+        } else {
+            Some(TheDebugSnapshot::new(&self.frames, sandbox))
+        }
+    }
+
+    /// Pushes a module on the debugger and the sandbox call stacks.
+    fn enter(&mut self, mut module: TheCodeModule, sandbox: &mut TheCodeSandbox) {
+        if module.chunk.is_empty() {
+            module.chunk = TheCodeChunk::from_function(&module.function);
+        }
+
+        let mut function = TheCodeFunction::named(module.function.name.clone());
+        function.arguments.clone_from(&module.function.arguments);
+
+        sandbox.push_current_module(module.id, module.codegrid_id);
+        sandbox.call_stack.push(function);
+
+        self.frames.push(TheDebugFrame {
+            module,
+            vm: TheCodeVM::new(),
+            last_location: None,
+            visited: FxHashSet::default(),
+        });
+    }
+
+    /// Pops the finished module and hands its result to the calling module.
+    fn leave(&mut self, sandbox: &mut TheCodeSandbox) -> Vec<TheValue> {
+        let mut rc = vec![];
+        if let Some(mut frame) = self.frames.pop() {
+            rc = frame.vm.take_stack();
+            sandbox.call_stack.pop();
+            sandbox.pop_current_module();
+
+            if let Some(parent) = self.frames.last_mut() {
+                parent.vm.call_returned(&parent.module.chunk, &rc, sandbox);
+            }
+        }
+        rc
+    }
+}
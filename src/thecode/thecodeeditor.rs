@@ -24,6 +24,22 @@ impl TheExternalCode {
             returns,
         }
     }
+
+    /// Returns the name of the external function.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Returns the atom which calls the external function.
+    pub fn to_atom(&self) -> TheCodeAtom {
+        TheCodeAtom::ExternalCall(
+            self.name.clone(),
+            self.description.clone(),
+            self.arg_names.clone(),
+            self.arg_values.clone(),
+            self.returns.clone(),
+        )
+    }
 }
 
 pub struct TheCodeEditor {
@@ -153,17 +169,14 @@ impl TheCodeEditor {
                     self.get_code_list_items(*index, code_list, ctx);
                 }
             }
-            TheEvent::DragStarted(id, text, offset) => {
-                if id.name == "Code Editor Code List Item" {
-                    if let Some(atom) = Some(self.create_atom(text.as_str(), id.uuid)) {
-                        let mut drop = TheDrop::new(TheId::named("Code Editor Atom"));
-                        drop.set_data(atom.to_json());
-                        drop.set_title(text.clone());
-                        drop.set_offset(*offset);
-                        ui.style.create_drop_image(&mut drop, ctx);
-                        ctx.ui.set_drop(drop);
-                    }
-                }
+            TheEvent::DragStarted(id, text, offset) if id.name == "Code Editor Code List Item" => {
+                let atom = self.create_atom(text.as_str(), id.uuid);
+                let mut drop = TheDrop::new(TheId::named("Code Editor Atom"));
+                drop.set_data(atom.to_json());
+                drop.set_title(text.clone());
+                drop.set_offset(*offset);
+                ui.style.create_drop_image(&mut drop, ctx);
+                ctx.ui.set_drop(drop);
             }
             // TheEvent::CodeEditorDelete(_id) => {
             //     if let Some(selection) = self.grid_selection {
@@ -632,6 +645,27 @@ impl TheCodeEditor {
         }
     }
 
+    /// Shows the breakpoints of the debugger and the given pause location if it belongs to the
+    /// current codegrid.
+    pub fn set_debug_location(
+        &mut self,
+        pause: Option<&TheDebugPause>,
+        debugger: &TheCodeDebugger,
+        ui: &mut TheUI,
+    ) {
+        if let Some(layout) = ui.get_code_layout("Code Editor") {
+            if let Some(code_view) = layout.code_view_mut().as_code_view() {
+                let codegrid_id = code_view.codegrid().id;
+                code_view.set_breakpoints(debugger.breakpoints(codegrid_id));
+                code_view.set_debug_location(
+                    pause
+                        .filter(|pause| pause.codegrid_id == codegrid_id)
+                        .map(|pause| pause.location),
+                );
+            }
+        }
+    }
+
     /// Sets the UI of the currently selected atom into the top toolbar.
     pub fn set_grid_selection_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
         if let Some(atom) = self.get_selected_atom(ui) {
@@ -1177,7 +1211,7 @@ impl TheCodeEditor {
 
     /// Insert a selected context menu item.
     pub fn insert_context_menu_id(&mut self, id: TheId, ui: &mut TheUI, ctx: &mut TheContext) {
-        if let Some(last) = id.name.split(' ').last() {
+        if let Some(last) = id.name.split(' ').next_back() {
             let atom = self.create_atom(last, id.uuid);
 
             if atom != TheCodeAtom::EndOfCode {
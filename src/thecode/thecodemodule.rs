@@ -8,6 +8,8 @@ pub struct TheCodeModule {
     /// The id of the codegrid that was used to compile this module.
     pub codegrid_id: Uuid,
     pub function: TheCodeFunction,
+    /// The bytecode of the function, executed by TheCodeVM.
+    pub chunk: TheCodeChunk,
 }
 
 impl Default for TheCodeModule {
@@ -23,11 +25,13 @@ impl TheCodeModule {
             id: Uuid::new_v4(),
             codegrid_id: Uuid::nil(),
             function: TheCodeFunction::default(),
+            chunk: TheCodeChunk::default(),
         }
     }
 
-    /// Insert a function into the module.
+    /// Insert a function into the module and lower it to bytecode.
     pub fn set_function(&mut self, function: TheCodeFunction) {
+        self.chunk = TheCodeChunk::from_function(&function);
         self.function = function;
     }
 
@@ -54,4 +58,25 @@ impl TheCodeModule {
 
         rc
     }
+
+    /// Execute the bytecode of the module in TheCodeVM. Falls back to the nodes of the function
+    /// if the module has no bytecode.
+    pub fn execute_vm(&mut self, sandbox: &mut TheCodeSandbox) -> Vec<TheValue> {
+        if self.chunk.is_empty() && !self.function.is_empty() {
+            return self.execute(sandbox);
+        }
+
+        // The VM only needs the locals of the function.
+        let mut frame = TheCodeFunction::named(self.function.name.clone());
+        frame.arguments.clone_from(&self.function.arguments);
+
+        sandbox.push_current_module(self.id, self.codegrid_id);
+        sandbox.call_stack.push(frame);
+
+        let rc = TheCodeVM::new().run(&mut self.chunk, sandbox);
+        sandbox.call_stack.pop();
+        sandbox.pop_current_module();
+
+        rc
+    }
 }
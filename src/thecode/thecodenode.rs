@@ -57,10 +57,16 @@ impl TheCodeNodeData {
 pub struct TheCodeNode {
     pub call: TheCodeNodeCall,
     pub data: TheCodeNodeData,
+    /// The atom this node was compiled from, used when lowering the node to bytecode.
+    pub atom: Option<TheCodeAtom>,
 }
 
 impl TheCodeNode {
     pub fn new(call: TheCodeNodeCall, data: TheCodeNodeData) -> Self {
-        Self { call, data }
+        Self {
+            call,
+            data,
+            atom: None,
+        }
     }
 }
@@ -0,0 +1,648 @@
+use crate::prelude::*;
+
+/// TheCodeSource converts between a textual source syntax and TheCodeGrid. The resulting grid is
+/// compiled by TheCompiler like any grid created in the code editor.
+///
+/// Every text line becomes a grid line (text line `n` is grid line `n * 2`), an indentation of
+/// four spaces (or one tab) is one block level. The syntax is:
+///
+/// ```text
+/// # Comments start with a hash.
+/// a = 2 + 3 * 4            # Local variables.
+/// self.health -= 1         # Object variables (ObjectGet / ObjectSet).
+/// @self.stats.speed = 2.5  # Object paths (Get / Set), a leading $ is a local path.
+/// a > 10:                  # A comparison opens a block.
+///     b = rint(1, 6)
+/// == 14:                   # Compares the same left side again.
+///     b = float2(0.5, 1.0)
+/// move(1, "north")         # External calls.
+/// call("Bundle", "Module") # Module calls.
+/// return b
+/// ```
+///
+/// Errors are reported as (column, line) of the source text.
+#[derive(Clone, Debug, Default)]
+pub struct TheCodeSource {
+    externals: FxHashMap<String, TheCodeAtom>,
+    modules: FxHashMap<(String, String), TheCodeAtom>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TheSourceToken {
+    Int(i32),
+    Float(f32),
+    Text(String),
+    Ident(String),
+    Symbol(&'static str),
+}
+
+use TheSourceToken as Token;
+
+const SYMBOLS: [&str; 21] = [
+    "+=", "-=", "*=", "/=", "%=", "==", "!=", "<=", ">=", "+", "-", "*", "/", "%", "=", "<", ">",
+    "(", ")", ",", ":",
+];
+
+impl TheCodeSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an external function which can be called from the source.
+    pub fn add_external(&mut self, external: TheExternalCode) {
+        self.externals
+            .insert(external.name().clone(), external.to_atom());
+    }
+
+    /// Add a module which can be called from the source.
+    pub fn insert_module(&mut self, bundle_name: String, bundle_id: Uuid, module: &TheCodeModule) {
+        self.modules.insert(
+            (bundle_name.clone(), module.name.clone()),
+            TheCodeAtom::ModuleCall(
+                bundle_name,
+                bundle_id,
+                module.name.clone(),
+                module.codegrid_id,
+            ),
+        );
+    }
+
+    /// Adds all modules of the given packages.
+    pub fn set_packages(&mut self, packages: &FxHashMap<Uuid, TheCodePackage>) {
+        self.modules.clear();
+        for p in packages.values() {
+            for m in p.modules.values() {
+                self.insert_module(p.name.clone(), p.id, m);
+            }
+        }
+    }
+
+    /// Converts the source text into a TheCodeGrid.
+    pub fn compile(&self, source: &str) -> Result<TheCodeGrid, TheCompilerError> {
+        let mut grid = TheCodeGrid::new();
+
+        for (line, text) in source.lines().enumerate() {
+            let (depth, tokens) = tokenize(text, line)?;
+            if tokens.is_empty() {
+                continue;
+            }
+
+            let mut parser = TheSourceLine {
+                source: self,
+                tokens,
+                pos: 0,
+                line,
+                end: text.len(),
+            };
+            parser.statement(&mut grid, depth as u16 * 2, line as u16 * 2)?;
+        }
+
+        Ok(grid)
+    }
+
+    /// Converts the grid into source text.
+    pub fn to_source(grid: &TheCodeGrid) -> Result<String, TheCompilerError> {
+        let mut lines: Vec<String> = vec![];
+
+        if let Some((_, max_y)) = grid.max_xy() {
+            for y in (0..=max_y).step_by(2) {
+                let mut cells: Vec<(u16, &TheCodeAtom)> = grid
+                    .code
+                    .iter()
+                    .filter(|((_, cy), _)| *cy == y)
+                    .map(|((x, _), atom)| (*x, atom))
+                    .collect();
+                cells.sort_by_key(|(x, _)| *x);
+
+                let Some((x0, first)) = cells.first() else {
+                    lines.push(String::new());
+                    continue;
+                };
+
+                let indent = "    ".repeat(*x0 as usize / 2);
+                let text = match first {
+                    TheCodeAtom::ExternalCall(name, _, _, _, _) => {
+                        let mut args = vec![];
+                        for (x, atom) in cells.iter().skip(1) {
+                            args.push(atom_to_source(atom, (*x, y))?);
+                        }
+                        format!("{}({})", name, args.join(", "))
+                    }
+                    TheCodeAtom::ModuleCall(bundle, _, module, _) => {
+                        format!("call({}, {})", quote(bundle), quote(module))
+                    }
+                    _ => {
+                        let mut tokens = vec![];
+                        let mut comparison = false;
+                        for (x, atom) in &cells {
+                            if let TheCodeAtom::Comparison(_) = atom {
+                                comparison = true;
+                            }
+                            tokens.push(atom_to_source(atom, (*x, y))?);
+                        }
+                        let mut text = tokens.join(" ");
+                        if comparison {
+                            text.push(':');
+                        }
+                        text
+                    }
+                };
+
+                lines.push(format!("{}{}", indent, text));
+            }
+        }
+
+        let mut source = lines.join("\n");
+        source.push('\n');
+        Ok(source)
+    }
+}
+
+/// The parser state for a single line of source text.
+struct TheSourceLine<'a> {
+    source: &'a TheCodeSource,
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    line: usize,
+    end: usize,
+}
+
+impl TheSourceLine<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|(t, _)| t)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(t, _)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    /// The column of the current token.
+    fn column(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map(|(_, c)| *c)
+            .unwrap_or(self.end)
+    }
+
+    fn error(&self, message: &str) -> TheCompilerError {
+        TheCompilerError::new(
+            (self.column() as u16, self.line as u16),
+            message.to_string(),
+        )
+    }
+
+    fn expect(&mut self, symbol: &'static str) -> Result<(), TheCompilerError> {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected '{}'.", symbol)))
+        }
+    }
+
+    fn expect_end(&mut self) -> Result<(), TheCompilerError> {
+        if self.pos < self.tokens.len() {
+            Err(self.error("Unexpected code."))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn statement(
+        &mut self,
+        grid: &mut TheCodeGrid,
+        x: u16,
+        y: u16,
+    ) -> Result<(), TheCompilerError> {
+        // Multi comparison against the left side of the previous comparison.
+        if let Some(Token::Symbol(s)) = self.peek() {
+            if let Some(op) = comparison(s) {
+                self.pos += 1;
+                grid.insert_atom((x + 1, y), TheCodeAtom::Comparison(op));
+                self.expression(grid, x + 2, y)?;
+                self.expect(":")?;
+                return self.expect_end();
+            }
+        }
+
+        if let Some(Token::Ident(name)) = self.peek().cloned() {
+            match name.as_str() {
+                "return" => {
+                    self.pos += 1;
+                    grid.insert_atom((x, y), TheCodeAtom::Return);
+                    if self.peek().is_some() {
+                        self.expression(grid, x + 2, y)?;
+                    }
+                    return self.expect_end();
+                }
+                "argument" => {
+                    self.pos += 1;
+                    if let Some(Token::Ident(arg)) = self.next() {
+                        grid.insert_atom((x, y), TheCodeAtom::Argument(arg));
+                        return self.expect_end();
+                    }
+                    self.pos -= 1;
+                    return Err(self.error("Expected argument name."));
+                }
+                "call" if self.peek_at(1) == Some(&Token::Symbol("(")) => {
+                    self.pos += 2;
+                    let bundle = self.text()?;
+                    self.expect(",")?;
+                    let module = self.text()?;
+                    self.expect(")")?;
+                    if let Some(atom) = self.source.modules.get(&(bundle, module.clone())) {
+                        grid.insert_atom((x, y), atom.clone());
+                        return self.expect_end();
+                    }
+                    self.pos = 0;
+                    return Err(self.error(&format!("Unknown module call ({}).", module)));
+                }
+                _ => {
+                    if let Some(atom) = self.source.externals.get(&name) {
+                        return self.external_call(grid, atom.clone(), x, y);
+                    }
+                }
+            }
+        }
+
+        let column = self.column();
+        let left = self.operand()?;
+
+        match self.next() {
+            Some(Token::Symbol(s)) if assignment(s).is_some() => {
+                let op = assignment(s).unwrap();
+                let target = match left {
+                    TheCodeAtom::LocalGet(name) => TheCodeAtom::LocalSet(name, op),
+                    TheCodeAtom::ObjectGet(object, name) => {
+                        TheCodeAtom::ObjectSet(object, name, op)
+                    }
+                    TheCodeAtom::Get(path) => TheCodeAtom::Set(path, op),
+                    _ => {
+                        return Err(TheCompilerError::new(
+                            (column as u16, self.line as u16),
+                            "Expected a variable to assign to.".to_string(),
+                        ))
+                    }
+                };
+                grid.insert_atom((x, y), target);
+                grid.insert_atom((x + 1, y), TheCodeAtom::Assignment(op));
+                self.expression(grid, x + 2, y)?;
+                self.expect_end()
+            }
+            Some(Token::Symbol(s)) if comparison(s).is_some() => {
+                grid.insert_atom((x, y), left);
+                grid.insert_atom((x + 1, y), TheCodeAtom::Comparison(comparison(s).unwrap()));
+                self.expression(grid, x + 2, y)?;
+                self.expect(":")?;
+                self.expect_end()
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.error("Expected assignment or comparison operator."))
+            }
+        }
+    }
+
+    fn external_call(
+        &mut self,
+        grid: &mut TheCodeGrid,
+        atom: TheCodeAtom,
+        x: u16,
+        y: u16,
+    ) -> Result<(), TheCompilerError> {
+        let arg_count = if let TheCodeAtom::ExternalCall(_, _, _, arg_values, _) = &atom {
+            arg_values.len()
+        } else {
+            0
+        };
+
+        self.pos += 1;
+        self.expect("(")?;
+        grid.insert_atom((x, y), atom);
+
+        for index in 0..arg_count {
+            if index > 0 {
+                self.expect(",")?;
+            }
+            let arg = self.operand()?;
+            grid.insert_atom((x + (index + 1) as u16 * 2, y), arg);
+        }
+
+        self.expect(")")?;
+        self.expect_end()
+    }
+
+    /// Parses an expression of operands and arithmetic operators.
+    fn expression(
+        &mut self,
+        grid: &mut TheCodeGrid,
+        mut x: u16,
+        y: u16,
+    ) -> Result<(), TheCompilerError> {
+        loop {
+            let operand = self.operand()?;
+            grid.insert_atom((x, y), operand);
+            x += 1;
+
+            let op = match self.peek() {
+                Some(Token::Symbol("+")) => TheCodeAtom::Add,
+                Some(Token::Symbol("-")) => TheCodeAtom::Subtract,
+                Some(Token::Symbol("*")) => TheCodeAtom::Multiply,
+                Some(Token::Symbol("/")) => TheCodeAtom::Divide,
+                Some(Token::Symbol("%")) => TheCodeAtom::Modulus,
+                _ => return Ok(()),
+            };
+            self.pos += 1;
+            grid.insert_atom((x, y), op);
+            x += 1;
+        }
+    }
+
+    /// Parses a single value or variable.
+    fn operand(&mut self) -> Result<TheCodeAtom, TheCompilerError> {
+        match self.peek().cloned() {
+            Some(Token::Int(_)) | Some(Token::Float(_)) | Some(Token::Symbol("-")) => {
+                Ok(TheCodeAtom::Value(self.number()?))
+            }
+            Some(Token::Text(text)) => {
+                self.pos += 1;
+                Ok(TheCodeAtom::Value(TheValue::Text(text)))
+            }
+            Some(Token::Ident(name)) => {
+                self.pos += 1;
+
+                if self.peek() == Some(&Token::Symbol("(")) {
+                    return self.constructor(&name);
+                }
+
+                match name.as_str() {
+                    "true" => Ok(TheCodeAtom::Value(TheValue::Bool(true))),
+                    "false" => Ok(TheCodeAtom::Value(TheValue::Bool(false))),
+                    "empty" => Ok(TheCodeAtom::Value(TheValue::Empty)),
+                    _ => {
+                        if name.starts_with('@') {
+                            Ok(TheCodeAtom::Get(name))
+                        } else if let Some(path) = name.strip_prefix('$') {
+                            Ok(TheCodeAtom::Get(path.to_string()))
+                        } else {
+                            let parts: Vec<&str> = name.split('.').collect();
+                            match parts.len() {
+                                1 => Ok(TheCodeAtom::LocalGet(name)),
+                                2 => Ok(TheCodeAtom::ObjectGet(
+                                    parts[0].to_string(),
+                                    parts[1].to_string(),
+                                )),
+                                _ => Ok(TheCodeAtom::Get(name)),
+                            }
+                        }
+                    }
+                }
+            }
+            _ => Err(self.error("Expected value.")),
+        }
+    }
+
+    /// Parses the value constructors and random functions, i.e. `int2(1, 2)` or `rint(1, 6)`.
+    fn constructor(&mut self, name: &str) -> Result<TheCodeAtom, TheCompilerError> {
+        self.pos -= 1;
+        let column = self.column();
+        self.pos += 2;
+
+        let mut args = vec![];
+        loop {
+            args.push(self.number()?);
+            if self.peek() == Some(&Token::Symbol(",")) {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        self.expect(")")?;
+
+        let ints: Option<Vec<i32>> = args
+            .iter()
+            .map(|v| {
+                if let TheValue::Int(i) = v {
+                    Some(*i)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let floats: Vec<f32> = args.iter().filter_map(|v| v.to_f32()).collect();
+
+        let atom = match (name, args.len()) {
+            ("int2", 2) => ints.map(|i| TheCodeAtom::Value(TheValue::Int2(Vec2::new(i[0], i[1])))),
+            ("rint", 2) => ints.map(|i| TheCodeAtom::RandInt(Vec2::new(i[0], i[1]))),
+            ("float2", 2) => Some(TheCodeAtom::Value(TheValue::Float2(Vec2::new(
+                floats[0], floats[1],
+            )))),
+            ("float3", 3) => Some(TheCodeAtom::Value(TheValue::Float3(Vec3::new(
+                floats[0], floats[1], floats[2],
+            )))),
+            ("rfloat", 2) => Some(TheCodeAtom::RandFloat(Vec2::new(floats[0], floats[1]))),
+            _ => None,
+        };
+
+        atom.ok_or_else(|| {
+            TheCompilerError::new(
+                (column as u16, self.line as u16),
+                format!("Invalid function or arguments ({}).", name),
+            )
+        })
+    }
+
+    /// Parses a number with an optional leading minus.
+    fn number(&mut self) -> Result<TheValue, TheCompilerError> {
+        let negate = self.peek() == Some(&Token::Symbol("-"));
+        if negate {
+            self.pos += 1;
+        }
+
+        match self.next() {
+            Some(Token::Int(i)) => Ok(TheValue::Int(if negate { -i } else { i })),
+            Some(Token::Float(f)) => Ok(TheValue::Float(if negate { -f } else { f })),
+            _ => {
+                self.pos -= 1;
+                Err(self.error("Expected number."))
+            }
+        }
+    }
+
+    fn text(&mut self) -> Result<String, TheCompilerError> {
+        if let Some(Token::Text(text)) = self.next() {
+            Ok(text)
+        } else {
+            self.pos -= 1;
+            Err(self.error("Expected text."))
+        }
+    }
+}
+
+/// Splits a line into its indentation depth and tokens with their columns.
+fn tokenize(text: &str, line: usize) -> Result<(usize, Vec<(Token, usize)>), TheCompilerError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = vec![];
+
+    let mut indent = 0;
+    let mut i = 0;
+    while i < chars.len() && (chars[i] == ' ' || chars[i] == '\t') {
+        indent += if chars[i] == '\t' { 4 } else { 1 };
+        i += 1;
+    }
+
+    let error = |column: usize, message: &str| {
+        TheCompilerError::new((column as u16, line as u16), message.to_string())
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '#' {
+            break;
+        } else if c == '"' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    Some('"') => break,
+                    Some('\\') => {
+                        if let Some(c) = chars.get(i + 1) {
+                            text.push(*c);
+                        }
+                        i += 2;
+                    }
+                    Some(c) => {
+                        text.push(*c);
+                        i += 1;
+                    }
+                    None => return Err(error(start, "Unterminated text.")),
+                }
+            }
+            i += 1;
+            tokens.push((Token::Text(text), start));
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let number: String = chars[start..i].iter().collect();
+            let token = if number.contains('.') {
+                number.parse::<f32>().map(Token::Float).ok()
+            } else {
+                number.parse::<i32>().map(Token::Int).ok()
+            };
+            tokens.push((token.ok_or_else(|| error(start, "Invalid number."))?, start));
+        } else if c.is_alphabetic() || c == '_' || c == '@' || c == '$' {
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '@' | '$'))
+            {
+                i += 1;
+            }
+            tokens.push((Token::Ident(chars[start..i].iter().collect()), start));
+        } else if let Some(symbol) = SYMBOLS.iter().find(|s| {
+            s.chars()
+                .enumerate()
+                .all(|(o, sc)| chars.get(i + o) == Some(&sc))
+        }) {
+            i += symbol.len();
+            tokens.push((Token::Symbol(symbol), start));
+        } else {
+            return Err(error(start, &format!("Unexpected character '{}'.", c)));
+        }
+    }
+
+    if !tokens.is_empty() && indent % 4 != 0 {
+        return Err(error(0, "Indentation must be a multiple of four spaces."));
+    }
+
+    Ok((indent / 4, tokens))
+}
+
+fn assignment(symbol: &str) -> Option<TheValueAssignment> {
+    match symbol {
+        "=" => Some(TheValueAssignment::Assign),
+        "+=" => Some(TheValueAssignment::AddAssign),
+        "-=" => Some(TheValueAssignment::SubtractAssign),
+        "*=" => Some(TheValueAssignment::MultiplyAssign),
+        "/=" => Some(TheValueAssignment::DivideAssign),
+        "%=" => Some(TheValueAssignment::ModulusAssign),
+        _ => None,
+    }
+}
+
+fn comparison(symbol: &str) -> Option<TheValueComparison> {
+    match symbol {
+        "==" => Some(TheValueComparison::Equal),
+        "!=" => Some(TheValueComparison::Unequal),
+        ">=" => Some(TheValueComparison::GreaterThanOrEqual),
+        "<=" => Some(TheValueComparison::LessThanOrEqual),
+        ">" => Some(TheValueComparison::GreaterThan),
+        "<" => Some(TheValueComparison::LessThan),
+        _ => None,
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Returns the source text of a single atom.
+fn atom_to_source(atom: &TheCodeAtom, location: (u16, u16)) -> Result<String, TheCompilerError> {
+    let text = match atom {
+        TheCodeAtom::Assignment(op) => op.to_string().to_string(),
+        TheCodeAtom::Comparison(op) => op.to_string().to_string(),
+        TheCodeAtom::LocalGet(name) | TheCodeAtom::LocalSet(name, _) => name.clone(),
+        TheCodeAtom::ObjectGet(object, name) | TheCodeAtom::ObjectSet(object, name, _) => {
+            format!("{}.{}", object, name)
+        }
+        TheCodeAtom::Get(path) | TheCodeAtom::Set(path, _) => {
+            if path.starts_with('@') {
+                path.clone()
+            } else {
+                format!("${}", path)
+            }
+        }
+        TheCodeAtom::Value(value) => match value {
+            TheValue::Empty => "empty".to_string(),
+            TheValue::Bool(v) => v.to_string(),
+            TheValue::Int(v) => v.to_string(),
+            TheValue::Float(v) => format!("{:?}", v),
+            TheValue::Text(v) => quote(v),
+            TheValue::Int2(v) => format!("int2({}, {})", v.x, v.y),
+            TheValue::Float2(v) => format!("float2({:?}, {:?})", v.x, v.y),
+            TheValue::Float3(v) => format!("float3({:?}, {:?}, {:?})", v.x, v.y, v.z),
+            _ => {
+                return Err(TheCompilerError::new(
+                    location,
+                    format!("{} values can not be written as source.", value.to_kind()),
+                ))
+            }
+        },
+        TheCodeAtom::RandInt(v) => format!("rint({}, {})", v.x, v.y),
+        TheCodeAtom::RandFloat(v) => format!("rfloat({:?}, {:?})", v.x, v.y),
+        TheCodeAtom::Add => "+".to_string(),
+        TheCodeAtom::Subtract => "-".to_string(),
+        TheCodeAtom::Multiply => "*".to_string(),
+        TheCodeAtom::Divide => "/".to_string(),
+        TheCodeAtom::Modulus => "%".to_string(),
+        TheCodeAtom::Return => "return".to_string(),
+        TheCodeAtom::Argument(name) => format!("argument {}", name),
+        _ => {
+            return Err(TheCompilerError::new(
+                location,
+                format!("{} can not be written as source.", atom.describe()),
+            ))
+        }
+    };
+
+    Ok(text)
+}
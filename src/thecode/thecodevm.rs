@@ -0,0 +1,281 @@
+use crate::prelude::*;
+
+/// The result of executing a single instruction.
+#[derive(Clone, Debug)]
+pub enum TheCodeVMStep {
+    Continue,
+    /// The instruction calls the given module. The caller executes it and passes its result to
+    /// `call_returned()`.
+    Call(Box<TheCodeModule>),
+    Finished,
+}
+
+/// A stack based virtual machine which executes the bytecode of a TheCodeChunk. It produces the
+/// same results and debug values as executing the nodes of the function directly.
+#[derive(Clone, Debug, Default)]
+pub struct TheCodeVM {
+    stack: Vec<TheValue>,
+    /// The stack heights before the right side of the open comparisons.
+    marks: Vec<usize>,
+    /// The stack height and the EndBlock instruction of the open blocks.
+    blocks: Vec<(usize, usize)>,
+    /// The next instruction.
+    ip: usize,
+}
+
+impl TheCodeVM {
+    pub fn new() -> Self {
+        Self {
+            stack: Vec::with_capacity(16),
+            marks: vec![],
+            blocks: vec![],
+            ip: 0,
+        }
+    }
+
+    /// Resets the VM to the start of a chunk.
+    pub fn reset(&mut self) {
+        self.stack.clear();
+        self.marks.clear();
+        self.blocks.clear();
+        self.ip = 0;
+    }
+
+    /// The index of the next instruction.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// The current stack.
+    pub fn stack(&self) -> &Vec<TheValue> {
+        &self.stack
+    }
+
+    /// Takes the stack out of the VM, this is the result of a finished chunk.
+    pub fn take_stack(&mut self) -> Vec<TheValue> {
+        std::mem::take(&mut self.stack)
+    }
+
+    /// Runs the chunk and returns the stack at the time the chunk ended or returned.
+    pub fn run(&mut self, chunk: &mut TheCodeChunk, sandbox: &mut TheCodeSandbox) -> Vec<TheValue> {
+        self.reset();
+
+        loop {
+            match self.step(chunk, sandbox) {
+                TheCodeVMStep::Continue => {}
+                TheCodeVMStep::Call(mut module) => {
+                    let rc = module.execute_vm(sandbox);
+                    self.call_returned(chunk, &rc, sandbox);
+                }
+                TheCodeVMStep::Finished => break,
+            }
+        }
+
+        self.take_stack()
+    }
+
+    /// Executes the next instruction of the chunk.
+    pub fn step(
+        &mut self,
+        chunk: &mut TheCodeChunk,
+        sandbox: &mut TheCodeSandbox,
+    ) -> TheCodeVMStep {
+        if self.ip >= chunk.code.len() {
+            return TheCodeVMStep::Finished;
+        }
+
+        let op = chunk.code[self.ip];
+        let location = chunk.locations[self.ip];
+        self.ip += 1;
+
+        match op {
+            TheCodeOp::Constant(index) => {
+                self.stack.push(chunk.constants[index as usize].clone());
+            }
+            TheCodeOp::GetLocal(index) => {
+                let name = &chunk.names[index as usize];
+                if let Some(function) = sandbox.call_stack.last() {
+                    if let Some(local) = function.get_local(name) {
+                        self.stack.push(local.clone());
+                    } else {
+                        println!(
+                            "Runtime error: Unknown local variable {} at {:?}.",
+                            name, location
+                        );
+                    }
+                }
+            }
+            TheCodeOp::SetLocal(index) => {
+                if let Some(v) = self.stack.pop() {
+                    if sandbox.debug_mode {
+                        sandbox.set_debug_value(location, (None, v.clone()));
+                    }
+                    if let Some(function) = sandbox.call_stack.last_mut() {
+                        function.set_local(chunk.names[index as usize].clone(), v);
+                    }
+                }
+            }
+            TheCodeOp::Add => {
+                if let Some((a, b)) = self.pop_two() {
+                    if let TheValue::Text(a) = a {
+                        self.stack
+                            .push(TheValue::Text(format!("{} {}", a, b.describe())));
+                    } else if let Some(result) = TheValue::add(&a, &b) {
+                        self.stack.push(result);
+                    } else {
+                        println!("Runtime error: Add. Invalid types.");
+                    }
+                }
+            }
+            TheCodeOp::Subtract => {
+                if let Some((a, b)) = self.pop_two() {
+                    if let Some(result) = TheValue::sub(&a, &b) {
+                        self.stack.push(result);
+                    } else {
+                        println!("Runtime error: Sub. Invalid types.");
+                    }
+                }
+            }
+            TheCodeOp::Multiply => {
+                if let Some((a, b)) = self.pop_two() {
+                    if let Some(result) = TheValue::mul(&a, &b) {
+                        self.stack.push(result);
+                    } else {
+                        println!("Runtime error: Multiply. Invalid types.");
+                    }
+                }
+            }
+            TheCodeOp::Divide => {
+                if let Some((a, b)) = self.pop_two() {
+                    if let Some(result) = TheValue::div(&a, &b) {
+                        self.stack.push(result);
+                    } else {
+                        println!("Runtime error: Division. Invalid types.");
+                    }
+                }
+            }
+            TheCodeOp::Modulus => {
+                if let Some((a, b)) = self.pop_two() {
+                    if let Some(result) = TheValue::modulus(&a, &b) {
+                        self.stack.push(result);
+                    } else {
+                        println!("Runtime error: Modulus. Invalid types.");
+                    }
+                }
+            }
+            TheCodeOp::Mark => {
+                self.marks.push(self.stack.len());
+            }
+            TheCodeOp::Compare(op) => {
+                let mark = self.marks.pop().unwrap_or(0);
+                let right = if self.stack.len() > mark {
+                    self.stack.pop()
+                } else {
+                    None
+                };
+                self.stack.truncate(mark);
+                let left = self.stack.pop();
+
+                let result = match (left, right) {
+                    (Some(left), Some(right)) => match op {
+                        TheValueComparison::Equal => left.is_equal(&right),
+                        TheValueComparison::Unequal => !left.is_equal(&right),
+                        TheValueComparison::GreaterThanOrEqual => {
+                            left.is_greater_than_or_equal(&right)
+                        }
+                        TheValueComparison::LessThanOrEqual => left.is_less_than_or_equal(&right),
+                        TheValueComparison::GreaterThan => left.is_greater_than(&right),
+                        TheValueComparison::LessThan => left.is_less_than(&right),
+                    },
+                    _ => false,
+                };
+                self.stack.push(TheValue::Bool(result));
+            }
+            TheCodeOp::JumpIfFalse(target) => {
+                if self.stack.pop() != Some(TheValue::Bool(true)) {
+                    self.ip = target as usize;
+                }
+            }
+            TheCodeOp::Executed => {
+                if sandbox.debug_mode {
+                    sandbox.set_debug_executed(location);
+                }
+            }
+            TheCodeOp::BeginBlock(end) => {
+                self.blocks.push((self.stack.len(), end as usize));
+            }
+            TheCodeOp::EndBlock => {
+                if let Some((height, _)) = self.blocks.pop() {
+                    self.stack.truncate(height);
+                }
+            }
+            TheCodeOp::Return => {
+                if sandbox.debug_mode {
+                    let height = self.blocks.last().map(|b| b.0).unwrap_or(0);
+                    if self.stack.len() > height {
+                        if let Some(v) = self.stack.last() {
+                            sandbox.set_debug_value(location, (None, v.clone()));
+                        }
+                    }
+                }
+
+                return self.leave_block(chunk);
+            }
+            TheCodeOp::CallModule(index) => {
+                let data = &chunk.nodes[index as usize].data;
+                if let (TheValue::Id(package_id), TheValue::Id(codegrid_id)) =
+                    (&data.values[0], &data.values[1])
+                {
+                    if let Some(module) = sandbox.get_package_module_cloned(package_id, codegrid_id)
+                    {
+                        return TheCodeVMStep::Call(Box::new(module));
+                    }
+                }
+            }
+            TheCodeOp::Node(index) => {
+                let node = &mut chunk.nodes[index as usize];
+                let rc = (node.call)(&mut self.stack, &mut node.data, sandbox);
+                if rc == TheCodeNodeCallResult::Break {
+                    return self.leave_block(chunk);
+                }
+            }
+        }
+
+        TheCodeVMStep::Continue
+    }
+
+    /// Records the result of a module call made by the previous instruction.
+    pub fn call_returned(
+        &mut self,
+        chunk: &TheCodeChunk,
+        rc: &[TheValue],
+        sandbox: &mut TheCodeSandbox,
+    ) {
+        if sandbox.debug_mode {
+            let location = chunk.locations[self.ip - 1];
+            let v = rc.last().cloned().unwrap_or(TheValue::Empty);
+            sandbox.set_debug_value(location, (None, v));
+            sandbox.set_debug_executed(location);
+        }
+    }
+
+    /// Pops the right and the left operand of a binary operation.
+    #[inline]
+    fn pop_two(&mut self) -> Option<(TheValue, TheValue)> {
+        let b = self.stack.pop()?;
+        let a = self.stack.pop()?;
+        Some((a, b))
+    }
+
+    /// Continues at the end of the innermost block, or finishes the chunk if no block is open.
+    #[inline]
+    fn leave_block(&mut self, chunk: &TheCodeChunk) -> TheCodeVMStep {
+        if let Some((_, end)) = self.blocks.last() {
+            self.ip = *end;
+            TheCodeVMStep::Continue
+        } else {
+            self.ip = chunk.code.len();
+            TheCodeVMStep::Finished
+        }
+    }
+}
@@ -39,15 +39,90 @@ pub enum TheCursorIcon {
     RowResize,
 }
 
+/// The regions of the frame which changed since the frame was last presented.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TheDamage {
+    /// The whole frame changed.
+    Full,
+    /// Only the given regions, in logical coordinates, changed. Empty if nothing changed.
+    Regions(Vec<TheDim>),
+}
+
+impl TheDamage {
+    /// The number of regions after which the damage is treated as a full frame.
+    pub const MAX_REGIONS: usize = 64;
+
+    /// Returns true if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        matches!(self, TheDamage::Regions(regions) if regions.is_empty())
+    }
+
+    /// Adds a changed region.
+    pub fn add(&mut self, dim: TheDim) {
+        if let TheDamage::Regions(regions) = self {
+            if !dim.is_valid() || regions.contains(&dim) {
+                return;
+            }
+            if regions.len() >= Self::MAX_REGIONS {
+                *self = TheDamage::Full;
+            } else {
+                regions.push(dim);
+            }
+        }
+    }
+
+    /// Converts the damage to rectangles (x, y, width, height) of a frame of the given physical
+    /// size. Regions are scaled to physical pixels, rounded outwards and clipped to the frame.
+    pub fn physical_rects(
+        &self,
+        scale_factor: f32,
+        width: usize,
+        height: usize,
+    ) -> Vec<(usize, usize, usize, usize)> {
+        match self {
+            TheDamage::Full => {
+                if width > 0 && height > 0 {
+                    vec![(0, 0, width, height)]
+                } else {
+                    vec![]
+                }
+            }
+            TheDamage::Regions(regions) => {
+                let mut rects = vec![];
+                for dim in regions {
+                    let x0 = ((dim.x as f32 * scale_factor).floor().max(0.0) as usize).min(width);
+                    let y0 = ((dim.y as f32 * scale_factor).floor().max(0.0) as usize).min(height);
+                    let x1 = (((dim.x + dim.width) as f32 * scale_factor).ceil().max(0.0) as usize)
+                        .min(width);
+                    let y1 = (((dim.y + dim.height) as f32 * scale_factor).ceil().max(0.0)
+                        as usize)
+                        .min(height);
+                    if x1 > x0 && y1 > y0 {
+                        rects.push((x0, y0, x1 - x0, y1 - y0));
+                    }
+                }
+                rects
+            }
+        }
+    }
+}
+
 pub struct TheContext {
+    /// The logical width of the window.
     pub width: usize,
+    /// The logical height of the window.
     pub height: usize,
+    /// The ratio between physical and logical pixels, change it via set_scale_factor().
     pub scale_factor: f32,
 
     pub draw: TheDraw2D,
     #[cfg(feature = "ui")]
     pub ui: TheUIContext,
 
+    /// The regions of the frame changed by draw() calls since the last present. Backends use it
+    /// to present only the changed parts of the frame.
+    pub(crate) damage: TheDamage,
+
     pub cursor_icon: TheCursorIcon,
     pub cursor_changed: bool,
     pub cursor_visible: bool,
@@ -56,20 +131,72 @@ pub struct TheContext {
 
 impl TheContext {
     pub fn new(width: usize, height: usize, scale_factor: f32) -> Self {
-        Self {
+        let mut ctx = Self {
             width,
             height,
             scale_factor,
             draw: TheDraw2D::new(),
             #[cfg(feature = "ui")]
             ui: TheUIContext::new(),
+            damage: TheDamage::Full,
             cursor_icon: TheCursorIcon::Default,
             cursor_changed: false,
             cursor_visible: true,
             cursor_visible_changed: false,
+        };
+        ctx.set_scale_factor(scale_factor);
+        ctx
+    }
+
+    /// Sets the scale factor of the display. Layout stays in logical units, drawing and the frame
+    /// passed to draw() use physical pixels.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if self.scale_factor != scale_factor {
+            self.damage_all();
+        }
+        self.scale_factor = scale_factor;
+        self.draw.scale = scale_factor;
+        #[cfg(feature = "ui")]
+        {
+            if self.ui.scale_factor != scale_factor {
+                self.ui.scale_factor = scale_factor;
+                self.ui.relayout = true;
+                self.ui.redraw_all = true;
+            }
         }
     }
 
+    /// The width of the frame in physical pixels.
+    pub fn physical_width(&self) -> usize {
+        (self.width as f32 * self.scale_factor).round() as usize
+    }
+
+    /// The height of the frame in physical pixels.
+    pub fn physical_height(&self) -> usize {
+        (self.height as f32 * self.scale_factor).round() as usize
+    }
+
+    /// Marks the given region of the frame, in logical coordinates, as changed. Apps which draw
+    /// into the frame in TheTrait::draw() have to report the regions they change.
+    pub fn add_damage(&mut self, dim: TheDim) {
+        self.damage.add(dim);
+    }
+
+    /// Marks the whole frame as changed.
+    pub fn damage_all(&mut self) {
+        self.damage = TheDamage::Full;
+    }
+
+    /// Returns the damage accumulated since the last call and resets it.
+    pub fn take_damage(&mut self) -> TheDamage {
+        std::mem::replace(&mut self.damage, TheDamage::Regions(vec![]))
+    }
+
+    /// Returns the damage accumulated so far.
+    pub fn damage(&self) -> &TheDamage {
+        &self.damage
+    }
+
     /// Set the cursor icon
     pub fn set_cursor_icon(&mut self, icon: TheCursorIcon) {
         if self.cursor_icon != icon {
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::ops::Deref;
 
 use fontdue::layout::{
@@ -36,12 +37,18 @@ pub struct TheFontSettings {
     pub size: f32,
 }
 
+/// The 2D drawing helpers. All coordinates, sizes and strides passed to the drawing functions are
+/// in logical units, the target frame has to be allocated at physical resolution, i.e. the
+/// logical size multiplied by the scale. Shapes and text are rasterized at physical resolution.
 #[derive(Debug)]
 pub struct TheDraw2D {
     pub mask: Option<Vec<f32>>,
     pub mask_size: (usize, usize),
     pub fonts: Vec<Font>,
     pub code_fonts: Vec<Font>,
+
+    /// The ratio between physical and logical pixels, set via TheContext::set_scale_factor.
+    pub scale: f32,
 }
 
 impl Default for TheDraw2D {
@@ -71,7 +78,129 @@ impl TheDraw2D {
             mask_size: (0, 0),
             fonts,
             code_fonts,
+            scale: 1.0,
+        }
+    }
+
+    /// Converts a logical length to physical pixels.
+    pub fn physical(&self, length: usize) -> usize {
+        if self.scale == 1.0 {
+            length
+        } else {
+            (length as f32 * self.scale).round() as usize
+        }
+    }
+
+    /// Converts a logical rect to physical pixels. The edges of the rect are rounded
+    /// individually so that adjacent rects stay adjacent.
+    pub fn physical_rect(
+        &self,
+        rect: &(usize, usize, usize, usize),
+    ) -> (usize, usize, usize, usize) {
+        let x = self.physical(rect.0);
+        let y = self.physical(rect.1);
+        (
+            x,
+            y,
+            self.physical(rect.0 + rect.2) - x,
+            self.physical(rect.1 + rect.3) - y,
+        )
+    }
+
+    /// Converts a logical rect with a signed position to physical pixels.
+    fn physical_irect(&self, rect: &(isize, isize, usize, usize)) -> (isize, isize, usize, usize) {
+        let p = |v: isize| (v as f32 * self.scale).round() as isize;
+        let x = p(rect.0);
+        let y = p(rect.1);
+        (
+            x,
+            y,
+            (p(rect.0 + rect.2 as isize) - x) as usize,
+            (p(rect.1 + rect.3 as isize) - y) as usize,
+        )
+    }
+
+    /// Converts a logical position to physical pixels.
+    fn physical_point(&self, point: &Vec2<i32>) -> Vec2<i32> {
+        point.map(|v| (v as f32 * self.scale).round() as i32)
+    }
+
+    /// Converts the corner radii of a rounded rect to physical pixels.
+    fn physical_rounding(&self, rounding: &(f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
+        (
+            rounding.0 * self.scale,
+            rounding.1 * self.scale,
+            rounding.2 * self.scale,
+            rounding.3 * self.scale,
+        )
+    }
+
+    /// The font settings used for rasterizing at physical resolution.
+    fn physical_settings(&self, settings: &TheFontSettings) -> TheFontSettings {
+        TheFontSettings {
+            preference: settings.preference.clone(),
+            size: settings.size * self.scale,
+        }
+    }
+
+    /// The scale of a source slice which is passed without an explicit scale: slices with the
+    /// logical size of the rect have a scale of 1, all other slices have to be rendered at the
+    /// current scale.
+    fn source_scale(&self, len: usize, size: (usize, usize), channels: usize) -> f32 {
+        if len == size.0 * size.1 * channels {
+            1.0
+        } else {
+            self.scale
+        }
+    }
+
+    /// Returns the source pixels of a slice with the given logical size and scale at the given
+    /// physical size. Sources which were not rendered at the current scale (like 1x icons) are
+    /// resampled.
+    fn physical_source<'a, T: Copy>(
+        &self,
+        source: &'a [T],
+        logical: (usize, usize),
+        source_scale: f32,
+        size: (usize, usize),
+        channels: usize,
+    ) -> Cow<'a, [T]> {
+        if source_scale == self.scale || logical.0 == 0 || logical.1 == 0 {
+            return Cow::Borrowed(source);
+        }
+
+        let source_size = if source_scale == 1.0 {
+            logical
+        } else {
+            (
+                (logical.0 as f32 * source_scale).round() as usize,
+                (logical.1 as f32 * source_scale).round() as usize,
+            )
+        };
+        if source_size == size {
+            return Cow::Borrowed(source);
+        }
+
+        Cow::Owned(Self::resample(source, source_size, size, channels))
+    }
+
+    /// Nearest neighbor resampling of a pixel slice.
+    fn resample<T: Copy>(
+        source: &[T],
+        source_size: (usize, usize),
+        size: (usize, usize),
+        channels: usize,
+    ) -> Vec<T> {
+        let mut pixels = Vec::with_capacity(size.0 * size.1 * channels);
+        for y in 0..size.1 {
+            let sy = (y * source_size.1 / size.1.max(1)).min(source_size.1.saturating_sub(1));
+            for x in 0..size.0 {
+                let sx = (x * source_size.0 / size.0.max(1)).min(source_size.0.saturating_sub(1));
+                let s = (sx + sy * source_size.0) * channels;
+                pixels.extend_from_slice(&source[s..s + channels]);
+            }
         }
+        pixels
     }
 
     /// Draws the mask
@@ -84,6 +213,12 @@ impl TheDraw2D {
         mask_size: &(usize, usize),
         color: &[u8; 4],
     ) {
+        let logical = (rect.0, rect.1, mask_size.0, mask_size.1);
+        let (rect, stride) = (&self.physical_rect(&logical), self.physical(stride));
+        let source_scale = self.source_scale(mask_frame.len(), *mask_size, 1);
+        let mask = self.physical_source(mask_frame, *mask_size, source_scale, (rect.2, rect.3), 1);
+        let (mask_frame, mask_size) = (&*mask, &(rect.2, rect.3));
+
         for y in 0..mask_size.1 {
             for x in 0..mask_size.0 {
                 let i = (x + rect.0) * 4 + (y + rect.1) * stride * 4;
@@ -104,6 +239,7 @@ impl TheDraw2D {
         stride: usize,
         color: &[u8; 4],
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
         for y in rect.1..rect.1 + rect.3 {
             for x in rect.0..rect.0 + rect.2 {
                 let i = x * 4 + y * stride * 4;
@@ -121,6 +257,8 @@ impl TheDraw2D {
         color: &[u8; 4],
         safe_rect: &(usize, usize, usize, usize),
     ) {
+        let (rect, stride) = (&self.physical_irect(rect), self.physical(stride));
+        let safe_rect = &self.physical_rect(safe_rect);
         let dest_stride_isize: isize = stride as isize;
         for y in rect.1..rect.1 + rect.3 as isize {
             if y >= safe_rect.1 as isize && y < (safe_rect.1 + safe_rect.3) as isize {
@@ -142,6 +280,7 @@ impl TheDraw2D {
         stride: usize,
         color: &[u8; 4],
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
         for y in rect.1..rect.1 + rect.3 {
             for x in rect.0..rect.0 + rect.2 {
                 let i = x * 4 + y * stride * 4;
@@ -164,6 +303,18 @@ impl TheDraw2D {
         stride: usize,
         color: &[u8; 4],
     ) {
+        if self.scale != 1.0 {
+            // Outlines are one logical pixel wide
+            let (x, y, w, h) = *rect;
+            if w > 0 && h > 0 {
+                self.rect(frame, &(x, y, w, 1), stride, color);
+                self.rect(frame, &(x, y + h - 1, w, 1), stride, color);
+                self.rect(frame, &(x, y, 1, h), stride, color);
+                self.rect(frame, &(x + w - 1, y, 1, h), stride, color);
+            }
+            return;
+        }
+
         let y = rect.1;
         for x in rect.0..rect.0 + rect.2 {
             let mut i = x * 4 + y * stride * 4;
@@ -192,6 +343,28 @@ impl TheDraw2D {
         color: &[u8; 4],
         border: usize,
     ) {
+        if self.scale != 1.0 {
+            // Outlines are one logical pixel wide
+            let (x, y, w, h) = *rect;
+            if w > 2 * border && h > 2 * border {
+                self.rect(frame, &(x + border, y, w - 2 * border, 1), stride, color);
+                self.rect(
+                    frame,
+                    &(x + border, y + h - 1, w - 2 * border, 1),
+                    stride,
+                    color,
+                );
+                self.rect(frame, &(x, y + border, 1, h - 2 * border), stride, color);
+                self.rect(
+                    frame,
+                    &(x + w - 1, y + border, 1, h - 2 * border),
+                    stride,
+                    color,
+                );
+            }
+            return;
+        }
+
         let y = rect.1;
         for x in rect.0 + border..rect.0 + rect.2 - border {
             let mut i = x * 4 + y * stride * 4;
@@ -220,6 +393,22 @@ impl TheDraw2D {
         color: &[u8; 4],
         border: usize,
     ) {
+        if self.scale != 1.0 {
+            // Outlines are one logical pixel wide
+            let (x, y, w, h) = *rect;
+            if w > 2 * border && h > 2 * border {
+                self.rect(frame, &(x + border, y, w - 2 * border, 1), stride, color);
+                self.rect(
+                    frame,
+                    &(x + border, y + h - 1, w - 2 * border, 1),
+                    stride,
+                    color,
+                );
+                self.rect(frame, &(x, y + border, 1, h - 2 * border), stride, color);
+            }
+            return;
+        }
+
         let y = rect.1;
         for x in rect.0 + border..rect.0 + rect.2 - border {
             let mut i = x * 4 + y * stride * 4;
@@ -246,6 +435,32 @@ impl TheDraw2D {
         border: isize,
         safe_rect: &(usize, usize, usize, usize),
     ) {
+        if self.scale != 1.0 {
+            // Outlines are one logical pixel wide
+            let (x, y, w, h) = *rect;
+            let b = border.max(0) as usize;
+            if w > 2 * b && h > 2 * b {
+                let iw = (w - 2 * b, h - 2 * b);
+                self.rect_safe(frame, &(x + border, y, iw.0, 1), stride, color, safe_rect);
+                self.rect_safe(
+                    frame,
+                    &(x + border, y + h as isize - 1, iw.0, 1),
+                    stride,
+                    color,
+                    safe_rect,
+                );
+                self.rect_safe(frame, &(x, y + border, 1, iw.1), stride, color, safe_rect);
+                self.rect_safe(
+                    frame,
+                    &(x + w as isize - 1, y + border, 1, iw.1),
+                    stride,
+                    color,
+                    safe_rect,
+                );
+            }
+            return;
+        }
+
         let dest_stride_isize: isize = stride as isize;
         let y = rect.1;
         if y >= safe_rect.1 as isize && y < (safe_rect.1 + safe_rect.3) as isize {
@@ -291,6 +506,9 @@ impl TheDraw2D {
         color: &[u8; 4],
         radius: f32,
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+        let radius = radius * self.scale;
+
         let center = (
             rect.0 as f32 + rect.2 as f32 / 2.0,
             rect.1 as f32 + rect.3 as f32 / 2.0,
@@ -327,6 +545,8 @@ impl TheDraw2D {
         border_color: &[u8; 4],
         border_size: f32,
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+        let (radius, border_size) = (radius * self.scale, border_size * self.scale);
         let center = (
             rect.0 as f32 + rect.2 as f32 / 2.0,
             rect.1 as f32 + rect.3 as f32 / 2.0,
@@ -362,6 +582,8 @@ impl TheDraw2D {
         color: &[u8; 4],
         rounding: &(f32, f32, f32, f32),
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+        let rounding = &self.physical_rounding(rounding);
         let center = (
             (rect.0 as f32 + rect.2 as f32 / 2.0).round(),
             (rect.1 as f32 + rect.3 as f32 / 2.0).round(),
@@ -416,6 +638,9 @@ impl TheDraw2D {
         border_color: &[u8; 4],
         border_size: f32,
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+        let rounding = &self.physical_rounding(rounding);
+        let border_size = border_size * self.scale;
         let hb = border_size / 2.0;
         let center = (
             (rect.0 as f32 + rect.2 as f32 / 2.0 - hb).round(),
@@ -473,6 +698,8 @@ impl TheDraw2D {
         border_color: &[u8; 4],
         border_size: f32,
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+        let border_size = border_size * self.scale;
         let hb = border_size / 2.0;
         let center = (
             (rect.0 as f32 + rect.2 as f32 / 2.0 - hb).round(),
@@ -519,6 +746,8 @@ impl TheDraw2D {
         border_color: &[u8; 4],
         border_size: f32,
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+        let border_size = border_size * self.scale;
         let hb = border_size / 2.0;
         let center = (
             (rect.0 as f32 + rect.2 as f32 / 2.0 - hb).round(),
@@ -577,6 +806,9 @@ impl TheDraw2D {
         line_color: &[u8; 4],
         pattern_size: usize,
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+        let pattern_size = self.physical(pattern_size).max(1);
+
         for y in rect.1..rect.1 + rect.3 {
             for x in rect.0..rect.0 + rect.2 {
                 let i = x * 4 + y * stride * 4;
@@ -602,8 +834,17 @@ impl TheDraw2D {
         stride: usize,
         color: &[u8; 4],
     ) {
+        let (left, base) = (
+            (left as f32 * self.scale).round() as i32,
+            (base as f32 * self.scale).round() as i32,
+        );
+        let (length, amplitude, stride) = (
+            self.physical(length),
+            amplitude * self.scale,
+            self.physical(stride),
+        );
         for x in left..left + length as i32 {
-            let y = ((x as f32).sin() * amplitude) as i32 + base;
+            let y = ((x as f32 / self.scale).sin() * amplitude) as i32 + base;
 
             let i = x * 4 + y * stride as i32 * 4;
             if i < 0 {
@@ -649,6 +890,8 @@ impl TheDraw2D {
             text_to_use += "...";
         }
 
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+        let settings = self.physical_settings(&settings);
         let fonts = self.fonts_iter(&settings.preference);
 
         let layout = self.get_text_layout(
@@ -716,6 +959,12 @@ impl TheDraw2D {
             return;
         }
 
+        let (top_left, clip_rect, stride) = (
+            &self.physical_point(top_left),
+            &self.physical_rect(clip_rect),
+            self.physical(stride),
+        );
+        let settings = self.physical_settings(&settings);
         let fonts = self.fonts_iter(&settings.preference);
 
         let layout = self.get_text_layout(
@@ -777,7 +1026,8 @@ impl TheDraw2D {
     }
 
     #[allow(clippy::too_many_arguments)]
-    /// Blends a text aligned inside a rect and blends it with the existing background
+    /// Blends a text aligned inside a rect and blends it with the existing background, the alpha
+    /// of the color scales the coverage of the glyphs.
     pub fn text_rect_blend(
         &self,
         frame: &mut [u8],
@@ -808,7 +1058,10 @@ impl TheDraw2D {
             text_to_use += "...";
         }
 
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+        let settings = self.physical_settings(&settings);
         let fonts = self.fonts_iter(&settings.preference);
+        let alpha = color[3] as f32 / 255.0;
 
         let layout = self.get_text_layout(
             &text_to_use,
@@ -854,7 +1107,7 @@ impl TheDraw2D {
                     frame[i..i + 4].copy_from_slice(&self.mix_color(
                         background,
                         color,
-                        m as f32 / 255.0,
+                        m as f32 / 255.0 * alpha,
                     ));
                 }
             }
@@ -880,6 +1133,12 @@ impl TheDraw2D {
             return;
         }
 
+        let (top_left, clip_rect, stride) = (
+            &self.physical_point(top_left),
+            &self.physical_rect(clip_rect),
+            self.physical(stride),
+        );
+        let settings = self.physical_settings(&settings);
         let fonts = self.fonts_iter(&settings.preference);
 
         let layout = self.get_text_layout(
@@ -956,6 +1215,11 @@ impl TheDraw2D {
             return;
         }
 
+        let (pos, stride) = (
+            &(self.physical(pos.0), self.physical(pos.1)),
+            self.physical(stride),
+        );
+        let settings = self.physical_settings(&settings);
         let fonts = self.fonts_iter(&settings.preference);
 
         let layout = self.get_text_layout(text, &settings, LayoutSettings::default());
@@ -995,6 +1259,11 @@ impl TheDraw2D {
             return;
         }
 
+        let (pos, stride) = (
+            &(self.physical(pos.0), self.physical(pos.1)),
+            self.physical(stride),
+        );
+        let settings = self.physical_settings(&settings);
         let fonts = self.fonts_iter(&settings.preference);
 
         let layout = self.get_text_layout(text, &settings, LayoutSettings::default());
@@ -1075,7 +1344,9 @@ impl TheDraw2D {
         (x, layout.height() as usize)
     }
 
-    /// Copies rect from the source frame into the dest frame
+    /// Copies rect from the source frame into the dest frame.
+    /// Sources with the logical size of the rect are scaled up, all other sources have to be
+    /// rendered at the current scale, see copy_slice_scaled() for sources at other scales.
     pub fn copy_slice(
         &self,
         dest: &mut [u8],
@@ -1083,6 +1354,31 @@ impl TheDraw2D {
         rect: &(usize, usize, usize, usize),
         dest_stride: usize,
     ) {
+        let source_scale = self.source_scale(source.len(), (rect.2, rect.3), 4);
+        self.copy_slice_scaled(dest, source, source_scale, rect, dest_stride);
+    }
+
+    /// Copies rect from the source frame into the dest frame.
+    /// The source was rendered at the given scale (like the 2x variant of an icon) and is
+    /// resampled if the scale differs from the current scale.
+    pub fn copy_slice_scaled(
+        &self,
+        dest: &mut [u8],
+        source: &[u8],
+        source_scale: f32,
+        rect: &(usize, usize, usize, usize),
+        dest_stride: usize,
+    ) {
+        let (physical, dest_stride) = (self.physical_rect(rect), self.physical(dest_stride));
+        let source = self.physical_source(
+            source,
+            (rect.2, rect.3),
+            source_scale,
+            (physical.2, physical.3),
+            4,
+        );
+        let (source, rect) = (&*source, &physical);
+
         for y in 0..rect.3 {
             let d = rect.0 * 4 + (y + rect.1) * dest_stride * 4;
             let s = y * rect.2 * 4;
@@ -1090,7 +1386,9 @@ impl TheDraw2D {
         }
     }
 
-    /// Blends rect from the source frame into the dest frame
+    /// Blends rect from the source frame into the dest frame.
+    /// Sources with the logical size of the rect are scaled up, all other sources have to be
+    /// rendered at the current scale, see blend_slice_scaled() for sources at other scales.
     pub fn blend_slice(
         &self,
         dest: &mut [u8],
@@ -1098,6 +1396,31 @@ impl TheDraw2D {
         rect: &(usize, usize, usize, usize),
         dest_stride: usize,
     ) {
+        let source_scale = self.source_scale(source.len(), (rect.2, rect.3), 4);
+        self.blend_slice_scaled(dest, source, source_scale, rect, dest_stride);
+    }
+
+    /// Blends rect from the source frame into the dest frame.
+    /// The source was rendered at the given scale (like the 2x variant of an icon) and is
+    /// resampled if the scale differs from the current scale.
+    pub fn blend_slice_scaled(
+        &self,
+        dest: &mut [u8],
+        source: &[u8],
+        source_scale: f32,
+        rect: &(usize, usize, usize, usize),
+        dest_stride: usize,
+    ) {
+        let (physical, dest_stride) = (self.physical_rect(rect), self.physical(dest_stride));
+        let source = self.physical_source(
+            source,
+            (rect.2, rect.3),
+            source_scale,
+            (physical.2, physical.3),
+            4,
+        );
+        let (source, rect) = (&*source, &physical);
+
         for y in 0..rect.3 {
             let d = rect.0 * 4 + (y + rect.1) * dest_stride * 4;
             let s = y * rect.2 * 4;
@@ -1117,7 +1440,9 @@ impl TheDraw2D {
         }
     }
 
-    /// Blends rect from the source frame into the dest frame
+    /// Blends rect from the source frame into the dest frame.
+    /// Sources with the logical size of the rect are scaled up, all other sources have to be
+    /// rendered at the current scale, see blend_slice_alpha_scaled() for sources at other scales.
     pub fn blend_slice_alpha(
         &self,
         dest: &mut [u8],
@@ -1126,6 +1451,32 @@ impl TheDraw2D {
         dest_stride: usize,
         alpha: f32,
     ) {
+        let source_scale = self.source_scale(source.len(), (rect.2, rect.3), 4);
+        self.blend_slice_alpha_scaled(dest, source, source_scale, rect, dest_stride, alpha);
+    }
+
+    /// Blends rect from the source frame into the dest frame.
+    /// The source was rendered at the given scale (like the 2x variant of an icon) and is
+    /// resampled if the scale differs from the current scale.
+    pub fn blend_slice_alpha_scaled(
+        &self,
+        dest: &mut [u8],
+        source: &[u8],
+        source_scale: f32,
+        rect: &(usize, usize, usize, usize),
+        dest_stride: usize,
+        alpha: f32,
+    ) {
+        let (physical, dest_stride) = (self.physical_rect(rect), self.physical(dest_stride));
+        let source = self.physical_source(
+            source,
+            (rect.2, rect.3),
+            source_scale,
+            (physical.2, physical.3),
+            4,
+        );
+        let (source, rect) = (&*source, &physical);
+
         for y in 0..rect.3 {
             let d = rect.0 * 4 + (y + rect.1) * dest_stride * 4;
             let s = y * rect.2 * 4;
@@ -1153,6 +1504,17 @@ impl TheDraw2D {
         rect: &(usize, usize, usize, usize),
         dest_stride: usize,
     ) {
+        let (physical, dest_stride) = (self.physical_rect(rect), self.physical(dest_stride));
+        let source_scale = self.source_scale(source.len(), (rect.2, rect.3), 4);
+        let source = self.physical_source(
+            source,
+            (rect.2, rect.3),
+            source_scale,
+            (physical.2, physical.3),
+            4,
+        );
+        let (source, rect) = (&*source, &physical);
+
         for y in 0..rect.3 {
             let d = rect.0 * 4 + (y + rect.1) * dest_stride * 4;
             let s = y * rect.2 * 4;
@@ -1186,6 +1548,13 @@ impl TheDraw2D {
         offset: usize,
         dest_stride: usize,
     ) {
+        // The source has to be rendered at the current scale
+        let (rect, offset, dest_stride) = (
+            &self.physical_rect(rect),
+            self.physical(offset),
+            self.physical(dest_stride),
+        );
+
         for y in 0..rect.3 {
             let d = rect.0 * 4 + (y + rect.1) * dest_stride * 4;
             let s = (y + offset) * rect.2 * 4;
@@ -1205,7 +1574,9 @@ impl TheDraw2D {
         }
     }
 
-    /// Blends rect from the source frame into the dest frame and honors the safe rect
+    /// Blends rect from the source frame into the dest frame and honors the safe rect.
+    /// Sources with the logical size of the rect are scaled up, all other sources have to be
+    /// rendered at the current scale, see blend_slice_safe_scaled() for sources at other scales.
     pub fn blend_slice_safe(
         &self,
         dest: &mut [u8],
@@ -1214,6 +1585,33 @@ impl TheDraw2D {
         dest_stride: usize,
         safe_rect: &(usize, usize, usize, usize),
     ) {
+        let source_scale = self.source_scale(source.len(), (rect.2, rect.3), 4);
+        self.blend_slice_safe_scaled(dest, source, source_scale, rect, dest_stride, safe_rect);
+    }
+
+    /// Blends rect from the source frame into the dest frame and honors the safe rect.
+    /// The source was rendered at the given scale (like the 2x variant of an icon) and is
+    /// resampled if the scale differs from the current scale.
+    pub fn blend_slice_safe_scaled(
+        &self,
+        dest: &mut [u8],
+        source: &[u8],
+        source_scale: f32,
+        rect: &(isize, isize, usize, usize),
+        dest_stride: usize,
+        safe_rect: &(usize, usize, usize, usize),
+    ) {
+        let (physical, dest_stride) = (self.physical_irect(rect), self.physical(dest_stride));
+        let source = self.physical_source(
+            source,
+            (rect.2, rect.3),
+            source_scale,
+            (physical.2, physical.3),
+            4,
+        );
+        let (source, rect) = (&*source, &physical);
+        let safe_rect = &self.physical_rect(safe_rect);
+
         let dest_stride_isize = dest_stride as isize;
         for y in 0..rect.3 as isize {
             let d = rect.0 * 4 + (y + rect.1) * dest_stride_isize * 4;
@@ -1254,6 +1652,8 @@ impl TheDraw2D {
         source_size: &(usize, usize),
         blend_factor: f32,
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+
         let x_ratio = source_size.0 as f32 / rect.2 as f32;
         let y_ratio = source_size.1 as f32 / rect.3 as f32;
 
@@ -1285,6 +1685,8 @@ impl TheDraw2D {
         source_frame: &[u8],
         source_size: &(usize, usize),
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+
         let x_ratio = source_size.0 as f32 / rect.2 as f32;
         let y_ratio = source_size.1 as f32 / rect.3 as f32;
 
@@ -1323,6 +1725,8 @@ impl TheDraw2D {
         source_size: &(usize, usize),
         alpha: f32,
     ) {
+        let (rect, stride) = (&self.physical_rect(rect), self.physical(stride));
+
         let x_ratio = source_size.0 as f32 / rect.2 as f32;
         let y_ratio = source_size.1 as f32 / rect.3 as f32;
 
@@ -1360,6 +1764,8 @@ impl TheDraw2D {
         source: &[u8],
         source_size: &(usize, usize),
     ) {
+        let (dest_rect, dest_stride) = (&self.physical_rect(dest_rect), self.physical(dest_stride));
+
         let x_ratio = (source_size.0 - 1) as f32 / dest_rect.2 as f32;
         let y_ratio = (source_size.1 - 1) as f32 / dest_rect.3 as f32;
 
@@ -0,0 +1,364 @@
+use crate::prelude::*;
+
+/// A scripted input event for TheHeadlessApp. Coordinates are in logical pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TheHeadlessEvent {
+    TouchDown(f32, f32),
+    TouchDragged(f32, f32),
+    TouchUp(f32, f32),
+    Hover(f32, f32),
+    /// A right click at the given position.
+    Context(f32, f32),
+    KeyDown(Option<char>, Option<TheKeyCode>),
+    KeyUp(Option<char>, Option<TheKeyCode>),
+    MouseWheel(i32, i32),
+    /// Shift, Ctrl, Alt, Logo.
+    ModifierChanged(bool, bool, bool, bool),
+    DroppedFile(String),
+    /// Runs the given amount of update / draw cycles.
+    Frames(usize),
+    /// Draws a frame and stores it in the captured frames of the runner.
+    Capture,
+}
+
+/// Runs a TheTrait based app without a window. The runner owns TheContext and the pixel buffer and
+/// drives the app in the same order as the winit backend, which makes it possible to test apps in CI.
+pub struct TheHeadlessApp {
+    pub ctx: TheContext,
+    pub app: Box<dyn TheTrait>,
+
+    #[cfg(feature = "ui")]
+    pub ui: TheUI,
+
+    pixels: Vec<u8>,
+    captures: Vec<TheRGBABuffer>,
+    damage: TheDamage,
+
+    left_mouse_down: bool,
+}
+
+impl TheHeadlessApp {
+    /// Creates a headless runner of the given logical size and initializes the app.
+    pub fn new(width: usize, height: usize, app: Box<dyn TheTrait>) -> Self {
+        Self::with_args(width, height, None, app)
+    }
+
+    /// Creates a headless runner and passes the optional command line arguments to the app.
+    pub fn with_args(
+        width: usize,
+        height: usize,
+        args: Option<Vec<String>>,
+        app: Box<dyn TheTrait>,
+    ) -> Self {
+        Self::with_scale_factor(width, height, 1.0, args, app)
+    }
+
+    /// Creates a headless runner which renders at the given scale factor, like a window on a
+    /// HiDPI display. The frame has the physical size, input coordinates stay logical.
+    pub fn with_scale_factor(
+        width: usize,
+        height: usize,
+        scale_factor: f32,
+        args: Option<Vec<String>>,
+        app: Box<dyn TheTrait>,
+    ) -> Self {
+        let ctx = TheContext::new(width, height, scale_factor);
+        let pixels = vec![0; ctx.physical_width() * ctx.physical_height() * 4];
+
+        let mut headless = Self {
+            ctx,
+            app,
+
+            #[cfg(feature = "ui")]
+            ui: TheUI::new(),
+
+            pixels,
+            captures: vec![],
+            damage: TheDamage::Full,
+
+            left_mouse_down: false,
+        };
+
+        if let Some(args) = &args {
+            headless.app.set_cmd_line_args_early(args.clone());
+        }
+
+        #[cfg(feature = "ui")]
+        {
+            let ctx = &mut headless.ctx;
+            headless.ui.init(ctx);
+
+            headless.ui.canvas.root = true;
+            headless
+                .ui
+                .canvas
+                .set_dim(TheDim::new(0, 0, width as i32, height as i32), ctx);
+
+            headless.app.init_ui(&mut headless.ui, ctx);
+            headless.ui.canvas.layout(width as i32, height as i32, ctx);
+        }
+
+        headless.app.init(&mut headless.ctx);
+
+        if let Some(args) = args {
+            headless.app.set_cmd_line_args(args, &mut headless.ctx);
+        }
+
+        headless
+    }
+
+    /// Resizes the virtual window.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        self.ctx.width = width;
+        self.ctx.height = height;
+        self.ctx.damage_all();
+        self.pixels.resize(
+            self.ctx.physical_width() * self.ctx.physical_height() * 4,
+            0,
+        );
+
+        #[cfg(feature = "ui")]
+        {
+            self.ui.canvas.set_dim(
+                TheDim::new(0, 0, width as i32, height as i32),
+                &mut self.ctx,
+            );
+            self.ctx.ui.send(TheEvent::Resize);
+        }
+    }
+
+    /// Gives the app and the UI a chance to update, returns true if a redraw was requested.
+    pub fn update(&mut self) -> bool {
+        let mut redraw = false;
+
+        #[cfg(feature = "ui")]
+        {
+            if self.ui.update(&mut self.ctx) {
+                redraw = true;
+            }
+
+            if self.app.update_ui(&mut self.ui, &mut self.ctx) {
+                redraw = true;
+            }
+        }
+
+        if self.app.update(&mut self.ctx) {
+            redraw = true;
+        }
+
+        redraw
+    }
+
+    /// Draws a frame into the pixel buffer.
+    pub fn draw(&mut self) {
+        if self.ctx.width == 0 || self.ctx.height == 0 {
+            return;
+        }
+
+        #[cfg(feature = "ui")]
+        {
+            self.app.pre_ui(&mut self.ctx);
+            self.ui.draw(&mut self.pixels, &mut self.ctx);
+        }
+
+        self.app.draw(&mut self.pixels, &mut self.ctx);
+        if !self.app.reports_damage() {
+            self.ctx.damage_all();
+        }
+        self.damage = self.ctx.take_damage();
+
+        #[cfg(feature = "ui")]
+        self.app.post_ui(&mut self.ctx);
+    }
+
+    /// Runs one update / draw cycle.
+    pub fn step(&mut self) {
+        self.update();
+        self.draw();
+    }
+
+    /// Dispatches the given event to the UI and the app, returns true if a redraw was requested.
+    pub fn dispatch(&mut self, event: &TheHeadlessEvent) -> bool {
+        let mut redraw = false;
+        let ctx = &mut self.ctx;
+
+        match event {
+            TheHeadlessEvent::TouchDown(x, y) => {
+                self.left_mouse_down = true;
+
+                #[cfg(feature = "ui")]
+                if self.ui.touch_down(*x, *y, ctx) {
+                    redraw = true;
+                }
+                if self.app.touch_down(*x, *y, ctx) {
+                    redraw = true;
+                }
+            }
+            TheHeadlessEvent::TouchDragged(x, y) => {
+                #[cfg(feature = "ui")]
+                if self.ui.touch_dragged(*x, *y, ctx) {
+                    redraw = true;
+                }
+                if self.app.touch_dragged(*x, *y, ctx) {
+                    redraw = true;
+                }
+            }
+            TheHeadlessEvent::TouchUp(x, y) => {
+                self.left_mouse_down = false;
+
+                #[cfg(feature = "ui")]
+                if self.ui.touch_up(*x, *y, ctx) {
+                    redraw = true;
+                }
+                if self.app.touch_up(*x, *y, ctx) {
+                    redraw = true;
+                }
+            }
+            TheHeadlessEvent::Hover(x, y) => {
+                // Same as the winit backend: a move with the left button pressed is a drag.
+                if self.left_mouse_down {
+                    return self.dispatch(&TheHeadlessEvent::TouchDragged(*x, *y));
+                }
+
+                #[cfg(feature = "ui")]
+                if self.ui.hover(*x, *y, ctx) {
+                    redraw = true;
+                }
+                if self.app.hover(*x, *y, ctx) {
+                    redraw = true;
+                }
+            }
+            TheHeadlessEvent::Context(x, y) => {
+                #[cfg(feature = "ui")]
+                if self.ui.context(*x, *y, ctx) {
+                    redraw = true;
+                }
+                if self.app.touch_down(*x, *y, ctx) {
+                    redraw = true;
+                }
+            }
+            TheHeadlessEvent::KeyDown(char, key) => {
+                if char.is_none() && key.is_none() {
+                    return false;
+                }
+
+                #[cfg(feature = "ui")]
+                if self.ui.key_down(*char, *key, ctx) {
+                    redraw = true;
+                }
+                if self.app.key_down(*char, *key, ctx) {
+                    redraw = true;
+                }
+            }
+            TheHeadlessEvent::KeyUp(char, key) => {
+                if char.is_none() && key.is_none() {
+                    return false;
+                }
+
+                #[cfg(feature = "ui")]
+                if self.ui.key_up(*char, *key, ctx) {
+                    redraw = true;
+                }
+                if self.app.key_up(*char, *key, ctx) {
+                    redraw = true;
+                }
+            }
+            TheHeadlessEvent::MouseWheel(x, y) => {
+                #[cfg(feature = "ui")]
+                if self.ui.mouse_wheel((*x, *y), ctx) {
+                    redraw = true;
+                }
+                if self.app.mouse_wheel((*x as isize, *y as isize), ctx) {
+                    redraw = true;
+                }
+            }
+            TheHeadlessEvent::ModifierChanged(shift, ctrl, alt, logo) => {
+                #[cfg(feature = "ui")]
+                if self.ui.modifier_changed(*shift, *ctrl, *alt, *logo, ctx) {
+                    redraw = true;
+                }
+                if self.app.modifier_changed(*shift, *ctrl, *alt, *logo) {
+                    redraw = true;
+                }
+            }
+            TheHeadlessEvent::DroppedFile(path) => {
+                self.app.dropped_file(path.clone());
+                redraw = true;
+            }
+            TheHeadlessEvent::Frames(count) => {
+                for _ in 0..*count {
+                    self.step();
+                }
+            }
+            TheHeadlessEvent::Capture => {
+                self.step();
+                let frame = self.frame();
+                self.captures.push(frame);
+            }
+        }
+
+        redraw
+    }
+
+    /// Runs the given script. Every event is followed by an update / draw cycle, the same way the
+    /// winit backend redraws after user input.
+    pub fn run_script(&mut self, script: &[TheHeadlessEvent]) {
+        for event in script {
+            match event {
+                TheHeadlessEvent::Frames(_) | TheHeadlessEvent::Capture => {
+                    self.dispatch(event);
+                }
+                _ => {
+                    self.dispatch(event);
+                    self.step();
+                }
+            }
+        }
+    }
+
+    /// Returns the raw RGBA pixels of the last drawn frame, at physical resolution.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the regions changed by the last drawn frame, the regions a window backend presents.
+    pub fn damage(&self) -> &TheDamage {
+        &self.damage
+    }
+
+    /// Returns the last drawn frame as a TheRGBABuffer.
+    pub fn frame(&self) -> TheRGBABuffer {
+        TheRGBABuffer::from_scaled(
+            self.pixels.clone(),
+            self.ctx.physical_width() as u32,
+            self.ctx.physical_height() as u32,
+            self.ctx.scale_factor,
+        )
+    }
+
+    /// Returns a textual dump of the accessibility tree of the UI, see TheAccessTree::dump().
+    #[cfg(feature = "ui")]
+    pub fn accessibility_dump(&mut self) -> String {
+        self.ui.accessibility_tree(&self.ctx).dump()
+    }
+
+    /// Returns the frames captured via TheHeadlessEvent::Capture.
+    pub fn captures(&self) -> &Vec<TheRGBABuffer> {
+        &self.captures
+    }
+
+    /// Takes the captured frames out of the runner.
+    pub fn take_captures(&mut self) -> Vec<TheRGBABuffer> {
+        std::mem::take(&mut self.captures)
+    }
+
+    /// Writes the last drawn frame as a PNG file.
+    pub fn save_png(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let data = self.frame().to_png().map_err(std::io::Error::other)?;
+        std::fs::write(path, data)
+    }
+}
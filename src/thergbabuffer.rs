@@ -4,6 +4,7 @@ use fontdue::layout::{
     CoordinateSystem, HorizontalAlign, Layout, LayoutSettings, TextStyle, VerticalAlign,
 };
 use png::{BitDepth, ColorType, Encoder};
+use std::borrow::Cow;
 use std::ops::{Index, IndexMut, Range};
 
 use rayon::prelude::*;
@@ -13,10 +14,18 @@ use rayon::slice::ParallelSliceMut;
 pub struct TheRGBABuffer {
     dim: TheDim,
 
+    /// The scale is a runtime property of render targets, it is not serialized.
+    #[serde(skip, default = "default_scale")]
+    scale: f32,
+
     #[serde(serialize_with = "compress", deserialize_with = "decompress")]
     buffer: Vec<u8>,
 }
 
+fn default_scale() -> f32 {
+    1.0
+}
+
 impl Default for TheRGBABuffer {
     fn default() -> Self {
         Self::empty()
@@ -24,31 +33,66 @@ impl Default for TheRGBABuffer {
 }
 
 /// TheRGBABuffer contains the pixel buffer for a canvas or icon.
+///
+/// The dimension of the buffer is in logical units, the pixels are stored at physical resolution,
+/// the logical size multiplied by the scale of the buffer. Copy and blend operations between
+/// buffers take logical coordinates and resample buffers of a different scale, pixel level access
+/// (like get_pixel, set_pixel or to_png) works on the physical pixels.
 impl TheRGBABuffer {
     /// Create an empty buffer.
     pub fn empty() -> Self {
         Self {
             dim: TheDim::zero(),
+            scale: 1.0,
             buffer: vec![],
         }
     }
 
     /// Creates a buffer of the given dimension.
     pub fn new(dim: TheDim) -> Self {
-        Self {
+        Self::new_scaled(dim, 1.0)
+    }
+
+    /// Creates a buffer of the given logical dimension which stores its pixels at the given scale.
+    pub fn new_scaled(dim: TheDim, scale: f32) -> Self {
+        let mut buffer = Self {
             dim,
-            buffer: vec![0; dim.width as usize * dim.height as usize * 4],
-        }
+            scale,
+            buffer: vec![],
+        };
+        buffer.allocate();
+        buffer
     }
 
     /// Creates a buffer from existing data.
     pub fn from(buffer: Vec<u8>, width: u32, height: u32) -> Self {
         Self {
             dim: TheDim::new(0, 0, width as i32, height as i32),
+            scale: 1.0,
             buffer,
         }
     }
 
+    /// Creates a buffer from existing data which was rendered at the given scale, like a @2x
+    /// icon. Width and height are the size of the data in physical pixels.
+    pub fn from_scaled(buffer: Vec<u8>, width: u32, height: u32, scale: f32) -> Self {
+        let source = Self::from(buffer, width, height);
+        let dim = TheDim::sized(
+            ((width as f32 / scale).round() as i32).max(1),
+            ((height as f32 / scale).round() as i32).max(1),
+        );
+
+        let mut scaled = Self::new_scaled(dim, scale);
+        if scaled.physical_width() == source.dim.width
+            && scaled.physical_height() == source.dim.height
+        {
+            scaled.buffer = source.buffer;
+        } else {
+            source.scaled_into(&mut scaled);
+        }
+        scaled
+    }
+
     /// Resizes the buffer.
     pub fn resize(&mut self, width: i32, height: i32) {
         if self.dim.width != width || self.dim.height != height {
@@ -73,11 +117,63 @@ impl TheRGBABuffer {
         &mut self.dim
     }
 
-    /// Gets the width (stride) of the buffer.
+    /// Gets the logical width (stride) of the buffer, as expected by TheDraw2D.
     pub fn stride(&self) -> usize {
         self.dim.width as usize
     }
 
+    /// Gets the scale of the buffer, the ratio between physical and logical pixels.
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Sets the scale of the buffer and reallocates the pixels if the scale changed.
+    pub fn set_scale(&mut self, scale: f32) {
+        if scale != self.scale {
+            self.scale = scale;
+            self.allocate();
+        }
+    }
+
+    /// Gets the width of the buffer in physical pixels.
+    pub fn physical_width(&self) -> i32 {
+        self.physical(self.dim.width)
+    }
+
+    /// Gets the height of the buffer in physical pixels.
+    pub fn physical_height(&self) -> i32 {
+        self.physical(self.dim.height)
+    }
+
+    /// Converts a logical coordinate or length of the buffer to physical pixels.
+    fn physical(&self, value: i32) -> i32 {
+        if self.scale == 1.0 {
+            value
+        } else {
+            (value as f32 * self.scale).round() as i32
+        }
+    }
+
+    /// Returns the buffer at the given scale, resampling it if its scale differs.
+    pub fn to_scale(&self, scale: f32) -> Self {
+        if scale == self.scale {
+            return self.clone();
+        }
+
+        let mut scaled = Self::new_scaled(self.dim, scale);
+        self.scaled_into(&mut scaled);
+        scaled
+    }
+
+    /// Returns the other buffer at the scale of this buffer.
+    fn matching<'a>(&self, other: &'a TheRGBABuffer) -> Cow<'a, TheRGBABuffer> {
+        if other.scale == self.scale {
+            Cow::Borrowed(other)
+        } else {
+            Cow::Owned(other.to_scale(self.scale))
+        }
+    }
+
     /// Gets a slice of the buffer.
     pub fn pixels(&self) -> &[u8] {
         &self.buffer[..]
@@ -103,30 +199,35 @@ impl TheRGBABuffer {
 
     /// Returns the len of the underlying Vec<u8>
     pub fn len(&self) -> usize {
-        self.dim.width as usize * self.dim.height as usize * 4
+        self.physical_width() as usize * self.physical_height() as usize * 4
     }
 
     /// Allocates the buffer.
     pub fn allocate(&mut self) {
         if self.dim.is_valid() {
-            self.buffer = vec![0; self.dim.width as usize * self.dim.height as usize * 4];
+            self.buffer = vec![0; self.len()];
         } else {
             self.buffer = vec![];
         }
     }
 
-    /// Extracts a sub-buffer of given dimensions from the current buffer.
+    /// Extracts a sub-buffer of given logical dimensions from the current buffer.
     pub fn extract(&self, dim: &TheDim) -> Self {
-        let mut new_buffer = Self::new(*dim);
-
-        for y in 0..dim.height {
-            for x in 0..dim.width {
-                let src_x = dim.x + x;
-                let src_y = dim.y + y;
-
-                if src_x >= 0 && src_x < self.dim.width && src_y >= 0 && src_y < self.dim.height {
-                    let src_index = ((src_y * self.dim.width) + src_x) as usize * 4;
-                    let dest_index = ((y * dim.width) + x) as usize * 4;
+        let mut new_buffer = Self::new_scaled(*dim, self.scale);
+        let (x0, y0) = (self.physical(dim.x), self.physical(dim.y));
+
+        for y in 0..new_buffer.physical_height() {
+            for x in 0..new_buffer.physical_width() {
+                let src_x = x0 + x;
+                let src_y = y0 + y;
+
+                if src_x >= 0
+                    && src_x < self.physical_width()
+                    && src_y >= 0
+                    && src_y < self.physical_height()
+                {
+                    let src_index = ((src_y * self.physical_width()) + src_x) as usize * 4;
+                    let dest_index = ((y * new_buffer.physical_width()) + x) as usize * 4;
 
                     if src_index + 3 < self.buffer.len() && dest_index + 3 < new_buffer.buffer.len()
                     {
@@ -141,12 +242,18 @@ impl TheRGBABuffer {
     }
 
     /// Copy the other buffer into this buffer at the given coordinates.
-    pub fn copy_into(&mut self, mut x: i32, mut y: i32, other: &TheRGBABuffer) {
+    pub fn copy_into(&mut self, x: i32, y: i32, other: &TheRGBABuffer) {
+        let other = self.matching(other);
+        self.physical_copy_into(self.physical(x), self.physical(y), &other);
+    }
+
+    /// Physical pixel version of `copy_into`.
+    fn physical_copy_into(&mut self, mut x: i32, mut y: i32, other: &TheRGBABuffer) {
         // Early return if the whole other buffer is outside this buffer
-        if x + other.dim.width <= 0
-            || y + other.dim.height <= 0
-            || x >= self.dim.width
-            || y >= self.dim.height
+        if x + other.physical_width() <= 0
+            || y + other.physical_height() <= 0
+            || x >= self.physical_width()
+            || y >= self.physical_height()
         {
             return;
         }
@@ -154,8 +261,8 @@ impl TheRGBABuffer {
         // Adjust source and destination coordinates and dimensions
         let mut source_offset_x = 0;
         let mut source_y_start = 0;
-        let mut copy_width = other.dim.width;
-        let mut copy_height = other.dim.height;
+        let mut copy_width = other.physical_width();
+        let mut copy_height = other.physical_height();
 
         // Adjust for negative x
         if x < 0 {
@@ -172,13 +279,13 @@ impl TheRGBABuffer {
         }
 
         // Adjust for width overflow
-        if x + copy_width > self.dim.width {
-            copy_width = self.dim.width - x;
+        if x + copy_width > self.physical_width() {
+            copy_width = self.physical_width() - x;
         }
 
         // Adjust for height overflow
-        if y + copy_height > self.dim.height {
-            copy_height = self.dim.height - y;
+        if y + copy_height > self.physical_height() {
+            copy_height = self.physical_height() - y;
         }
 
         // Calculate the byte width to copy per row
@@ -186,8 +293,9 @@ impl TheRGBABuffer {
 
         // Copy the buffer
         for src_y in source_y_start..source_y_start + copy_height {
-            let src_start = (src_y * other.dim.width * 4) as usize + source_offset_x;
-            let dst_start = ((src_y + y - source_y_start) * self.dim.width * 4 + x * 4) as usize;
+            let src_start = (src_y * other.physical_width() * 4) as usize + source_offset_x;
+            let dst_start =
+                ((src_y + y - source_y_start) * self.physical_width() * 4 + x * 4) as usize;
 
             // Perform the copy
             self.buffer[dst_start..dst_start + byte_width]
@@ -197,12 +305,18 @@ impl TheRGBABuffer {
 
     /// Parallel version of `copy_into` using Rayon. Has identical clipping/safety behavior.
     /// Enabled when the `rayon` feature is on. When the feature is off, it falls back to the serial version.
-    pub fn copy_into_par(&mut self, mut x: i32, mut y: i32, other: &TheRGBABuffer) {
+    pub fn copy_into_par(&mut self, x: i32, y: i32, other: &TheRGBABuffer) {
+        let other = self.matching(other);
+        self.physical_copy_into_par(self.physical(x), self.physical(y), &other);
+    }
+
+    /// Physical pixel version of `copy_into_par`.
+    fn physical_copy_into_par(&mut self, mut x: i32, mut y: i32, other: &TheRGBABuffer) {
         // Early return if the whole other buffer is outside this buffer
-        if x + other.dim.width <= 0
-            || y + other.dim.height <= 0
-            || x >= self.dim.width
-            || y >= self.dim.height
+        if x + other.physical_width() <= 0
+            || y + other.physical_height() <= 0
+            || x >= self.physical_width()
+            || y >= self.physical_height()
         {
             return;
         }
@@ -210,8 +324,8 @@ impl TheRGBABuffer {
         // Adjust source and destination coordinates and dimensions (same as serial)
         let mut source_offset_x: usize = 0;
         let mut source_y_start: i32 = 0;
-        let mut copy_width: i32 = other.dim.width;
-        let mut copy_height: i32 = other.dim.height;
+        let mut copy_width: i32 = other.physical_width();
+        let mut copy_height: i32 = other.physical_height();
 
         if x < 0 {
             source_offset_x = (-x * 4) as usize;
@@ -223,11 +337,11 @@ impl TheRGBABuffer {
             copy_height += y;
             y = 0;
         }
-        if x + copy_width > self.dim.width {
-            copy_width = self.dim.width - x;
+        if x + copy_width > self.physical_width() {
+            copy_width = self.physical_width() - x;
         }
-        if y + copy_height > self.dim.height {
-            copy_height = self.dim.height - y;
+        if y + copy_height > self.physical_height() {
+            copy_height = self.physical_height() - y;
         }
 
         if copy_width <= 0 || copy_height <= 0 {
@@ -235,8 +349,8 @@ impl TheRGBABuffer {
         }
 
         let byte_width: usize = (copy_width * 4) as usize;
-        let dst_row_stride: usize = (self.dim.width * 4) as usize;
-        let src_row_stride: usize = (other.dim.width * 4) as usize;
+        let dst_row_stride: usize = (self.physical_width() * 4) as usize;
+        let src_row_stride: usize = (other.physical_width() * 4) as usize;
 
         // FAST PATH: if we copy whole rows at x==0 and the copy width equals the destination stride,
         // do a single contiguous memcpy instead of per-row copies.
@@ -255,7 +369,7 @@ impl TheRGBABuffer {
         let total_bytes = byte_width.saturating_mul(copy_height as usize);
         const PAR_THRESHOLD: usize = 2 * 1024 * 1024; // 2 MiB
         if total_bytes < PAR_THRESHOLD {
-            return self.copy_into(x, y, other);
+            return self.physical_copy_into(x, y, other);
         }
 
         // Parallel over destination rows using disjoint mutable chunks.
@@ -296,12 +410,18 @@ impl TheRGBABuffer {
     }
 
     /// Blend the other buffer into this buffer at the given coordinates (single-threaded reference path).
-    pub fn blend_into(&mut self, mut x: i32, mut y: i32, other: &TheRGBABuffer) {
+    pub fn blend_into(&mut self, x: i32, y: i32, other: &TheRGBABuffer) {
+        let other = self.matching(other);
+        self.physical_blend_into(self.physical(x), self.physical(y), &other);
+    }
+
+    /// Physical pixel version of `blend_into`.
+    fn physical_blend_into(&mut self, mut x: i32, mut y: i32, other: &TheRGBABuffer) {
         // Early return if the whole other buffer is outside this buffer
-        if x + other.dim.width <= 0
-            || y + other.dim.height <= 0
-            || x >= self.dim.width
-            || y >= self.dim.height
+        if x + other.physical_width() <= 0
+            || y + other.physical_height() <= 0
+            || x >= self.physical_width()
+            || y >= self.physical_height()
         {
             return;
         }
@@ -309,8 +429,8 @@ impl TheRGBABuffer {
         // Adjust source and destination coordinates and dimensions
         let mut source_offset_x = 0;
         let mut source_y_start = 0;
-        let mut copy_width = other.dim.width;
-        let mut copy_height = other.dim.height;
+        let mut copy_width = other.physical_width();
+        let mut copy_height = other.physical_height();
 
         // Adjust for negative x
         if x < 0 {
@@ -327,19 +447,20 @@ impl TheRGBABuffer {
         }
 
         // Adjust for width overflow
-        if x + copy_width > self.dim.width {
-            copy_width = self.dim.width - x;
+        if x + copy_width > self.physical_width() {
+            copy_width = self.physical_width() - x;
         }
 
         // Adjust for height overflow
-        if y + copy_height > self.dim.height {
-            copy_height = self.dim.height - y;
+        if y + copy_height > self.physical_height() {
+            copy_height = self.physical_height() - y;
         }
 
         // Blend the buffer
         for src_y in source_y_start..source_y_start + copy_height {
-            let src_start = (src_y * other.dim.width * 4) as usize + source_offset_x;
-            let dst_start = ((src_y + y - source_y_start) * self.dim.width * 4 + x * 4) as usize;
+            let src_start = (src_y * other.physical_width() * 4) as usize + source_offset_x;
+            let dst_start =
+                ((src_y + y - source_y_start) * self.physical_width() * 4 + x * 4) as usize;
 
             for i in 0..copy_width {
                 let src_idx = src_start + i as usize * 4;
@@ -365,12 +486,18 @@ impl TheRGBABuffer {
 
     /// Blend the other buffer into this buffer at the given coordinates (adaptive parallel version).
     /// Falls back to the single-threaded path for small regions to avoid overhead.
-    pub fn blend_into_par(&mut self, mut x: i32, mut y: i32, other: &TheRGBABuffer) {
+    pub fn blend_into_par(&mut self, x: i32, y: i32, other: &TheRGBABuffer) {
+        let other = self.matching(other);
+        self.physical_blend_into_par(self.physical(x), self.physical(y), &other);
+    }
+
+    /// Physical pixel version of `blend_into_par`.
+    fn physical_blend_into_par(&mut self, mut x: i32, mut y: i32, other: &TheRGBABuffer) {
         // Early out if completely outside
-        if x + other.dim.width <= 0
-            || y + other.dim.height <= 0
-            || x >= self.dim.width
-            || y >= self.dim.height
+        if x + other.physical_width() <= 0
+            || y + other.physical_height() <= 0
+            || x >= self.physical_width()
+            || y >= self.physical_height()
         {
             return;
         }
@@ -378,8 +505,8 @@ impl TheRGBABuffer {
         // Clipping (same as single-threaded)
         let mut source_offset_x: usize = 0;
         let mut source_y_start: i32 = 0;
-        let mut copy_width: i32 = other.dim.width;
-        let mut copy_height: i32 = other.dim.height;
+        let mut copy_width: i32 = other.physical_width();
+        let mut copy_height: i32 = other.physical_height();
 
         if x < 0 {
             source_offset_x = (-x * 4) as usize;
@@ -391,25 +518,25 @@ impl TheRGBABuffer {
             copy_height += y;
             y = 0;
         }
-        if x + copy_width > self.dim.width {
-            copy_width = self.dim.width - x;
+        if x + copy_width > self.physical_width() {
+            copy_width = self.physical_width() - x;
         }
-        if y + copy_height > self.dim.height {
-            copy_height = self.dim.height - y;
+        if y + copy_height > self.physical_height() {
+            copy_height = self.physical_height() - y;
         }
         if copy_width <= 0 || copy_height <= 0 {
             return;
         }
 
         let byte_width: usize = (copy_width * 4) as usize;
-        let dst_row_stride: usize = (self.dim.width * 4) as usize;
-        let src_row_stride: usize = (other.dim.width * 4) as usize;
+        let dst_row_stride: usize = (self.physical_width() * 4) as usize;
+        let src_row_stride: usize = (other.physical_width() * 4) as usize;
 
         // Threshold: only parallelize very large blends
         let total_bytes = byte_width.saturating_mul(copy_height as usize);
         const PAR_THRESHOLD: usize = 2 * 1024 * 1024; // 2 MiB
         if total_bytes < PAR_THRESHOLD {
-            return self.blend_into(x, y, other);
+            return self.physical_blend_into(x, y, other);
         }
 
         // Parallel over destination rows using disjoint mutable chunks, batched for lower overhead
@@ -476,19 +603,25 @@ impl TheRGBABuffer {
         other: &TheRGBABuffer,
         range: Range<i32>,
     ) {
+        let other = &*self.matching(other);
+        let (x, y) = (self.physical(x), self.physical(y));
+        let range = self.physical(range.start)..self.physical(range.end);
+
+        let stride = self.physical_width() * 4;
+        let dest_width = self.physical_width();
+        let dest_height = self.physical_height();
         let dest = &mut self.buffer[..];
-        let height = other.dim.height as usize;
-        let stride = self.dim.width * 4;
+        let height = other.physical_height().min(dest_height - y).max(0) as usize;
 
         for (dw, w) in range.enumerate() {
-            if w >= other.dim.width {
+            if w >= other.physical_width() || x + dw as i32 >= dest_width {
                 break;
             }
             let s_start = (w * 4) as usize;
             let d_start = ((x + dw as i32) * 4) as usize;
 
             for h in 0..height {
-                let s = s_start + h * other.dim().width as usize * 4;
+                let s = s_start + h * other.physical_width() as usize * 4;
                 let d = d_start + ((y + h as i32) * stride) as usize;
                 dest[d..d + 4].copy_from_slice(&other.buffer[s..s + 4]);
             }
@@ -503,23 +636,30 @@ impl TheRGBABuffer {
         other: &TheRGBABuffer,
         range: Range<i32>,
     ) {
+        let other = &*self.matching(other);
+        let (x, y) = (self.physical(x), self.physical(y));
+        let range = self.physical(range.start)..self.physical(range.end);
+
+        let stride = self.physical_width() * 4;
+        let height = self.physical_height();
         let dest = &mut self.buffer[..];
-        let width = (other.dim.width * 4) as usize;
+        // Clip rounding differences of fractional scales at the right border
+        let width = (other.physical_width() * 4).min(stride - x * 4) as usize;
 
         for (dh, h) in range.enumerate() {
-            if h >= other.dim.height {
+            if h >= other.physical_height() || dh as i32 + y >= height {
                 break;
             }
-            let s = (h * other.dim.width * 4) as usize;
-            let d = ((dh as i32 + y) * self.dim.width * 4 + x * 4) as usize;
+            let s = (h * other.physical_width() * 4) as usize;
+            let d = ((dh as i32 + y) * stride + x * 4) as usize;
             dest[d..d + width].copy_from_slice(&other.buffer[s..s + width]);
         }
     }
 
     /// Creates a scaled version of the buffer.
     pub fn scaled(&self, new_width: i32, new_height: i32) -> Self {
-        let scale_x = new_width as f32 / self.dim.width as f32;
-        let scale_y = new_height as f32 / self.dim.height as f32;
+        let scale_x = new_width as f32 / self.physical_width() as f32;
+        let scale_y = new_height as f32 / self.physical_height() as f32;
 
         let mut new_buffer = TheRGBABuffer::new(TheDim::new(0, 0, new_width, new_height));
 
@@ -528,7 +668,7 @@ impl TheRGBABuffer {
                 let src_x = (x as f32 / scale_x).round() as i32;
                 let src_y = (y as f32 / scale_y).round() as i32;
 
-                let pixel_index = (src_y * self.dim.width + src_x) as usize * 4;
+                let pixel_index = (src_y * self.physical_width() + src_x) as usize * 4;
                 let new_pixel_index = (y * new_width + x) as usize * 4;
 
                 if pixel_index < self.buffer.len() && new_pixel_index < new_buffer.buffer.len() {
@@ -543,18 +683,18 @@ impl TheRGBABuffer {
 
     /// Creates a scaled version of the buffer by writing into the other buffer.
     pub fn scaled_into(&self, into: &mut TheRGBABuffer) {
-        let new_width = into.dim().width;
-        let new_height = into.dim().height;
+        let new_width = into.physical_width();
+        let new_height = into.physical_height();
 
-        let scale_x = new_width as f32 / self.dim.width as f32;
-        let scale_y = new_height as f32 / self.dim.height as f32;
+        let scale_x = new_width as f32 / self.physical_width() as f32;
+        let scale_y = new_height as f32 / self.physical_height() as f32;
 
         for y in 0..new_height {
             for x in 0..new_width {
                 let src_x = (x as f32 / scale_x).round() as i32;
                 let src_y = (y as f32 / scale_y).round() as i32;
 
-                let pixel_index = (src_y * self.dim.width + src_x) as usize * 4;
+                let pixel_index = (src_y * self.physical_width() + src_x) as usize * 4;
                 let new_pixel_index = (y * new_width + x) as usize * 4;
 
                 if pixel_index < self.buffer.len() && new_pixel_index < into.buffer.len() {
@@ -567,11 +707,11 @@ impl TheRGBABuffer {
 
     /// Creates a scaled version of the buffer by writing into the other buffer.
     pub fn scaled_into_linear(&self, into: &mut TheRGBABuffer) {
-        let new_width = into.dim().width;
-        let new_height = into.dim().height;
+        let new_width = into.physical_width();
+        let new_height = into.physical_height();
 
-        let scale_x = self.dim.width as f32 / new_width as f32;
-        let scale_y = self.dim.height as f32 / new_height as f32;
+        let scale_x = self.physical_width() as f32 / new_width as f32;
+        let scale_y = self.physical_height() as f32 / new_height as f32;
 
         for y in 0..new_height {
             for x in 0..new_width {
@@ -580,16 +720,16 @@ impl TheRGBABuffer {
 
                 let src_x0 = src_x.floor() as i32;
                 let src_y0 = src_y.floor() as i32;
-                let src_x1 = (src_x0 + 1).min(self.dim.width - 1);
-                let src_y1 = (src_y0 + 1).min(self.dim.height - 1);
+                let src_x1 = (src_x0 + 1).min(self.physical_width() - 1);
+                let src_y1 = (src_y0 + 1).min(self.physical_height() - 1);
 
                 let t_x = src_x - src_x0 as f32;
                 let t_y = src_y - src_y0 as f32;
 
-                let pixel_index00 = (src_y0 * self.dim.width + src_x0) as usize * 4;
-                let pixel_index10 = (src_y0 * self.dim.width + src_x1) as usize * 4;
-                let pixel_index01 = (src_y1 * self.dim.width + src_x0) as usize * 4;
-                let pixel_index11 = (src_y1 * self.dim.width + src_x1) as usize * 4;
+                let pixel_index00 = (src_y0 * self.physical_width() + src_x0) as usize * 4;
+                let pixel_index10 = (src_y0 * self.physical_width() + src_x1) as usize * 4;
+                let pixel_index01 = (src_y1 * self.physical_width() + src_x0) as usize * 4;
+                let pixel_index11 = (src_y1 * self.physical_width() + src_x1) as usize * 4;
 
                 let new_pixel_index = (y * new_width + x) as usize * 4;
 
@@ -614,17 +754,17 @@ impl TheRGBABuffer {
         let new_width = dim.width;
         let new_height = dim.height;
 
-        let scale_x = new_width as f32 / self.dim.width as f32;
-        let scale_y = new_height as f32 / self.dim.height as f32;
+        let scale_x = new_width as f32 / self.physical_width() as f32;
+        let scale_y = new_height as f32 / self.physical_height() as f32;
 
         for y in 0..new_height {
             for x in 0..new_width {
                 let src_x = (x as f32 / scale_x).round() as i32;
                 let src_y = (y as f32 / scale_y).round() as i32;
 
-                let pixel_index = (src_y * self.dim.width + src_x) as usize * 4;
+                let pixel_index = (src_y * self.physical_width() + src_x) as usize * 4;
                 let new_pixel_index =
-                    ((y + dim.buffer_y) * into.stride() as i32 + x + dim.buffer_x) as usize * 4;
+                    ((y + dim.buffer_y) * into.physical_width() + x + dim.buffer_x) as usize * 4;
 
                 if pixel_index < self.buffer.len() && new_pixel_index < into.buffer.len() {
                     into.buffer[new_pixel_index..new_pixel_index + 4]
@@ -641,7 +781,7 @@ impl TheRGBABuffer {
 
         for y in 0..region.height as i32 {
             for x in 0..region.width as i32 {
-                let buffer_index = ((self.dim.y + region.y as i32 + y) * self.dim.width
+                let buffer_index = ((self.dim.y + region.y as i32 + y) * self.physical_width()
                     + self.dim.x
                     + region.x as i32
                     + x) as usize
@@ -669,8 +809,8 @@ impl TheRGBABuffer {
 
     /// Returns the pixel at the given UV coordinate as [f32;4]
     pub fn at_f_vec4f(&self, uv: Vec2<f32>) -> Option<Vec4<f32>> {
-        let x = (uv.x * self.dim.width as f32) as i32;
-        let y = (uv.y * self.dim.height as f32) as i32;
+        let x = (uv.x * self.physical_width() as f32) as i32;
+        let y = (uv.y * self.physical_height() as f32) as i32;
 
         self.pixel_index(x, y).map(|pixel_index| {
             Vec4::new(
@@ -684,11 +824,11 @@ impl TheRGBABuffer {
 
     /// Returns the pixel at the given UV coordinate.
     pub fn at_f(&self, uv: Vec2<f32>) -> Option<[u8; 4]> {
-        let x = (uv.x * self.dim.width as f32) as i32;
-        let y = (uv.y * self.dim.height as f32) as i32;
+        let x = (uv.x * self.physical_width() as f32) as i32;
+        let y = (uv.y * self.physical_height() as f32) as i32;
 
-        if x >= 0 && x < self.dim.width && y >= 0 && y < self.dim.height {
-            let pixel_index = (y * self.dim.width + x) as usize * 4;
+        if x >= 0 && x < self.physical_width() && y >= 0 && y < self.physical_height() {
+            let pixel_index = (y * self.physical_width() + x) as usize * 4;
             Some([
                 self.buffer[pixel_index],
                 self.buffer[pixel_index + 1],
@@ -705,8 +845,8 @@ impl TheRGBABuffer {
         let x = position.x;
         let y = position.y;
 
-        if x >= 0 && x < self.dim.width && y >= 0 && y < self.dim.height {
-            let pixel_index = (y * self.dim.width + x) as usize * 4;
+        if x >= 0 && x < self.physical_width() && y >= 0 && y < self.physical_height() {
+            let pixel_index = (y * self.physical_width() + x) as usize * 4;
             Some([
                 self.buffer[pixel_index],
                 self.buffer[pixel_index + 1],
@@ -722,8 +862,8 @@ impl TheRGBABuffer {
         let x = position.x;
         let y = position.y;
 
-        if x >= 0 && x < self.dim.width && y >= 0 && y < self.dim.height {
-            let pixel_index = (y * self.dim.width + x) as usize * 4;
+        if x >= 0 && x < self.physical_width() && y >= 0 && y < self.physical_height() {
+            let pixel_index = (y * self.physical_width() + x) as usize * 4;
             Some(Vec4::new(
                 (self.buffer[pixel_index] as f32) / 255.0,
                 (self.buffer[pixel_index + 1] as f32) / 255.0,
@@ -737,9 +877,9 @@ impl TheRGBABuffer {
 
     /// Fills the entire buffer with the given RGBA color.
     pub fn fill(&mut self, color: [u8; 4]) {
-        for y in 0..self.dim.height {
-            for x in 0..self.dim.width {
-                let index = (y * self.dim.width + x) as usize * 4;
+        for y in 0..self.physical_height() {
+            for x in 0..self.physical_width() {
+                let index = (y * self.physical_width() + x) as usize * 4;
                 // Check to make sure we don't write out of bounds
                 if index < self.buffer.len() {
                     self.buffer[index..index + 4].copy_from_slice(&color);
@@ -800,7 +940,7 @@ impl TheRGBABuffer {
         let end_x = x0.max(x1);
 
         // Ensure that the line is within bounds
-        if y < 0 || y >= self.dim.height || start_x >= self.dim.width {
+        if y < 0 || y >= self.physical_height() || start_x >= self.physical_width() {
             return;
         }
 
@@ -810,7 +950,7 @@ impl TheRGBABuffer {
         }
 
         for x in start_x..=end_x {
-            if x < self.dim.width {
+            if x < self.physical_width() {
                 self.set_pixel(x, y, &color);
             } else {
                 break;
@@ -824,7 +964,7 @@ impl TheRGBABuffer {
         let end_y = y0.max(y1);
 
         // Ensure that the line is within bounds
-        if x < 0 || x >= self.dim.width || start_y >= self.dim.height {
+        if x < 0 || x >= self.physical_width() || start_y >= self.physical_height() {
             return;
         }
 
@@ -834,7 +974,7 @@ impl TheRGBABuffer {
         }
 
         for y in start_y..=end_y {
-            if y < self.dim.height {
+            if y < self.physical_height() {
                 self.set_pixel(x, y, &color);
             } else {
                 break;
@@ -1015,8 +1155,8 @@ impl TheRGBABuffer {
 
         let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
         layout.reset(&LayoutSettings {
-            max_width: Some(self.dim.width as f32),
-            max_height: Some(self.dim.height as f32),
+            max_width: Some(self.physical_width() as f32),
+            max_height: Some(self.physical_height() as f32),
             horizontal_align: if halign == TheHorizontalAlign::Left {
                 HorizontalAlign::Left
             } else if halign == TheHorizontalAlign::Right {
@@ -1064,8 +1204,8 @@ impl TheRGBABuffer {
 
     /// Helper method to calculate the buffer index for a pixel at (x, y).
     pub fn pixel_index(&self, x: i32, y: i32) -> Option<usize> {
-        if x >= 0 && x < self.dim.width && y >= 0 && y < self.dim.height {
-            Some((y as usize * self.dim.width as usize + x as usize) * 4)
+        if x >= 0 && x < self.physical_width() && y >= 0 && y < self.physical_height() {
+            Some((y as usize * self.physical_width() as usize + x as usize) * 4)
         } else {
             None
         }
@@ -1094,8 +1234,8 @@ impl TheRGBABuffer {
     pub fn to_png(&self) -> Result<Vec<u8>, png::EncodingError> {
         let mut png_data = Vec::new();
         {
-            let width = self.dim.width as u32;
-            let height = self.dim.height as u32;
+            let width = self.physical_width() as u32;
+            let height = self.physical_height() as u32;
             let mut encoder = Encoder::new(&mut png_data, width, height);
             encoder.set_color(ColorType::Rgba);
             encoder.set_depth(BitDepth::Eight);
@@ -1108,8 +1248,8 @@ impl TheRGBABuffer {
 
     /// Draw an hsl hue waveform used by color pickers.
     pub fn render_hsl_hue_waveform(&mut self) {
-        let width = self.dim.width;
-        let height = self.dim.height;
+        let width = self.physical_width();
+        let height = self.physical_height();
 
         for x in 0..width {
             let fx = x as f32 / width as f32;
@@ -1185,8 +1325,8 @@ impl TheRGBABuffer {
         let mut best_pos = Vec2::zero();
         let mut best_dist = f32::MAX;
 
-        for y in 0..self.dim().height {
-            for x in 0..self.dim().width {
+        for y in 0..self.physical_height() {
+            for x in 0..self.physical_width() {
                 if let Some([r, g, b, _]) = self.get_pixel(x, y) {
                     let dist = color_distance_sq([r, g, b], target_rgb);
                     if dist < best_dist {
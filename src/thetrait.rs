@@ -35,8 +35,17 @@ pub trait TheTrait {
     #[cfg(feature = "ui")]
     fn init_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {}
 
+    /// Draws into the frame. The whole frame is presented after every draw unless the app
+    /// reports its damage, see reports_damage().
     fn draw(&mut self, pixels: &mut [u8], ctx: &mut TheContext) {}
 
+    /// Returns true if draw() reports every region it changes via ctx.add_damage() or
+    /// ctx.damage_all(), backends then only present the damaged regions of the frame. Apps which
+    /// only draw through TheUI can return true.
+    fn reports_damage(&self) -> bool {
+        false
+    }
+
     fn update(&mut self, ctx: &mut TheContext) -> bool {
         false
     }
@@ -96,6 +105,13 @@ pub trait TheTrait {
         false
     }
 
+    /// The user wants to close the additional window of the given id, see TheUI::open_window().
+    /// Return true to keep the window open.
+    #[cfg(feature = "ui")]
+    fn window_closing(&mut self, id: &TheId, ctx: &mut TheContext) -> bool {
+        false
+    }
+
     // Life Circles
 
     #[cfg(feature = "ui")]
@@ -1,31 +1,43 @@
+pub mod theaccessibility;
 pub mod thecanvas;
+pub mod thecanvasdescription;
 pub mod thecodehighlighter;
 pub mod thecollection;
 pub mod thecontextmenu;
+pub mod thedialog;
 pub mod thedrop;
 pub mod theflattenedmap;
+pub mod thehotreload;
 pub mod theid;
+pub mod thekeymap;
 pub mod thelayout;
 pub mod thenodeui;
 pub mod thergbbuffer;
 pub mod thesdf;
+pub mod theshortcuts;
 pub mod thesizelimiter;
+pub mod thesnapshot;
 pub mod thestyle;
 pub mod thetheme;
 pub mod thetilemask;
 pub mod thetimeline;
+pub mod thetoast;
+pub mod thetooltip;
 pub mod theuicontext;
 pub mod theuiglobals;
 pub mod theundo;
 pub mod thevalue;
 pub mod thevent;
 pub mod thewidget;
+pub mod thewindow;
 
 use ::serde::de::{self, Deserializer};
 use ::serde::ser::{self, Serializer};
 use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
 use std::io::{Read, Write};
+use std::path::Path;
 use std::sync::mpsc::{self, Receiver, Sender};
+use web_time::Instant;
 
 fn compress<S>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -72,10 +84,13 @@ pub mod prelude {
     pub use crate::theui::theid::TheId;
 
     pub use crate::theui::thecanvas::*;
+    pub use crate::theui::thecanvasdescription::*;
     pub use crate::theui::thecodehighlighter::{TheCodeHighlighter, TheCodeHighlighterTrait};
+    pub use crate::theui::thehotreload::*;
 
     pub use crate::theui::thergbbuffer::TheRGBBuffer;
     pub use crate::theui::thesizelimiter::TheSizeLimiter;
+    pub use crate::theui::thesnapshot::{TheSnapshot, TheSnapshotError};
     pub use crate::theui::theuicontext::*;
     pub use crate::theui::TheUI;
 
@@ -91,14 +106,21 @@ pub mod prelude {
     pub use crate::theui::thetheme::prelude::*;
     pub use crate::theui::thetheme::{TheTheme, TheThemeColors, TheThemeColors::*};
 
+    pub use crate::theui::thekeymap::TheKeymap;
     pub use crate::theui::thelayout::prelude::*;
     pub use crate::theui::thesdf::thepattern::ThePattern;
     pub use crate::theui::thesdf::thesdfcanvas::TheSDFCanvas;
     pub use crate::theui::thesdf::*;
+    pub use crate::theui::theshortcuts::*;
     pub use crate::theui::thewidget::TheWidget;
+    pub use crate::theui::thewindow::{TheWindow, TheWindowSettings};
 
+    pub use crate::theui::theaccessibility::*;
     pub use crate::theui::thecontextmenu::*;
+    pub use crate::theui::thedialog::*;
     pub use crate::theui::thedrop::*;
+    pub use crate::theui::thetoast::*;
+    pub use crate::theui::thetooltip::*;
     pub use crate::theui::theuiglobals::*;
     pub use crate::theui::theundo::*;
 
@@ -142,15 +164,59 @@ bitflags::bitflags! {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
-/// An accelerator for context menus and similar.
+/// An accelerator for context menus and similar. Create it with new() or new_key_code(), or
+/// with a struct literal ending in `..Default::default()`.
 pub struct TheAccelerator {
     pub accel: TheAcceleratorKey,
     pub key: char,
+    /// A named key (like F5 or PageUp). If set it is used instead of the char key.
+    pub key_code: Option<TheKeyCode>,
+}
+
+// Accelerators are stored in their keymap notation.
+
+impl Serialize for TheAccelerator {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_keymap_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for TheAccelerator {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let str = String::deserialize(deserializer)?;
+        TheAccelerator::from_keymap_str(&str)
+            .ok_or_else(|| de::Error::custom(format!("invalid accelerator \"{}\"", str)))
+    }
+}
+
+impl Default for TheAccelerator {
+    fn default() -> Self {
+        Self::new(TheAcceleratorKey::empty(), '\0')
+    }
 }
 
 impl TheAccelerator {
     pub fn new(accel: TheAcceleratorKey, key: char) -> Self {
-        Self { accel, key }
+        Self {
+            accel,
+            key,
+            key_code: None,
+        }
+    }
+
+    /// Creates an accelerator for a named key. Named keys can be used without modifiers.
+    pub fn new_key_code(accel: TheAcceleratorKey, key_code: TheKeyCode) -> Self {
+        Self {
+            accel,
+            key: '\0',
+            key_code: Some(key_code),
+        }
     }
 
     /// Converts the accelerator to a string.
@@ -178,55 +244,167 @@ impl TheAccelerator {
         }
 
         let mut s = str.to_string();
-        s += &self.key.to_string().to_uppercase();
+        if let Some(key_code) = &self.key_code {
+            s += &format!("{:?}", key_code);
+        } else {
+            s += &self.key.to_string().to_uppercase();
+        }
 
         s
     }
 
-    /// Test if we match the given modifiers and key.
-    pub fn matches(&self, shift: bool, ctrl: bool, alt: bool, logo: bool, key: char) -> bool {
-        #[allow(clippy::if_same_then_else)]
-        // We assume that accelerators are always case-insensitive.
-        if self.key == key.to_ascii_lowercase() {
-            if shift || ctrl || alt || logo {
-                let mut ok = true;
-
-                if (shift && !self.accel.contains(TheAcceleratorKey::SHIFT))
-                    || (!shift && self.accel.contains(TheAcceleratorKey::SHIFT))
-                {
-                    ok = false;
-                }
-                // Check Ctrl: Allow for cases where either Ctrl or Cmd is part of CtrlCmd
-                if (ctrl
-                    && !(self.accel.contains(TheAcceleratorKey::CTRL)
-                        || self.accel.contains(TheAcceleratorKey::CMD)))
-                    || (!ctrl
-                        && self.accel.contains(TheAcceleratorKey::CTRL)
-                        && !self.accel.contains(TheAcceleratorKey::CMD))
-                {
-                    ok = false;
-                }
-                if alt && !self.accel.contains(TheAcceleratorKey::ALT) {
-                    ok = false;
-                }
-                // Check Cmd (Logo): Allow for cases where either Ctrl or Cmd is part of CtrlCmd
-                if (logo
-                    && !(self.accel.contains(TheAcceleratorKey::CMD)
-                        || self.accel.contains(TheAcceleratorKey::CTRL)))
-                    || (!logo
-                        && self.accel.contains(TheAcceleratorKey::CMD)
-                        && !self.accel.contains(TheAcceleratorKey::CTRL))
-                {
-                    ok = false;
-                }
+    /// Parses an accelerator in keymap notation, the modifiers followed by the key, separated by
+    /// '+'. Modifiers are Shift, Ctrl, Cmd, CtrlCmd and Alt, the key is either a single character
+    /// or the name of a TheKeyCode, for example "CtrlCmd+Shift+S" or "Alt+F5".
+    pub fn from_keymap_str(str: &str) -> Option<Self> {
+        let str = str.trim();
+        let (modifiers, key) = if let Some(modifiers) = str.strip_suffix("++") {
+            (modifiers, "+")
+        } else {
+            str.rsplit_once('+').unwrap_or(("", str))
+        };
 
-                ok
-            } else {
-                false
+        let mut accel = TheAcceleratorKey::empty();
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            match modifier.trim().to_lowercase().as_str() {
+                "shift" => accel |= TheAcceleratorKey::SHIFT,
+                "ctrl" => accel |= TheAcceleratorKey::CTRL,
+                "cmd" => accel |= TheAcceleratorKey::CMD,
+                "ctrlcmd" => accel |= TheAcceleratorKey::CTRLCMD,
+                "alt" | "option" => accel |= TheAcceleratorKey::ALT,
+                _ => return None,
             }
+        }
+
+        let mut chars = key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(Self::new(accel, c.to_ascii_lowercase())),
+            (Some(_), Some(_)) => {
+                use ::serde::de::IntoDeserializer;
+                let key_code: Result<TheKeyCode, de::value::Error> =
+                    TheKeyCode::deserialize(key.into_deserializer());
+                key_code
+                    .ok()
+                    .map(|key_code| Self::new_key_code(accel, key_code))
+            }
+            _ => None,
+        }
+    }
+
+    /// Converts the accelerator to its platform independent keymap notation.
+    pub fn to_keymap_string(&self) -> String {
+        let mut str = String::new();
+
+        if self.accel.contains(TheAcceleratorKey::CTRLCMD) {
+            str += "CtrlCmd+";
+        } else if self.accel.contains(TheAcceleratorKey::CTRL) {
+            str += "Ctrl+";
+        } else if self.accel.contains(TheAcceleratorKey::CMD) {
+            str += "Cmd+";
+        }
+        if self.accel.contains(TheAcceleratorKey::SHIFT) {
+            str += "Shift+";
+        }
+        if self.accel.contains(TheAcceleratorKey::ALT) {
+            str += "Alt+";
+        }
+
+        if let Some(key_code) = &self.key_code {
+            str += &format!("{:?}", key_code);
         } else {
-            false
+            str += &self.key.to_uppercase().to_string();
         }
+
+        str
+    }
+
+    /// Test if we match the given modifiers and key. The pressed modifiers have to match the
+    /// modifiers of the accelerator exactly: Shift and Alt must be pressed if and only if the
+    /// accelerator contains them, and CtrlCmd accelerators need Ctrl or Cmd. Accelerators for
+    /// named keys never match a char key, see matches_key_code().
+    pub fn matches(&self, shift: bool, ctrl: bool, alt: bool, logo: bool, key: char) -> bool {
+        // We assume that accelerators are always case-insensitive.
+        self.key_code.is_none()
+            && self.key == key.to_ascii_lowercase()
+            && (shift || ctrl || alt || logo)
+            && self.matches_modifiers(shift, ctrl, alt, logo)
+    }
+
+    /// Test if we match the given modifiers and named key.
+    pub fn matches_key_code(
+        &self,
+        shift: bool,
+        ctrl: bool,
+        alt: bool,
+        logo: bool,
+        key_code: TheKeyCode,
+    ) -> bool {
+        self.key_code == Some(key_code) && self.matches_modifiers(shift, ctrl, alt, logo)
+    }
+
+    /// Test if the accelerator and the given one can be triggered by the same key press.
+    pub fn overlaps(&self, other: &TheAccelerator) -> bool {
+        let same_key = match (&self.key_code, &other.key_code) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => self.key.eq_ignore_ascii_case(&other.key),
+            _ => false,
+        };
+
+        let ctrl_cmd = |accel: TheAcceleratorKey| {
+            (
+                accel.contains(TheAcceleratorKey::CTRL),
+                accel.contains(TheAcceleratorKey::CMD),
+            )
+        };
+        let (a_ctrl, a_cmd) = ctrl_cmd(self.accel);
+        let (b_ctrl, b_cmd) = ctrl_cmd(other.accel);
+        let same_ctrl_cmd =
+            (a_ctrl && b_ctrl) || (a_cmd && b_cmd) || (!a_ctrl && !a_cmd && !b_ctrl && !b_cmd);
+
+        same_key
+            && same_ctrl_cmd
+            && self.accel.contains(TheAcceleratorKey::SHIFT)
+                == other.accel.contains(TheAcceleratorKey::SHIFT)
+            && self.accel.contains(TheAcceleratorKey::ALT)
+                == other.accel.contains(TheAcceleratorKey::ALT)
+    }
+
+    /// Test if the pressed modifiers match the modifiers of the accelerator.
+    fn matches_modifiers(&self, shift: bool, ctrl: bool, alt: bool, logo: bool) -> bool {
+        let mut ok = true;
+
+        if shift != self.accel.contains(TheAcceleratorKey::SHIFT) {
+            ok = false;
+        }
+        // Check Ctrl: Allow for cases where either Ctrl or Cmd is part of CtrlCmd
+        if (ctrl
+            && !(self.accel.contains(TheAcceleratorKey::CTRL)
+                || self.accel.contains(TheAcceleratorKey::CMD)))
+            || (!ctrl
+                && self.accel.contains(TheAcceleratorKey::CTRL)
+                && !self.accel.contains(TheAcceleratorKey::CMD))
+        {
+            ok = false;
+        }
+        if alt != self.accel.contains(TheAcceleratorKey::ALT) {
+            ok = false;
+        }
+        // Check Cmd (Logo): Allow for cases where either Ctrl or Cmd is part of CtrlCmd
+        if (logo
+            && !(self.accel.contains(TheAcceleratorKey::CMD)
+                || self.accel.contains(TheAcceleratorKey::CTRL)))
+            || (!logo
+                && self.accel.contains(TheAcceleratorKey::CMD)
+                && !self.accel.contains(TheAcceleratorKey::CTRL))
+        {
+            ok = false;
+        }
+        // A CtrlCmd accelerator needs one of the two
+        if self.accel.contains(TheAcceleratorKey::CTRLCMD) && !ctrl && !logo {
+            ok = false;
+        }
+
+        ok
     }
 }
 
@@ -237,23 +415,25 @@ pub enum TheDialogButtonRole {
     Reject,
     Delete,
     Rename,
+    /// An app defined button, distinguished by its number.
+    Custom(u32),
 }
 
 impl TheDialogButtonRole {
+    /// The default label of a button of the role.
     pub fn to_string(self) -> &'static str {
         match self {
             Self::Accept => "Accept",
             Self::Reject => "Cancel",
             Self::Delete => "Delete",
-            Self::Rename => "Delete",
+            Self::Rename => "Rename",
+            Self::Custom(_) => "OK",
         }
     }
-    pub fn to_id(self) -> &'static str {
+    pub fn to_id(self) -> String {
         match self {
-            Self::Accept => "TheDialogButtonRole::Accept",
-            Self::Reject => "TheDialogButtonRole::Reject",
-            Self::Delete => "TheDialogButtonRole::Delete",
-            Self::Rename => "TheDialogButtonRole::Rename",
+            Self::Custom(index) => format!("TheDialogButtonRole::Custom({})", index),
+            _ => format!("TheDialogButtonRole::{:?}", self),
         }
     }
     pub fn iterator() -> impl Iterator<Item = TheDialogButtonRole> {
@@ -266,8 +446,17 @@ impl TheDialogButtonRole {
 pub struct TheUI {
     pub canvas: TheCanvas,
 
-    pub dialog_text: String,
-    pub dialog: Option<TheCanvas>,
+    /// The stack of modal dialogs, the last one is on top.
+    pub dialogs: Vec<TheDialog>,
+
+    /// The transient notifications, see show_toast().
+    pub toasts: TheToasts,
+
+    /// The tooltip of the hovered widget.
+    pub tooltip: TheTooltipOverlay,
+
+    /// The watched canvas description files, see watch_canvas_file().
+    pub hot_reload: TheHotReload,
 
     pub style: Box<dyn TheStyle>,
 
@@ -280,8 +469,28 @@ pub struct TheUI {
     pub context_menu: Option<TheContextMenu>,
     pub menu_widget_id: Option<TheId>,
 
+    /// The global keyboard shortcuts, see TheEvent::ShortcutTriggered.
+    pub shortcuts: TheShortcutRegistry,
+
     pub is_dirty: bool,
 
+    /// The regions covered by overlays (dialogs, menus, drag images) in the last frame, they are
+    /// damaged again when the overlays move or close.
+    overlay_regions: Vec<TheDim>,
+
+    /// The accessibility tree of the last accessibility_update() call.
+    access_tree: Option<TheAccessTree>,
+
+    /// The additional windows, see open_window().
+    windows: Vec<TheWindow>,
+    /// The window which is currently active, None for the main window.
+    active_window: Option<Uuid>,
+    /// The windows which were closed and have to be destroyed by the backend.
+    closed_windows: Vec<TheId>,
+
+    /// The tree layout which moves one of its items by drag and drop.
+    tree_drag: Option<TheId>,
+
     // Modifiers
     pub shift: bool,
     pub ctrl: bool,
@@ -309,15 +518,30 @@ impl TheUI {
             state_events_receiver: None,
             app_state_events: FxHashMap::default(),
 
-            dialog_text: "".to_string(),
-            dialog: None,
+            dialogs: vec![],
+
+            toasts: TheToasts::default(),
+
+            tooltip: TheTooltipOverlay::default(),
+            hot_reload: TheHotReload::new(),
 
             statusbar_name: None,
 
             context_menu: None,
             menu_widget_id: None,
+            shortcuts: TheShortcutRegistry::default(),
             is_dirty: false,
 
+            overlay_regions: vec![],
+
+            access_tree: None,
+
+            windows: vec![],
+            active_window: None,
+            closed_windows: vec![],
+
+            tree_drag: None,
+
             shift: false,
             ctrl: false,
             alt: false,
@@ -345,6 +569,13 @@ impl TheUI {
         self.statusbar_name = Some(name);
     }
 
+    /// Switches the theme of the style and redraws the UI.
+    pub fn set_theme(&mut self, theme: Box<dyn TheTheme>, ctx: &mut TheContext) {
+        self.style.set_theme(theme);
+        ctx.ui.redraw_all = true;
+        self.is_dirty = true;
+    }
+
     pub fn relayout(&mut self, ctx: &mut TheContext) {
         let width = self.canvas.buffer().dim().width;
         let height = self.canvas.buffer().dim().height;
@@ -448,36 +679,234 @@ impl TheUI {
         if ctx.width == 0 || ctx.height == 0 {
             return;
         };
+        // Redraw and relayout requests apply to all windows.
+        for window in &mut self.windows {
+            window.redraw_all |= ctx.ui.redraw_all;
+            window.relayout |= ctx.ui.relayout;
+        }
+
         if self.canvas.resize(ctx.width as i32, ctx.height as i32, ctx) {
             ctx.ui.send(TheEvent::Resize);
             ctx.ui.relayout = false;
+            ctx.damage_all();
         }
         if ctx.ui.relayout {
             self.relayout(ctx);
+            ctx.damage_all();
         }
+        if ctx.ui.redraw_all {
+            ctx.damage_all();
+        }
+
+        let mut overlay_regions = vec![];
+
         self.canvas.draw(&mut self.style, ctx);
-        if self.dialog.is_some() {
+        if !self.dialogs.is_empty() && self.active_window.is_none() {
             self.draw_dialog(ctx);
+            overlay_regions.push(TheDim::sized(ctx.width as i32, ctx.height as i32));
+        }
+        if let Some(region) = self.canvas.draw_overlay(&mut self.style, ctx) {
+            overlay_regions.push(region);
+        }
+        let show_toasts = !self.toasts.is_empty() && self.active_window.is_none();
+        if show_toasts {
+            self.toasts.layout(ctx);
+            overlay_regions.extend(self.toasts.regions(ctx));
         }
-        self.canvas.draw_overlay(&mut self.style, ctx);
         if let Some(drop) = &ctx.ui.drop {
             if let Some(position) = &drop.position {
                 self.canvas.buffer.blend_into(
                     position.x - drop.offset.x,
                     position.y - drop.offset.y,
                     &drop.image,
-                )
+                );
+                overlay_regions.push(TheDim::new(
+                    position.x - drop.offset.x,
+                    position.y - drop.offset.y,
+                    drop.image.dim().width,
+                    drop.image.dim().height,
+                ));
             }
         }
         if let Some(menu) = &mut self.context_menu {
             menu.draw(self.canvas.buffer.pixels_mut(), &mut self.style, ctx);
+            overlay_regions.extend(menu.regions());
+        } else if let Some(region) =
+            self.tooltip
+                .draw(self.canvas.buffer.pixels_mut(), &mut self.style, ctx)
+        {
+            overlay_regions.push(region);
         }
         ctx.ui.redraw_all = false;
 
-        pixels.copy_from_slice(self.canvas.buffer().pixels());
+        // Overlays are drawn on top of the canvas every frame, damage where they are and where
+        // they were in the last frame.
+        for region in self.overlay_regions.iter().chain(overlay_regions.iter()) {
+            ctx.add_damage(*region);
+        }
+        self.overlay_regions = overlay_regions;
+
+        let source = self.canvas.buffer();
+        let stride = source.physical_width() as usize * 4;
+        if pixels.len() == source.pixels().len() {
+            for (x, y, width, height) in ctx.damage().physical_rects(
+                source.scale(),
+                source.physical_width() as usize,
+                source.physical_height() as usize,
+            ) {
+                for y in y..y + height {
+                    let start = y * stride + x * 4;
+                    let end = start + width * 4;
+                    pixels[start..end].copy_from_slice(&source.pixels()[start..end]);
+                }
+            }
+        } else {
+            pixels.copy_from_slice(source.pixels());
+        }
+
+        // Toasts are drawn on top of the copied canvas, which restores the frame below them.
+        if show_toasts {
+            self.toasts
+                .draw(pixels, &mut self.style, ctx, Instant::now());
+        }
         self.is_dirty = false;
     }
 
+    /// Opens an additional window with the given canvas. The backend creates the OS window, close
+    /// requests of the window are passed to TheTrait::window_closing().
+    pub fn open_window(&mut self, id: TheId, settings: TheWindowSettings, canvas: TheCanvas) {
+        self.windows.push(TheWindow::new(id, settings, canvas));
+    }
+
+    /// Closes the window of the given id and sends TheEvent::WindowClosed.
+    pub fn close_window(&mut self, id: &Uuid, ctx: &mut TheContext) -> bool {
+        if self.active_window == Some(*id) {
+            self.activate_window(None, ctx);
+        }
+
+        if let Some(index) = self.windows.iter().position(|w| w.id.uuid == *id) {
+            let window = self.windows.remove(index);
+            ctx.ui.send(TheEvent::WindowClosed(window.id.clone()));
+            self.closed_windows.push(window.id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the additional windows. The state of a window is only stored in it while the
+    /// window is not active, which is the case outside of event processing and drawing.
+    pub fn windows(&self) -> &Vec<TheWindow> {
+        &self.windows
+    }
+
+    /// Returns the window of the given id.
+    pub fn window_mut(&mut self, id: &Uuid) -> Option<&mut TheWindow> {
+        self.windows.iter_mut().find(|w| w.id.uuid == *id)
+    }
+
+    /// Returns the id of the active window, None for the main window.
+    pub fn active_window(&self) -> Option<Uuid> {
+        self.active_window
+    }
+
+    /// Returns the windows which were opened since the last call, the backend creates their OS
+    /// windows.
+    pub fn take_new_windows(&mut self) -> Vec<(TheId, TheWindowSettings)> {
+        let mut new_windows = vec![];
+        for window in &mut self.windows {
+            if !window.is_created() {
+                window.set_created();
+                new_windows.push((window.id.clone(), window.settings.clone()));
+            }
+        }
+        new_windows
+    }
+
+    /// Returns the windows which were closed since the last call, the backend destroys their OS
+    /// windows.
+    pub fn take_closed_windows(&mut self) -> Vec<TheId> {
+        std::mem::take(&mut self.closed_windows)
+    }
+
+    /// Makes the given window, or the main window for None, the active one. Its canvas, size and
+    /// damage become the ones of TheUI and TheContext, so that events and draw calls apply to it.
+    /// Backends activate a window before processing its events and drawing it and reactivate the
+    /// main window afterwards.
+    pub fn activate_window(&mut self, id: Option<&Uuid>, ctx: &mut TheContext) -> bool {
+        if self.active_window.as_ref() == id {
+            return true;
+        }
+
+        let index = match id {
+            Some(id) => {
+                let Some(index) = self.windows.iter().position(|w| w.id.uuid == *id) else {
+                    return false;
+                };
+                Some(index)
+            }
+            None => None,
+        };
+
+        // The state of the main window is stored in the slot of the active window.
+        if let Some(active) = self.active_window.take() {
+            if let Some(active_index) = self.windows.iter().position(|w| w.id.uuid == active) {
+                self.swap_window_state(active_index, ctx);
+            }
+        }
+        if let Some(index) = index {
+            self.swap_window_state(index, ctx);
+            self.active_window = Some(self.windows[index].id.uuid);
+            ctx.ui.window = Some(self.windows[index].id.clone());
+        } else {
+            ctx.ui.window = None;
+        }
+
+        true
+    }
+
+    /// Exchanges the per window state of TheUI and TheContext with the state stored in the window.
+    fn swap_window_state(&mut self, index: usize, ctx: &mut TheContext) {
+        let window = &mut self.windows[index];
+
+        std::mem::swap(&mut self.canvas, &mut window.canvas);
+        std::mem::swap(&mut self.context_menu, &mut window.context_menu);
+        std::mem::swap(&mut self.menu_widget_id, &mut window.menu_widget_id);
+        std::mem::swap(&mut self.overlay_regions, &mut window.overlay_regions);
+        self.tooltip.swap_state(&mut window.tooltip);
+        std::mem::swap(&mut self.access_tree, &mut window.access_tree);
+
+        std::mem::swap(&mut ctx.width, &mut window.width);
+        std::mem::swap(&mut ctx.height, &mut window.height);
+        std::mem::swap(&mut ctx.scale_factor, &mut window.scale_factor);
+        ctx.draw.scale = ctx.scale_factor;
+        ctx.ui.scale_factor = ctx.scale_factor;
+        std::mem::swap(&mut ctx.damage, &mut window.damage);
+        std::mem::swap(&mut ctx.ui.redraw_all, &mut window.redraw_all);
+        std::mem::swap(&mut ctx.ui.relayout, &mut window.relayout);
+    }
+
+    /// Ends the edit of the table referenced by the given cell editor, returns true if the editor
+    /// was still editing.
+    fn end_table_edit(
+        canvas: &mut TheCanvas,
+        editor_id: &TheId,
+        commit: bool,
+        ctx: &mut TheContext,
+    ) -> bool {
+        let Some(table) = canvas
+            .get_layout(None, Some(&editor_id.references))
+            .and_then(|layout| layout.as_table_layout())
+        else {
+            return false;
+        };
+        if table.editor_id() != Some(editor_id) {
+            return false;
+        }
+        table.end_edit(commit, ctx);
+        true
+    }
+
     /// Processes widget state events, these are mostly send from TheUIContext based on state changes provided by the widgets.
     pub fn process_events(&mut self, ctx: &mut TheContext) {
         if let Some(receiver) = &mut self.state_events_receiver {
@@ -515,16 +944,65 @@ impl TheUI {
                         self.style.create_drop_image(&mut drop, ctx);
                         ctx.ui.drop = Some(drop);
                     }
+                    TheEvent::TreeItemDragStarted(id, layout_id, text, offset)
+                        if ctx.ui.drop.is_none() =>
+                    {
+                        let tree = self.canvas.get_layout(None, Some(&layout_id.uuid));
+                        if let Some(tree) = tree.and_then(|layout| layout.as_tree_layout()) {
+                            if let Some(mut drop) = tree.start_drag(&id, &text, offset) {
+                                self.style.create_drop_image(&mut drop, ctx);
+                                ctx.ui.set_drop(drop);
+                                self.tree_drag = Some(layout_id);
+                            }
+                        }
+                    }
                     TheEvent::NewListItemSelected(id, layout_id) => {
+                        // List and tree layouts send this event themselves after a click.
                         if let Some(layout) = self.canvas.get_layout(None, Some(&layout_id.uuid)) {
-                            if let Some(list) = layout.as_list_layout() {
+                            if let Some(list) = layout.as_rowlist_layout() {
                                 list.new_item_selected(id);
                                 self.is_dirty = true;
-                            } else if let Some(list) = layout.as_rowlist_layout() {
-                                list.new_item_selected(id);
+                            }
+                        }
+                    }
+                    TheEvent::ListItemClicked(id, layout_id) => {
+                        // Shift selects ranges and Ctrl / Cmd toggles items in multi selections.
+                        let (range, toggle) = (self.shift, self.ctrl || self.logo);
+                        if let Some(layout) = self.canvas.get_layout(None, Some(&layout_id.uuid)) {
+                            if let Some(list) = layout.as_list_layout() {
+                                list.item_clicked(id, range, toggle, ctx);
+                                self.is_dirty = true;
+                            } else if let Some(tree) = layout.as_tree_layout() {
+                                tree.item_clicked(id.clone(), range, toggle, ctx);
+                                self.is_dirty = true;
+                                ctx.ui.redraw_all = true;
+                            }
+                        }
+                    }
+                    TheEvent::ListItemKeyDown(_, layout_id, key) => {
+                        // Up / Down move the selection, with Shift they extend it.
+                        let delta = match key {
+                            TheValue::KeyCode(TheKeyCode::Up) => Some(-1),
+                            TheValue::KeyCode(TheKeyCode::Down) => Some(1),
+                            _ => None,
+                        };
+                        let select_all = (self.ctrl || self.logo)
+                            && matches!(key, TheValue::Char('a') | TheValue::Char('A'));
+                        let extend = self.shift;
+                        if let Some(layout) = self.canvas.get_layout(None, Some(&layout_id.uuid)) {
+                            if let Some(list) = layout.as_list_layout() {
+                                if let Some(delta) = delta {
+                                    list.move_selection(delta, extend, ctx);
+                                } else if select_all {
+                                    list.select_all(ctx);
+                                }
                                 self.is_dirty = true;
                             } else if let Some(tree) = layout.as_tree_layout() {
-                                tree.new_item_selected(id.clone());
+                                if let Some(delta) = delta {
+                                    tree.move_selection(delta, extend, ctx);
+                                } else if select_all {
+                                    tree.select_all(ctx);
+                                }
                                 self.is_dirty = true;
                                 ctx.ui.redraw_all = true;
                             }
@@ -575,31 +1053,14 @@ impl TheUI {
                             }
                         }
                     }
-                    TheEvent::StateChanged(id, state) => {
-                        //println!("Widget State changed {:?}: {:?}", id, state);
-
-                        if let Some(dialog) = &mut self.dialog {
-                            // If a dialog, close it if one of the dialog buttons was clicked.
-                            if state == TheWidgetState::Clicked
-                                && id.name.starts_with("TheDialogButtonRole")
-                            {
-                                for button in TheDialogButtonRole::iterator() {
-                                    if id.name == button.to_id() {
-                                        if let Some(widget) = dialog
-                                            .get_widget(Some(&"Dialog Value".to_string()), None)
-                                        {
-                                            let value = widget.value();
-                                            ctx.ui.send(TheEvent::DialogValueOnClose(
-                                                button,
-                                                self.dialog_text.clone(),
-                                                widget.id().uuid,
-                                                value,
-                                            ));
-                                        }
-                                    }
-                                }
-                                self.dialog = None;
-                            }
+                    // If a dialog button was clicked, close the dialog.
+                    TheEvent::StateChanged(id, TheWidgetState::Clicked) => {
+                        if let Some(role) = self
+                            .dialogs
+                            .last()
+                            .and_then(|dialog| dialog.button_role(&id))
+                        {
+                            Self::pop_dialog(&mut self.dialogs, role, ctx);
                         }
                     }
                     TheEvent::SetState(name, state) => {
@@ -642,6 +1103,28 @@ impl TheUI {
                             // Reset cursor to default when widget loses focus
                             ctx.set_cursor_icon(TheCursorIcon::Default);
                         }
+                        // Cell editors of tables commit their edit when the focus moves on.
+                        if !id.references.is_nil()
+                            && !ctx.ui.has_focus(&id)
+                            && !id.equals(&ctx.ui.overlay)
+                            && Self::end_table_edit(&mut self.canvas, &id, true, ctx)
+                        {
+                            self.is_dirty = true;
+                        }
+                    }
+                    // Return commits and Escape cancels the edit of a table cell, modified text
+                    // edits commit their value on Return themselves.
+                    TheEvent::KeyCodeDown(TheValue::KeyCode(
+                        key @ (TheKeyCode::Return | TheKeyCode::Escape),
+                    )) => {
+                        if let Some(id) = ctx.ui.focus.clone() {
+                            if !id.references.is_nil() {
+                                let commit = key == TheKeyCode::Return;
+                                if Self::end_table_edit(&mut self.canvas, &id, commit, ctx) {
+                                    self.is_dirty = true;
+                                }
+                            }
+                        }
                     }
                     TheEvent::GainedHover(id) => {
                         //println!("Gained hover {:?}", id);
@@ -684,6 +1167,10 @@ impl TheUI {
                             }
                         }
                     }
+                    TheEvent::ShowToast(toast) => {
+                        self.toasts.push(toast);
+                        self.is_dirty = true;
+                    }
                     TheEvent::SetStatusText(_id, text) => {
                         if let Some(statusbar_name) = &self.statusbar_name {
                             if let Some(widget) = self.canvas.get_widget(Some(statusbar_name), None)
@@ -694,8 +1181,22 @@ impl TheUI {
                             }
                         }
                     }
-                    TheEvent::ValueChanged(id, value) => {
-                        //println!("Widget Value changed {:?}: {:?}", id, value);
+                    // Cell editors of tables reference their table.
+                    TheEvent::ValueChanged(id, value) if !id.references.is_nil() => {
+                        if let Some(layout) = self.canvas.get_layout(None, Some(&id.references)) {
+                            if layout.as_table_layout().is_some() {
+                                layout.redirected_widget_value(&id, &value, ctx);
+                                self.is_dirty = true;
+                            }
+                        }
+                    }
+                    TheEvent::TableEditCell(layout_id, row, column) => {
+                        if let Some(layout) = self.canvas.get_layout(None, Some(&layout_id.uuid)) {
+                            if let Some(table) = layout.as_table_layout() {
+                                table.edit_cell(row, column, ctx);
+                                self.is_dirty = true;
+                            }
+                        }
                     }
                     TheEvent::SetValue(uuid, value) => {
                         //println!("Set Value {:?}: {:?}", name, value);
@@ -739,11 +1240,57 @@ impl TheUI {
         }
 
         self.process_events(ctx);
+
+        // Toasts fade out, redraw while they are shown.
+        if self.toasts.update(Instant::now()) {
+            self.is_dirty = true;
+        }
+        // Redraw when the hover delay of a tooltip passed.
+        if self.tooltip.update(Instant::now()) {
+            self.is_dirty = true;
+        }
+        // Trees scroll and open nodes while an item is dragged, also without pointer movement.
+        if let Some(layout_id) = self.tree_drag.clone() {
+            let dropped = ctx.ui.drop.is_none();
+            if let Some(tree) = self.get_tree_layout_by_id(&layout_id) {
+                if dropped {
+                    tree.end_drag(false, ctx);
+                } else if tree.update_drag(Instant::now(), ctx) {
+                    self.is_dirty = true;
+                    ctx.ui.redraw_all = true;
+                }
+            }
+            if dropped {
+                self.tree_drag = None;
+                self.is_dirty = true;
+            }
+        }
+
+        for index in self.hot_reload.changed(Instant::now()) {
+            let file = self.hot_reload.file_mut(index);
+            let (path, canvas) = (file.path.clone(), file.canvas);
+
+            let error_toast = match self.reload_canvas(&path, canvas, ctx) {
+                Ok(()) => None,
+                Err(err) => {
+                    let name = path.file_name().unwrap_or_default().to_string_lossy();
+                    let mut toast = TheToast::error(&format!("{}: {}", name, err));
+                    toast.set_timeout(None);
+                    let uuid = toast.id.uuid;
+                    self.toasts.push(toast);
+                    Some(uuid)
+                }
+            };
+            if let Some(previous) = self.hot_reload.file_mut(index).set_error_toast(error_toast) {
+                self.toasts.dismiss(&previous);
+            }
+            self.is_dirty = true;
+        }
         self.is_dirty
     }
 
     pub fn context(&mut self, x: f32, y: f32, ctx: &mut TheContext) -> bool {
-        let mut redraw = false;
+        let mut redraw = self.tooltip.dismiss();
         let coord = Vec2::new(x as i32, y as i32);
         if let Some(widget) = self.get_widget_at_coord(coord) {
             let event = TheEvent::Context(coord);
@@ -759,6 +1306,7 @@ impl TheUI {
         let coord = Vec2::new(x as i32, y as i32);
 
         ctx.ui.send(TheEvent::MouseDown(coord));
+        self.tooltip.dismiss();
 
         //ctx.ui.clear_focus();
 
@@ -803,6 +1351,18 @@ impl TheUI {
             return redraw;
         }
 
+        // Toasts are on top of everything else but do not block the rest of the UI.
+        if self.active_window.is_none() && self.toasts.touch_down(coord, ctx) {
+            self.process_events(ctx);
+            return true;
+        }
+
+        if let Some(dialog) = self.dialogs.last_mut() {
+            if dialog.start_drag(coord) {
+                return true;
+            }
+        }
+
         if let Some(widget) = self.get_widget_at_coord(coord) {
             let event = TheEvent::MouseDown(widget.dim().to_local(coord));
             redraw = widget.on_event(&event, ctx);
@@ -820,6 +1380,13 @@ impl TheUI {
             return redraw;
         }
 
+        if let Some(dialog) = self.dialogs.last_mut() {
+            if dialog.drag(coord, ctx) {
+                ctx.ui.redraw_all = true;
+                return true;
+            }
+        }
+
         if let Some(id) = &ctx.ui.overlay {
             if let Some(widget) = self.get_widget_abs(None, Some(&id.uuid)) {
                 let event = TheEvent::MouseDragged(widget.dim().to_local(coord));
@@ -832,7 +1399,7 @@ impl TheUI {
                 redraw = widget.on_event(&event, ctx);
                 self.process_events(ctx);
             }
-        } else if let Some(widget) = self.canvas.get_widget_at_coord(coord) {
+        } else if let Some(widget) = self.get_widget_at_coord(coord) {
             let event = TheEvent::MouseDragged(widget.dim().to_local(coord));
             redraw = widget.on_event(&event, ctx);
             self.process_events(ctx);
@@ -840,11 +1407,24 @@ impl TheUI {
 
         if let Some(drop) = &mut ctx.ui.drop {
             drop.set_position(coord);
-            if let Some(widget) = self.canvas.get_widget_at_coord(coord) {
-                let event = TheEvent::DropPreview(widget.dim().to_local(coord), drop.clone());
-                redraw = widget.on_event(&event, ctx);
-                self.process_events(ctx);
+            let drop = drop.clone();
+
+            // Moves inside a tree are previewed by the tree itself.
+            let mut in_tree = false;
+            if let Some(layout_id) = self.tree_drag.clone() {
+                if let Some(tree) = self.get_tree_layout_by_id(&layout_id) {
+                    in_tree = tree.drag_to(coord, Instant::now(), ctx);
+                    ctx.ui.redraw_all = true;
+                }
             }
+
+            if !in_tree {
+                if let Some(widget) = self.canvas.get_widget_at_coord(coord) {
+                    let event = TheEvent::DropPreview(widget.dim().to_local(coord), drop);
+                    redraw = widget.on_event(&event, ctx);
+                }
+            }
+            self.process_events(ctx);
             redraw = true;
         }
 
@@ -861,6 +1441,12 @@ impl TheUI {
             return redraw;
         }
 
+        if let Some(dialog) = self.dialogs.last_mut() {
+            if dialog.end_drag() {
+                return true;
+            }
+        }
+
         if let Some(id) = &ctx.ui.overlay {
             if let Some(widget) = self.get_widget_abs(Some(&id.name), Some(&id.uuid)) {
                 let event = TheEvent::MouseUp(widget.dim().to_local(coord));
@@ -873,21 +1459,31 @@ impl TheUI {
                 redraw = widget.on_event(&event, ctx);
                 self.process_events(ctx);
             }
-        } else if let Some(widget) = self.canvas.get_widget_at_coord(coord) {
+        } else if let Some(widget) = self.get_widget_at_coord(coord) {
             let event = TheEvent::MouseUp(widget.dim().to_local(coord));
             redraw = widget.on_event(&event, ctx);
             self.process_events(ctx);
         }
 
-        if let Some(drop) = &ctx.ui.drop {
-            if let Some(widget) = self.canvas.get_widget_at_coord(coord) {
-                let mut drop_copy = drop.clone();
-                drop_copy.target_id = widget.id().clone();
-                let event = TheEvent::Drop(widget.dim().to_local(coord), drop.clone());
-                redraw = widget.on_event(&event, ctx);
-                ctx.ui.send(event);
-                self.process_events(ctx);
+        if let Some(drop) = ctx.ui.drop.clone() {
+            // Drops inside the tree the item was dragged from move the item.
+            let mut in_tree = false;
+            if let Some(layout_id) = self.tree_drag.take() {
+                if let Some(tree) = self.get_tree_layout_by_id(&layout_id) {
+                    in_tree = tree.drag_to(coord, Instant::now(), ctx);
+                    tree.end_drag(in_tree, ctx);
+                    ctx.ui.redraw_all = true;
+                }
+            }
+
+            if !in_tree {
+                if let Some(widget) = self.canvas.get_widget_at_coord(coord) {
+                    let event = TheEvent::Drop(widget.dim().to_local(coord), drop);
+                    redraw = widget.on_event(&event, ctx);
+                    ctx.ui.send(event);
+                }
             }
+            self.process_events(ctx);
             redraw = true;
         }
 
@@ -901,6 +1497,7 @@ impl TheUI {
         self.mouse_coord = coord;
 
         if let Some(context) = &mut self.context_menu {
+            self.tooltip.hover(None, coord);
             if context.contains(coord) {
                 let event = TheEvent::Hover(context.dim.to_local(coord));
                 redraw = context.on_event(&event, ctx);
@@ -917,7 +1514,24 @@ impl TheUI {
             return redraw;
         }
 
+        if self.active_window.is_none() {
+            redraw = self.toasts.hover(coord, ctx);
+            if self.toasts.contains(coord, ctx) {
+                self.tooltip.hover(None, coord);
+                return redraw;
+            }
+        }
+
+        let mut tooltip = None;
         if let Some(widget) = self.get_widget_at_coord(coord) {
+            tooltip = ctx
+                .ui
+                .tooltips
+                .get(&widget.id().uuid)
+                .cloned()
+                .or_else(|| widget.tooltip())
+                .map(|tooltip| (widget.id().clone(), tooltip));
+
             let event = TheEvent::Hover(widget.dim().to_local(coord));
             redraw = widget.on_event(&event, ctx);
 
@@ -949,11 +1563,15 @@ impl TheUI {
 
             self.process_events(ctx);
         }
+
+        if self.tooltip.hover(tooltip, coord) {
+            redraw = true;
+        }
         redraw
     }
 
     pub fn mouse_wheel(&mut self, delta: (i32, i32), ctx: &mut TheContext) -> bool {
-        let mut redraw = false;
+        let mut redraw = self.tooltip.dismiss();
 
         let mut layout_id = None;
         if let Some(id) = self.get_layout_at_coord(self.mouse_coord) {
@@ -991,14 +1609,32 @@ impl TheUI {
         key: Option<TheKeyCode>,
         ctx: &mut TheContext,
     ) -> bool {
-        let mut redraw = false;
+        let mut redraw = self.tooltip.dismiss();
         let mut consumed = false;
+        let is_tab = char.is_none() && key == Some(TheKeyCode::Tab);
+
+        // Enter and Escape trigger the default buttons of the topmost dialog.
+        if char.is_none() && self.context_menu.is_none() {
+            if let Some(role) = self.dialog_key_role(key, ctx) {
+                self.close_dialog(role, ctx);
+                return true;
+            }
+        }
+
+        // Shortcuts of the app are not available while a modal dialog is open.
+        let modal = !self.dialogs.is_empty();
 
         if let Some(c) = char {
-            if self.ctrl || self.shift || self.alt || self.logo {
-                // Check for accelerators in context menus.
+            if (self.ctrl || self.shift || self.alt || self.logo)
+                && !self.shortcuts.is_pending()
+                && !modal
+            {
+                // Check for accelerators in context menus, actions in the keymap are handled
+                // by the shortcut registry.
                 for (id, accel) in &ctx.ui.accelerators.clone() {
-                    if accel.matches(self.shift, self.ctrl, self.alt, self.logo, c) {
+                    if !ctx.ui.keymap.contains(&id.name)
+                        && accel.matches(self.shift, self.ctrl, self.alt, self.logo, c)
+                    {
                         consumed = true;
                         ctx.ui
                             .send(TheEvent::ContextMenuSelected(id.clone(), id.clone()));
@@ -1010,12 +1646,57 @@ impl TheUI {
             }
         }
 
+        if !consumed && self.context_menu.is_none() && !modal {
+            // Plain typing and text navigation go to text widgets, unless a chord is waiting
+            // for its next key.
+            let text_input = !self.ctrl
+                && !self.alt
+                && !self.logo
+                && !key.is_some_and(|key| key.is_function_key())
+                && !self.shortcuts.is_pending()
+                && self.focus_widget_supports_text_input(ctx);
+
+            if !text_input {
+                match self.shortcuts.process(
+                    &ctx.ui.keymap,
+                    self.shift,
+                    self.ctrl,
+                    self.alt,
+                    self.logo,
+                    char,
+                    key,
+                ) {
+                    TheShortcutMatch::Pending => {
+                        consumed = true;
+                    }
+                    TheShortcutMatch::Matched(id) => {
+                        consumed = true;
+                        // Rebound menu items are selected like through their accelerator.
+                        let menu_id = ctx
+                            .ui
+                            .accelerators
+                            .keys()
+                            .find(|menu_id| menu_id.name == id.name)
+                            .cloned();
+                        if let Some(id) = menu_id {
+                            ctx.ui
+                                .send(TheEvent::ContextMenuSelected(id.clone(), id.clone()));
+                            ctx.ui
+                                .send(TheEvent::StateChanged(id, TheWidgetState::Selected));
+                        } else {
+                            ctx.ui.send(TheEvent::ShortcutTriggered(id));
+                        }
+                    }
+                    TheShortcutMatch::None => {}
+                }
+            }
+        }
+
         if !consumed {
             let event = if let Some(c) = char {
                 TheEvent::KeyDown(TheValue::Char(c))
             } else {
-                if key.clone().unwrap().clone() == TheKeyCode::Escape && self.context_menu.is_some()
-                {
+                if key == Some(TheKeyCode::Escape) && self.context_menu.is_some() {
                     self.context_menu = None;
                     let menu_widget_id = self.menu_widget_id.clone();
                     if let Some(menu_widget_id) = menu_widget_id {
@@ -1032,6 +1713,10 @@ impl TheUI {
                 TheEvent::KeyCodeDown(TheValue::KeyCode(key.unwrap()))
             };
             ctx.ui.send(event.clone());
+
+            if is_tab && self.tab_moves_focus(ctx) {
+                return true;
+            }
             if let Some(id) = &ctx.ui.focus {
                 if let Some(widget) = self.get_widget_abs(Some(&id.name), Some(&id.uuid)) {
                     redraw = widget.on_event(&event, ctx);
@@ -1094,10 +1779,225 @@ impl TheUI {
         redraw
     }
 
+    /// Watches a canvas description file (see TheCanvasDescription) and rebuilds the canvas of
+    /// the given uuid in the active window whenever the file changes. Widget values are kept by
+    /// the names of the widget ids. If the file can not be loaded the canvas stays as it is and
+    /// the error is shown in a toast until the file loads again.
+    pub fn watch_canvas_file(&mut self, path: &Path, canvas: Uuid) {
+        self.hot_reload.watch(path, canvas);
+    }
+
+    /// Rebuilds the canvas of the given uuid from a description file, keeping the values of the
+    /// widgets whose names are in the old and in the new canvas.
+    pub fn reload_canvas(
+        &mut self,
+        path: &Path,
+        uuid: Uuid,
+        ctx: &mut TheContext,
+    ) -> Result<(), String> {
+        let description = TheCanvasDescription::load(path).map_err(|err| err.to_string())?;
+        let Some(canvas) = self.canvas.get_canvas(uuid) else {
+            return Err("The canvas does not exist".to_string());
+        };
+
+        let mut values = vec![];
+        for name in description.widget_names() {
+            if let Some(widget) = canvas.get_widget(Some(&name), None) {
+                let value = widget.value();
+                if value != TheValue::Empty {
+                    values.push((name, value));
+                }
+            }
+        }
+
+        let mut new_canvas = description.build(ctx)?;
+        for (name, value) in values {
+            if let Some(widget) = new_canvas.get_widget(Some(&name), None) {
+                widget.set_value(value);
+            }
+        }
+        new_canvas.uuid = canvas.uuid;
+        new_canvas.root = canvas.root;
+        *canvas = new_canvas;
+
+        ctx.ui.relayout = true;
+        ctx.ui.redraw_all = true;
+        self.is_dirty = true;
+        Ok(())
+    }
+
+    /// Builds the accessibility tree of the active window. The root is the window, below it are
+    /// the layouts and widgets of the canvas, followed by the open dialogs, the toasts and the
+    /// visible tooltip. Positions are those of the last layout.
+    pub fn accessibility_tree(&mut self, ctx: &TheContext) -> TheAccessTree {
+        let window = self
+            .active_window
+            .and_then(|uuid| self.windows.iter().find(|window| window.id.uuid == uuid));
+        let (id, title) = match window {
+            Some(window) => (window.id.clone(), window.settings.title.clone()),
+            None => (TheId::named_with_id("Window", Uuid::nil()), String::new()),
+        };
+
+        let bounds = TheDim::rect(0, 0, ctx.width as i32, ctx.height as i32);
+        let mut tree = TheAccessTree::new(
+            id,
+            TheAccessInfo::named(TheAccessRole::Window, &title),
+            bounds,
+        );
+        let root = tree.root;
+
+        self.canvas.collect_access_nodes(&mut tree, root, ctx);
+        if self.active_window.is_none() {
+            for dialog in &mut self.dialogs {
+                dialog.collect_access_nodes(&mut tree, root, ctx);
+            }
+            self.toasts.collect_access_nodes(&mut tree, root, ctx);
+        }
+        self.tooltip.collect_access_nodes(&mut tree, root, ctx);
+
+        tree
+    }
+
+    /// Returns the accessibility nodes of the active window which changed since the last call,
+    /// None if nothing changed. The first call returns the whole tree. Backends call this after
+    /// each frame and pass the result on to the platform accessibility API, the winit backend
+    /// passes it on to AccessKit with the "accessibility" feature.
+    pub fn accessibility_update(&mut self, ctx: &TheContext) -> Option<TheAccessTreeUpdate> {
+        let tree = self.accessibility_tree(ctx);
+        let update = tree.update_since(self.access_tree.as_ref());
+        self.access_tree = Some(tree);
+        update
+    }
+
+    /// Forgets the last accessibility tree, the next accessibility_update() returns the whole
+    /// tree. Backends call this when an assistive technology connects.
+    pub fn reset_accessibility(&mut self) {
+        self.access_tree = None;
+    }
+
+    /// Performs an action requested by an assistive technology on the node of the given id in the
+    /// last accessibility tree. Returns true if the node was found.
+    pub fn accessibility_action(
+        &mut self,
+        node: u64,
+        action: TheAccessAction,
+        ctx: &mut TheContext,
+    ) -> bool {
+        let Some(node) = self.access_tree.as_ref().and_then(|tree| tree.node(node)) else {
+            return false;
+        };
+        let (source, bounds) = (node.source.clone(), node.bounds);
+
+        match action {
+            TheAccessAction::Focus => ctx.ui.set_keyboard_focus(&source),
+            TheAccessAction::Click => {
+                let x = (bounds.x + bounds.width / 2) as f32;
+                let y = (bounds.y + bounds.height / 2) as f32;
+                self.touch_down(x, y, ctx);
+                self.touch_up(x, y, ctx);
+            }
+        }
+        true
+    }
+
+    /// Returns the widgets which can be focused from the keyboard, in focus order. Widgets with an
+    /// explicit tab index come first (see TheUIContext::set_tab_index()), followed by all other
+    /// widgets in layout order. While a dialog is open only its widgets are returned.
+    pub fn focus_order(&mut self, ctx: &TheContext) -> Vec<TheId> {
+        let mut ids = vec![];
+        if let Some(dialog) = self.dialogs.last_mut() {
+            dialog.canvas.collect_focus_ids(&mut ids);
+        } else {
+            self.canvas.collect_focus_ids(&mut ids);
+        }
+
+        let tab_indices = &ctx.ui.tab_indices;
+        let mut indexed: Vec<(i32, TheId)> = ids
+            .iter()
+            .filter_map(|id| tab_indices.get(&id.uuid).map(|index| (*index, id.clone())))
+            .filter(|(index, _)| *index >= 0)
+            .collect();
+        indexed.sort_by_key(|(index, _)| *index);
+
+        let mut order: Vec<TheId> = indexed.into_iter().map(|(_, id)| id).collect();
+        order.extend(
+            ids.into_iter()
+                .filter(|id| !tab_indices.contains_key(&id.uuid)),
+        );
+        order
+    }
+
+    /// Moves the keyboard focus to the next (or previous) widget in the focus order, wrapping
+    /// around at the ends. Returns true if the focus changed.
+    pub fn focus_next(&mut self, forward: bool, ctx: &mut TheContext) -> bool {
+        let order = self.focus_order(ctx);
+        if order.is_empty() {
+            return false;
+        }
+
+        let count = order.len();
+        let current = order.iter().position(|id| id.equals(&ctx.ui.focus));
+        let next = match current {
+            Some(index) if forward => (index + 1) % count,
+            Some(index) => (index + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+
+        if Some(next) == current {
+            return false;
+        }
+
+        ctx.ui.set_keyboard_focus(&order[next]);
+        true
+    }
+
+    /// Handles Tab / Shift+Tab. Widgets which use Tab themselves keep it, for them focus is moved
+    /// with Ctrl+Tab.
+    fn tab_moves_focus(&mut self, ctx: &mut TheContext) -> bool {
+        let mut uses_tab = false;
+        if let Some(id) = ctx.ui.focus.clone() {
+            if let Some(widget) = self.get_widget_abs(Some(&id.name), Some(&id.uuid)) {
+                uses_tab = widget.supports_tab_input();
+            }
+        }
+
+        if uses_tab && !self.ctrl {
+            return false;
+        }
+
+        self.focus_next(!self.shift, ctx)
+    }
+
+    /// Returns the button role of the topmost dialog triggered by the key. Enter is left to
+    /// focused widgets which handle Tab themselves, like multi line text edits.
+    fn dialog_key_role(
+        &mut self,
+        key: Option<TheKeyCode>,
+        ctx: &mut TheContext,
+    ) -> Option<TheDialogButtonRole> {
+        let dialog = self.dialogs.last()?;
+        match key? {
+            TheKeyCode::Escape => dialog.escape_button(),
+            TheKeyCode::Return | TheKeyCode::NumpadEnter => {
+                let role = dialog.enter_button()?;
+                if let Some(id) = ctx.ui.focus.clone() {
+                    if let Some(widget) = self.get_widget_abs(Some(&id.name), Some(&id.uuid)) {
+                        if widget.supports_tab_input() {
+                            return None;
+                        }
+                    }
+                }
+                Some(role)
+            }
+            _ => None,
+        }
+    }
+
     /// Returns the layout at the given position.
     pub fn get_layout_at_coord(&mut self, coord: Vec2<i32>) -> Option<TheId> {
-        if let Some(dialog) = &mut self.dialog {
-            if let Some(layout) = dialog.get_layout_at_coord(coord) {
+        if let Some(dialog) = self.dialogs.last_mut() {
+            if let Some(layout) = dialog.canvas.get_layout_at_coord(coord) {
                 return Some(layout);
             }
         } else if let Some(layout) = self.canvas.get_layout_at_coord(coord) {
@@ -1108,8 +2008,8 @@ impl TheUI {
 
     /// Returns the absolute widget at the given position.
     pub fn get_widget_at_coord(&mut self, coord: Vec2<i32>) -> Option<&mut Box<dyn TheWidget>> {
-        if let Some(dialog) = &mut self.dialog {
-            if let Some(widget) = dialog.get_widget_at_coord(coord) {
+        if let Some(dialog) = self.dialogs.last_mut() {
+            if let Some(widget) = dialog.canvas.get_widget_at_coord(coord) {
                 return Some(widget);
             }
         } else if let Some(widget) = self.canvas.get_widget_at_coord(coord) {
@@ -1123,8 +2023,8 @@ impl TheUI {
         name: Option<&String>,
         uuid: Option<&Uuid>,
     ) -> Option<&mut Box<dyn TheWidget>> {
-        if let Some(dialog) = &mut self.dialog {
-            dialog.get_widget(name, uuid)
+        if let Some(dialog) = self.dialogs.last_mut() {
+            dialog.canvas.get_widget(name, uuid)
         } else {
             self.canvas.get_widget(name, uuid)
         }
@@ -1256,6 +2156,30 @@ impl TheUI {
         None
     }
 
+    /// Gets a given TheTableLayout by name
+    pub fn get_table_layout(&mut self, name: &str) -> Option<&mut dyn TheTableLayoutTrait> {
+        if let Some(layout) = self.canvas.get_layout(Some(&name.to_string()), None) {
+            return layout.as_table_layout();
+        }
+        None
+    }
+
+    /// Gets a given TheDockLayout by name
+    pub fn get_dock_layout(&mut self, name: &str) -> Option<&mut dyn TheDockLayoutTrait> {
+        if let Some(layout) = self.canvas.get_layout(Some(&name.to_string()), None) {
+            return layout.as_dock_layout();
+        }
+        None
+    }
+
+    /// Gets a given TheTreeLayout by id
+    fn get_tree_layout_by_id(&mut self, id: &TheId) -> Option<&mut dyn TheTreeLayoutTrait> {
+        if let Some(layout) = self.canvas.get_layout(None, Some(&id.uuid)) {
+            return layout.as_tree_layout();
+        }
+        None
+    }
+
     /// Gets a given TheRowListLayout by name
     pub fn get_rowlist_layout(&mut self, name: &str) -> Option<&mut dyn TheRowListLayoutTrait> {
         if let Some(text_line_edit) = self.canvas.get_layout(Some(&name.to_string()), None) {
@@ -1293,6 +2217,15 @@ impl TheUI {
         None
     }
 
+    /// Gets a given TheCodeLayout by name
+    #[cfg(feature = "code")]
+    pub fn get_code_layout(&mut self, name: &str) -> Option<&mut dyn TheCodeLayoutTrait> {
+        if let Some(layout) = self.canvas.get_layout(Some(&name.to_string()), None) {
+            return layout.as_code_layout();
+        }
+        None
+    }
+
     /// Gets a given TheSharedHLayout by name
     pub fn get_sharedhlayout(&mut self, name: &str) -> Option<&mut dyn TheSharedHLayoutTrait> {
         if let Some(layout) = self.canvas.get_layout(Some(&name.to_string()), None) {
@@ -1309,6 +2242,22 @@ impl TheUI {
         None
     }
 
+    /// Gets a given TheGridLayout by name
+    pub fn get_grid_layout(&mut self, name: &str) -> Option<&mut dyn TheGridLayoutTrait> {
+        if let Some(layout) = self.canvas.get_layout(Some(&name.to_string()), None) {
+            return layout.as_grid_layout();
+        }
+        None
+    }
+
+    /// Gets a given TheFlexLayout by name
+    pub fn get_flex_layout(&mut self, name: &str) -> Option<&mut dyn TheFlexLayoutTrait> {
+        if let Some(layout) = self.canvas.get_layout(Some(&name.to_string()), None) {
+            return layout.as_flex_layout();
+        }
+        None
+    }
+
     /// Gets a given TheHLayout by name
     pub fn get_hlayout(&mut self, name: &str) -> Option<&mut dyn TheHLayoutTrait> {
         if let Some(layout) = self.canvas.get_layout(Some(&name.to_string()), None) {
@@ -1406,7 +2355,8 @@ impl TheUI {
     }
 
     #[cfg(feature = "ui")]
-    /// Opens a dialog which will have the canvas as context and the given text as title.
+    /// Opens a dialog which will have the canvas as context and the given text as title. The id
+    /// of the dialog is the id of the "Dialog Value" widget of the canvas, if any.
     pub fn show_dialog(
         &mut self,
         text: &str,
@@ -1414,57 +2364,85 @@ impl TheUI {
         buttons: Vec<TheDialogButtonRole>,
         ctx: &mut TheContext,
     ) {
-        self.dialog_text = text.to_string();
+        let id = canvas
+            .get_widget(Some(&"Dialog Value".to_string()), None)
+            .map(|widget| widget.id().clone())
+            .unwrap_or_else(TheId::empty);
 
-        let width = canvas.limiter.get_max_width();
-        let mut height = canvas.limiter.get_max_height();
-
-        if !buttons.is_empty() {
-            let mut toolbar_hlayout = TheHLayout::new(TheId::empty());
-            toolbar_hlayout.set_background_color(Some(TheThemeColors::ListLayoutBackground));
-            toolbar_hlayout.limiter_mut().set_max_width(width);
-            toolbar_hlayout.set_margin(Vec4::new(5, 2, 5, 2));
+        let mut dialog = TheDialog::new(id, text, canvas);
+        for role in buttons {
+            dialog.add_button(role.into());
+        }
+        self.push_dialog(dialog, ctx);
+    }
 
-            for b in &buttons {
-                let mut button = TheTraybarButton::new(TheId::named(b.to_id()));
-                button.set_text(b.to_string().to_string());
-                toolbar_hlayout.add_widget(Box::new(button));
-            }
+    /// Shows a transient notification in the corner of the main window.
+    pub fn show_toast(&mut self, toast: TheToast) {
+        self.toasts.push(toast);
+        self.is_dirty = true;
+    }
 
-            toolbar_hlayout.set_reverse_index(Some(buttons.len() as i32));
+    #[cfg(feature = "ui")]
+    /// Shows the dialog on top of all other dialogs. The first focusable widget of the dialog
+    /// gets the keyboard focus.
+    pub fn push_dialog(&mut self, mut dialog: TheDialog, ctx: &mut TheContext) {
+        dialog.build();
+        dialog.layout(ctx);
 
-            let mut toolbar_canvas = TheCanvas::default();
-            // toolbar_canvas.set_widget(TheTraybar::new(TheId::empty()));
-            toolbar_hlayout.limiter_mut().set_max_height(30);
-            toolbar_canvas.set_layout(toolbar_hlayout);
-            canvas.set_bottom(toolbar_canvas);
-        }
+        ctx.ui.clear_focus();
+        ctx.ui.clear_hover();
 
-        let off_x = (ctx.width as i32 - width) / 2;
-        let off_y = (ctx.height as i32 - height) / 2;
+        self.dialogs.push(dialog);
 
-        let mut dim = TheDim::new(off_x, off_y, width, height);
-        dim.buffer_x = off_x;
-        dim.buffer_y = off_y;
+        let order = self.focus_order(ctx);
+        let value = order.iter().find(|id| id.name == "Dialog Value");
+        if let Some(id) = value.or(order.first()) {
+            ctx.ui.set_keyboard_focus(id);
+        }
+        ctx.ui.redraw_all = true;
+    }
 
-        canvas.set_dim(dim, ctx);
+    #[cfg(feature = "ui")]
+    /// Closes the topmost dialog like a click on a button of the given role, sending
+    /// TheEvent::DialogValueOnClose.
+    pub fn close_dialog(&mut self, role: TheDialogButtonRole, ctx: &mut TheContext) {
+        Self::pop_dialog(&mut self.dialogs, role, ctx);
+    }
 
-        ctx.ui.clear_focus();
-        ctx.ui.clear_hover();
+    #[cfg(feature = "ui")]
+    fn pop_dialog(dialogs: &mut Vec<TheDialog>, role: TheDialogButtonRole, ctx: &mut TheContext) {
+        if let Some(mut dialog) = dialogs.pop() {
+            let value = dialog.close_value();
+            ctx.ui.send(TheEvent::DialogValueOnClose(
+                role,
+                dialog.title.clone(),
+                dialog.id.uuid,
+                value,
+            ));
+            ctx.ui.clear_focus();
+            ctx.ui.clear_hover();
+            ctx.ui.redraw_all = true;
+        }
+    }
 
-        self.dialog = Some(canvas);
+    #[cfg(feature = "ui")]
+    /// Returns true if a modal dialog is open.
+    pub fn is_dialog_open(&self) -> bool {
+        !self.dialogs.is_empty()
     }
 
     #[cfg(feature = "ui")]
-    /// Clears / closes the dialog.
+    /// Clears / closes the topmost dialog without sending an event.
     pub fn clear_dialog(&mut self) {
-        self.dialog = None;
+        self.dialogs.pop();
     }
 
     #[cfg(feature = "ui")]
-    /// Draws the current dialog.
+    /// Draws the dialogs, from the bottom of the stack to the top.
     pub fn draw_dialog(&mut self, ctx: &mut TheContext) {
-        if let Some(dialog_canvas) = &mut self.dialog {
+        for dialog in &mut self.dialogs {
+            dialog.layout(ctx);
+            let dialog_canvas = &mut dialog.canvas;
             dialog_canvas.draw(&mut self.style, ctx);
 
             let width = dialog_canvas.limiter.get_max_width();
@@ -1559,12 +2537,12 @@ impl TheUI {
                 self.canvas.buffer.pixels_mut(),
                 &(tuple.0 + 13, tuple.1, tuple.2 - 13, 23),
                 ctx.width,
-                &self.dialog_text,
+                &dialog.title,
                 TheFontSettings {
                     size: 15.0,
                     ..Default::default()
                 },
-                &WHITE,
+                self.style.theme().color(DefaultTextColor),
                 TheHorizontalAlign::Left,
                 TheVerticalAlign::Center,
             );
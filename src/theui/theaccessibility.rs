@@ -0,0 +1,472 @@
+use crate::prelude::*;
+use std::fmt::Write;
+use std::hash::{Hash, Hasher};
+
+/// The role of an accessibility node. The roles are named after the roles of AccessKit
+/// (accesskit::Role), a backend maps them one to one.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TheAccessRole {
+    #[default]
+    Unknown,
+    Window,
+    Dialog,
+    Alert,
+    Tooltip,
+    GenericContainer,
+    Group,
+    Toolbar,
+    MenuBar,
+    Button,
+    CheckBox,
+    ColorWell,
+    ComboBox,
+    Label,
+    TextInput,
+    MultilineTextInput,
+    SpinButton,
+    Slider,
+    ScrollBar,
+    ListBox,
+    ListBoxOption,
+    Tree,
+    TreeItem,
+    Table,
+    ColumnHeader,
+    Row,
+    Cell,
+    TabList,
+    Tab,
+    RadioGroup,
+    Status,
+    Image,
+    Canvas,
+    Document,
+}
+
+/// An action an assistive technology requests on a node, see TheUI::accessibility_action().
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TheAccessAction {
+    /// Moves the keyboard focus to the widget.
+    Focus,
+    /// Clicks the center of the widget.
+    Click,
+}
+
+/// The accessibility description of a widget or layout, see TheWidget::accessibility().
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TheAccessInfo {
+    pub role: TheAccessRole,
+    /// The label, e.g. the text of a button.
+    pub name: Option<String>,
+    /// The textual value, e.g. the text of a text edit or the selected option of a dropdown.
+    pub value: Option<String>,
+    /// A longer description, by default the status text of the widget.
+    pub description: Option<String>,
+
+    pub numeric_value: Option<f64>,
+    pub min_numeric_value: Option<f64>,
+    pub max_numeric_value: Option<f64>,
+
+    /// The checked state of toggles, None if the widget can not be checked.
+    pub checked: Option<bool>,
+    /// The selected state of items, None if the widget can not be selected.
+    pub selected: Option<bool>,
+    /// The expanded state of collapsible items, None if the widget can not be expanded.
+    pub expanded: Option<bool>,
+    pub read_only: bool,
+    pub modal: bool,
+}
+
+impl TheAccessInfo {
+    pub fn new(role: TheAccessRole) -> Self {
+        Self {
+            role,
+            ..Default::default()
+        }
+    }
+
+    /// Creates an info with a role and a name.
+    pub fn named(role: TheAccessRole, name: &str) -> Self {
+        Self {
+            role,
+            name: Some(name.to_string()).filter(|name| !name.is_empty()),
+            ..Default::default()
+        }
+    }
+}
+
+/// A node of the accessibility tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TheAccessNode {
+    /// The id of the node, used as the AccessKit NodeId. It is derived from the uuid of the widget
+    /// and stays the same between updates.
+    pub id: u64,
+    /// The id of the widget or layout the node describes.
+    pub source: TheId,
+    pub info: TheAccessInfo,
+    /// The logical screen rectangle.
+    pub bounds: TheDim,
+    pub focused: bool,
+    pub disabled: bool,
+    pub children: Vec<u64>,
+}
+
+/// The changed nodes of the tree since the last update, the equivalent of an AccessKit
+/// TreeUpdate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TheAccessTreeUpdate {
+    pub nodes: Vec<TheAccessNode>,
+    /// The root of the tree if it changed, i.e. for the first update.
+    pub root: Option<u64>,
+    /// The node with the keyboard focus, the root if no widget has focus.
+    pub focus: u64,
+}
+
+/// The accessibility tree of a window, built from the canvas hierarchy by
+/// TheUI::accessibility_tree(). Canvases are transparent, the nodes are the layouts and widgets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TheAccessTree {
+    pub root: u64,
+    pub focus: u64,
+    pub nodes: FxHashMap<u64, TheAccessNode>,
+}
+
+impl TheAccessTree {
+    /// Creates a tree with the given root node.
+    pub fn new(source: TheId, info: TheAccessInfo, bounds: TheDim) -> Self {
+        let root = Self::node_id(&source.uuid);
+        let mut nodes = FxHashMap::default();
+        nodes.insert(
+            root,
+            TheAccessNode {
+                id: root,
+                source,
+                info,
+                bounds,
+                focused: false,
+                disabled: false,
+                children: vec![],
+            },
+        );
+        Self {
+            root,
+            focus: root,
+            nodes,
+        }
+    }
+
+    /// Adds a node below the parent and returns its id.
+    pub fn add(
+        &mut self,
+        parent: u64,
+        source: &TheId,
+        info: TheAccessInfo,
+        bounds: TheDim,
+        ctx: &TheContext,
+    ) -> u64 {
+        let mut id = Self::node_id(&source.uuid);
+        // Widgets may share a uuid, derive a stable id from the position in the tree then.
+        if self.nodes.contains_key(&id) {
+            let mut hasher = FxHasher::default();
+            (parent, self.children(parent).len(), id).hash(&mut hasher);
+            id = hasher.finish();
+        }
+
+        let focused = source.equals(&ctx.ui.focus);
+        if focused {
+            self.focus = id;
+        }
+
+        self.nodes.insert(
+            id,
+            TheAccessNode {
+                id,
+                source: source.clone(),
+                info,
+                bounds,
+                focused,
+                disabled: ctx.ui.is_disabled(&source.name),
+                children: vec![],
+            },
+        );
+        if let Some(parent) = self.nodes.get_mut(&parent) {
+            parent.children.push(id);
+        }
+        id
+    }
+
+    /// Adds the node of a widget below the parent.
+    pub fn add_widget(&mut self, parent: u64, widget: &dyn TheWidget, ctx: &TheContext) -> u64 {
+        let id = self.add(
+            parent,
+            widget.id(),
+            widget.accessibility(),
+            *widget.dim(),
+            ctx,
+        );
+        if widget.disabled() {
+            if let Some(node) = self.nodes.get_mut(&id) {
+                node.disabled = true;
+            }
+        }
+        id
+    }
+
+    /// Returns the node of the given id.
+    pub fn node(&self, id: u64) -> Option<&TheAccessNode> {
+        self.nodes.get(&id)
+    }
+
+    /// Returns the first node, in tree order, describing the widget or layout of the given name.
+    pub fn find(&self, name: &str) -> Option<&TheAccessNode> {
+        self.ordered()
+            .into_iter()
+            .find(|node| node.source.name == name)
+    }
+
+    /// The children of the given node.
+    pub fn children(&self, id: u64) -> &[u64] {
+        self.nodes
+            .get(&id)
+            .map(|node| node.children.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// The nodes in depth first order.
+    pub fn ordered(&self) -> Vec<&TheAccessNode> {
+        let mut nodes = vec![];
+        let mut stack = vec![self.root];
+        while let Some(id) = stack.pop() {
+            if let Some(node) = self.nodes.get(&id) {
+                nodes.push(node);
+                stack.extend(node.children.iter().rev());
+            }
+        }
+        nodes
+    }
+
+    /// Returns the nodes which changed since the previous tree, None if nothing changed.
+    pub fn update_since(&self, previous: Option<&TheAccessTree>) -> Option<TheAccessTreeUpdate> {
+        let Some(previous) = previous else {
+            return Some(TheAccessTreeUpdate {
+                nodes: self.ordered().into_iter().cloned().collect(),
+                root: Some(self.root),
+                focus: self.focus,
+            });
+        };
+
+        let nodes: Vec<TheAccessNode> = self
+            .ordered()
+            .into_iter()
+            .filter(|node| previous.nodes.get(&node.id) != Some(node))
+            .cloned()
+            .collect();
+        let root_changed = self.root != previous.root;
+
+        if nodes.is_empty() && !root_changed && self.focus == previous.focus {
+            None
+        } else {
+            Some(TheAccessTreeUpdate {
+                nodes,
+                root: root_changed.then_some(self.root),
+                focus: self.focus,
+            })
+        }
+    }
+
+    /// A textual dump of the tree, one node per line, indented by depth. Each line shows the
+    /// role, the name, the value and the states of the node.
+    pub fn dump(&self) -> String {
+        let mut out = String::new();
+        self.dump_node(self.root, 0, &mut out);
+        out
+    }
+
+    fn dump_node(&self, id: u64, depth: usize, out: &mut String) {
+        let Some(node) = self.nodes.get(&id) else {
+            return;
+        };
+        let info = &node.info;
+
+        let _ = write!(out, "{}{:?}", "  ".repeat(depth), info.role);
+        if let Some(name) = &info.name {
+            let _ = write!(out, " {:?}", name);
+        }
+        if let Some(value) = &info.value {
+            let _ = write!(out, " value={:?}", value);
+        }
+
+        let mut states = vec![];
+        if node.focused {
+            states.push("focused");
+        }
+        if node.disabled {
+            states.push("disabled");
+        }
+        if info.read_only {
+            states.push("read-only");
+        }
+        if info.modal {
+            states.push("modal");
+        }
+        match info.checked {
+            Some(true) => states.push("checked"),
+            Some(false) => states.push("unchecked"),
+            None => {}
+        }
+        if info.selected == Some(true) {
+            states.push("selected");
+        }
+        match info.expanded {
+            Some(true) => states.push("expanded"),
+            Some(false) => states.push("collapsed"),
+            None => {}
+        }
+        if !states.is_empty() {
+            let _ = write!(out, " [{}]", states.join(", "));
+        }
+        out.push('\n');
+
+        for child in &node.children {
+            self.dump_node(*child, depth + 1, out);
+        }
+    }
+
+    /// The AccessKit NodeId of the given uuid.
+    pub fn node_id(uuid: &Uuid) -> u64 {
+        let (high, low) = uuid.as_u64_pair();
+        high ^ low
+    }
+}
+
+#[cfg(feature = "accessibility")]
+impl From<TheAccessRole> for accesskit::Role {
+    fn from(role: TheAccessRole) -> Self {
+        use accesskit::Role;
+        match role {
+            TheAccessRole::Unknown => Role::Unknown,
+            TheAccessRole::Window => Role::Window,
+            TheAccessRole::Dialog => Role::Dialog,
+            TheAccessRole::Alert => Role::Alert,
+            TheAccessRole::Tooltip => Role::Tooltip,
+            TheAccessRole::GenericContainer => Role::GenericContainer,
+            TheAccessRole::Group => Role::Group,
+            TheAccessRole::Toolbar => Role::Toolbar,
+            TheAccessRole::MenuBar => Role::MenuBar,
+            TheAccessRole::Button => Role::Button,
+            TheAccessRole::CheckBox => Role::CheckBox,
+            TheAccessRole::ColorWell => Role::ColorWell,
+            TheAccessRole::ComboBox => Role::ComboBox,
+            TheAccessRole::Label => Role::Label,
+            TheAccessRole::TextInput => Role::TextInput,
+            TheAccessRole::MultilineTextInput => Role::MultilineTextInput,
+            TheAccessRole::SpinButton => Role::SpinButton,
+            TheAccessRole::Slider => Role::Slider,
+            TheAccessRole::ScrollBar => Role::ScrollBar,
+            TheAccessRole::ListBox => Role::ListBox,
+            TheAccessRole::ListBoxOption => Role::ListBoxOption,
+            TheAccessRole::Tree => Role::Tree,
+            TheAccessRole::TreeItem => Role::TreeItem,
+            TheAccessRole::Table => Role::Table,
+            TheAccessRole::ColumnHeader => Role::ColumnHeader,
+            TheAccessRole::Row => Role::Row,
+            TheAccessRole::Cell => Role::Cell,
+            TheAccessRole::TabList => Role::TabList,
+            TheAccessRole::Tab => Role::Tab,
+            TheAccessRole::RadioGroup => Role::RadioGroup,
+            TheAccessRole::Status => Role::Status,
+            TheAccessRole::Image => Role::Image,
+            TheAccessRole::Canvas => Role::Canvas,
+            TheAccessRole::Document => Role::Document,
+        }
+    }
+}
+
+#[cfg(feature = "accessibility")]
+impl TheAccessNode {
+    /// Converts the node to an AccessKit node with its bounds in physical pixels.
+    pub fn to_accesskit(&self, scale_factor: f64) -> accesskit::Node {
+        let info = &self.info;
+        let mut node = accesskit::Node::new(info.role.into());
+
+        if let Some(name) = &info.name {
+            node.set_label(name.clone());
+        }
+        if let Some(value) = &info.value {
+            node.set_value(value.clone());
+        }
+        if let Some(description) = &info.description {
+            node.set_description(description.clone());
+        }
+        if let Some(value) = info.numeric_value {
+            node.set_numeric_value(value);
+        }
+        if let Some(value) = info.min_numeric_value {
+            node.set_min_numeric_value(value);
+        }
+        if let Some(value) = info.max_numeric_value {
+            node.set_max_numeric_value(value);
+        }
+        if let Some(checked) = info.checked {
+            node.set_toggled(if checked {
+                accesskit::Toggled::True
+            } else {
+                accesskit::Toggled::False
+            });
+        }
+        if let Some(selected) = info.selected {
+            node.set_selected(selected);
+        }
+        if let Some(expanded) = info.expanded {
+            node.set_expanded(expanded);
+        }
+        if info.read_only {
+            node.set_read_only();
+        }
+        if info.modal {
+            node.set_modal();
+        }
+        if self.disabled {
+            node.set_disabled();
+        }
+
+        let bounds = &self.bounds;
+        node.set_bounds(accesskit::Rect::new(
+            bounds.x as f64 * scale_factor,
+            bounds.y as f64 * scale_factor,
+            (bounds.x + bounds.width) as f64 * scale_factor,
+            (bounds.y + bounds.height) as f64 * scale_factor,
+        ));
+        node.set_children(
+            self.children
+                .iter()
+                .map(|id| accesskit::NodeId(*id))
+                .collect::<Vec<_>>(),
+        );
+
+        if !self.disabled && self.source.uuid != Uuid::nil() {
+            node.add_action(accesskit::Action::Focus);
+            node.add_action(accesskit::Action::Click);
+        }
+        node
+    }
+}
+
+#[cfg(feature = "accessibility")]
+impl TheAccessTreeUpdate {
+    /// Converts the update to an AccessKit TreeUpdate with the bounds in physical pixels.
+    pub fn to_accesskit(&self, scale_factor: f64) -> accesskit::TreeUpdate {
+        accesskit::TreeUpdate {
+            nodes: self
+                .nodes
+                .iter()
+                .map(|node| (accesskit::NodeId(node.id), node.to_accesskit(scale_factor)))
+                .collect(),
+            tree: self.root.map(|root| accesskit::Tree {
+                toolkit_name: Some("TheFramework".to_string()),
+                ..accesskit::Tree::new(accesskit::NodeId(root))
+            }),
+            focus: accesskit::NodeId(self.focus),
+        }
+    }
+}
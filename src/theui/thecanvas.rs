@@ -63,6 +63,7 @@ impl TheCanvas {
     pub fn set_dim(&mut self, dim: TheDim, ctx: &mut TheContext) {
         if dim != self.dim || ctx.ui.relayout {
             self.dim = dim;
+            self.buffer.set_scale(ctx.scale_factor);
             self.buffer.set_dim(self.dim);
             self.layout(self.dim.width, self.dim.height, ctx);
         }
@@ -112,6 +113,16 @@ impl TheCanvas {
         self.layout = Some(Box::new(layout));
     }
 
+    /// Sets an already boxed widget.
+    pub fn set_boxed_widget(&mut self, widget: Box<dyn TheWidget>) {
+        self.widget = Some(widget);
+    }
+
+    /// Sets an already boxed layout.
+    pub fn set_boxed_layout(&mut self, layout: Box<dyn TheLayout>) {
+        self.layout = Some(layout);
+    }
+
     /// Sets the canvas to the left of this canvas.
     pub fn set_left(&mut self, canvas: TheCanvas) {
         self.left = Some(Box::new(canvas));
@@ -139,7 +150,10 @@ impl TheCanvas {
 
     /// Resize the canvas if needed
     pub fn resize(&mut self, width: i32, height: i32, ctx: &mut TheContext) -> bool {
-        if width != self.dim.width || height != self.dim.height {
+        if width != self.dim.width
+            || height != self.dim.height
+            || self.buffer.scale() != ctx.scale_factor
+        {
             self.set_dim(TheDim::new(self.dim.x, self.dim.y, width, height), ctx);
             true
         } else {
@@ -242,6 +256,79 @@ impl TheCanvas {
         None
     }
 
+    /// Appends the ids of the visible widgets which accept keyboard focus, in reading order:
+    /// top, left, the content, right and bottom.
+    pub fn collect_focus_ids(&mut self, ids: &mut Vec<TheId>) {
+        if let Some(top) = &mut self.top {
+            top.collect_focus_ids(ids);
+        }
+
+        if let Some(left) = &mut self.left {
+            left.collect_focus_ids(ids);
+        }
+
+        if let Some(center) = &mut self.center {
+            center.collect_focus_ids(ids);
+        } else {
+            if let Some(layout) = &mut self.layout {
+                layout.collect_focus_ids(ids);
+            }
+
+            if let Some(widget) = &mut self.widget {
+                if widget.accepts_focus() && !widget.disabled() && widget.dim().is_valid() {
+                    ids.push(widget.id().clone());
+                }
+            }
+        }
+
+        if let Some(right) = &mut self.right {
+            right.collect_focus_ids(ids);
+        }
+
+        if let Some(bottom) = &mut self.bottom {
+            bottom.collect_focus_ids(ids);
+        }
+    }
+
+    /// Adds the accessibility nodes of the layouts and widgets of the canvas below the parent
+    /// node, in layout order.
+    pub fn collect_access_nodes(
+        &mut self,
+        tree: &mut TheAccessTree,
+        parent: u64,
+        ctx: &TheContext,
+    ) {
+        if let Some(top) = &mut self.top {
+            top.collect_access_nodes(tree, parent, ctx);
+        }
+
+        if let Some(left) = &mut self.left {
+            left.collect_access_nodes(tree, parent, ctx);
+        }
+
+        if let Some(center) = &mut self.center {
+            center.collect_access_nodes(tree, parent, ctx);
+        } else {
+            if let Some(layout) = &mut self.layout {
+                layout.collect_access_nodes(tree, parent, ctx);
+            }
+
+            if let Some(widget) = &self.widget {
+                if widget.dim().is_valid() {
+                    tree.add_widget(parent, widget.as_ref(), ctx);
+                }
+            }
+        }
+
+        if let Some(right) = &mut self.right {
+            right.collect_access_nodes(tree, parent, ctx);
+        }
+
+        if let Some(bottom) = &mut self.bottom {
+            bottom.collect_access_nodes(tree, parent, ctx);
+        }
+    }
+
     /// Returns the layout of the given id
     pub fn get_layout(
         &mut self,
@@ -547,6 +634,7 @@ impl TheCanvas {
                     //     force_widget_redraw
                     // );
                     widget.draw(&mut self.buffer, style, ctx);
+                    ctx.add_damage(*widget.dim());
                 }
             }
 
@@ -555,21 +643,34 @@ impl TheCanvas {
                 if ctx.ui.redraw_all || layout.needs_redraw() {
                     //|| layout.widgets().is_empty() {
                     layout.draw(&mut self.buffer, style, ctx);
+                    ctx.add_damage(*layout.dim());
                 }
             }
         }
     }
 
-    pub fn draw_overlay(&mut self, style: &mut Box<dyn TheStyle>, ctx: &mut TheContext) {
+    /// Draws the overlay of the overlay widget, if any, and returns the region it covers.
+    pub fn draw_overlay(
+        &mut self,
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+    ) -> Option<TheDim> {
         if let Some(overlay) = &ctx.ui.overlay {
             if let Some(widget) = self.get_widget(None, Some(&overlay.uuid)) {
                 let buffer = widget.draw_overlay(style, ctx);
                 if buffer.is_valid() {
-                    self.buffer
-                        .copy_into(buffer.dim().buffer_x, buffer.dim().buffer_y, &buffer);
+                    let dim = buffer.dim();
+                    self.buffer.copy_into(dim.buffer_x, dim.buffer_y, &buffer);
+                    return Some(TheDim::new(
+                        dim.buffer_x,
+                        dim.buffer_y,
+                        dim.width,
+                        dim.height,
+                    ));
                 }
             }
         }
+        None
     }
 
     /// Returns true if any widget or layout attached to this canvas (or its children) needs a redraw.
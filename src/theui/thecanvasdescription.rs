@@ -0,0 +1,556 @@
+use crate::prelude::*;
+use std::path::Path;
+
+/// The size limits of a canvas, layout or widget. Missing values keep the default of
+/// TheSizeLimiter.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TheLimiterDescription {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_width: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_height: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_width: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_height: Option<i32>,
+}
+
+impl TheLimiterDescription {
+    /// Applies the limits to the given limiter.
+    pub fn apply(&self, limiter: &mut TheSizeLimiter) {
+        if let Some(width) = self.min_width {
+            limiter.set_min_width(width);
+        }
+        if let Some(height) = self.min_height {
+            limiter.set_min_height(height);
+        }
+        if let Some(width) = self.max_width {
+            limiter.set_max_width(width);
+        }
+        if let Some(height) = self.max_height {
+            limiter.set_max_height(height);
+        }
+    }
+}
+
+/// The layouts which can be described.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TheLayoutKind {
+    HLayout,
+    VLayout,
+    /// Pairs the label of each widget with the widget.
+    TextLayout,
+    /// A list of ListItem widgets.
+    ListLayout,
+    /// A list of RowListItem widgets.
+    RowListLayout,
+    /// Shows one of its canvases, selected by the index.
+    StackLayout,
+    /// Shows one of its canvases in tabs named by the canvas names.
+    TabLayout,
+    /// Splits two canvases horizontally at the ratio.
+    SharedHLayout,
+    /// Splits two canvases vertically at the ratio.
+    SharedVLayout,
+}
+
+/// The widgets which can be described.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TheWidgetKind {
+    Text,
+    TextLineEdit,
+    TextAreaEdit,
+    TextView,
+    MarkdownView,
+    TraybarButton,
+    ToolbarButton,
+    SectionbarButton,
+    CheckButton,
+    DropdownMenu,
+    Slider,
+    ColorButton,
+    GroupButton,
+    Tabbar,
+    ListItem,
+    RowListItem,
+    Statusbar,
+    Switchbar,
+    Spacer,
+    Separator,
+    HDivider,
+}
+
+/// The description of a widget.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TheWidgetDescription {
+    #[serde(rename = "type")]
+    pub kind: TheWidgetKind,
+    /// The name of the TheId of the widget, widgets without a name get an empty id.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    /// The text of labels, buttons, text edits and list items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// The label shown in front of the widget in a TextLayout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// The initial value, set via TheWidget::set_value().
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<TheValue>,
+    /// The range of sliders and numeric text edits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<TheValue>,
+    /// The options of dropdown menus, the texts of group buttons and the tabs of tabbars.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limiter: Option<TheLimiterDescription>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+/// The description of a layout and its content.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TheLayoutDescription {
+    #[serde(rename = "type")]
+    pub kind: TheLayoutKind,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    /// The margin as left, top, right, bottom.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub margin: Option<[i32; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub padding: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<TheThemeColors>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limiter: Option<TheLimiterDescription>,
+    /// The widgets of box, text and list layouts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub widgets: Vec<TheWidgetDescription>,
+    /// The canvases of stack, tab and shared layouts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub canvases: Vec<TheCanvasDescription>,
+    /// The visible canvas of stack and tab layouts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    /// The split ratio of shared layouts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ratio: Option<f32>,
+    /// The height of the items of list layouts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_size: Option<i32>,
+}
+
+/// A declarative description of a TheCanvas hierarchy, stored as JSON. It describes the border
+/// canvases, the layout or widget of each canvas and the ids, limits and initial values of the
+/// widgets. build() creates the canvas.
+///
+/// ```json
+/// {
+///   "limiter": { "max_width": 400 },
+///   "top": { "widget": { "type": "Switchbar", "text": "Material" } },
+///   "layout": {
+///     "type": "TextLayout",
+///     "name": "Material Layout",
+///     "widgets": [
+///       { "type": "Slider", "name": "Metallic", "label": "Metallic", "value": { "Float": 0.5 } }
+///     ]
+///   }
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TheCanvasDescription {
+    /// The name of the canvas, used as the tab name in tab layouts.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limiter: Option<TheLimiterDescription>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub left: Option<Box<TheCanvasDescription>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top: Option<Box<TheCanvasDescription>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub right: Option<Box<TheCanvasDescription>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bottom: Option<Box<TheCanvasDescription>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub center: Option<Box<TheCanvasDescription>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<TheLayoutDescription>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub widget: Option<TheWidgetDescription>,
+}
+
+impl TheCanvasDescription {
+    /// Parses a description from its JSON representation.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|err| err.to_string())
+    }
+
+    /// Loads a description from the given JSON file.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Self::parse(&json).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    /// Saves the description as a JSON file.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    /// Convert the instance to json.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// The names of the described widgets, in the order of the description.
+    pub fn widget_names(&self) -> Vec<String> {
+        let mut names = vec![];
+        self.collect_widget_names(&mut names);
+        names
+    }
+
+    fn collect_widget_names(&self, names: &mut Vec<String>) {
+        for canvas in [
+            &self.left,
+            &self.top,
+            &self.right,
+            &self.bottom,
+            &self.center,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            canvas.collect_widget_names(names);
+        }
+        if let Some(layout) = &self.layout {
+            for widget in &layout.widgets {
+                names.push(widget.name.clone());
+            }
+            for canvas in &layout.canvases {
+                canvas.collect_widget_names(names);
+            }
+        }
+        if let Some(widget) = &self.widget {
+            names.push(widget.name.clone());
+        }
+        names.retain(|name| !name.is_empty());
+    }
+
+    /// Builds the described canvas. Descriptions which do not fit the layout, e.g. a shared
+    /// layout without two canvases, are reported as errors.
+    pub fn build(&self, ctx: &mut TheContext) -> Result<TheCanvas, String> {
+        let mut canvas = TheCanvas::new();
+        if let Some(limiter) = &self.limiter {
+            limiter.apply(canvas.limiter_mut());
+        }
+
+        if let Some(left) = &self.left {
+            canvas.set_left(left.build(ctx)?);
+        }
+        if let Some(top) = &self.top {
+            canvas.set_top(top.build(ctx)?);
+        }
+        if let Some(right) = &self.right {
+            canvas.set_right(right.build(ctx)?);
+        }
+        if let Some(bottom) = &self.bottom {
+            canvas.set_bottom(bottom.build(ctx)?);
+        }
+        if let Some(center) = &self.center {
+            canvas.set_center(center.build(ctx)?);
+        }
+
+        match (&self.layout, &self.widget) {
+            (Some(_), Some(_)) => {
+                return Err("A canvas can have either a layout or a widget".to_string());
+            }
+            (Some(layout), None) => canvas.set_boxed_layout(layout.build(ctx)?),
+            (None, Some(widget)) => canvas.set_boxed_widget(widget.build(ctx)?),
+            (None, None) => {}
+        }
+
+        Ok(canvas)
+    }
+}
+
+impl TheLayoutDescription {
+    /// Builds the described layout.
+    pub fn build(&self, ctx: &mut TheContext) -> Result<Box<dyn TheLayout>, String> {
+        let id = Self::id(&self.name);
+
+        let mut layout: Box<dyn TheLayout> = match self.kind {
+            TheLayoutKind::HLayout => {
+                let mut layout = TheHLayout::new(id);
+                for widget in &self.widgets {
+                    layout.add_widget(widget.build(ctx)?);
+                }
+                Box::new(layout)
+            }
+            TheLayoutKind::VLayout => {
+                let mut layout = TheVLayout::new(id);
+                for widget in &self.widgets {
+                    layout.add_widget(widget.build(ctx)?);
+                }
+                Box::new(layout)
+            }
+            TheLayoutKind::TextLayout => {
+                let mut layout = TheTextLayout::new(id);
+                for widget in &self.widgets {
+                    let label = widget.label.clone().unwrap_or_default();
+                    layout.add_pair(label, widget.build(ctx)?);
+                }
+                Box::new(layout)
+            }
+            TheLayoutKind::ListLayout => {
+                let mut layout = TheListLayout::new(id);
+                if let Some(item_size) = self.item_size {
+                    layout.set_item_size(item_size);
+                }
+                for widget in &self.widgets {
+                    layout.add_item(widget.build_list_item(ctx)?, ctx);
+                }
+                Box::new(layout)
+            }
+            TheLayoutKind::RowListLayout => {
+                let mut layout = TheRowListLayout::new(id);
+                if let Some(item_size) = self.item_size {
+                    layout.set_item_size(item_size);
+                }
+                for widget in &self.widgets {
+                    layout.add_item(widget.build_row_list_item(ctx)?, ctx);
+                }
+                Box::new(layout)
+            }
+            TheLayoutKind::StackLayout => {
+                let mut layout = TheStackLayout::new(id);
+                for canvas in &self.canvases {
+                    layout.add_canvas(canvas.build(ctx)?);
+                }
+                layout.set_index(self.index.unwrap_or_default());
+                Box::new(layout)
+            }
+            TheLayoutKind::TabLayout => {
+                let mut layout = TheTabLayout::new(id);
+                for canvas in &self.canvases {
+                    layout.add_canvas(canvas.name.clone(), canvas.build(ctx)?);
+                }
+                if let Some(index) = self.index {
+                    layout.set_index(index);
+                }
+                Box::new(layout)
+            }
+            TheLayoutKind::SharedHLayout | TheLayoutKind::SharedVLayout => {
+                let [first, second] = self.canvases.as_slice() else {
+                    return Err(format!(
+                        "{:?} \"{}\" needs two canvases",
+                        self.kind, self.name
+                    ));
+                };
+                if self.kind == TheLayoutKind::SharedHLayout {
+                    let mut layout = TheSharedHLayout::new(id);
+                    layout.add_canvas(first.build(ctx)?);
+                    layout.add_canvas(second.build(ctx)?);
+                    if let Some(ratio) = self.ratio {
+                        layout.set_shared_ratio(ratio);
+                    }
+                    Box::new(layout)
+                } else {
+                    let mut layout = TheSharedVLayout::new(id);
+                    layout.add_canvas(first.build(ctx)?);
+                    layout.add_canvas(second.build(ctx)?);
+                    if let Some(ratio) = self.ratio {
+                        layout.set_shared_ratio(ratio);
+                    }
+                    Box::new(layout)
+                }
+            }
+        };
+
+        if let Some(margin) = self.margin {
+            layout.set_margin(Vec4::from(margin));
+        }
+        if let Some(padding) = self.padding {
+            layout.set_padding(padding);
+        }
+        if self.background.is_some() {
+            layout.set_background_color(self.background);
+        }
+        if let Some(limiter) = &self.limiter {
+            limiter.apply(layout.limiter_mut());
+        }
+
+        Ok(layout)
+    }
+
+    fn id(name: &str) -> TheId {
+        if name.is_empty() {
+            TheId::empty()
+        } else {
+            TheId::named(name)
+        }
+    }
+}
+
+impl TheWidgetDescription {
+    /// Builds the described widget.
+    pub fn build(&self, ctx: &mut TheContext) -> Result<Box<dyn TheWidget>, String> {
+        let id = TheLayoutDescription::id(&self.name);
+        let text = self.text.clone().unwrap_or_default();
+
+        let mut widget: Box<dyn TheWidget> = match self.kind {
+            TheWidgetKind::Text => {
+                let mut widget = TheText::new(id);
+                widget.set_text(text);
+                Box::new(widget)
+            }
+            TheWidgetKind::TextLineEdit => {
+                let mut widget = TheTextLineEdit::new(id);
+                widget.set_text(text);
+                if let Some(range) = &self.range {
+                    widget.set_range(range.clone());
+                }
+                Box::new(widget)
+            }
+            TheWidgetKind::TextAreaEdit => {
+                let mut widget = TheTextAreaEdit::new(id);
+                widget.set_text(text);
+                Box::new(widget)
+            }
+            TheWidgetKind::TextView => {
+                let mut widget = TheTextView::new(id);
+                widget.set_text(text);
+                Box::new(widget)
+            }
+            TheWidgetKind::MarkdownView => {
+                let mut widget = TheMarkdownView::new(id);
+                widget.set_text(text);
+                Box::new(widget)
+            }
+            TheWidgetKind::TraybarButton => {
+                let mut widget = TheTraybarButton::new(id);
+                widget.set_text(text);
+                Box::new(widget)
+            }
+            TheWidgetKind::ToolbarButton => {
+                let mut widget = TheToolbarButton::new(id);
+                widget.set_text(text);
+                Box::new(widget)
+            }
+            TheWidgetKind::SectionbarButton => {
+                let mut widget = TheSectionbarButton::new(id);
+                widget.set_text(text);
+                Box::new(widget)
+            }
+            TheWidgetKind::CheckButton => Box::new(TheCheckButton::new(id)),
+            TheWidgetKind::DropdownMenu => {
+                let mut widget = TheDropdownMenu::new(id);
+                for option in &self.options {
+                    widget.add_option(option.clone());
+                }
+                Box::new(widget)
+            }
+            TheWidgetKind::Slider => {
+                let mut widget = TheSlider::new(id);
+                if let Some(range) = &self.range {
+                    widget.set_range(range.clone());
+                }
+                Box::new(widget)
+            }
+            TheWidgetKind::ColorButton => Box::new(TheColorButton::new(id)),
+            TheWidgetKind::GroupButton => {
+                let mut widget = TheGroupButton::new(id);
+                for option in &self.options {
+                    widget.add_text(option.clone());
+                }
+                Box::new(widget)
+            }
+            TheWidgetKind::Tabbar => {
+                let mut widget = TheTabbar::new(id);
+                for option in &self.options {
+                    widget.add_tab(option.clone());
+                }
+                Box::new(widget)
+            }
+            // The items apply the shared properties themselves.
+            TheWidgetKind::ListItem => return Ok(Box::new(self.build_list_item(ctx)?)),
+            TheWidgetKind::RowListItem => return Ok(Box::new(self.build_row_list_item(ctx)?)),
+            TheWidgetKind::Statusbar => {
+                let mut widget = TheStatusbar::new(id);
+                widget.set_text(text);
+                Box::new(widget)
+            }
+            TheWidgetKind::Switchbar => {
+                let mut widget = TheSwitchbar::new(id);
+                widget.set_text(text);
+                Box::new(widget)
+            }
+            TheWidgetKind::Spacer => Box::new(TheSpacer::new(id)),
+            TheWidgetKind::Separator => Box::new(TheSeparator::new(id)),
+            TheWidgetKind::HDivider => Box::new(TheHDivider::new(id)),
+        };
+
+        self.apply(widget.as_mut(), ctx);
+        Ok(widget)
+    }
+
+    /// Applies the properties shared by all widgets.
+    fn apply(&self, widget: &mut dyn TheWidget, ctx: &mut TheContext) {
+        if let Some(status) = &self.status {
+            widget.set_status_text(status);
+        }
+        if let Some(value) = &self.value {
+            widget.set_value(value.clone());
+        }
+        if let Some(limiter) = &self.limiter {
+            limiter.apply(widget.limiter_mut());
+        }
+        if self.disabled {
+            widget.set_disabled(true);
+            // Not via set_disabled(), which resets the state and with it the initial value. The
+            // ids are names, unnamed widgets are only disabled themselves.
+            if !self.name.is_empty() {
+                ctx.ui.disabled_ids.insert(self.name.clone());
+            }
+        }
+    }
+
+    /// Builds the item of a list layout, the description has to be a ListItem.
+    fn build_list_item(&self, ctx: &mut TheContext) -> Result<TheListItem, String> {
+        if self.kind != TheWidgetKind::ListItem {
+            return Err(format!(
+                "List layouts can only contain ListItem widgets, not {:?}",
+                self.kind
+            ));
+        }
+
+        let mut item = TheListItem::new(TheLayoutDescription::id(&self.name));
+        item.set_text(self.text.clone().unwrap_or_default());
+        self.apply(&mut item, ctx);
+        Ok(item)
+    }
+
+    /// Builds the item of a row list layout, the description has to be a RowListItem.
+    fn build_row_list_item(&self, ctx: &mut TheContext) -> Result<TheRowListItem, String> {
+        if self.kind != TheWidgetKind::RowListItem {
+            return Err(format!(
+                "Row list layouts can only contain RowListItem widgets, not {:?}",
+                self.kind
+            ));
+        }
+
+        let mut item = TheRowListItem::new(TheLayoutDescription::id(&self.name));
+        item.set_text(self.text.clone().unwrap_or_default());
+        self.apply(&mut item, ctx);
+        Ok(item)
+    }
+}
@@ -216,6 +216,24 @@ impl TheContextMenu {
         false
     }
 
+    /// Returns the regions covered by the menu and its open sub_menus.
+    pub fn regions(&self) -> Vec<TheDim> {
+        let mut regions = vec![TheDim::new(
+            self.dim.buffer_x,
+            self.dim.buffer_y,
+            self.dim.width,
+            self.dim.height,
+        )];
+        for item in &self.items {
+            if let Some(sub_menu) = &item.sub_menu {
+                if sub_menu.is_open {
+                    regions.extend(sub_menu.regions());
+                }
+            }
+        }
+        regions
+    }
+
     /// Recursively returns the currently hovered menu id / item id.
     pub fn get_hovered_id(&mut self) -> Option<(TheId, TheId)> {
         for item in self.items.iter_mut() {
@@ -318,12 +336,19 @@ impl TheContextMenu {
                 );
             }
 
-            if let Some(accel) = &item.accel {
+            // The keymap overrides the accelerator of the item.
+            let accel_description = if ctx.ui.keymap.contains(&item.id.name) {
+                ctx.ui.keymap.description(&item.id.name)
+            } else {
+                item.accel.map(|accel| accel.description())
+            };
+
+            if let Some(accel_description) = &accel_description {
                 ctx.draw.text_rect_blend(
                     pixels,
                     &(rect.0, rect.1, &rect.2 - 6, rect.3),
                     ctx.width,
-                    &accel.description(),
+                    accel_description,
                     TheFontSettings {
                         size: 12.0,
                         ..Default::default()
@@ -360,8 +385,14 @@ impl TheContextMenu {
                         icon.dim().width as usize,
                         icon.dim().height as usize,
                     );
-                    ctx.draw
-                        .blend_slice_alpha(pixels, icon.pixels(), &r, ctx.width, alpha);
+                    ctx.draw.blend_slice_alpha_scaled(
+                        pixels,
+                        icon.pixels(),
+                        icon.scale(),
+                        &r,
+                        ctx.width,
+                        alpha,
+                    );
                 }
             }
 
@@ -0,0 +1,324 @@
+use crate::prelude::*;
+
+/// The height of the title bar of a dialog, above its canvas.
+pub const THE_DIALOG_TITLE_HEIGHT: i32 = 29;
+
+/// A button of a dialog. Clicking it closes the dialog and sends TheEvent::DialogValueOnClose
+/// with the role of the button.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TheDialogButton {
+    pub role: TheDialogButtonRole,
+    pub text: String,
+}
+
+impl TheDialogButton {
+    pub fn new(role: TheDialogButtonRole, text: &str) -> Self {
+        Self {
+            role,
+            text: text.to_string(),
+        }
+    }
+}
+
+impl From<TheDialogButtonRole> for TheDialogButton {
+    fn from(role: TheDialogButtonRole) -> Self {
+        Self::new(role, role.to_string())
+    }
+}
+
+/// A modal dialog, shown via TheUI::push_dialog(). Dialogs are stacked, only the topmost one
+/// receives input.
+///
+/// When the dialog closes, TheEvent::DialogValueOnClose is sent with the role of the button, the
+/// title, the uuid of the dialog id and the value of the widget named "Dialog Value" in the
+/// canvas, or the value of the dialog if there is no such widget.
+pub struct TheDialog {
+    pub id: TheId,
+    pub title: String,
+    pub canvas: TheCanvas,
+    pub buttons: Vec<TheDialogButton>,
+
+    /// The value sent on close if the canvas has no "Dialog Value" widget.
+    pub value: TheValue,
+
+    /// The role of the button triggered by Enter, by default the Accept button.
+    pub enter_role: Option<TheDialogButtonRole>,
+    /// The role of the button triggered by Escape, by default the Reject button or the only
+    /// button of the dialog.
+    pub escape_role: Option<TheDialogButtonRole>,
+
+    /// If true, the dialog can be moved by dragging its title bar.
+    pub draggable: bool,
+
+    /// The screen position of the canvas, None to center the dialog.
+    position: Option<Vec2<i32>>,
+    /// The offset of the mouse to the position while the title bar is dragged.
+    drag_offset: Option<Vec2<i32>>,
+}
+
+impl TheDialog {
+    /// Creates a dialog with the given canvas as content. The size of the content is taken from
+    /// the maximum size of the limiter of the canvas.
+    pub fn new(id: TheId, title: &str, canvas: TheCanvas) -> Self {
+        Self {
+            id,
+            title: title.to_string(),
+            canvas,
+            buttons: vec![],
+            value: TheValue::Empty,
+            enter_role: None,
+            escape_role: None,
+            draggable: false,
+            position: None,
+            drag_offset: None,
+        }
+    }
+
+    /// Creates a message box showing the text with an Accept button.
+    pub fn message(id: TheId, title: &str, text: &str) -> Self {
+        let mut dialog = Self::new(id, title, Self::text_canvas(text, None));
+        dialog.add_button(TheDialogButtonRole::Accept.into());
+        dialog
+    }
+
+    /// Creates a dialog asking to confirm the text. The answer is the role of the clicked button,
+    /// Accept (labeled with the given text) or Reject.
+    pub fn confirm(id: TheId, title: &str, text: &str, accept_text: &str) -> Self {
+        let mut dialog = Self::new(id, title, Self::text_canvas(text, None));
+        dialog.add_button(TheDialogButton::new(
+            TheDialogButtonRole::Accept,
+            accept_text,
+        ));
+        dialog.add_button(TheDialogButtonRole::Reject.into());
+        dialog
+    }
+
+    /// Creates a dialog with a labeled text edit, the edited text is returned as TheValue::Text.
+    pub fn text_input(id: TheId, title: &str, label: &str, text: &str) -> Self {
+        let width = 300;
+        let height = 100;
+
+        let mut canvas = TheCanvas::new();
+        canvas.limiter_mut().set_max_size(Vec2::new(width, height));
+
+        let mut text_layout: TheTextLayout = TheTextLayout::new(TheId::empty());
+        text_layout.set_margin(Vec4::new(20, 20, 20, 20));
+        text_layout.limiter_mut().set_max_width(width);
+
+        let mut name_edit = TheTextLineEdit::new(TheId::named_with_id("Dialog Value", id.uuid));
+        name_edit.set_text(text.to_string());
+        name_edit.limiter_mut().set_max_width(200);
+        text_layout.add_pair(label.to_string(), Box::new(name_edit));
+
+        canvas.set_layout(text_layout);
+
+        let mut dialog = Self::new(id, title, canvas);
+        dialog.add_button(TheDialogButtonRole::Accept.into());
+        dialog.add_button(TheDialogButtonRole::Reject.into());
+        dialog
+    }
+
+    /// A canvas showing the text, optionally as the "Dialog Value" widget of the given uuid.
+    pub(crate) fn text_canvas(text: &str, value_uuid: Option<Uuid>) -> TheCanvas {
+        let width = 300;
+        let height = 100;
+
+        let mut canvas = TheCanvas::new();
+        canvas.limiter_mut().set_max_size(Vec2::new(width, height));
+
+        let mut hlayout: TheHLayout = TheHLayout::new(TheId::empty());
+        hlayout.limiter_mut().set_max_width(width);
+
+        let id = match value_uuid {
+            Some(uuid) => TheId::named_with_id("Dialog Value", uuid),
+            None => TheId::empty(),
+        };
+        let mut text_widget = TheText::new(id);
+        text_widget.set_text(text.to_string());
+        text_widget.limiter_mut().set_max_width(200);
+        hlayout.add_widget(Box::new(text_widget));
+
+        canvas.set_layout(hlayout);
+        canvas
+    }
+
+    /// Adds a button, buttons are shown from right to left in the order they were added.
+    pub fn add_button(&mut self, button: TheDialogButton) {
+        self.buttons.push(button);
+    }
+
+    /// Sets if the dialog can be moved by dragging its title bar.
+    pub fn set_draggable(&mut self, draggable: bool) {
+        self.draggable = draggable;
+    }
+
+    /// Moves the dialog to the given screen position, None centers it.
+    pub fn set_position(&mut self, position: Option<Vec2<i32>>) {
+        self.position = position;
+    }
+
+    /// The role triggered by Enter.
+    pub fn enter_button(&self) -> Option<TheDialogButtonRole> {
+        self.enter_role.or_else(|| {
+            self.buttons
+                .iter()
+                .find(|button| button.role == TheDialogButtonRole::Accept)
+                .map(|button| button.role)
+        })
+    }
+
+    /// The role triggered by Escape.
+    pub fn escape_button(&self) -> Option<TheDialogButtonRole> {
+        self.escape_role.or_else(|| {
+            if let Some(button) = self
+                .buttons
+                .iter()
+                .find(|button| button.role == TheDialogButtonRole::Reject)
+            {
+                Some(button.role)
+            } else if self.buttons.len() == 1 {
+                Some(self.buttons[0].role)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the button role of the given widget id.
+    pub fn button_role(&self, id: &TheId) -> Option<TheDialogButtonRole> {
+        self.buttons
+            .iter()
+            .find(|button| id.name == button.role.to_id())
+            .map(|button| button.role)
+    }
+
+    /// The value sent on close.
+    pub fn close_value(&mut self) -> TheValue {
+        if let Some(widget) = self
+            .canvas
+            .get_widget(Some(&"Dialog Value".to_string()), None)
+        {
+            widget.value()
+        } else {
+            self.value.clone()
+        }
+    }
+
+    /// The screen rectangle of the title bar.
+    pub fn title_bar(&self) -> TheDim {
+        let dim = self.canvas.dim;
+        TheDim::new(
+            dim.x - 3,
+            dim.y - THE_DIALOG_TITLE_HEIGHT,
+            dim.width + 6,
+            THE_DIALOG_TITLE_HEIGHT,
+        )
+    }
+
+    /// The screen rectangle of the dialog including its border and title bar.
+    pub fn frame(&self) -> TheDim {
+        let dim = self.canvas.dim;
+        TheDim::new(
+            dim.x - 3,
+            dim.y - THE_DIALOG_TITLE_HEIGHT,
+            dim.width + 6,
+            dim.height + THE_DIALOG_TITLE_HEIGHT + 3,
+        )
+    }
+
+    /// Adds the dialog as a modal node with its canvas to the accessibility tree.
+    pub(crate) fn collect_access_nodes(
+        &mut self,
+        tree: &mut TheAccessTree,
+        parent: u64,
+        ctx: &TheContext,
+    ) {
+        let info = TheAccessInfo {
+            modal: true,
+            ..TheAccessInfo::named(TheAccessRole::Dialog, &self.title)
+        };
+        let id = tree.add(parent, &self.id, info, self.frame(), ctx);
+        self.canvas.collect_access_nodes(tree, id, ctx);
+    }
+
+    /// Adds the button bar to the canvas, called once when the dialog is shown.
+    pub(crate) fn build(&mut self) {
+        if self.buttons.is_empty() {
+            return;
+        }
+
+        let width = self.canvas.limiter.get_max_width();
+
+        let mut toolbar_hlayout = TheHLayout::new(TheId::empty());
+        toolbar_hlayout.set_background_color(Some(TheThemeColors::ListLayoutBackground));
+        toolbar_hlayout.limiter_mut().set_max_width(width);
+        toolbar_hlayout.set_margin(Vec4::new(5, 2, 5, 2));
+
+        for b in &self.buttons {
+            let mut button = TheTraybarButton::new(TheId::named(&b.role.to_id()));
+            button.set_text(b.text.clone());
+            toolbar_hlayout.add_widget(Box::new(button));
+        }
+
+        toolbar_hlayout.set_reverse_index(Some(self.buttons.len() as i32));
+
+        let mut toolbar_canvas = TheCanvas::default();
+        toolbar_hlayout.limiter_mut().set_max_height(30);
+        toolbar_canvas.set_layout(toolbar_hlayout);
+        self.canvas.set_bottom(toolbar_canvas);
+    }
+
+    /// Positions the canvas, centered or at its position, keeping the title bar on screen.
+    pub(crate) fn layout(&mut self, ctx: &mut TheContext) {
+        let width = self.canvas.limiter.get_max_width();
+        let height = self.canvas.limiter.get_max_height();
+
+        let (x, y) = match self.position {
+            Some(position) => (
+                position
+                    .x
+                    .clamp(3 - width, (ctx.width as i32 - 3).max(3 - width)),
+                position.y.clamp(
+                    THE_DIALOG_TITLE_HEIGHT,
+                    (ctx.height as i32).max(THE_DIALOG_TITLE_HEIGHT),
+                ),
+            ),
+            None => (
+                (ctx.width as i32 - width) / 2,
+                (ctx.height as i32 - height) / 2,
+            ),
+        };
+
+        let mut dim = TheDim::new(x, y, width, height);
+        dim.buffer_x = x;
+        dim.buffer_y = y;
+
+        self.canvas.set_dim(dim, ctx);
+    }
+
+    /// Starts dragging if the coordinate is on the title bar of a draggable dialog.
+    pub(crate) fn start_drag(&mut self, coord: Vec2<i32>) -> bool {
+        if self.draggable && self.title_bar().contains(coord) {
+            self.drag_offset = Some(coord - Vec2::new(self.canvas.dim.x, self.canvas.dim.y));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves the dialog while its title bar is dragged.
+    pub(crate) fn drag(&mut self, coord: Vec2<i32>, ctx: &mut TheContext) -> bool {
+        if let Some(offset) = self.drag_offset {
+            self.position = Some(coord - offset);
+            self.layout(ctx);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Ends dragging, returns true if the dialog was dragged.
+    pub(crate) fn end_drag(&mut self) -> bool {
+        self.drag_offset.take().is_some()
+    }
+}
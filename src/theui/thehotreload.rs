@@ -0,0 +1,107 @@
+use crate::prelude::*;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use web_time::{Duration, Instant};
+
+/// A canvas description file watched by TheHotReload.
+pub struct TheWatchedFile {
+    pub path: PathBuf,
+    /// The canvas rebuilt from the file.
+    pub canvas: Uuid,
+
+    /// The modification time of the last load, None if the file could not be read.
+    modified: Option<SystemTime>,
+    /// The toast showing the error of the last reload.
+    error_toast: Option<Uuid>,
+}
+
+/// Watches canvas description files (see TheCanvasDescription) during development. When a file
+/// changes, TheUI rebuilds the canvas in place, see TheUI::watch_canvas_file().
+pub struct TheHotReload {
+    /// The time between two checks of the modification times.
+    pub interval: Duration,
+    pub enabled: bool,
+
+    files: Vec<TheWatchedFile>,
+    last_check: Instant,
+}
+
+impl Default for TheHotReload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TheHotReload {
+    pub fn new() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+            enabled: true,
+
+            files: vec![],
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Watches the file, changes rebuild the given canvas. The current state of the file counts
+    /// as loaded.
+    pub fn watch(&mut self, path: &Path, canvas: Uuid) {
+        self.unwatch(path);
+        self.files.push(TheWatchedFile {
+            path: path.to_path_buf(),
+            canvas,
+            modified: Self::modified(path),
+            error_toast: None,
+        });
+    }
+
+    /// Stops watching the file.
+    pub fn unwatch(&mut self, path: &Path) {
+        self.files.retain(|file| file.path != path);
+    }
+
+    /// The watched files.
+    pub fn files(&self) -> &Vec<TheWatchedFile> {
+        &self.files
+    }
+
+    /// Returns the indices of the files which changed since they were loaded. The files are
+    /// checked at most once per interval.
+    pub(crate) fn changed(&mut self, now: Instant) -> Vec<usize> {
+        if !self.enabled
+            || self.files.is_empty()
+            || now.saturating_duration_since(self.last_check) < self.interval
+        {
+            return vec![];
+        }
+        self.last_check = now;
+
+        let mut changed = vec![];
+        for (index, file) in self.files.iter_mut().enumerate() {
+            let modified = Self::modified(&file.path);
+            if modified != file.modified {
+                file.modified = modified;
+                changed.push(index);
+            }
+        }
+        changed
+    }
+
+    /// The file at the given index.
+    pub(crate) fn file_mut(&mut self, index: usize) -> &mut TheWatchedFile {
+        &mut self.files[index]
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()
+    }
+}
+
+impl TheWatchedFile {
+    /// Replaces the toast of the last error, returns the id of the previous one.
+    pub(crate) fn set_error_toast(&mut self, toast: Option<Uuid>) -> Option<Uuid> {
+        std::mem::replace(&mut self.error_toast, toast)
+    }
+}
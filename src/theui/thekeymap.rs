@@ -0,0 +1,76 @@
+use crate::prelude::*;
+use indexmap::IndexMap;
+use std::path::Path;
+
+/// A user editable keymap. It maps action names, the names of the TheIds of menu items and of
+/// registered shortcuts, to shortcuts and overrides their default accelerators. An empty
+/// shortcut removes the binding of an action.
+///
+/// The keymap is stored in TheUIContext, changes take effect immediately.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TheKeymap {
+    pub bindings: IndexMap<String, TheShortcut>,
+}
+
+impl TheKeymap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds the shortcut to the given action.
+    pub fn bind(&mut self, action: &str, shortcut: TheShortcut) {
+        self.bindings.insert(action.to_string(), shortcut);
+    }
+
+    /// Removes any shortcut from the given action, including its default accelerator.
+    pub fn unbind(&mut self, action: &str) {
+        self.bind(action, TheShortcut::chord(vec![]));
+    }
+
+    /// Removes the action from the keymap, the default accelerator of the action applies again.
+    pub fn reset(&mut self, action: &str) {
+        self.bindings.shift_remove(action);
+    }
+
+    /// Returns true if the keymap contains the action, either bound or unbound.
+    pub fn contains(&self, action: &str) -> bool {
+        self.bindings.contains_key(action)
+    }
+
+    /// Returns the shortcut of the action. None if the action is not part of the keymap or was
+    /// unbound.
+    pub fn shortcut(&self, action: &str) -> Option<&TheShortcut> {
+        self.bindings
+            .get(action)
+            .filter(|shortcut| !shortcut.sequence.is_empty())
+    }
+
+    /// Returns the description of the shortcut of the action.
+    pub fn description(&self, action: &str) -> Option<String> {
+        self.shortcut(action).map(|shortcut| shortcut.description())
+    }
+
+    /// Loads a keymap from the given JSON file.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    /// Saves the keymap as a JSON file.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        std::fs::write(path, json)
+    }
+
+    /// Create an instance from json.
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).unwrap_or_default()
+    }
+
+    /// Convert the instance to json.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or_default()
+    }
+}
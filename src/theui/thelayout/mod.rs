@@ -1,20 +1,38 @@
 use crate::prelude::*;
 
+#[cfg(feature = "code")]
+pub mod thecodelayout;
+pub mod thedocklayout;
+pub mod thedockview;
+pub mod theflexlayout;
+pub mod thegridlayout;
 pub mod thehlayout;
+pub mod thelistdatasource;
 pub mod thelistlayout;
 pub mod thergbalayout;
 pub mod therowlistlayout;
+pub mod theselection;
 pub mod thesharedhlayout;
 pub mod thesharedvlayout;
 pub mod thesnapperlayout;
 pub mod thestacklayout;
 pub mod thetablayout;
+pub mod thetablelayout;
+pub mod thetableview;
 pub mod thetextlayout;
 pub mod thetreelayout;
 pub mod thevlayout;
 
 pub mod prelude {
+    #[cfg(feature = "code")]
+    pub use crate::theui::thelayout::thecodelayout::{TheCodeLayout, TheCodeLayoutTrait};
+    pub use crate::theui::thelayout::thedocklayout::{
+        TheDockAxis, TheDockLayout, TheDockLayoutTrait, TheDockNode, TheDockSide,
+    };
+    pub use crate::theui::thelayout::theflexlayout::*;
+    pub use crate::theui::thelayout::thegridlayout::*;
     pub use crate::theui::thelayout::thehlayout::{TheHLayout, TheHLayoutMode, TheHLayoutTrait};
+    pub use crate::theui::thelayout::thelistdatasource::TheListDataSource;
     pub use crate::theui::thelayout::thelistlayout::{TheListLayout, TheListLayoutTrait};
     pub use crate::theui::thelayout::thergbalayout::{TheRGBALayout, TheRGBALayoutTrait};
     pub use crate::theui::thelayout::therowlistlayout::{TheRowListLayout, TheRowListLayoutTrait};
@@ -23,13 +41,56 @@ pub mod prelude {
     pub use crate::theui::thelayout::thesnapperlayout::{TheSnapperLayout, TheSnapperLayoutTrait};
     pub use crate::theui::thelayout::thestacklayout::{TheStackLayout, TheStackLayoutTrait};
     pub use crate::theui::thelayout::thetablayout::{TheTabLayout, TheTabLayoutTrait};
+    pub use crate::theui::thelayout::thetablelayout::{
+        TheTableColumn, TheTableColumnType, TheTableDataSource, TheTableLayout, TheTableLayoutTrait,
+    };
     pub use crate::theui::thelayout::thetextlayout::{TheTextLayout, TheTextLayoutTrait};
     pub use crate::theui::thelayout::thetreelayout::{
-        TheTreeLayout, TheTreeLayoutTrait, TheTreeNode,
+        TheTreeLayout, TheTreeLayoutTrait, TheTreeMove, TheTreeNode,
     };
     pub use crate::theui::thelayout::thevlayout::{TheVLayout, TheVLayoutMode, TheVLayoutTrait};
 
-    pub use crate::theui::thelayout::TheLayout;
+    pub use crate::theui::thelayout::{TheLayout, TheLayoutAlign};
+}
+
+/// The alignment of a widget in the space a layout assigns to it, see TheGridLayout and
+/// TheFlexLayout.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum TheLayoutAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// Fills the space, up to the maximum size of the widget.
+    Stretch,
+}
+
+impl TheLayoutAlign {
+    /// Returns the offset and the size of a widget of the given preferred size and size limits
+    /// in the available space.
+    pub fn place(&self, available: i32, preferred: i32, max: i32) -> (i32, i32) {
+        let size = match self {
+            TheLayoutAlign::Stretch => available.min(max),
+            _ => preferred.min(available),
+        }
+        .max(0);
+        let offset = match self {
+            TheLayoutAlign::Start | TheLayoutAlign::Stretch => 0,
+            TheLayoutAlign::Center => (available - size) / 2,
+            TheLayoutAlign::End => available - size,
+        };
+        (offset, size)
+    }
+}
+
+/// The preferred size of a widget along one axis: its maximum size if it has one, otherwise its
+/// minimum size.
+pub(crate) fn preferred_size(min: i32, max: i32) -> i32 {
+    if max == i32::MAX {
+        min
+    } else {
+        max.max(min)
+    }
 }
 
 /// TheLayout trait defines an abstract layout interface for widgets.
@@ -109,6 +170,32 @@ pub trait TheLayout: Send {
 
     fn widgets(&mut self) -> &mut Vec<Box<dyn TheWidget>>;
 
+    /// Appends the ids of the visible widgets which accept keyboard focus in layout order.
+    /// Layouts which only show some of their content only collect the visible part.
+    fn collect_focus_ids(&mut self, ids: &mut Vec<TheId>) {
+        for widget in self.widgets() {
+            if widget.accepts_focus() && !widget.disabled() && widget.dim().is_valid() {
+                ids.push(widget.id().clone());
+            }
+        }
+    }
+
+    /// The accessibility description of the layout.
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::GenericContainer)
+    }
+
+    /// Adds the accessibility node of the layout and the nodes of its visible content below the
+    /// parent node.
+    fn collect_access_nodes(&mut self, tree: &mut TheAccessTree, parent: u64, ctx: &TheContext) {
+        let id = tree.add(parent, self.id(), self.accessibility(), *self.dim(), ctx);
+        for widget in self.widgets() {
+            if widget.dim().is_valid() {
+                tree.add_widget(id, widget.as_ref(), ctx);
+            }
+        }
+    }
+
     fn redirected_widget_value(
         &mut self,
         widget_id: &TheId,
@@ -145,6 +232,12 @@ pub trait TheLayout: Send {
         None
     }
 
+    /// Attempts to cast to TheCodeLayoutTrait. Only valid for TheCodeLayout.
+    #[cfg(feature = "code")]
+    fn as_code_layout(&mut self) -> Option<&mut dyn TheCodeLayoutTrait> {
+        None
+    }
+
     /// Attempts to cast to TheTabLayoutTrait. Only valid for TheTabLayout.
     fn as_tab_layout(&mut self) -> Option<&mut dyn TheTabLayoutTrait> {
         None
@@ -160,6 +253,16 @@ pub trait TheLayout: Send {
         None
     }
 
+    /// Attempts to cast to TheGridLayoutTrait. Only valid for TheGridLayout.
+    fn as_grid_layout(&mut self) -> Option<&mut dyn TheGridLayoutTrait> {
+        None
+    }
+
+    /// Attempts to cast to TheFlexLayoutTrait. Only valid for TheFlexLayout.
+    fn as_flex_layout(&mut self) -> Option<&mut dyn TheFlexLayoutTrait> {
+        None
+    }
+
     /// Attempts to cast to TheHLayoutTrait. Only valid for TheHLayout.
     fn as_hlayout(&mut self) -> Option<&mut dyn TheHLayoutTrait> {
         None
@@ -179,4 +282,14 @@ pub trait TheLayout: Send {
     fn as_tree_layout(&mut self) -> Option<&mut dyn TheTreeLayoutTrait> {
         None
     }
+
+    /// Attempts to cast to TheTableLayout. Only valid for TheTableLayout.
+    fn as_table_layout(&mut self) -> Option<&mut dyn TheTableLayoutTrait> {
+        None
+    }
+
+    /// Attempts to cast to TheDockLayoutTrait. Only valid for TheDockLayout.
+    fn as_dock_layout(&mut self) -> Option<&mut dyn TheDockLayoutTrait> {
+        None
+    }
 }
@@ -129,6 +129,10 @@ impl TheLayout for TheCodeLayout {
     }
 
     fn needs_redraw(&mut self) -> bool {
+        if self.code_view.needs_redraw() {
+            return true;
+        }
+
         if self.vertical_scrollbar_visible && self.vertical_scrollbar.needs_redraw() {
             return true;
         }
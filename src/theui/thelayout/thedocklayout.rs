@@ -0,0 +1,592 @@
+use crate::prelude::*;
+use crate::theui::thelayout::thedockview::TheDockView;
+
+/// The direction in which a split of TheDockLayout divides its area.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TheDockAxis {
+    /// The two nodes are side by side.
+    Horizontal,
+    /// The two nodes are on top of each other.
+    Vertical,
+}
+
+/// Where a panel docks relative to the area of another panel.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TheDockSide {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// Tabbed together with the panels of the area.
+    Center,
+}
+
+/// The arrangement of the panels of TheDockLayout. Panels are referenced by name, so an
+/// arrangement saved with to_json() restores the dock once the app added its panels again.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub enum TheDockNode {
+    /// An area showing the tabs of the panels and the panel at the index.
+    Tabs { panels: Vec<String>, index: usize },
+    /// Two nodes divided along the axis, the ratio is the share of the first node.
+    Split {
+        axis: TheDockAxis,
+        ratio: f32,
+        first: Box<TheDockNode>,
+        second: Box<TheDockNode>,
+    },
+}
+
+impl TheDockNode {
+    /// An area with the given panels, showing the first one.
+    pub fn tabs(panels: &[&str]) -> Self {
+        TheDockNode::Tabs {
+            panels: panels.iter().map(|name| name.to_string()).collect(),
+            index: 0,
+        }
+    }
+
+    /// Divides the space between the two nodes.
+    pub fn split(axis: TheDockAxis, ratio: f32, first: TheDockNode, second: TheDockNode) -> Self {
+        TheDockNode::Split {
+            axis,
+            ratio,
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    /// The names of all panels of the arrangement.
+    pub fn panels(&self) -> Vec<String> {
+        match self {
+            TheDockNode::Tabs { panels, .. } => panels.clone(),
+            TheDockNode::Split { first, second, .. } => {
+                let mut panels = first.panels();
+                panels.extend(second.panels());
+                panels
+            }
+        }
+    }
+
+    /// Create an arrangement from json.
+    pub fn from_json(json: &str) -> Option<Self> {
+        serde_json::from_str(json).ok()
+    }
+
+    /// Convert the arrangement to json.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self).unwrap_or_default()
+    }
+
+    /// Keeps the panels for which keep() returns true. Areas without panels are removed and
+    /// their split is replaced by the remaining node. Returns None if no panel is left.
+    pub(crate) fn retain(self, keep: &mut impl FnMut(&str) -> bool) -> Option<Self> {
+        match self {
+            TheDockNode::Tabs { panels, index } => {
+                let visible = panels.get(index).cloned();
+                let panels: Vec<String> = panels.into_iter().filter(|name| keep(name)).collect();
+                if panels.is_empty() {
+                    return None;
+                }
+                let index = visible
+                    .and_then(|visible| panels.iter().position(|name| *name == visible))
+                    .unwrap_or(index.min(panels.len() - 1));
+                Some(TheDockNode::Tabs { panels, index })
+            }
+            TheDockNode::Split {
+                axis,
+                ratio,
+                first,
+                second,
+            } => match (first.retain(keep), second.retain(keep)) {
+                (Some(first), Some(second)) => Some(TheDockNode::split(axis, ratio, first, second)),
+                (Some(node), None) | (None, Some(node)) => Some(node),
+                (None, None) => None,
+            },
+        }
+    }
+
+    /// Docks the panel to the side of the area of the target panel, or inserts its tab at the
+    /// index for TheDockSide::Center. The panel must not be part of the arrangement.
+    pub(crate) fn dock(
+        &mut self,
+        panel: &str,
+        target: &str,
+        side: TheDockSide,
+        index: Option<usize>,
+    ) -> bool {
+        match self {
+            TheDockNode::Tabs {
+                panels,
+                index: selected,
+            } => {
+                if !panels.iter().any(|name| name == target) {
+                    return false;
+                }
+                if side == TheDockSide::Center {
+                    let index = index.unwrap_or(panels.len()).min(panels.len());
+                    panels.insert(index, panel.to_string());
+                    *selected = index;
+                } else {
+                    let area = std::mem::replace(self, TheDockNode::tabs(&[]));
+                    let docked = TheDockNode::tabs(&[panel]);
+                    *self = match side {
+                        TheDockSide::Left => {
+                            TheDockNode::split(TheDockAxis::Horizontal, 0.5, docked, area)
+                        }
+                        TheDockSide::Right => {
+                            TheDockNode::split(TheDockAxis::Horizontal, 0.5, area, docked)
+                        }
+                        TheDockSide::Top => {
+                            TheDockNode::split(TheDockAxis::Vertical, 0.5, docked, area)
+                        }
+                        _ => TheDockNode::split(TheDockAxis::Vertical, 0.5, area, docked),
+                    };
+                }
+                true
+            }
+            TheDockNode::Split { first, second, .. } => {
+                first.dock(panel, target, side, index) || second.dock(panel, target, side, index)
+            }
+        }
+    }
+
+    /// The node at the path, false selects the first and true the second node of a split.
+    pub(crate) fn node_mut(&mut self, path: &[bool]) -> Option<&mut TheDockNode> {
+        match (path.split_first(), self) {
+            (None, node) => Some(node),
+            (
+                Some((second, path)),
+                TheDockNode::Split {
+                    first,
+                    second: other,
+                    ..
+                },
+            ) => {
+                if *second {
+                    other.node_mut(path)
+                } else {
+                    first.node_mut(path)
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Docks panels (canvases) into areas which can be split and tabbed. Dragging a tab out of its
+/// strip docks the panel to a side of any area or tabs it together with the panels of an area,
+/// dragging the gap between two areas changes their ratio. The arrangement is a TheDockNode,
+/// apps save it on TheEvent::DockArrangementChanged and restore it on the next launch.
+pub struct TheDockLayout {
+    id: TheId,
+    limiter: TheSizeLimiter,
+
+    dim: TheDim,
+
+    /// The TheDockView, which owns the arrangement.
+    view: Box<dyn TheWidget>,
+    widgets: Vec<Box<dyn TheWidget>>,
+
+    panels: Vec<(String, TheCanvas)>,
+    /// The arrangement passed to set_arrangement() while some of its panels are not added yet.
+    pending: Option<TheDockNode>,
+
+    is_dirty: bool,
+}
+
+impl TheLayout for TheDockLayout {
+    fn new(id: TheId) -> Self
+    where
+        Self: Sized,
+    {
+        let mut view = TheDockView::new(TheId::named((id.name.clone() + " Dock View").as_str()));
+        view.layout_id = id.clone();
+
+        Self {
+            id,
+            limiter: TheSizeLimiter::new(),
+
+            dim: TheDim::zero(),
+
+            view: Box::new(view),
+            widgets: vec![],
+
+            panels: vec![],
+            pending: None,
+
+            is_dirty: false,
+        }
+    }
+
+    fn id(&self) -> &TheId {
+        &self.id
+    }
+
+    fn widgets(&mut self) -> &mut Vec<Box<dyn TheWidget>> {
+        &mut self.widgets
+    }
+
+    fn collect_focus_ids(&mut self, ids: &mut Vec<TheId>) {
+        for name in self.visible_panels() {
+            if let Some(canvas) = self.panel_mut(&name) {
+                canvas.collect_focus_ids(ids);
+            }
+        }
+    }
+
+    fn collect_access_nodes(&mut self, tree: &mut TheAccessTree, parent: u64, ctx: &TheContext) {
+        let id = tree.add(parent, &self.id, self.accessibility(), self.dim, ctx);
+        let view_id = self.view.id().clone();
+        let view_info = self.view.accessibility();
+        for (dim, tabs) in self.view().access_tabs() {
+            let strip = tree.add(id, &view_id, view_info.clone(), dim, ctx);
+            for (info, dim) in tabs {
+                tree.add(strip, &view_id, info, dim, ctx);
+            }
+        }
+        for name in self.visible_panels() {
+            if let Some(canvas) = self.panel_mut(&name) {
+                canvas.collect_access_nodes(tree, id, ctx);
+            }
+        }
+    }
+
+    fn get_widget_at_coord(&mut self, coord: Vec2<i32>) -> Option<&mut Box<dyn TheWidget>> {
+        if !self.dim.contains(coord) {
+            return None;
+        }
+
+        let local = self.dim.to_local(coord);
+        let view = self.view();
+        if view.hit(local) {
+            return Some(&mut self.view);
+        }
+
+        let name = view
+            .areas
+            .iter()
+            .find(|area| area.content().contains(local))
+            .and_then(|area| area.visible().cloned())?;
+        self.panel_mut(&name)?.get_widget_at_coord(coord)
+    }
+
+    fn get_layout(
+        &mut self,
+        name: Option<&String>,
+        uuid: Option<&Uuid>,
+    ) -> Option<&mut Box<dyn TheLayout>> {
+        for (_, canvas) in &mut self.panels {
+            if let Some(layout) = canvas.get_layout(name, uuid) {
+                return Some(layout);
+            }
+        }
+        None
+    }
+
+    fn get_widget(
+        &mut self,
+        name: Option<&String>,
+        uuid: Option<&Uuid>,
+    ) -> Option<&mut Box<dyn TheWidget>> {
+        if self.view.id().matches(name, uuid) {
+            return Some(&mut self.view);
+        }
+
+        for (_, canvas) in &mut self.panels {
+            if let Some(widget) = canvas.get_widget(name, uuid) {
+                return Some(widget);
+            }
+        }
+        None
+    }
+
+    fn needs_redraw(&mut self) -> bool {
+        if self.is_dirty || self.view.needs_redraw() {
+            return true;
+        }
+
+        for name in self.visible_panels() {
+            if self
+                .panel_mut(&name)
+                .is_some_and(|canvas| canvas.needs_redraw())
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn dim(&self) -> &TheDim {
+        &self.dim
+    }
+
+    fn dim_mut(&mut self) -> &mut TheDim {
+        &mut self.dim
+    }
+
+    fn set_dim(&mut self, dim: TheDim, ctx: &mut TheContext) {
+        if self.dim != dim || ctx.ui.relayout {
+            self.dim = dim;
+            self.place(ctx);
+        }
+    }
+
+    fn limiter(&self) -> &TheSizeLimiter {
+        &self.limiter
+    }
+
+    fn limiter_mut(&mut self) -> &mut TheSizeLimiter {
+        &mut self.limiter
+    }
+
+    fn draw(
+        &mut self,
+        buffer: &mut TheRGBABuffer,
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+    ) {
+        if !self.dim().is_valid() {
+            return;
+        }
+
+        if self.view().changed {
+            self.place(ctx);
+        }
+
+        let stride = buffer.stride();
+        ctx.draw.rect(
+            buffer.pixels_mut(),
+            &self.dim.to_buffer_utuple(),
+            stride,
+            style.theme().color(DefaultWidgetDarkBackground),
+        );
+
+        let dim = self.dim;
+        for area in self.view().areas.clone() {
+            let content = area.content();
+            if !content.is_valid() {
+                continue;
+            }
+            if let Some(canvas) = area.visible().and_then(|name| self.panel_mut(name)) {
+                canvas.draw(style, ctx);
+                buffer.copy_into(
+                    dim.buffer_x + content.x,
+                    dim.buffer_y + content.y,
+                    canvas.buffer(),
+                );
+            }
+        }
+
+        self.view.draw(buffer, style, ctx);
+
+        self.is_dirty = false;
+    }
+
+    /// Convert to the dock layout trait
+    fn as_dock_layout(&mut self) -> Option<&mut dyn TheDockLayoutTrait> {
+        Some(self)
+    }
+}
+
+impl TheDockLayout {
+    fn view(&mut self) -> &mut TheDockView {
+        self.view
+            .as_any()
+            .downcast_mut::<TheDockView>()
+            .expect("the view of a dock layout is a TheDockView")
+    }
+
+    fn panel_mut(&mut self, name: &str) -> Option<&mut TheCanvas> {
+        self.panels
+            .iter_mut()
+            .find(|(panel, _)| panel == name)
+            .map(|(_, canvas)| canvas)
+    }
+
+    /// Computes the areas of the arrangement and places the visible panels.
+    fn place(&mut self, ctx: &mut TheContext) {
+        let dim = self.dim;
+        self.view.set_dim(dim, ctx);
+        self.view
+            .dim_mut()
+            .set_buffer_offset(dim.buffer_x, dim.buffer_y);
+
+        let view = self.view();
+        view.changed = false;
+        view.arrange();
+
+        for area in self.view().areas.clone() {
+            let content = area.content();
+            if let Some(canvas) = area.visible().and_then(|name| self.panel_mut(name)) {
+                canvas.set_dim(
+                    TheDim::new(
+                        dim.x + content.x,
+                        dim.y + content.y,
+                        content.width,
+                        content.height,
+                    ),
+                    ctx,
+                );
+            }
+        }
+        self.is_dirty = true;
+    }
+
+    /// Arranges the added panels like the pending arrangement, which is kept until all of its
+    /// panels were added.
+    fn restore_pending(&mut self) {
+        let Some(pending) = self.pending.clone() else {
+            return;
+        };
+        let panels = self.panels();
+        if pending.panels().iter().all(|name| panels.contains(name)) {
+            self.pending = None;
+        }
+
+        let mut seen = vec![];
+        let root = pending.retain(&mut |name| {
+            if panels.iter().any(|panel| panel == name) && !seen.contains(&name.to_string()) {
+                seen.push(name.to_string());
+                true
+            } else {
+                false
+            }
+        });
+        self.arrange(root);
+    }
+
+    /// Sets the arrangement, panels which are not part of it are tabbed into the first area.
+    fn arrange(&mut self, root: Option<TheDockNode>) {
+        let mut root = root;
+        for (name, _) in &self.panels {
+            match &mut root {
+                Some(node) => {
+                    if !node.panels().contains(name) {
+                        let first = node.panels()[0].clone();
+                        node.dock(name, &first, TheDockSide::Center, None);
+                    }
+                }
+                None => root = Some(TheDockNode::tabs(&[name])),
+            }
+        }
+
+        let view = self.view();
+        view.root = root;
+        view.arrange();
+        view.changed = true;
+        view.set_needs_redraw(true);
+        self.is_dirty = true;
+    }
+}
+
+/// TheDockLayout specific functions.
+pub trait TheDockLayoutTrait: TheLayout {
+    /// Adds a panel with the given name or replaces the canvas of an existing panel. New panels
+    /// are tabbed into the first area unless the arrangement already places them.
+    fn add_panel(&mut self, name: &str, canvas: TheCanvas);
+    /// Removes the panel and returns its canvas.
+    fn remove_panel(&mut self, name: &str) -> Option<TheCanvas>;
+    /// The names of the panels in the order they were added.
+    fn panels(&self) -> Vec<String>;
+    /// The names of the panels shown in front of their areas.
+    fn visible_panels(&mut self) -> Vec<String>;
+    /// Shows the panel in front of its area.
+    fn select_panel(&mut self, name: &str) -> bool;
+    /// Docks the panel to the side of the area of the target panel, or tabs it together with
+    /// the target for TheDockSide::Center. Returns false for unknown panels.
+    fn dock_panel(&mut self, panel: &str, target: &str, side: TheDockSide) -> bool;
+    /// The arrangement of the panels, None while the dock has no panels.
+    fn arrangement(&mut self) -> Option<TheDockNode>;
+    /// Restores an arrangement. Duplicate panels are ignored and panels which were not added yet
+    /// are placed once add_panel() adds them, panels missing from the arrangement are tabbed
+    /// into the first area.
+    fn set_arrangement(&mut self, arrangement: TheDockNode);
+}
+
+impl TheDockLayoutTrait for TheDockLayout {
+    fn add_panel(&mut self, name: &str, canvas: TheCanvas) {
+        if let Some(panel) = self.panel_mut(name) {
+            *panel = canvas;
+        } else {
+            self.panels.push((name.to_string(), canvas));
+            if self
+                .pending
+                .as_ref()
+                .is_some_and(|pending| pending.panels().iter().any(|panel| panel == name))
+            {
+                self.restore_pending();
+                return;
+            }
+        }
+        let root = self.view().root.take();
+        self.arrange(root);
+    }
+
+    fn remove_panel(&mut self, name: &str) -> Option<TheCanvas> {
+        let index = self.panels.iter().position(|(panel, _)| panel == name)?;
+        let (_, canvas) = self.panels.remove(index);
+        self.pending = self
+            .pending
+            .take()
+            .and_then(|pending| pending.retain(&mut |panel| panel != name));
+        let root = self
+            .view()
+            .root
+            .take()
+            .and_then(|root| root.retain(&mut |panel| panel != name));
+        self.arrange(root);
+        Some(canvas)
+    }
+
+    fn panels(&self) -> Vec<String> {
+        self.panels.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    fn visible_panels(&mut self) -> Vec<String> {
+        self.view()
+            .areas
+            .iter()
+            .filter_map(|area| area.visible().cloned())
+            .collect()
+    }
+
+    fn select_panel(&mut self, name: &str) -> bool {
+        let view = self.view();
+        let Some((path, index)) = view.areas.iter().find_map(|area| {
+            area.panels
+                .iter()
+                .position(|panel| panel == name)
+                .map(|index| (area.path.clone(), index))
+        }) else {
+            return false;
+        };
+        if let Some(TheDockNode::Tabs {
+            index: selected, ..
+        }) = view.root.as_mut().and_then(|root| root.node_mut(&path))
+        {
+            *selected = index;
+        }
+        view.arrange();
+        view.changed = true;
+        self.is_dirty = true;
+        true
+    }
+
+    fn dock_panel(&mut self, panel: &str, target: &str, side: TheDockSide) -> bool {
+        if self.panel_mut(panel).is_none() || self.panel_mut(target).is_none() {
+            return false;
+        }
+        let docked = self.view().dock(panel, target, side, None);
+        self.is_dirty = true;
+        docked
+    }
+
+    fn arrangement(&mut self) -> Option<TheDockNode> {
+        self.view().root.clone()
+    }
+
+    fn set_arrangement(&mut self, arrangement: TheDockNode) {
+        self.pending = Some(arrangement);
+        self.restore_pending();
+    }
+}
@@ -0,0 +1,714 @@
+use crate::prelude::*;
+
+/// The height of the tab strip of a dock area.
+pub(crate) const DOCK_TAB_HEIGHT: i32 = 22;
+/// The widest a tab gets, tabs shrink to share narrow strips.
+const DOCK_TAB_WIDTH: i32 = 120;
+/// The gap between two docked areas, dragging it changes the ratio of the split.
+pub(crate) const DOCK_SPLITTER_SIZE: i32 = 4;
+/// Dragging a splitter does not shrink an area below this size.
+const DOCK_MIN_AREA_SIZE: i32 = 40;
+/// The distance a tab has to be dragged before its panel undocks.
+const DOCK_DRAG_THRESHOLD: f32 = 5.0;
+/// Dropping a panel within this share of the border of an area docks it to that side.
+const DOCK_SIDE_ZONE: f32 = 0.25;
+
+/// An area of the dock, the tab strip of its panels above the selected panel.
+#[derive(Clone, Debug)]
+pub(crate) struct TheDockArea {
+    /// The path of the tabs node, false selects the first and true the second node of a split.
+    pub path: Vec<bool>,
+    pub panels: Vec<String>,
+    pub index: usize,
+    /// The area including the tab strip, relative to the layout.
+    pub rect: TheDim,
+}
+
+impl TheDockArea {
+    /// The rectangle of the selected panel, relative to the layout.
+    pub fn content(&self) -> TheDim {
+        TheDim::new(
+            self.rect.x,
+            self.rect.y + DOCK_TAB_HEIGHT,
+            self.rect.width,
+            (self.rect.height - DOCK_TAB_HEIGHT).max(0),
+        )
+    }
+
+    /// The selected panel.
+    pub fn visible(&self) -> Option<&String> {
+        self.panels.get(self.index)
+    }
+
+    fn tab_width(&self) -> i32 {
+        if self.panels.is_empty() {
+            return 0;
+        }
+        (self.rect.width / self.panels.len() as i32).min(DOCK_TAB_WIDTH)
+    }
+
+    /// The rectangle of the tab at the index, relative to the layout.
+    fn tab_rect(&self, index: usize) -> TheDim {
+        let width = self.tab_width();
+        TheDim::new(
+            self.rect.x + index as i32 * width,
+            self.rect.y,
+            width,
+            DOCK_TAB_HEIGHT,
+        )
+    }
+
+    fn tab_at(&self, coord: Vec2<i32>) -> Option<usize> {
+        (0..self.panels.len()).find(|index| self.tab_rect(*index).contains(coord))
+    }
+}
+
+/// The gap between the two nodes of a split.
+#[derive(Clone, Debug)]
+struct TheDockSplitter {
+    path: Vec<bool>,
+    axis: TheDockAxis,
+    rect: TheDim,
+    /// The rectangle of the split node.
+    parent: TheDim,
+}
+
+/// Where a dragged panel would be docked.
+#[derive(Clone, Debug, PartialEq)]
+struct TheDockDrop {
+    /// The panel the dragged panel docks next to.
+    target: String,
+    side: TheDockSide,
+    /// The position of the tab for TheDockSide::Center, None appends it.
+    index: Option<usize>,
+    /// The highlighted region, relative to the layout.
+    preview: TheDim,
+}
+
+/// A drag in the dock.
+#[derive(Clone, Debug)]
+enum TheDockDrag {
+    /// The tab of the panel was pressed, dragging it further than the threshold undocks it.
+    Press { panel: String, start: Vec2<i32> },
+    /// The panel is dragged, the pointer is at the given position.
+    Panel {
+        panel: String,
+        pointer: Vec2<i32>,
+        drop: Option<TheDockDrop>,
+    },
+    /// Moves the splitter of the split node at the path.
+    Splitter(Vec<bool>),
+}
+
+/// Draws the tab strips and splitters of TheDockLayout and handles dragging tabs and splitters.
+/// The layout owns the view and the canvases of the panels, the view owns the arrangement.
+pub(crate) struct TheDockView {
+    id: TheId,
+    limiter: TheSizeLimiter,
+    dim: TheDim,
+    is_dirty: bool,
+
+    pub layout_id: TheId,
+
+    pub root: Option<TheDockNode>,
+    /// The areas of the arrangement, see arrange().
+    pub areas: Vec<TheDockArea>,
+    /// Set when the arrangement changed and the layout has to place the panels again.
+    pub changed: bool,
+
+    splitters: Vec<TheDockSplitter>,
+    drag: Option<TheDockDrag>,
+    hover_axis: Option<TheDockAxis>,
+}
+
+impl TheWidget for TheDockView {
+    fn new(id: TheId) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            id,
+            limiter: TheSizeLimiter::new(),
+            dim: TheDim::zero(),
+            is_dirty: true,
+
+            layout_id: TheId::empty(),
+
+            root: None,
+            areas: vec![],
+            changed: false,
+
+            splitters: vec![],
+            drag: None,
+            hover_axis: None,
+        }
+    }
+
+    fn id(&self) -> &TheId {
+        &self.id
+    }
+
+    fn dim(&self) -> &TheDim {
+        &self.dim
+    }
+
+    fn dim_mut(&mut self) -> &mut TheDim {
+        &mut self.dim
+    }
+
+    fn set_dim(&mut self, dim: TheDim, _ctx: &mut TheContext) {
+        if self.dim != dim {
+            self.dim = dim;
+            self.is_dirty = true;
+        }
+    }
+
+    fn limiter(&self) -> &TheSizeLimiter {
+        &self.limiter
+    }
+
+    fn limiter_mut(&mut self) -> &mut TheSizeLimiter {
+        &mut self.limiter
+    }
+
+    fn needs_redraw(&mut self) -> bool {
+        self.is_dirty
+    }
+
+    fn set_needs_redraw(&mut self, redraw: bool) {
+        self.is_dirty = redraw;
+    }
+
+    fn supports_hover(&mut self) -> bool {
+        true
+    }
+
+    fn cursor_icon(&self) -> Option<TheCursorIcon> {
+        let axis = match &self.drag {
+            Some(TheDockDrag::Splitter(path)) => self
+                .splitters
+                .iter()
+                .find(|splitter| splitter.path == *path)
+                .map(|splitter| splitter.axis),
+            Some(TheDockDrag::Panel { .. }) => return Some(TheCursorIcon::Grabbing),
+            _ => self.hover_axis,
+        };
+        match axis {
+            Some(TheDockAxis::Horizontal) => Some(TheCursorIcon::EWResize),
+            Some(TheDockAxis::Vertical) => Some(TheCursorIcon::NSResize),
+            None => None,
+        }
+    }
+
+    fn on_event(&mut self, event: &TheEvent, ctx: &mut TheContext) -> bool {
+        let mut redraw = false;
+        match event {
+            TheEvent::MouseDown(coord) => {
+                ctx.ui.set_focus(self.id());
+                if let Some(splitter) = self.splitter_at(*coord) {
+                    self.drag = Some(TheDockDrag::Splitter(splitter.path.clone()));
+                } else if let Some((area, index)) = self.tab_at(*coord) {
+                    let area = &self.areas[area];
+                    let (path, panel) = (area.path.clone(), area.panels[index].clone());
+                    if area.index != index {
+                        if let Some(TheDockNode::Tabs {
+                            index: selected, ..
+                        }) = self.root.as_mut().and_then(|root| root.node_mut(&path))
+                        {
+                            *selected = index;
+                        }
+                        self.arrangement_changed(ctx);
+                    }
+                    self.drag = Some(TheDockDrag::Press {
+                        panel,
+                        start: *coord,
+                    });
+                }
+                self.is_dirty = true;
+                redraw = true;
+            }
+            TheEvent::MouseDragged(coord) => match self.drag.clone() {
+                Some(TheDockDrag::Press { panel, start }) => {
+                    let distance = Vec2::new(start.x as f32, start.y as f32)
+                        .distance(Vec2::new(coord.x as f32, coord.y as f32));
+                    if distance >= DOCK_DRAG_THRESHOLD {
+                        let drop = self.drop_at(&panel, *coord);
+                        self.drag = Some(TheDockDrag::Panel {
+                            panel,
+                            pointer: *coord,
+                            drop,
+                        });
+                        self.is_dirty = true;
+                        redraw = true;
+                    }
+                }
+                Some(TheDockDrag::Panel { panel, .. }) => {
+                    let drop = self.drop_at(&panel, *coord);
+                    self.drag = Some(TheDockDrag::Panel {
+                        panel,
+                        pointer: *coord,
+                        drop,
+                    });
+                    self.is_dirty = true;
+                    redraw = true;
+                }
+                Some(TheDockDrag::Splitter(path)) if self.move_splitter(&path, *coord) => {
+                    self.changed = true;
+                    self.arrange();
+                    self.is_dirty = true;
+                    redraw = true;
+                }
+                _ => {}
+            },
+            TheEvent::MouseUp(_coord) => {
+                match self.drag.take() {
+                    Some(TheDockDrag::Panel {
+                        panel,
+                        drop: Some(drop),
+                        ..
+                    }) if self.dock(&panel, &drop.target, drop.side, drop.index) => {
+                        self.arrangement_changed(ctx);
+                    }
+                    Some(TheDockDrag::Splitter(_)) => {
+                        ctx.ui
+                            .send(TheEvent::DockArrangementChanged(self.layout_id.clone()));
+                    }
+                    _ => {}
+                }
+                self.is_dirty = true;
+                redraw = true;
+            }
+            TheEvent::Hover(coord) => {
+                self.hover_axis = self.splitter_at(*coord).map(|splitter| splitter.axis);
+                if !self.id().equals(&ctx.ui.hover) {
+                    ctx.ui.set_hover(self.id());
+                }
+            }
+            _ => {}
+        }
+        redraw
+    }
+
+    fn draw(
+        &mut self,
+        buffer: &mut TheRGBABuffer,
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+    ) {
+        if !self.dim().is_valid() {
+            return;
+        }
+
+        let stride = buffer.stride();
+        let (buffer_x, buffer_y) = (self.dim.buffer_x, self.dim.buffer_y);
+        let to_buffer = |rect: &TheDim| {
+            (
+                (buffer_x + rect.x) as usize,
+                (buffer_y + rect.y) as usize,
+                rect.width.max(0) as usize,
+                rect.height.max(0) as usize,
+            )
+        };
+
+        for splitter in &self.splitters {
+            ctx.draw.rect(
+                buffer.pixels_mut(),
+                &to_buffer(&splitter.rect),
+                stride,
+                style.theme().color(LayoutSeparator),
+            );
+        }
+
+        let dragged = match &self.drag {
+            Some(TheDockDrag::Panel { panel, .. }) => Some(panel.clone()),
+            _ => None,
+        };
+
+        for area in &self.areas {
+            if area.rect.width <= 0 || area.rect.height < DOCK_TAB_HEIGHT {
+                continue;
+            }
+            let strip = TheDim::new(area.rect.x, area.rect.y, area.rect.width, DOCK_TAB_HEIGHT);
+            ctx.draw.rect(
+                buffer.pixels_mut(),
+                &to_buffer(&strip),
+                stride,
+                style.theme().color(TabbarBackground),
+            );
+
+            for (index, panel) in area.panels.iter().enumerate() {
+                let tab = area.tab_rect(index);
+                if tab.width <= 1 {
+                    continue;
+                }
+                if index == area.index {
+                    ctx.draw.rect(
+                        buffer.pixels_mut(),
+                        &to_buffer(&tab),
+                        stride,
+                        style.theme().color(TabbarConnector),
+                    );
+                }
+                if index > 0 {
+                    ctx.draw.rect(
+                        buffer.pixels_mut(),
+                        &to_buffer(&TheDim::new(tab.x, tab.y + 4, 1, tab.height - 8)),
+                        stride,
+                        style.theme().color(LayoutSeparator),
+                    );
+                }
+                let mut text_color = *style.theme().color(TabbarText);
+                if dragged.as_ref() == Some(panel) {
+                    text_color[3] = 128;
+                }
+                ctx.draw.text_rect_blend(
+                    buffer.pixels_mut(),
+                    &to_buffer(&tab),
+                    stride,
+                    panel,
+                    TheFontSettings {
+                        size: 12.5,
+                        ..Default::default()
+                    },
+                    &text_color,
+                    TheHorizontalAlign::Center,
+                    TheVerticalAlign::Center,
+                );
+            }
+        }
+
+        if let Some(TheDockDrag::Panel {
+            panel,
+            pointer,
+            drop,
+        }) = &self.drag
+        {
+            if let Some(drop) = drop {
+                let mut color = *style.theme().color(DockDropIndicator);
+                ctx.draw.rect_outline(
+                    buffer.pixels_mut(),
+                    &to_buffer(&drop.preview),
+                    stride,
+                    &color,
+                );
+                color[3] = 80;
+                ctx.draw.blend_rect(
+                    buffer.pixels_mut(),
+                    &to_buffer(&drop.preview),
+                    stride,
+                    &color,
+                );
+            }
+
+            // The tab follows the pointer, kept inside the dock.
+            let width = DOCK_TAB_WIDTH.min(self.dim.width);
+            let height = DOCK_TAB_HEIGHT.min(self.dim.height);
+            let tab = TheDim::new(
+                (pointer.x - width / 2).clamp(0, self.dim.width - width),
+                (pointer.y - height / 2).clamp(0, self.dim.height - height),
+                width,
+                height,
+            );
+            let mut color = *style.theme().color(TabbarConnector);
+            color[3] = 200;
+            ctx.draw
+                .blend_rect(buffer.pixels_mut(), &to_buffer(&tab), stride, &color);
+            ctx.draw.text_rect_blend(
+                buffer.pixels_mut(),
+                &to_buffer(&tab),
+                stride,
+                panel,
+                TheFontSettings {
+                    size: 12.5,
+                    ..Default::default()
+                },
+                style.theme().color(TabbarText),
+                TheHorizontalAlign::Center,
+                TheVerticalAlign::Center,
+            );
+        }
+
+        self.is_dirty = false;
+    }
+
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::TabList)
+    }
+
+    fn as_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl TheDockView {
+    /// Computes the areas and splitters of the arrangement for the current size.
+    pub fn arrange(&mut self) {
+        self.areas.clear();
+        self.splitters.clear();
+        if let Some(root) = &self.root {
+            let rect = TheDim::new(0, 0, self.dim.width, self.dim.height);
+            arrange_node(root, vec![], rect, &mut self.areas, &mut self.splitters);
+        }
+        self.is_dirty = true;
+    }
+
+    /// Returns true while the pointer is over a tab strip or a splitter, or while dragging.
+    pub fn hit(&self, coord: Vec2<i32>) -> bool {
+        self.drag.is_some()
+            || self.splitter_at(coord).is_some()
+            || self
+                .areas
+                .iter()
+                .any(|area| area.rect.contains(coord) && coord.y < area.rect.y + DOCK_TAB_HEIGHT)
+    }
+
+    /// The tab nodes of the arrangement and their tab strips, for accessibility.
+    pub fn access_tabs(&self) -> Vec<(TheDim, Vec<(TheAccessInfo, TheDim)>)> {
+        let offset = |rect: TheDim| {
+            TheDim::new(
+                self.dim.x + rect.x,
+                self.dim.y + rect.y,
+                rect.width,
+                rect.height,
+            )
+        };
+        self.areas
+            .iter()
+            .map(|area| {
+                let strip = TheDim::new(area.rect.x, area.rect.y, area.rect.width, DOCK_TAB_HEIGHT);
+                let tabs = area
+                    .panels
+                    .iter()
+                    .enumerate()
+                    .map(|(index, panel)| {
+                        let info = TheAccessInfo {
+                            selected: Some(index == area.index),
+                            ..TheAccessInfo::named(TheAccessRole::Tab, panel)
+                        };
+                        (info, offset(area.tab_rect(index)))
+                    })
+                    .collect();
+                (offset(strip), tabs)
+            })
+            .collect()
+    }
+
+    /// Moves the panel next to the target panel, see TheDockNode::dock().
+    pub fn dock(
+        &mut self,
+        panel: &str,
+        target: &str,
+        side: TheDockSide,
+        index: Option<usize>,
+    ) -> bool {
+        let Some(root) = self.root.take() else {
+            return false;
+        };
+        if panel == target || !root.panels().iter().any(|name| name == target) {
+            self.root = Some(root);
+            return false;
+        }
+
+        let mut root = root
+            .retain(&mut |name| name != panel)
+            .expect("the target panel remains in the arrangement");
+        let docked = root.dock(panel, target, side, index);
+        self.root = Some(root);
+        self.changed = true;
+        self.arrange();
+        docked
+    }
+
+    fn arrangement_changed(&mut self, ctx: &mut TheContext) {
+        self.changed = true;
+        self.arrange();
+        ctx.ui
+            .send(TheEvent::DockArrangementChanged(self.layout_id.clone()));
+    }
+
+    fn splitter_at(&self, coord: Vec2<i32>) -> Option<&TheDockSplitter> {
+        self.splitters
+            .iter()
+            .find(|splitter| splitter.rect.contains(coord))
+    }
+
+    /// The area and the index of the tab at the coordinate.
+    fn tab_at(&self, coord: Vec2<i32>) -> Option<(usize, usize)> {
+        self.areas
+            .iter()
+            .enumerate()
+            .find_map(|(index, area)| area.tab_at(coord).map(|tab| (index, tab)))
+    }
+
+    /// Sets the ratio of the split at the path so that the splitter is at the coordinate.
+    fn move_splitter(&mut self, path: &[bool], coord: Vec2<i32>) -> bool {
+        let Some(splitter) = self.splitters.iter().find(|splitter| splitter.path == path) else {
+            return false;
+        };
+        let (position, start, size) = match splitter.axis {
+            TheDockAxis::Horizontal => (coord.x, splitter.parent.x, splitter.parent.width),
+            TheDockAxis::Vertical => (coord.y, splitter.parent.y, splitter.parent.height),
+        };
+        let available = size - DOCK_SPLITTER_SIZE;
+        if available <= 0 {
+            return false;
+        }
+        let min = DOCK_MIN_AREA_SIZE.min(available / 2);
+        let first = (position - start - DOCK_SPLITTER_SIZE / 2).clamp(min, available - min);
+
+        match self.root.as_mut().and_then(|root| root.node_mut(path)) {
+            Some(TheDockNode::Split { ratio, .. }) => {
+                let new_ratio = first as f32 / available as f32;
+                if *ratio == new_ratio {
+                    return false;
+                }
+                *ratio = new_ratio;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The drop target of the dragged panel at the coordinate. Dropping into the tab strip tabs
+    /// the panel at that position, dropping near the border of an area docks it to that side.
+    fn drop_at(&self, panel: &str, coord: Vec2<i32>) -> Option<TheDockDrop> {
+        let area = self.areas.iter().find(|area| area.rect.contains(coord))?;
+        // A panel alone in its area can not dock to itself.
+        let target = area.panels.iter().find(|name| *name != panel)?.clone();
+        let rect = area.rect;
+
+        if coord.y < rect.y + DOCK_TAB_HEIGHT {
+            let index = area
+                .panels
+                .iter()
+                .enumerate()
+                .filter(|(index, name)| {
+                    let tab = area.tab_rect(*index);
+                    *name != panel && tab.x + tab.width / 2 < coord.x
+                })
+                .count();
+            return Some(TheDockDrop {
+                target,
+                side: TheDockSide::Center,
+                index: Some(index),
+                preview: rect,
+            });
+        }
+
+        let content = area.content();
+        let fx = (coord.x - content.x) as f32 / content.width.max(1) as f32;
+        let fy = (coord.y - content.y) as f32 / content.height.max(1) as f32;
+        let (side, distance) = [
+            (TheDockSide::Left, fx),
+            (TheDockSide::Right, 1.0 - fx),
+            (TheDockSide::Top, fy),
+            (TheDockSide::Bottom, 1.0 - fy),
+        ]
+        .into_iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+        let (half_width, half_height) = (rect.width / 2, rect.height / 2);
+        let (side, preview) = if distance >= DOCK_SIDE_ZONE {
+            (TheDockSide::Center, rect)
+        } else {
+            let preview = match side {
+                TheDockSide::Left => TheDim::new(rect.x, rect.y, half_width, rect.height),
+                TheDockSide::Right => TheDim::new(
+                    rect.x + rect.width - half_width,
+                    rect.y,
+                    half_width,
+                    rect.height,
+                ),
+                TheDockSide::Top => TheDim::new(rect.x, rect.y, rect.width, half_height),
+                _ => TheDim::new(
+                    rect.x,
+                    rect.y + rect.height - half_height,
+                    rect.width,
+                    half_height,
+                ),
+            };
+            (side, preview)
+        };
+
+        Some(TheDockDrop {
+            target,
+            side,
+            index: None,
+            preview,
+        })
+    }
+}
+
+/// Splits the rectangle of the node between its children and collects the areas of the tabs
+/// nodes and the splitters of the split nodes.
+fn arrange_node(
+    node: &TheDockNode,
+    path: Vec<bool>,
+    rect: TheDim,
+    areas: &mut Vec<TheDockArea>,
+    splitters: &mut Vec<TheDockSplitter>,
+) {
+    match node {
+        TheDockNode::Tabs { panels, index } => {
+            areas.push(TheDockArea {
+                path,
+                panels: panels.clone(),
+                index: (*index).min(panels.len().saturating_sub(1)),
+                rect,
+            });
+        }
+        TheDockNode::Split {
+            axis,
+            ratio,
+            first,
+            second,
+        } => {
+            let size = match axis {
+                TheDockAxis::Horizontal => rect.width,
+                TheDockAxis::Vertical => rect.height,
+            };
+            let available = (size - DOCK_SPLITTER_SIZE).max(0);
+            let first_size =
+                ((available as f32 * ratio.clamp(0.0, 1.0)).round() as i32).clamp(0, available);
+            let second_size = available - first_size;
+
+            let (first_rect, splitter, second_rect) = match axis {
+                TheDockAxis::Horizontal => (
+                    TheDim::new(rect.x, rect.y, first_size, rect.height),
+                    TheDim::new(rect.x + first_size, rect.y, DOCK_SPLITTER_SIZE, rect.height),
+                    TheDim::new(
+                        rect.x + first_size + DOCK_SPLITTER_SIZE,
+                        rect.y,
+                        second_size,
+                        rect.height,
+                    ),
+                ),
+                TheDockAxis::Vertical => (
+                    TheDim::new(rect.x, rect.y, rect.width, first_size),
+                    TheDim::new(rect.x, rect.y + first_size, rect.width, DOCK_SPLITTER_SIZE),
+                    TheDim::new(
+                        rect.x,
+                        rect.y + first_size + DOCK_SPLITTER_SIZE,
+                        rect.width,
+                        second_size,
+                    ),
+                ),
+            };
+
+            splitters.push(TheDockSplitter {
+                path: path.clone(),
+                axis: *axis,
+                rect: splitter,
+                parent: rect,
+            });
+
+            let mut first_path = path.clone();
+            first_path.push(false);
+            arrange_node(first, first_path, first_rect, areas, splitters);
+
+            let mut second_path = path;
+            second_path.push(true);
+            arrange_node(second, second_path, second_rect, areas, splitters);
+        }
+    }
+}
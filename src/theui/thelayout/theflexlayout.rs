@@ -0,0 +1,492 @@
+use crate::prelude::*;
+use crate::theui::thelayout::preferred_size;
+
+/// The main axis of TheFlexLayout.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TheFlexDirection {
+    Row,
+    Column,
+}
+
+/// The distribution of the space left on a line of TheFlexLayout.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TheFlexJustify {
+    Start,
+    Center,
+    End,
+    /// The space is put between the widgets.
+    SpaceBetween,
+    /// The space is put around the widgets, half of it at the ends of the line.
+    SpaceAround,
+}
+
+/// How a widget of TheFlexLayout is sized along the main axis.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub struct TheFlexItem {
+    /// The share of the free space of the line the widget grows by.
+    pub grow: f32,
+    /// How much the widget shrinks, relative to its basis, if the line is too short.
+    pub shrink: f32,
+    /// The size before growing or shrinking, None for the preferred size of the widget.
+    pub basis: Option<i32>,
+    /// The alignment on the cross axis, None uses the default of the layout.
+    pub align: Option<TheLayoutAlign>,
+}
+
+impl Default for TheFlexItem {
+    fn default() -> Self {
+        Self {
+            grow: 0.0,
+            shrink: 1.0,
+            basis: None,
+            align: None,
+        }
+    }
+}
+
+impl TheFlexItem {
+    /// An item which grows by the given share of the free space.
+    pub fn grow(grow: f32) -> Self {
+        Self {
+            grow,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the basis of the item.
+    pub fn with_basis(mut self, basis: i32) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    /// Sets how much the item shrinks.
+    pub fn with_shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    /// Sets the cross axis alignment of the item.
+    pub fn with_align(mut self, align: TheLayoutAlign) -> Self {
+        self.align = Some(align);
+        self
+    }
+}
+
+/// Arranges widgets in a row or column, optionally wrapped into several lines. Widgets grow into
+/// free space and shrink when space is missing according to their TheFlexItem, but never beyond
+/// the limits of their TheSizeLimiter. The preferred size of a widget is its maximum size, or its
+/// minimum size if it has no maximum.
+pub struct TheFlexLayout {
+    id: TheId,
+    limiter: TheSizeLimiter,
+
+    dim: TheDim,
+
+    widgets: Vec<Box<dyn TheWidget>>,
+    items: Vec<TheFlexItem>,
+
+    direction: TheFlexDirection,
+    wrap: bool,
+    justify: TheFlexJustify,
+    align: TheLayoutAlign,
+
+    margin: Vec4<i32>,
+    /// The gap between the widgets of a line and between the lines.
+    gap: i32,
+
+    background: Option<TheThemeColors>,
+}
+
+/// The sizes of a widget along the main (x) and the cross (y) axis.
+struct TheFlexSizes {
+    min: Vec2<i32>,
+    max: Vec2<i32>,
+    basis: i32,
+}
+
+impl TheLayout for TheFlexLayout {
+    fn new(id: TheId) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            id,
+            limiter: TheSizeLimiter::new(),
+
+            dim: TheDim::zero(),
+
+            widgets: vec![],
+            items: vec![],
+
+            direction: TheFlexDirection::Row,
+            wrap: false,
+            justify: TheFlexJustify::Start,
+            align: TheLayoutAlign::Stretch,
+
+            margin: Vec4::new(10, 10, 10, 10),
+            gap: 5,
+
+            background: Some(DefaultWidgetBackground),
+        }
+    }
+
+    fn id(&self) -> &TheId {
+        &self.id
+    }
+
+    fn set_margin(&mut self, margin: Vec4<i32>) {
+        self.margin = margin;
+    }
+
+    /// Sets the gap between the widgets and between the lines.
+    fn set_padding(&mut self, padding: i32) {
+        self.gap = padding;
+    }
+
+    fn set_background_color(&mut self, color: Option<TheThemeColors>) {
+        self.background = color;
+    }
+
+    fn widgets(&mut self) -> &mut Vec<Box<dyn TheWidget>> {
+        &mut self.widgets
+    }
+
+    fn get_widget_at_coord(&mut self, coord: Vec2<i32>) -> Option<&mut Box<dyn TheWidget>> {
+        self.widgets.iter_mut().find(|w| w.dim().contains(coord))
+    }
+
+    fn get_widget(
+        &mut self,
+        name: Option<&String>,
+        uuid: Option<&Uuid>,
+    ) -> Option<&mut Box<dyn TheWidget>> {
+        self.widgets
+            .iter_mut()
+            .find(|w: &&mut Box<dyn TheWidget>| w.id().matches(name, uuid))
+    }
+
+    fn dim(&self) -> &TheDim {
+        &self.dim
+    }
+
+    fn dim_mut(&mut self) -> &mut TheDim {
+        &mut self.dim
+    }
+
+    fn set_dim(&mut self, dim: TheDim, ctx: &mut TheContext) {
+        if self.dim != dim || ctx.ui.relayout {
+            self.dim = dim;
+
+            let row = self.direction == TheFlexDirection::Row;
+            let inner = Vec2::new(
+                dim.width - self.margin.x - self.margin.z,
+                dim.height - self.margin.y - self.margin.w,
+            );
+            // The available space along the main (x) and the cross (y) axis.
+            let available = if row {
+                inner
+            } else {
+                Vec2::new(inner.y, inner.x)
+            };
+
+            let sizes: Vec<TheFlexSizes> = self
+                .widgets
+                .iter_mut()
+                .zip(&self.items)
+                .map(|(widget, item)| {
+                    widget.calculate_size(ctx);
+                    let limiter = widget.limiter();
+                    let mut min = Vec2::new(limiter.get_min_width(), limiter.get_min_height());
+                    let mut max = Vec2::new(limiter.get_max_width(), limiter.get_max_height());
+                    if !row {
+                        min = Vec2::new(min.y, min.x);
+                        max = Vec2::new(max.y, max.x);
+                    }
+                    max = Vec2::partial_max(max, min);
+                    let basis = item
+                        .basis
+                        .unwrap_or_else(|| preferred_size(min.x, max.x))
+                        .clamp(min.x, max.x);
+                    TheFlexSizes { min, max, basis }
+                })
+                .collect();
+
+            let lines = self.lines(&sizes, available.x);
+
+            let mut cross = 0;
+            for line in lines {
+                let main_sizes = self.resolve_line(&line, &sizes, available.x);
+
+                let line_cross = if self.wrap {
+                    line.iter()
+                        .map(|index| preferred_size(sizes[*index].min.y, sizes[*index].max.y))
+                        .max()
+                        .unwrap_or(0)
+                        .min(available.y)
+                } else {
+                    available.y
+                };
+
+                let count = line.len() as i32;
+                let used: i32 = main_sizes.iter().sum::<i32>() + self.gap * (count - 1);
+                let left = (available.x - used).max(0);
+                let (mut main, spacing) = match self.justify {
+                    TheFlexJustify::Start => (0, 0),
+                    TheFlexJustify::Center => (left / 2, 0),
+                    TheFlexJustify::End => (left, 0),
+                    TheFlexJustify::SpaceBetween if count > 1 => (0, left / (count - 1)),
+                    TheFlexJustify::SpaceBetween => (0, 0),
+                    TheFlexJustify::SpaceAround => (left / count / 2, left / count),
+                };
+
+                for (index, main_size) in line.iter().zip(main_sizes) {
+                    let size = &sizes[*index];
+                    let (cross_offset, cross_size) =
+                        self.items[*index].align.unwrap_or(self.align).place(
+                            line_cross,
+                            preferred_size(size.min.y, size.max.y),
+                            size.max.y,
+                        );
+
+                    let (x, y, width, height) = if row {
+                        (main, cross + cross_offset, main_size, cross_size)
+                    } else {
+                        (cross + cross_offset, main, cross_size, main_size)
+                    };
+                    main += main_size + self.gap + spacing;
+
+                    let widget = &mut self.widgets[*index];
+                    // Widgets outside of the visible area are hidden.
+                    if x + width > inner.x || y + height > inner.y || width <= 0 || height <= 0 {
+                        widget.set_dim(TheDim::zero(), ctx);
+                        continue;
+                    }
+
+                    let x = self.margin.x + x;
+                    let y = self.margin.y + y;
+                    widget.set_dim(TheDim::new(dim.x + x, dim.y + y, width, height), ctx);
+                    widget
+                        .dim_mut()
+                        .set_buffer_offset(self.dim.buffer_x + x, self.dim.buffer_y + y);
+                }
+
+                cross += line_cross + self.gap;
+            }
+        }
+    }
+
+    fn relayout(&mut self, ctx: &mut TheContext) {
+        let dim = self.dim;
+        self.dim = TheDim::zero();
+        self.set_dim(dim, ctx);
+    }
+
+    fn limiter(&self) -> &TheSizeLimiter {
+        &self.limiter
+    }
+
+    fn limiter_mut(&mut self) -> &mut TheSizeLimiter {
+        &mut self.limiter
+    }
+
+    fn draw(
+        &mut self,
+        buffer: &mut TheRGBABuffer,
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+    ) {
+        if let Some(background) = self.background {
+            let stride = buffer.stride();
+
+            ctx.draw.rect(
+                buffer.pixels_mut(),
+                &self.dim.to_buffer_utuple(),
+                stride,
+                style.theme().color(background),
+            );
+        }
+
+        for w in &mut self.widgets {
+            if w.dim().is_valid() {
+                w.draw(buffer, style, ctx);
+            }
+        }
+    }
+
+    fn as_flex_layout(&mut self) -> Option<&mut dyn TheFlexLayoutTrait> {
+        Some(self)
+    }
+}
+
+impl TheFlexLayout {
+    /// Splits the widgets into lines, a single line if wrapping is off.
+    fn lines(&self, sizes: &[TheFlexSizes], available: i32) -> Vec<Vec<usize>> {
+        let mut lines: Vec<Vec<usize>> = vec![];
+        let mut line: Vec<usize> = vec![];
+        let mut used = 0;
+        for (index, size) in sizes.iter().enumerate() {
+            if self.wrap && !line.is_empty() && used + self.gap + size.basis > available {
+                lines.push(std::mem::take(&mut line));
+            }
+            used = if line.is_empty() {
+                size.basis
+            } else {
+                used + self.gap + size.basis
+            };
+            line.push(index);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Grows or shrinks the widgets of a line to fill the available space. Widgets which reach a
+    /// limit are frozen and the rest of the space is distributed among the others.
+    fn resolve_line(&self, line: &[usize], sizes: &[TheFlexSizes], available: i32) -> Vec<i32> {
+        let gaps = self.gap * (line.len() as i32 - 1);
+        let mut main: Vec<i32> = line.iter().map(|index| sizes[*index].basis).collect();
+        let mut frozen = vec![false; line.len()];
+
+        loop {
+            let used: i32 = line
+                .iter()
+                .enumerate()
+                .map(|(i, index)| {
+                    if frozen[i] {
+                        main[i]
+                    } else {
+                        sizes[*index].basis
+                    }
+                })
+                .sum();
+            let free = available - gaps - used;
+            if free == 0 {
+                break;
+            }
+
+            let weight = |index: usize| {
+                let item = &self.items[index];
+                if free > 0 {
+                    item.grow.max(0.0)
+                } else {
+                    item.shrink.max(0.0) * sizes[index].basis as f32
+                }
+            };
+            let total: f32 = line
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !frozen[*i])
+                .map(|(_, index)| weight(*index))
+                .sum();
+            if total <= 0.0 {
+                break;
+            }
+
+            // The shares are rounded towards zero, the pixels lost by rounding are handed out
+            // one at a time so that the line is filled exactly.
+            let mut shares: Vec<i32> = line
+                .iter()
+                .map(|index| (free as f32 * weight(*index) / total) as i32)
+                .collect();
+            let mut leftover = free
+                - line
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| !frozen[*i])
+                    .map(|(i, _)| shares[i])
+                    .sum::<i32>();
+            for (i, index) in line.iter().enumerate() {
+                if leftover == 0 {
+                    break;
+                }
+                if !frozen[i] && weight(*index) > 0.0 {
+                    shares[i] += leftover.signum();
+                    leftover -= leftover.signum();
+                }
+            }
+
+            let mut clamped = false;
+            for (i, index) in line.iter().enumerate() {
+                if frozen[i] {
+                    continue;
+                }
+                let size = &sizes[*index];
+                let target = size.basis + shares[i];
+                main[i] = target.clamp(size.min.x, size.max.x);
+                if main[i] != target {
+                    frozen[i] = true;
+                    clamped = true;
+                }
+            }
+            if !clamped {
+                break;
+            }
+        }
+
+        main
+    }
+}
+
+/// TheFlexLayout specific functions.
+pub trait TheFlexLayoutTrait: TheLayout {
+    /// Sets the main axis.
+    fn set_direction(&mut self, direction: TheFlexDirection);
+    /// Sets if the widgets wrap into several lines.
+    fn set_wrap(&mut self, wrap: bool);
+    /// Sets how the space left on a line is distributed.
+    fn set_justify(&mut self, justify: TheFlexJustify);
+    /// Sets the default alignment of the widgets on the cross axis.
+    fn set_alignment(&mut self, align: TheLayoutAlign);
+    /// Sets the gap between the widgets and between the lines.
+    fn set_gap(&mut self, gap: i32);
+    /// Add a widget with the given flex properties.
+    fn add_widget(&mut self, widget: Box<dyn TheWidget>, item: TheFlexItem);
+    /// Returns the flex properties of the widget of the given id.
+    fn item(&self, id: &TheId) -> Option<TheFlexItem>;
+    /// Changes the flex properties of the widget of the given id.
+    fn set_item(&mut self, id: &TheId, item: TheFlexItem);
+    /// Clear the layout.
+    fn clear(&mut self);
+}
+
+impl TheFlexLayoutTrait for TheFlexLayout {
+    fn set_direction(&mut self, direction: TheFlexDirection) {
+        self.direction = direction;
+    }
+    fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+    fn set_justify(&mut self, justify: TheFlexJustify) {
+        self.justify = justify;
+    }
+    fn set_alignment(&mut self, align: TheLayoutAlign) {
+        self.align = align;
+    }
+    fn set_gap(&mut self, gap: i32) {
+        self.gap = gap;
+    }
+    fn add_widget(&mut self, widget: Box<dyn TheWidget>, item: TheFlexItem) {
+        self.widgets.push(widget);
+        self.items.push(item);
+    }
+    fn item(&self, id: &TheId) -> Option<TheFlexItem> {
+        self.widgets
+            .iter()
+            .position(|w| w.id().equals(&Some(id.clone())))
+            .map(|index| self.items[index])
+    }
+    fn set_item(&mut self, id: &TheId, item: TheFlexItem) {
+        if let Some(index) = self
+            .widgets
+            .iter()
+            .position(|w| w.id().equals(&Some(id.clone())))
+        {
+            self.items[index] = item;
+        }
+    }
+    fn clear(&mut self) {
+        self.widgets = vec![];
+        self.items = vec![];
+    }
+}
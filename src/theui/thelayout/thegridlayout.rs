@@ -0,0 +1,406 @@
+use crate::prelude::*;
+use crate::theui::thelayout::preferred_size;
+
+/// The size of a row or column of TheGridLayout.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub enum TheGridTrack {
+    /// A fixed size in logical pixels.
+    Fixed(i32),
+    /// The largest preferred size of the widgets which only span this track.
+    Auto,
+    /// A share of the space left after the fixed and auto tracks, relative to the other
+    /// fractions.
+    Fraction(f32),
+}
+
+/// The cell of a widget in TheGridLayout and its alignment in the cell.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Copy, Debug)]
+pub struct TheGridPlacement {
+    pub column: usize,
+    pub row: usize,
+    pub column_span: usize,
+    pub row_span: usize,
+    /// The horizontal alignment, None uses the default of the layout.
+    pub horizontal: Option<TheLayoutAlign>,
+    /// The vertical alignment, None uses the default of the layout.
+    pub vertical: Option<TheLayoutAlign>,
+}
+
+impl TheGridPlacement {
+    /// Places a widget in a single cell.
+    pub fn new(column: usize, row: usize) -> Self {
+        Self {
+            column,
+            row,
+            column_span: 1,
+            row_span: 1,
+            horizontal: None,
+            vertical: None,
+        }
+    }
+
+    /// Sets the number of columns and rows the widget spans.
+    pub fn span(mut self, columns: usize, rows: usize) -> Self {
+        self.column_span = columns.max(1);
+        self.row_span = rows.max(1);
+        self
+    }
+
+    /// Sets the alignment of the widget in its cell.
+    pub fn align(mut self, horizontal: TheLayoutAlign, vertical: TheLayoutAlign) -> Self {
+        self.horizontal = Some(horizontal);
+        self.vertical = Some(vertical);
+        self
+    }
+}
+
+/// Arranges widgets in the cells of a grid of row and column tracks. Widgets can span several
+/// tracks and are aligned in their cells. Rows and columns which are used by a widget but not
+/// defined are Auto tracks.
+pub struct TheGridLayout {
+    id: TheId,
+    limiter: TheSizeLimiter,
+
+    dim: TheDim,
+
+    widgets: Vec<Box<dyn TheWidget>>,
+    placements: Vec<TheGridPlacement>,
+
+    columns: Vec<TheGridTrack>,
+    rows: Vec<TheGridTrack>,
+
+    margin: Vec4<i32>,
+    /// The gap between columns (x) and between rows (y).
+    gap: Vec2<i32>,
+
+    horizontal: TheLayoutAlign,
+    vertical: TheLayoutAlign,
+
+    background: Option<TheThemeColors>,
+}
+
+impl TheLayout for TheGridLayout {
+    fn new(id: TheId) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            id,
+            limiter: TheSizeLimiter::new(),
+
+            dim: TheDim::zero(),
+
+            widgets: vec![],
+            placements: vec![],
+
+            columns: vec![],
+            rows: vec![],
+
+            margin: Vec4::new(10, 10, 10, 10),
+            gap: Vec2::new(5, 5),
+
+            horizontal: TheLayoutAlign::Stretch,
+            vertical: TheLayoutAlign::Center,
+
+            background: Some(DefaultWidgetBackground),
+        }
+    }
+
+    fn id(&self) -> &TheId {
+        &self.id
+    }
+
+    fn set_margin(&mut self, margin: Vec4<i32>) {
+        self.margin = margin;
+    }
+
+    /// Sets the gap between the columns and between the rows.
+    fn set_padding(&mut self, padding: i32) {
+        self.gap = Vec2::broadcast(padding);
+    }
+
+    fn set_background_color(&mut self, color: Option<TheThemeColors>) {
+        self.background = color;
+    }
+
+    fn widgets(&mut self) -> &mut Vec<Box<dyn TheWidget>> {
+        &mut self.widgets
+    }
+
+    fn get_widget_at_coord(&mut self, coord: Vec2<i32>) -> Option<&mut Box<dyn TheWidget>> {
+        self.widgets.iter_mut().find(|w| w.dim().contains(coord))
+    }
+
+    fn get_widget(
+        &mut self,
+        name: Option<&String>,
+        uuid: Option<&Uuid>,
+    ) -> Option<&mut Box<dyn TheWidget>> {
+        self.widgets
+            .iter_mut()
+            .find(|w: &&mut Box<dyn TheWidget>| w.id().matches(name, uuid))
+    }
+
+    fn dim(&self) -> &TheDim {
+        &self.dim
+    }
+
+    fn dim_mut(&mut self) -> &mut TheDim {
+        &mut self.dim
+    }
+
+    fn set_dim(&mut self, dim: TheDim, ctx: &mut TheContext) {
+        if self.dim != dim || ctx.ui.relayout {
+            self.dim = dim;
+
+            for widget in &mut self.widgets {
+                widget.calculate_size(ctx);
+            }
+
+            let column_count = self
+                .placements
+                .iter()
+                .map(|p| p.column + p.column_span)
+                .max()
+                .unwrap_or(0)
+                .max(self.columns.len());
+            let row_count = self
+                .placements
+                .iter()
+                .map(|p| p.row + p.row_span)
+                .max()
+                .unwrap_or(0)
+                .max(self.rows.len());
+
+            let columns = self.track_offsets(
+                &self.columns,
+                column_count,
+                dim.width - self.margin.x - self.margin.z,
+                self.gap.x,
+                true,
+            );
+            let rows = self.track_offsets(
+                &self.rows,
+                row_count,
+                dim.height - self.margin.y - self.margin.w,
+                self.gap.y,
+                false,
+            );
+
+            for (widget, placement) in self.widgets.iter_mut().zip(&self.placements) {
+                let column_end = placement.column + placement.column_span - 1;
+                let row_end = placement.row + placement.row_span - 1;
+                let cell_x = columns[placement.column].0;
+                let cell_y = rows[placement.row].0;
+                let cell_width = columns[column_end].0 + columns[column_end].1 - cell_x;
+                let cell_height = rows[row_end].0 + rows[row_end].1 - cell_y;
+
+                let limiter = widget.limiter();
+                let (x, width) = placement.horizontal.unwrap_or(self.horizontal).place(
+                    cell_width,
+                    preferred_size(limiter.get_min_width(), limiter.get_max_width()),
+                    limiter.get_max_width(),
+                );
+                let (y, height) = placement.vertical.unwrap_or(self.vertical).place(
+                    cell_height,
+                    preferred_size(limiter.get_min_height(), limiter.get_max_height()),
+                    limiter.get_max_height(),
+                );
+
+                let x = self.margin.x + cell_x + x;
+                let y = self.margin.y + cell_y + y;
+
+                // Widgets outside of the area inside the margin are hidden.
+                if x + width > dim.width - self.margin.z
+                    || y + height > dim.height - self.margin.w
+                    || width <= 0
+                    || height <= 0
+                {
+                    widget.set_dim(TheDim::zero(), ctx);
+                    continue;
+                }
+
+                widget.set_dim(TheDim::new(dim.x + x, dim.y + y, width, height), ctx);
+                widget
+                    .dim_mut()
+                    .set_buffer_offset(self.dim.buffer_x + x, self.dim.buffer_y + y);
+            }
+        }
+    }
+
+    fn relayout(&mut self, ctx: &mut TheContext) {
+        let dim = self.dim;
+        self.dim = TheDim::zero();
+        self.set_dim(dim, ctx);
+    }
+
+    fn limiter(&self) -> &TheSizeLimiter {
+        &self.limiter
+    }
+
+    fn limiter_mut(&mut self) -> &mut TheSizeLimiter {
+        &mut self.limiter
+    }
+
+    fn draw(
+        &mut self,
+        buffer: &mut TheRGBABuffer,
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+    ) {
+        if let Some(background) = self.background {
+            let stride = buffer.stride();
+
+            ctx.draw.rect(
+                buffer.pixels_mut(),
+                &self.dim.to_buffer_utuple(),
+                stride,
+                style.theme().color(background),
+            );
+        }
+
+        for w in &mut self.widgets {
+            if w.dim().is_valid() {
+                w.draw(buffer, style, ctx);
+            }
+        }
+    }
+
+    fn as_grid_layout(&mut self) -> Option<&mut dyn TheGridLayoutTrait> {
+        Some(self)
+    }
+}
+
+impl TheGridLayout {
+    /// Returns the offset (relative to the margin) and the size of each track.
+    fn track_offsets(
+        &self,
+        tracks: &[TheGridTrack],
+        count: usize,
+        available: i32,
+        gap: i32,
+        horizontal: bool,
+    ) -> Vec<(i32, i32)> {
+        let track = |index: usize| tracks.get(index).copied().unwrap_or(TheGridTrack::Auto);
+
+        let mut sizes = vec![0; count];
+        let mut fractions = 0.0;
+        for (index, size) in sizes.iter_mut().enumerate() {
+            match track(index) {
+                TheGridTrack::Fixed(fixed) => *size = fixed,
+                TheGridTrack::Auto => {
+                    *size = self
+                        .widgets
+                        .iter()
+                        .zip(&self.placements)
+                        .filter(|(_, p)| {
+                            if horizontal {
+                                p.column == index && p.column_span == 1
+                            } else {
+                                p.row == index && p.row_span == 1
+                            }
+                        })
+                        .map(|(widget, _)| {
+                            let limiter = widget.limiter();
+                            if horizontal {
+                                preferred_size(limiter.get_min_width(), limiter.get_max_width())
+                            } else {
+                                preferred_size(limiter.get_min_height(), limiter.get_max_height())
+                            }
+                        })
+                        .max()
+                        .unwrap_or(0);
+                }
+                TheGridTrack::Fraction(fraction) => fractions += fraction.max(0.0),
+            }
+        }
+
+        let used: i32 = sizes.iter().sum::<i32>() + gap * (count as i32 - 1).max(0);
+        let free = (available - used).max(0);
+        if fractions > 0.0 {
+            // The last fraction track gets the rounding remainder.
+            let mut left = free;
+            let last = (0..count).rfind(|index| matches!(track(*index), TheGridTrack::Fraction(_)));
+            for (index, size) in sizes.iter_mut().enumerate() {
+                if let TheGridTrack::Fraction(fraction) = track(index) {
+                    *size = if Some(index) == last {
+                        left
+                    } else {
+                        (free as f32 * fraction.max(0.0) / fractions) as i32
+                    };
+                    left -= *size;
+                }
+            }
+        }
+
+        let mut offset = 0;
+        sizes
+            .into_iter()
+            .map(|size| {
+                let track = (offset, size);
+                offset += size + gap;
+                track
+            })
+            .collect()
+    }
+}
+
+/// TheGridLayout specific functions.
+pub trait TheGridLayoutTrait: TheLayout {
+    /// Sets the column tracks.
+    fn set_columns(&mut self, columns: Vec<TheGridTrack>);
+    /// Sets the row tracks.
+    fn set_rows(&mut self, rows: Vec<TheGridTrack>);
+    /// Sets the gap between the columns (x) and between the rows (y).
+    fn set_gap(&mut self, gap: Vec2<i32>);
+    /// Sets the default alignment of the widgets in their cells.
+    fn set_alignment(&mut self, horizontal: TheLayoutAlign, vertical: TheLayoutAlign);
+    /// Add a widget to the given cell.
+    fn add_widget(&mut self, widget: Box<dyn TheWidget>, placement: TheGridPlacement);
+    /// Returns the placement of the widget of the given id.
+    fn placement(&self, id: &TheId) -> Option<TheGridPlacement>;
+    /// Changes the placement of the widget of the given id.
+    fn set_placement(&mut self, id: &TheId, placement: TheGridPlacement);
+    /// Clear the layout.
+    fn clear(&mut self);
+}
+
+impl TheGridLayoutTrait for TheGridLayout {
+    fn set_columns(&mut self, columns: Vec<TheGridTrack>) {
+        self.columns = columns;
+    }
+    fn set_rows(&mut self, rows: Vec<TheGridTrack>) {
+        self.rows = rows;
+    }
+    fn set_gap(&mut self, gap: Vec2<i32>) {
+        self.gap = gap;
+    }
+    fn set_alignment(&mut self, horizontal: TheLayoutAlign, vertical: TheLayoutAlign) {
+        self.horizontal = horizontal;
+        self.vertical = vertical;
+    }
+    fn add_widget(&mut self, widget: Box<dyn TheWidget>, placement: TheGridPlacement) {
+        self.widgets.push(widget);
+        self.placements
+            .push(placement.span(placement.column_span, placement.row_span));
+    }
+    fn placement(&self, id: &TheId) -> Option<TheGridPlacement> {
+        self.widgets
+            .iter()
+            .position(|w| w.id().equals(&Some(id.clone())))
+            .map(|index| self.placements[index])
+    }
+    fn set_placement(&mut self, id: &TheId, placement: TheGridPlacement) {
+        if let Some(index) = self
+            .widgets
+            .iter()
+            .position(|w| w.id().equals(&Some(id.clone())))
+        {
+            self.placements[index] = placement.span(placement.column_span, placement.row_span);
+        }
+    }
+    fn clear(&mut self) {
+        self.widgets = vec![];
+        self.placements = vec![];
+    }
+}
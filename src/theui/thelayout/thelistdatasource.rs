@@ -0,0 +1,142 @@
+use crate::prelude::*;
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+/// Provides the items of a virtualized TheListLayout or TheRowListLayout. The layout only keeps
+/// widgets for the visible items and asks the data source to fill them while scrolling.
+pub trait TheListDataSource: Send {
+    /// The number of items.
+    fn count(&self) -> usize;
+
+    /// The id of the item at the given index. Ids have to be unique and stable, they are used for
+    /// the widget of the item and in its events.
+    fn item_id(&self, index: usize) -> TheId;
+
+    /// Fills the widget of the item at the given index, a TheListItem or a TheRowListItem.
+    /// Widgets are recycled from items which scrolled out of view, so everything the items differ
+    /// in has to be set.
+    fn update_item(&mut self, index: usize, item: &mut dyn TheWidget);
+}
+
+/// The state of a virtualized list, shared by TheListLayout and TheRowListLayout.
+pub(crate) struct TheVirtualList {
+    pub source: Box<dyn TheListDataSource>,
+
+    /// The indices of the items held by the widgets of the layout.
+    pub visible: Range<usize>,
+    /// The content position of the start of the list buffer.
+    pub buffer_start: i32,
+
+    pub selected: BTreeSet<usize>,
+
+    /// Widgets of items which scrolled out of view.
+    recycled: Vec<Box<dyn TheWidget>>,
+    /// The items need to be refilled from the data source.
+    reload: bool,
+}
+
+impl TheVirtualList {
+    pub fn new(source: Box<dyn TheListDataSource>) -> Self {
+        Self {
+            source,
+            visible: 0..0,
+            buffer_start: 0,
+            selected: BTreeSet::new(),
+            recycled: vec![],
+            reload: true,
+        }
+    }
+
+    /// The items of the data source changed, refill all visible widgets.
+    pub fn reload(&mut self) {
+        self.reload = true;
+        let count = self.source.count();
+        self.selected.retain(|index| *index < count);
+    }
+
+    /// The range of items between the two content positions, given the start and stride of
+    /// the items.
+    pub fn range(&self, from: i32, to: i32, start: i32, stride: i32) -> Range<usize> {
+        let count = self.source.count();
+        let first = ((from - start).max(0) / stride.max(1)) as usize;
+        let last = ((to - start).max(0) / stride.max(1) + 1) as usize;
+        first.min(count)..last.min(count)
+    }
+
+    /// Makes the widgets hold the items of the given range. Widgets of items which stay visible
+    /// are kept, the others are recycled. Returns true if the widgets changed.
+    pub fn update(
+        &mut self,
+        widgets: &mut Vec<Box<dyn TheWidget>>,
+        range: Range<usize>,
+        create: impl Fn(TheId, Option<Box<dyn TheWidget>>) -> Box<dyn TheWidget>,
+    ) -> bool {
+        if range == self.visible && !self.reload {
+            return false;
+        }
+
+        let mut old: Vec<Option<Box<dyn TheWidget>>> = widgets.drain(..).map(Some).collect();
+        for (offset, widget) in old.iter_mut().enumerate() {
+            if self.reload || !range.contains(&(self.visible.start + offset)) {
+                if let Some(widget) = widget.take() {
+                    self.recycled.push(widget);
+                }
+            }
+        }
+
+        for index in range.clone() {
+            let kept = if self.visible.contains(&index) {
+                old[index - self.visible.start].take()
+            } else {
+                None
+            };
+            let widget = kept.unwrap_or_else(|| {
+                let mut widget = create(self.source.item_id(index), self.recycled.pop());
+                self.source.update_item(index, widget.as_mut());
+                widget.set_state(if self.selected.contains(&index) {
+                    TheWidgetState::Selected
+                } else {
+                    TheWidgetState::None
+                });
+                widget
+            });
+            widgets.push(widget);
+        }
+
+        self.visible = range;
+        self.reload = false;
+        true
+    }
+
+    /// Returns the index of the item with the given uuid.
+    pub fn index_of(&self, widgets: &[Box<dyn TheWidget>], uuid: Uuid) -> Option<usize> {
+        if let Some(offset) = widgets.iter().position(|w| w.id().uuid == uuid) {
+            return Some(self.visible.start + offset);
+        }
+        (0..self.source.count()).find(|index| self.source.item_id(*index).uuid == uuid)
+    }
+
+    /// Selects the items at the given indices and updates the states of the visible widgets.
+    pub fn select(&mut self, selection: BTreeSet<usize>, widgets: &mut [Box<dyn TheWidget>]) {
+        let count = self.source.count();
+        self.selected = selection;
+        self.selected.retain(|index| *index < count);
+        for (offset, widget) in widgets.iter_mut().enumerate() {
+            let state = if self.selected.contains(&(self.visible.start + offset)) {
+                TheWidgetState::Selected
+            } else {
+                TheWidgetState::None
+            };
+            if widget.state() != state {
+                widget.set_state(state);
+            }
+        }
+    }
+
+    /// The id of the first selected item.
+    pub fn selected_id(&self) -> Option<TheId> {
+        self.selected
+            .first()
+            .map(|index| self.source.item_id(*index))
+    }
+}
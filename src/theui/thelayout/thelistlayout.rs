@@ -1,4 +1,7 @@
 use crate::prelude::*;
+use crate::theui::thelayout::thelistdatasource::TheVirtualList;
+use crate::theui::thelayout::theselection::TheSelection;
+use std::collections::BTreeSet;
 
 const LIST_RIGHT_MARGIN: i32 = 1;
 const LIST_BOTTOM_MARGIN: i32 = 2;
@@ -21,6 +24,10 @@ pub struct TheListLayout {
     background: Option<TheThemeColors>,
     item_size: i32,
 
+    /// The items of a virtualized list, see TheListDataSource.
+    virtual_list: Option<TheVirtualList>,
+    selection: TheSelection,
+
     is_dirty: bool,
 }
 
@@ -48,6 +55,9 @@ impl TheLayout for TheListLayout {
             background: Some(TextLayoutBackground),
             item_size: 17,
 
+            virtual_list: None,
+            selection: TheSelection::default(),
+
             is_dirty: false,
         }
     }
@@ -78,6 +88,10 @@ impl TheLayout for TheListLayout {
         &mut self.widgets
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::ListBox)
+    }
+
     fn get_widget_at_coord(&mut self, coord: Vec2<i32>) -> Option<&mut Box<dyn TheWidget>> {
         if !self.dim.contains(coord) {
             return None;
@@ -140,7 +154,10 @@ impl TheLayout for TheListLayout {
             let mut y = 1;
             let mut width = dim.width;
 
-            let items = self.widgets.len() as i32;
+            let items = match &self.virtual_list {
+                Some(list) => list.source.count() as i32,
+                None => self.widgets.len() as i32,
+            };
             let mut total_height = 1 + items * self.item_size + 1;
             if items > 0 {
                 total_height += (items - 1) * 3;
@@ -168,6 +185,16 @@ impl TheLayout for TheListLayout {
 
             let content_width = (width - LIST_RIGHT_MARGIN).max(0);
 
+            self.list_buffer.set_scale(ctx.scale_factor);
+
+            if self.virtual_list.is_some() {
+                // The buffer only holds the visible items.
+                let height = total_height.min(dim.height + 2 * (self.item_size + 3));
+                self.list_buffer.set_dim(TheDim::new(0, 0, width, height));
+                self.update_virtual_list(true, ctx);
+                return;
+            }
+
             self.list_buffer
                 .set_dim(TheDim::new(0, 0, width, total_height));
 
@@ -204,6 +231,8 @@ impl TheLayout for TheListLayout {
             return;
         }
 
+        self.update_virtual_list(false, ctx);
+
         let stride = self.list_buffer.stride();
         let utuple: (usize, usize, usize, usize) = self.list_buffer.dim().to_buffer_utuple();
 
@@ -214,12 +243,38 @@ impl TheLayout for TheListLayout {
             style.theme().color(ListLayoutBackground),
         );
 
-        ctx.draw.rect_outline(
-            self.list_buffer.pixels_mut(),
-            &utuple,
-            stride,
-            style.theme().color(ListLayoutBorder),
-        );
+        if let Some(list) = &self.virtual_list {
+            // The buffer only holds a part of the content, draw the visible parts of the border.
+            let (width, height) = (utuple.2, utuple.3);
+            let start = list.buffer_start;
+            let total_height = self
+                .vertical_scrollbar
+                .as_vertical_scrollbar()
+                .map(|scroll_bar| scroll_bar.total_height())
+                .unwrap_or(height as i32);
+            let mut lines = vec![(0, 0, 1, height), (width - 1, 0, 1, height)];
+            if start == 0 {
+                lines.push((0, 0, width, 1));
+            }
+            if total_height - start <= height as i32 {
+                lines.push((0, (total_height - start - 1).max(0) as usize, width, 1));
+            }
+            for line in lines {
+                ctx.draw.rect(
+                    self.list_buffer.pixels_mut(),
+                    &line,
+                    stride,
+                    style.theme().color(ListLayoutBorder),
+                );
+            }
+        } else {
+            ctx.draw.rect_outline(
+                self.list_buffer.pixels_mut(),
+                &utuple,
+                stride,
+                style.theme().color(ListLayoutBorder),
+            );
+        }
 
         if self.vertical_scrollbar_visible {
             self.vertical_scrollbar.draw(buffer, style, ctx);
@@ -242,7 +297,15 @@ impl TheLayout for TheListLayout {
             }
         }
 
-        if self.vertical_scrollbar_visible {
+        if let Some(list) = &self.virtual_list {
+            let start = offset - list.buffer_start;
+            buffer.copy_vertical_range_into(
+                self.dim.buffer_x,
+                self.dim.buffer_y,
+                &self.list_buffer,
+                start..start + self.dim.height,
+            );
+        } else if self.vertical_scrollbar_visible {
             if let Some(scroll_bar) = self.vertical_scrollbar.as_vertical_scrollbar() {
                 let offset = scroll_bar.scroll_offset();
                 let range = offset..offset + self.dim.height;
@@ -272,6 +335,162 @@ impl TheLayout for TheListLayout {
     }
 }
 
+impl TheListLayout {
+    /// Creates or recycles the widgets of the items which became visible. If forced, all widgets
+    /// are placed again.
+    fn update_virtual_list(&mut self, force: bool, ctx: &mut TheContext) {
+        let mut offset = 0;
+        if self.vertical_scrollbar_visible {
+            if let Some(scroll_bar) = self.vertical_scrollbar.as_vertical_scrollbar() {
+                offset = scroll_bar.scroll_offset();
+            }
+        }
+
+        let Some(list) = &mut self.virtual_list else {
+            return;
+        };
+
+        let stride = self.item_size + 3;
+        let range = list.range(offset, offset + self.dim.height, 1, stride);
+        let layout_id = self.id.clone();
+        let changed = list.update(&mut self.widgets, range, |id, recycled| match recycled {
+            Some(mut widget) => {
+                if let Some(item) = widget.as_list_item() {
+                    item.set_id(id);
+                }
+                widget
+            }
+            None => {
+                let mut item = TheListItem::new(id);
+                item.set_associated_layout(layout_id.clone());
+                Box::new(item)
+            }
+        });
+
+        if changed || force {
+            list.buffer_start = list.visible.start as i32 * stride;
+            let x = 1;
+            let width = (self.list_buffer.dim().width - LIST_RIGHT_MARGIN - 2).max(0);
+            for (index, widget) in list.visible.clone().zip(self.widgets.iter_mut()) {
+                let y = 1 + index as i32 * stride;
+                widget.set_dim(
+                    TheDim::new(self.dim.x + x, self.dim.y + y, width, self.item_size),
+                    ctx,
+                );
+                widget.dim_mut().set_buffer_offset(x, y - list.buffer_start);
+            }
+            self.is_dirty = true;
+        }
+    }
+}
+
+impl TheListLayout {
+    /// The number of items.
+    fn item_count(&self) -> usize {
+        match &self.virtual_list {
+            Some(list) => list.source.count(),
+            None => self.widgets.len(),
+        }
+    }
+
+    /// The index of the item with the given uuid.
+    fn item_index(&self, uuid: Uuid) -> Option<usize> {
+        match &self.virtual_list {
+            Some(list) => list.index_of(&self.widgets, uuid),
+            None => self.widgets.iter().position(|w| w.id().uuid == uuid),
+        }
+    }
+
+    /// The id of the item at the given index.
+    fn item_id(&self, index: usize) -> Option<TheId> {
+        match &self.virtual_list {
+            Some(list) => (index < list.source.count()).then(|| list.source.item_id(index)),
+            None => self.widgets.get(index).map(|w| w.id().clone()),
+        }
+    }
+
+    /// The indices of the selected items.
+    fn selected_indices(&self) -> BTreeSet<usize> {
+        match &self.virtual_list {
+            Some(list) => list.selected.clone(),
+            None => self
+                .widgets
+                .iter()
+                .enumerate()
+                .filter(|(_, w)| w.state() == TheWidgetState::Selected)
+                .map(|(index, _)| index)
+                .collect(),
+        }
+    }
+
+    /// The indices of the items selected by the layout, see TheSelection::items.
+    fn selection_before_click(&self) -> BTreeSet<usize> {
+        match &self.virtual_list {
+            Some(list) => list.selected.clone(),
+            None => self
+                .widgets
+                .iter()
+                .enumerate()
+                .filter(|(_, w)| self.selection.items.contains(&w.id().uuid))
+                .map(|(index, _)| index)
+                .collect(),
+        }
+    }
+
+    /// Selects the items at the given indices. Returns true if the selection changed.
+    fn apply_selection(&mut self, selection: BTreeSet<usize>) -> bool {
+        let items: FxHashSet<Uuid> = selection
+            .iter()
+            .filter_map(|index| self.item_id(*index))
+            .map(|id| id.uuid)
+            .collect();
+
+        if let Some(list) = &mut self.virtual_list {
+            list.select(selection, &mut self.widgets);
+        } else {
+            for (index, w) in self.widgets.iter_mut().enumerate() {
+                let state = if selection.contains(&index) {
+                    TheWidgetState::Selected
+                } else {
+                    TheWidgetState::None
+                };
+                if w.state() != state {
+                    w.set_state(state);
+                }
+            }
+        }
+        self.is_dirty = true;
+
+        let changed = items != self.selection.items;
+        self.selection.items = items;
+        changed
+    }
+
+    fn send_selection_changed(&self, ctx: &mut TheContext) {
+        ctx.ui.send(TheEvent::SelectionChanged(
+            self.id.clone(),
+            self.selected_ids(),
+        ));
+    }
+
+    /// Scrolls the item at the given index into view.
+    fn scroll_to_item(&mut self, index: usize) {
+        let top = 1 + index as i32 * (self.item_size + 3);
+        let bottom = top + self.item_size + 1;
+        if !self.vertical_scrollbar_visible {
+            return;
+        }
+        if let Some(scroll_bar) = self.vertical_scrollbar.as_vertical_scrollbar() {
+            let offset = scroll_bar.scroll_offset();
+            if top - 1 < offset {
+                scroll_bar.scroll_by(top - 1 - offset);
+            } else if bottom > offset + self.dim.height {
+                scroll_bar.scroll_by(bottom - offset - self.dim.height);
+            }
+        }
+    }
+}
+
 /// TheListLayout specific functions.
 pub trait TheListLayoutTrait: TheLayout {
     /// Adds an item.
@@ -300,6 +519,26 @@ pub trait TheListLayoutTrait: TheLayout {
     fn set_item_text(&mut self, id: Uuid, text: String);
     /// Scroll by the given amount.
     fn scroll_by(&mut self, delta: Vec2<i32>);
+    /// Virtualizes the list, the items come from the data source and only the visible items get
+    /// widgets. None returns to a list of added items.
+    fn set_data_source(&mut self, source: Option<Box<dyn TheListDataSource>>, ctx: &mut TheContext);
+    /// The items of the data source changed, refills the visible items.
+    fn reload_data(&mut self, ctx: &mut TheContext);
+    /// Returns the index of the selected item (if any).
+    fn selected_index(&self) -> Option<usize>;
+    /// Allows selecting several items, ranges with Shift and single items with Ctrl / Cmd.
+    fn set_multi_selection(&mut self, multi: bool);
+    /// An item was clicked. A range click selects the items from the last clicked item, a toggle
+    /// click adds or removes the item. Sends TheEvent::NewListItemSelected and
+    /// TheEvent::SelectionChanged if the selection changed.
+    fn item_clicked(&mut self, item: TheId, range: bool, toggle: bool, ctx: &mut TheContext);
+    /// Moves the selection by the given number of items, if extended the range selection grows or
+    /// shrinks. Sends TheEvent::SelectionChanged if the selection changed.
+    fn move_selection(&mut self, delta: i32, extend: bool, ctx: &mut TheContext);
+    /// Selects all items if multi selection is enabled.
+    fn select_all(&mut self, ctx: &mut TheContext);
+    /// Returns the ids of all selected items in list order.
+    fn selected_ids(&self) -> Vec<TheId>;
 }
 
 impl TheListLayoutTrait for TheListLayout {
@@ -310,74 +549,74 @@ impl TheListLayoutTrait for TheListLayout {
     }
 
     fn new_item_selected(&mut self, item: TheId) {
-        for w in &mut self.widgets {
-            if !w.id().equals(&Some(item.clone())) {
-                w.set_state(TheWidgetState::None);
-            }
-        }
+        let index = self.item_index(item.uuid);
+        self.selection.reset(index);
+        self.apply_selection(index.into_iter().collect());
     }
 
     fn select_first_item(&mut self, ctx: &mut TheContext) {
-        self.deselect_all();
-        if !self.widgets.is_empty() {
-            self.widgets[0].set_state(TheWidgetState::Selected);
-            ctx.ui
-                .send_widget_state_changed(self.widgets[0].id(), TheWidgetState::Selected);
-        }
+        self.select_item_at(0, ctx, true);
     }
 
     fn select_item(&mut self, uuid: Uuid, ctx: &mut TheContext, send_event: bool) -> bool {
-        self.deselect_all();
-        for w in &mut self.widgets {
-            if w.id().uuid == uuid {
-                w.set_state(TheWidgetState::Selected);
-                if send_event {
-                    ctx.ui
-                        .send_widget_state_changed(w.id(), TheWidgetState::Selected);
-                }
-                return true;
+        match self.item_index(uuid) {
+            Some(index) => self.select_item_at(index as i32, ctx, send_event),
+            None => {
+                self.deselect_all();
+                false
             }
         }
-        false
     }
 
     fn select_item_at(&mut self, index: i32, ctx: &mut TheContext, send_event: bool) -> bool {
-        self.deselect_all();
-        if let Some(w) = self.widgets.get_mut(index as usize) {
-            w.set_state(TheWidgetState::Selected);
-            if send_event {
-                ctx.ui
-                    .send_widget_state_changed(w.id(), TheWidgetState::Selected);
+        let index = usize::try_from(index)
+            .ok()
+            .filter(|index| *index < self.item_count());
+        self.selection.reset(index);
+        self.apply_selection(index.into_iter().collect());
+        self.is_dirty = true;
+
+        match index.and_then(|index| self.item_id(index)) {
+            Some(id) => {
+                if send_event {
+                    ctx.ui
+                        .send_widget_state_changed(&id, TheWidgetState::Selected);
+                }
+                true
             }
-            return true;
+            None => false,
         }
-        false
     }
 
     fn clear(&mut self) {
+        self.virtual_list = None;
         self.widgets.clear();
+        self.selection.reset(None);
         self.is_dirty = true;
     }
 
     fn remove(&mut self, id: TheId) {
+        self.selection.reset(None);
+        if let Some(list) = &mut self.virtual_list {
+            // The item is owned by the data source, which has to remove it.
+            list.reload();
+            self.is_dirty = true;
+            return;
+        }
         self.widgets.retain(|item| *item.id() != id);
         self.is_dirty = true;
     }
 
     fn deselect_all(&mut self) {
-        for w in &mut self.widgets {
-            w.set_state(TheWidgetState::None);
-        }
+        self.selection.reset(None);
+        self.apply_selection(BTreeSet::new());
         self.is_dirty = true;
     }
 
     fn selected(&self) -> Option<TheId> {
-        for w in &self.widgets {
-            if w.state() == TheWidgetState::Selected {
-                return Some(w.id().clone());
-            }
-        }
-        None
+        self.selected_indices()
+            .first()
+            .and_then(|index| self.item_id(*index))
     }
     fn set_item_size(&mut self, item_size: i32) {
         self.item_size = item_size;
@@ -404,4 +643,77 @@ impl TheListLayoutTrait for TheListLayout {
             scroll_bar.scroll_by(-delta.y);
         }
     }
+    fn set_data_source(
+        &mut self,
+        source: Option<Box<dyn TheListDataSource>>,
+        ctx: &mut TheContext,
+    ) {
+        self.widgets.clear();
+        self.virtual_list = source.map(TheVirtualList::new);
+        self.is_dirty = true;
+        ctx.ui.relayout = true;
+    }
+    fn reload_data(&mut self, ctx: &mut TheContext) {
+        if let Some(list) = &mut self.virtual_list {
+            list.reload();
+            self.is_dirty = true;
+            ctx.ui.relayout = true;
+        }
+    }
+    fn selected_index(&self) -> Option<usize> {
+        self.selected_indices().first().copied()
+    }
+    fn set_multi_selection(&mut self, multi: bool) {
+        self.selection.multi = multi;
+        if !multi {
+            let first = self.selected_indices().first().copied();
+            self.apply_selection(first.into_iter().collect());
+        }
+    }
+    fn item_clicked(&mut self, item: TheId, range: bool, toggle: bool, ctx: &mut TheContext) {
+        let Some(index) = self.item_index(item.uuid) else {
+            return;
+        };
+        let current = self.selection_before_click();
+        let selection = self.selection.click(index, &current, range, toggle);
+        if self.apply_selection(selection) {
+            ctx.ui
+                .send(TheEvent::NewListItemSelected(item, self.id.clone()));
+            self.send_selection_changed(ctx);
+        }
+    }
+    fn move_selection(&mut self, delta: i32, extend: bool, ctx: &mut TheContext) {
+        let current = self.selected_indices();
+        let Some((lead, selection)) =
+            self.selection
+                .step(delta, self.item_count(), &current, extend)
+        else {
+            return;
+        };
+
+        self.scroll_to_item(lead);
+        if self.apply_selection(selection) {
+            if let Some(id) = self.item_id(lead) {
+                if !extend {
+                    ctx.ui
+                        .send_widget_state_changed(&id, TheWidgetState::Selected);
+                }
+                ctx.ui.set_focus(&id);
+            }
+            self.send_selection_changed(ctx);
+        }
+    }
+    fn select_all(&mut self, ctx: &mut TheContext) {
+        if let Some(selection) = self.selection.all(self.item_count()) {
+            if self.apply_selection(selection) {
+                self.send_selection_changed(ctx);
+            }
+        }
+    }
+    fn selected_ids(&self) -> Vec<TheId> {
+        self.selected_indices()
+            .into_iter()
+            .filter_map(|index| self.item_id(index))
+            .collect()
+    }
 }
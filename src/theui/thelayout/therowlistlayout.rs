@@ -1,4 +1,6 @@
 use crate::prelude::*;
+use crate::theui::thelayout::thelistdatasource::TheVirtualList;
+use std::collections::BTreeSet;
 
 pub struct TheRowListLayout {
     id: TheId,
@@ -18,6 +20,9 @@ pub struct TheRowListLayout {
     background: Option<TheThemeColors>,
     item_size: i32,
 
+    /// The items of a virtualized list, see TheListDataSource.
+    virtual_list: Option<TheVirtualList>,
+
     is_dirty: bool,
 }
 
@@ -45,6 +50,8 @@ impl TheLayout for TheRowListLayout {
             background: Some(TextLayoutBackground),
             item_size: 115,
 
+            virtual_list: None,
+
             is_dirty: false,
         }
     }
@@ -65,6 +72,10 @@ impl TheLayout for TheRowListLayout {
         &mut self.widgets
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::ListBox)
+    }
+
     fn get_widget_at_coord(&mut self, coord: Vec2<i32>) -> Option<&mut Box<dyn TheWidget>> {
         if !self.dim.contains(coord) {
             return None;
@@ -129,7 +140,10 @@ impl TheLayout for TheRowListLayout {
 
             self.item_size = dim.height - 13 - 20 - 2;
 
-            let items = self.widgets.len() as i32;
+            let items = match &self.virtual_list {
+                Some(list) => list.source.count() as i32,
+                None => self.widgets.len() as i32,
+            };
             let mut total_width = 2 + items * self.item_size + 2;
             if items > 0 {
                 total_width += (items - 1) * 3;
@@ -154,6 +168,16 @@ impl TheLayout for TheRowListLayout {
                 height -= 13;
             }
 
+            self.list_buffer.set_scale(ctx.scale_factor);
+
+            if self.virtual_list.is_some() {
+                // The buffer only holds the visible items.
+                let width = total_width.min(dim.width + 2 * (self.item_size + 3));
+                self.list_buffer.set_dim(TheDim::new(0, 0, width, height));
+                self.update_virtual_list(true, ctx);
+                return;
+            }
+
             self.list_buffer
                 .set_dim(TheDim::new(0, 0, total_width, height));
 
@@ -189,6 +213,8 @@ impl TheLayout for TheRowListLayout {
             return;
         }
 
+        self.update_virtual_list(false, ctx);
+
         let stride = self.list_buffer.stride();
         let utuple: (usize, usize, usize, usize) = self.list_buffer.dim().to_buffer_utuple();
 
@@ -199,12 +225,38 @@ impl TheLayout for TheRowListLayout {
             style.theme().color(ListLayoutBackground),
         );
 
-        ctx.draw.rect_outline(
-            self.list_buffer.pixels_mut(),
-            &utuple,
-            stride,
-            style.theme().color(ListLayoutBorder),
-        );
+        if let Some(list) = &self.virtual_list {
+            // The buffer only holds a part of the content, draw the visible parts of the border.
+            let (width, height) = (utuple.2, utuple.3);
+            let start = list.buffer_start;
+            let total_width = self
+                .horizontal_scrollbar
+                .as_horizontal_scrollbar()
+                .map(|scroll_bar| scroll_bar.total_width())
+                .unwrap_or(width as i32);
+            let mut lines = vec![(0, 0, width, 1), (0, height - 1, width, 1)];
+            if start == 0 {
+                lines.push((0, 0, 1, height));
+            }
+            if total_width - start <= width as i32 {
+                lines.push(((total_width - start - 1).max(0) as usize, 0, 1, height));
+            }
+            for line in lines {
+                ctx.draw.rect(
+                    self.list_buffer.pixels_mut(),
+                    &line,
+                    stride,
+                    style.theme().color(ListLayoutBorder),
+                );
+            }
+        } else {
+            ctx.draw.rect_outline(
+                self.list_buffer.pixels_mut(),
+                &utuple,
+                stride,
+                style.theme().color(ListLayoutBorder),
+            );
+        }
 
         if self.horizontal_scrollbar_visible {
             self.horizontal_scrollbar.draw(buffer, style, ctx);
@@ -227,7 +279,15 @@ impl TheLayout for TheRowListLayout {
             }
         }
 
-        if self.horizontal_scrollbar_visible {
+        if let Some(list) = &self.virtual_list {
+            let start = offset - list.buffer_start;
+            buffer.copy_horizontal_range_into(
+                self.dim.buffer_x,
+                self.dim.buffer_y,
+                &self.list_buffer,
+                start..start + self.dim.width,
+            );
+        } else if self.horizontal_scrollbar_visible {
             if let Some(scroll_bar) = self.horizontal_scrollbar.as_horizontal_scrollbar() {
                 let offset = scroll_bar.scroll_offset();
                 let range = offset..offset + self.dim.width;
@@ -257,6 +317,55 @@ impl TheLayout for TheRowListLayout {
     }
 }
 
+impl TheRowListLayout {
+    /// Creates or recycles the widgets of the items which became visible. If forced, all widgets
+    /// are placed again.
+    fn update_virtual_list(&mut self, force: bool, ctx: &mut TheContext) {
+        let mut offset = 0;
+        if self.horizontal_scrollbar_visible {
+            if let Some(scroll_bar) = self.horizontal_scrollbar.as_horizontal_scrollbar() {
+                offset = scroll_bar.scroll_offset();
+            }
+        }
+
+        let Some(list) = &mut self.virtual_list else {
+            return;
+        };
+
+        let stride = self.item_size + 3;
+        let range = list.range(offset, offset + self.dim.width, 2, stride);
+        let layout_id = self.id.clone();
+        let changed = list.update(&mut self.widgets, range, |id, recycled| match recycled {
+            Some(mut widget) => {
+                if let Some(item) = widget.as_rowlist_item() {
+                    item.set_id(id);
+                }
+                widget
+            }
+            None => {
+                let mut item = TheRowListItem::new(id);
+                item.set_associated_layout(layout_id.clone());
+                Box::new(item)
+            }
+        });
+
+        if changed || force {
+            list.buffer_start = list.visible.start as i32 * stride;
+            let y = 2;
+            let height = self.list_buffer.dim().height - 4;
+            for (index, widget) in list.visible.clone().zip(self.widgets.iter_mut()) {
+                let x = 2 + index as i32 * stride;
+                widget.set_dim(
+                    TheDim::new(self.dim.x + x, self.dim.y + y, self.item_size, height),
+                    ctx,
+                );
+                widget.dim_mut().set_buffer_offset(x - list.buffer_start, y);
+            }
+            self.is_dirty = true;
+        }
+    }
+}
+
 /// TheListLayout specific functions.
 pub trait TheRowListLayoutTrait: TheLayout {
     /// Adds an item.
@@ -285,6 +394,13 @@ pub trait TheRowListLayoutTrait: TheLayout {
     fn set_item_image(&mut self, id: Uuid, image: TheRGBABuffer);
     /// Scroll by the given amount.
     fn scroll_by(&mut self, delta: Vec2<i32>);
+    /// Virtualizes the list, the items come from the data source and only the visible items get
+    /// widgets. None returns to a list of added items.
+    fn set_data_source(&mut self, source: Option<Box<dyn TheListDataSource>>, ctx: &mut TheContext);
+    /// The items of the data source changed, refills the visible items.
+    fn reload_data(&mut self, ctx: &mut TheContext);
+    /// Returns the index of the selected item (if any).
+    fn selected_index(&self) -> Option<usize>;
 }
 
 impl TheRowListLayoutTrait for TheRowListLayout {
@@ -295,6 +411,11 @@ impl TheRowListLayoutTrait for TheRowListLayout {
     }
 
     fn new_item_selected(&mut self, item: TheId) {
+        if let Some(list) = &mut self.virtual_list {
+            let index = list.index_of(&self.widgets, item.uuid);
+            list.select(index.into_iter().collect(), &mut self.widgets);
+            return;
+        }
         for w in &mut self.widgets {
             if !w.id().equals(&Some(item.clone())) {
                 w.set_state(TheWidgetState::None);
@@ -303,6 +424,10 @@ impl TheRowListLayoutTrait for TheRowListLayout {
     }
 
     fn select_first_item(&mut self, ctx: &mut TheContext) {
+        if self.virtual_list.is_some() {
+            self.select_item_at(0, ctx, true);
+            return;
+        }
         self.deselect_all();
         if !self.widgets.is_empty() {
             self.widgets[0].set_state(TheWidgetState::Selected);
@@ -312,6 +437,14 @@ impl TheRowListLayoutTrait for TheRowListLayout {
     }
 
     fn select_item(&mut self, uuid: Uuid, ctx: &mut TheContext, send_event: bool) -> bool {
+        if let Some(list) = &self.virtual_list {
+            let index = list.index_of(&self.widgets, uuid);
+            self.deselect_all();
+            return match index {
+                Some(index) => self.select_item_at(index as i32, ctx, send_event),
+                None => false,
+            };
+        }
         self.deselect_all();
         for w in &mut self.widgets {
             if w.id().uuid == uuid {
@@ -327,6 +460,21 @@ impl TheRowListLayoutTrait for TheRowListLayout {
     }
 
     fn select_item_at(&mut self, index: i32, ctx: &mut TheContext, send_event: bool) -> bool {
+        if let Some(list) = &mut self.virtual_list {
+            let index = usize::try_from(index).ok();
+            list.select(index.into_iter().collect(), &mut self.widgets);
+            self.is_dirty = true;
+            return match list.selected_id() {
+                Some(id) => {
+                    if send_event {
+                        ctx.ui
+                            .send_widget_state_changed(&id, TheWidgetState::Selected);
+                    }
+                    true
+                }
+                None => false,
+            };
+        }
         self.deselect_all();
         if let Some(w) = self.widgets.get_mut(index as usize) {
             w.set_state(TheWidgetState::Selected);
@@ -340,16 +488,26 @@ impl TheRowListLayoutTrait for TheRowListLayout {
     }
 
     fn clear(&mut self) {
+        self.virtual_list = None;
         self.widgets.clear();
         self.is_dirty = true;
     }
 
     fn remove(&mut self, id: TheId) {
+        if let Some(list) = &mut self.virtual_list {
+            // The item is owned by the data source, which has to remove it.
+            list.reload();
+            self.is_dirty = true;
+            return;
+        }
         self.widgets.retain(|item| *item.id() != id);
         self.is_dirty = true;
     }
 
     fn deselect_all(&mut self) {
+        if let Some(list) = &mut self.virtual_list {
+            list.select(BTreeSet::new(), &mut self.widgets);
+        }
         for w in &mut self.widgets {
             w.set_state(TheWidgetState::None);
         }
@@ -357,6 +515,9 @@ impl TheRowListLayoutTrait for TheRowListLayout {
     }
 
     fn selected(&self) -> Option<TheId> {
+        if let Some(list) = &self.virtual_list {
+            return list.selected_id();
+        }
         for w in &self.widgets {
             if w.state() == TheWidgetState::Selected {
                 return Some(w.id().clone());
@@ -389,4 +550,29 @@ impl TheRowListLayoutTrait for TheRowListLayout {
             scroll_bar.scroll_by(-delta.x);
         }
     }
+    fn set_data_source(
+        &mut self,
+        source: Option<Box<dyn TheListDataSource>>,
+        ctx: &mut TheContext,
+    ) {
+        self.widgets.clear();
+        self.virtual_list = source.map(TheVirtualList::new);
+        self.is_dirty = true;
+        ctx.ui.relayout = true;
+    }
+    fn reload_data(&mut self, ctx: &mut TheContext) {
+        if let Some(list) = &mut self.virtual_list {
+            list.reload();
+            self.is_dirty = true;
+            ctx.ui.relayout = true;
+        }
+    }
+    fn selected_index(&self) -> Option<usize> {
+        if let Some(list) = &self.virtual_list {
+            return list.selected.first().copied();
+        }
+        self.widgets
+            .iter()
+            .position(|w| w.state() == TheWidgetState::Selected)
+    }
 }
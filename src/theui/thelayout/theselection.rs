@@ -0,0 +1,107 @@
+use crate::prelude::*;
+use std::collections::BTreeSet;
+
+/// The selection logic of the list and tree layouts. Items are addressed by their index in the
+/// displayed order.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct TheSelection {
+    /// Several items can be selected.
+    pub multi: bool,
+    /// The item a range selection starts at.
+    pub anchor: Option<usize>,
+    /// The item a range selection ends at, moved by the keyboard.
+    pub lead: Option<usize>,
+    /// The items selected by the layout. Items select themselves when clicked, before the
+    /// layout handles the click, so their states can not tell the previous selection.
+    pub items: FxHashSet<Uuid>,
+}
+
+impl TheSelection {
+    /// Starts a new selection at the given item.
+    pub fn reset(&mut self, index: Option<usize>) {
+        self.anchor = index;
+        self.lead = index;
+    }
+
+    /// Returns the selection after clicking the item at the index. A range click selects from the
+    /// anchor to the item, a toggle click adds or removes the item. Both are only available in
+    /// multi selection mode.
+    pub fn click(
+        &mut self,
+        index: usize,
+        current: &BTreeSet<usize>,
+        range: bool,
+        toggle: bool,
+    ) -> BTreeSet<usize> {
+        if self.multi && range {
+            let anchor = self.anchor.unwrap_or(index);
+            let mut selection = if toggle {
+                current.clone()
+            } else {
+                BTreeSet::new()
+            };
+            selection.extend(anchor.min(index)..=anchor.max(index));
+            self.anchor = Some(anchor);
+            self.lead = Some(index);
+            selection
+        } else if self.multi && toggle {
+            let mut selection = current.clone();
+            if !selection.remove(&index) {
+                selection.insert(index);
+            }
+            self.reset(Some(index));
+            selection
+        } else {
+            self.reset(Some(index));
+            BTreeSet::from([index])
+        }
+    }
+
+    /// Moves the lead by delta items and returns it with the new selection. If extended, the
+    /// selection ranges from the anchor to the lead.
+    pub fn step(
+        &mut self,
+        delta: i32,
+        count: usize,
+        current: &BTreeSet<usize>,
+        extend: bool,
+    ) -> Option<(usize, BTreeSet<usize>)> {
+        if count == 0 {
+            return None;
+        }
+
+        let lead = self
+            .lead
+            .filter(|lead| current.contains(lead))
+            .or_else(|| current.last().copied());
+        let next = match lead {
+            Some(lead) => (lead as i64 + delta as i64).clamp(0, count as i64 - 1) as usize,
+            None if delta < 0 => count - 1,
+            None => 0,
+        };
+
+        if self.multi && extend {
+            let anchor = self
+                .anchor
+                .filter(|anchor| *anchor < count)
+                .unwrap_or(lead.unwrap_or(next));
+            self.anchor = Some(anchor);
+            self.lead = Some(next);
+            Some((next, (anchor.min(next)..=anchor.max(next)).collect()))
+        } else {
+            self.reset(Some(next));
+            Some((next, BTreeSet::from([next])))
+        }
+    }
+
+    /// Returns the selection of all items, None if only one item can be selected.
+    pub fn all(&mut self, count: usize) -> Option<BTreeSet<usize>> {
+        if !self.multi {
+            return None;
+        }
+        if self.anchor.is_none_or(|anchor| anchor >= count) {
+            self.reset(count.checked_sub(1).map(|_| 0));
+        }
+        Some((0..count).collect())
+    }
+}
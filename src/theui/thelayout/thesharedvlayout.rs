@@ -68,6 +68,33 @@ impl TheLayout for TheSharedVLayout {
         &mut self.widgets
     }
 
+    fn collect_focus_ids(&mut self, ids: &mut Vec<TheId>) {
+        for (index, canvas) in self.canvas.iter_mut().enumerate().take(2) {
+            let visible = match self.mode {
+                TheSharedVLayoutMode::Top => index == 0,
+                TheSharedVLayoutMode::Bottom => index == 1,
+                _ => true,
+            };
+            if visible {
+                canvas.collect_focus_ids(ids);
+            }
+        }
+    }
+
+    fn collect_access_nodes(&mut self, tree: &mut TheAccessTree, parent: u64, ctx: &TheContext) {
+        let id = tree.add(parent, &self.id, self.accessibility(), self.dim, ctx);
+        for (index, canvas) in self.canvas.iter_mut().enumerate().take(2) {
+            let visible = match self.mode {
+                TheSharedVLayoutMode::Top => index == 0,
+                TheSharedVLayoutMode::Bottom => index == 1,
+                _ => true,
+            };
+            if visible {
+                canvas.collect_access_nodes(tree, id, ctx);
+            }
+        }
+    }
+
     fn needs_redraw(&mut self) -> bool {
         for canvas in &mut self.canvas {
             if canvas.needs_redraw() {
@@ -54,6 +54,24 @@ impl TheLayout for TheSnapperLayout {
         &mut self.widgets
     }
 
+    fn collect_focus_ids(&mut self, ids: &mut Vec<TheId>) {
+        for (bar, layout) in self.bars.iter().zip(self.layouts.iter_mut()) {
+            if bar.is_open() {
+                layout.collect_focus_ids(ids);
+            }
+        }
+    }
+
+    fn collect_access_nodes(&mut self, tree: &mut TheAccessTree, parent: u64, ctx: &TheContext) {
+        let id = tree.add(parent, &self.id, self.accessibility(), self.dim, ctx);
+        for (bar, layout) in self.bars.iter().zip(self.layouts.iter_mut()) {
+            let bar_id = tree.add_widget(id, bar.as_ref(), ctx);
+            if bar.is_open() {
+                layout.collect_access_nodes(tree, bar_id, ctx);
+            }
+        }
+    }
+
     fn get_widget_at_coord(&mut self, coord: Vec2<i32>) -> Option<&mut Box<dyn TheWidget>> {
         let mut open_states = vec![];
 
@@ -66,6 +66,19 @@ impl TheLayout for TheStackLayout {
         &mut self.widgets
     }
 
+    fn collect_focus_ids(&mut self, ids: &mut Vec<TheId>) {
+        if let Some(canvas) = self.canvas.get_mut(self.index) {
+            canvas.collect_focus_ids(ids);
+        }
+    }
+
+    fn collect_access_nodes(&mut self, tree: &mut TheAccessTree, parent: u64, ctx: &TheContext) {
+        let id = tree.add(parent, &self.id, self.accessibility(), self.dim, ctx);
+        if let Some(canvas) = self.canvas.get_mut(self.index) {
+            canvas.collect_access_nodes(tree, id, ctx);
+        }
+    }
+
     fn get_layout_at_coord(&mut self, coord: Vec2<i32>) -> Option<TheId> {
         if self.dim.contains(coord) {
             if !self.canvas.is_empty() && self.index < self.canvas.len() {
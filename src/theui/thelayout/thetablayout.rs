@@ -41,6 +41,20 @@ impl TheLayout for TheTabLayout {
         &mut self.widgets
     }
 
+    fn collect_focus_ids(&mut self, ids: &mut Vec<TheId>) {
+        if let Some(canvas) = self.canvas.get_mut(self.index) {
+            canvas.collect_focus_ids(ids);
+        }
+    }
+
+    fn collect_access_nodes(&mut self, tree: &mut TheAccessTree, parent: u64, ctx: &TheContext) {
+        let id = tree.add(parent, &self.id, self.accessibility(), self.dim, ctx);
+        tree.add_widget(id, self.tabbar.as_ref(), ctx);
+        if let Some(canvas) = self.canvas.get_mut(self.index) {
+            canvas.collect_access_nodes(tree, id, ctx);
+        }
+    }
+
     fn get_widget_at_coord(&mut self, coord: Vec2<i32>) -> Option<&mut Box<dyn TheWidget>> {
         if self.tabbar.dim().contains(coord) {
             return Some(&mut self.tabbar);
@@ -0,0 +1,709 @@
+use crate::prelude::*;
+use crate::theui::thelayout::thetableview::{cell_text, TheTableView, TABLE_HEADER_HEIGHT};
+
+/// The type of the values in a column of TheTableLayout, it decides how the cells are drawn,
+/// sorted and edited.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TheTableColumnType {
+    /// TheValue::Text, edited with a TheTextLineEdit.
+    Text,
+    /// TheValue::Int, edited with a TheTextLineEdit.
+    Int,
+    /// TheValue::Float, edited with a TheTextLineEdit.
+    Float,
+    /// TheValue::Bool, edited with a TheCheckButton.
+    Bool,
+    /// TheValue::TextList with the selected option, edited with a TheDropdownMenu.
+    Choice,
+}
+
+impl TheTableColumnType {
+    /// Converts the value of a cell editor to a value of the column type. Choices keep the
+    /// options of the current value of the cell. Returns None if the value does not convert.
+    pub fn convert(&self, value: &TheValue, current: &TheValue) -> Option<TheValue> {
+        let value = match value {
+            TheValue::Text(text) => TheValue::Text(text.trim().to_string()),
+            _ => value.clone(),
+        };
+        match self {
+            TheTableColumnType::Text => Some(TheValue::Text(cell_text(&value))),
+            TheTableColumnType::Int => value.to_i32().map(TheValue::Int),
+            TheTableColumnType::Float => value.to_f32().map(TheValue::Float),
+            TheTableColumnType::Bool => match value {
+                TheValue::Bool(value) => Some(TheValue::Bool(value)),
+                _ => None,
+            },
+            TheTableColumnType::Choice => match (value.to_i32(), current) {
+                (Some(index), TheValue::TextList(_, options))
+                    if index >= 0 && (index as usize) < options.len() =>
+                {
+                    Some(TheValue::TextList(index, options.clone()))
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+/// A column of TheTableLayout.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct TheTableColumn {
+    /// The text of the header.
+    pub name: String,
+    pub column_type: TheTableColumnType,
+    pub width: i32,
+    /// Resizing the column stops at this width.
+    pub min_width: i32,
+    /// Clicking the header sorts the rows by the column.
+    pub sortable: bool,
+    /// Clicking a cell of the selected row edits it.
+    pub editable: bool,
+}
+
+impl TheTableColumn {
+    /// A sortable, read only column.
+    pub fn new(name: &str, column_type: TheTableColumnType) -> Self {
+        Self {
+            name: name.to_string(),
+            column_type,
+            width: 100,
+            min_width: 30,
+            sortable: true,
+            editable: false,
+        }
+    }
+
+    /// Sets the width of the column.
+    pub fn with_width(mut self, width: i32) -> Self {
+        self.width = width.max(self.min_width);
+        self
+    }
+
+    /// Sets if the header sorts the rows.
+    pub fn with_sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    /// Sets if the cells can be edited.
+    pub fn with_editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+}
+
+/// Provides the rows of TheTableLayout. The table only asks for the cells of the visible rows,
+/// and for all cells of a column when sorting by it.
+pub trait TheTableDataSource: Send {
+    /// The number of rows.
+    fn row_count(&self) -> usize;
+
+    /// The value of a cell, of the type of its column.
+    fn cell(&self, row: usize, column: usize) -> TheValue;
+
+    /// Stores the value of an edited cell. Sources of read only tables can ignore it.
+    fn set_cell(&mut self, _row: usize, _column: usize, _value: TheValue) {}
+}
+
+/// The rows of a table set with TheTableLayoutTrait::set_rows().
+#[derive(Default)]
+pub(crate) struct TheTableRows {
+    rows: Vec<Vec<TheValue>>,
+}
+
+impl TheTableDataSource for TheTableRows {
+    fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn cell(&self, row: usize, column: usize) -> TheValue {
+        self.rows
+            .get(row)
+            .and_then(|row| row.get(column))
+            .cloned()
+            .unwrap_or(TheValue::Empty)
+    }
+
+    fn set_cell(&mut self, row: usize, column: usize, value: TheValue) {
+        if let Some(cell) = self.rows.get_mut(row).and_then(|row| row.get_mut(column)) {
+            *cell = value;
+        }
+    }
+}
+
+/// A table with typed columns. Headers can be resized and reordered by dragging and sort the
+/// rows when clicked. Only the visible rows are drawn, so large data sources stay cheap.
+pub struct TheTableLayout {
+    id: TheId,
+    limiter: TheSizeLimiter,
+
+    dim: TheDim,
+
+    /// The TheTableView, which owns the columns and rows.
+    view: Box<dyn TheWidget>,
+    widgets: Vec<Box<dyn TheWidget>>,
+
+    vertical_scrollbar: Box<dyn TheWidget>,
+    vertical_scrollbar_visible: bool,
+
+    /// The widget editing a cell and the edited cell (row of the data source, column).
+    editor: Option<(Box<dyn TheWidget>, usize, usize)>,
+
+    is_dirty: bool,
+}
+
+impl TheLayout for TheTableLayout {
+    fn new(id: TheId) -> Self
+    where
+        Self: Sized,
+    {
+        let mut view = TheTableView::new(TheId::named_with_reference("Table View", id.uuid));
+        view.layout_id = id.clone();
+
+        Self {
+            id,
+            limiter: TheSizeLimiter::new(),
+
+            dim: TheDim::zero(),
+
+            view: Box::new(view),
+            widgets: vec![],
+
+            vertical_scrollbar: Box::new(TheVerticalScrollbar::new(TheId::named(
+                "Vertical Scrollbar",
+            ))),
+            vertical_scrollbar_visible: false,
+
+            editor: None,
+
+            is_dirty: false,
+        }
+    }
+
+    fn id(&self) -> &TheId {
+        &self.id
+    }
+
+    fn supports_mouse_wheel(&self) -> bool {
+        true
+    }
+
+    fn mouse_wheel_scroll(&mut self, delta: Vec2<i32>) {
+        if let Some(scroll_bar) = self.vertical_scrollbar.as_vertical_scrollbar() {
+            scroll_bar.scroll_by(-delta.y);
+        }
+    }
+
+    fn widgets(&mut self) -> &mut Vec<Box<dyn TheWidget>> {
+        &mut self.widgets
+    }
+
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::Table)
+    }
+
+    fn collect_access_nodes(&mut self, tree: &mut TheAccessTree, parent: u64, ctx: &TheContext) {
+        let table = tree.add(parent, &self.id, self.accessibility(), self.dim, ctx);
+        let view_id = self.view.id().clone();
+        for (info, dim, cells) in self.view().access_rows() {
+            let row = tree.add(table, &view_id, info, dim, ctx);
+            for (info, dim) in cells {
+                tree.add(row, &view_id, info, dim, ctx);
+            }
+        }
+        if let Some((editor, ..)) = &self.editor {
+            tree.add_widget(table, editor.as_ref(), ctx);
+        }
+        if self.vertical_scrollbar_visible {
+            tree.add_widget(table, self.vertical_scrollbar.as_ref(), ctx);
+        }
+    }
+
+    fn collect_focus_ids(&mut self, ids: &mut Vec<TheId>) {
+        if self.view.dim().is_valid() {
+            ids.push(self.view.id().clone());
+        }
+    }
+
+    fn get_widget_at_coord(&mut self, coord: Vec2<i32>) -> Option<&mut Box<dyn TheWidget>> {
+        if !self.dim.contains(coord) {
+            return None;
+        }
+
+        if self
+            .editor
+            .as_ref()
+            .is_some_and(|(editor, ..)| editor.dim().contains(coord))
+        {
+            return self.editor.as_mut().map(|(editor, ..)| editor);
+        }
+
+        if self.vertical_scrollbar_visible && self.vertical_scrollbar.dim().contains(coord) {
+            return Some(&mut self.vertical_scrollbar);
+        }
+
+        self.sync_scroll_offset();
+        if self.view.dim().contains(coord) {
+            return Some(&mut self.view);
+        }
+        None
+    }
+
+    fn get_widget(
+        &mut self,
+        name: Option<&String>,
+        uuid: Option<&Uuid>,
+    ) -> Option<&mut Box<dyn TheWidget>> {
+        if self
+            .editor
+            .as_ref()
+            .is_some_and(|(editor, ..)| editor.id().matches(name, uuid))
+        {
+            return self.editor.as_mut().map(|(editor, ..)| editor);
+        }
+
+        if self.vertical_scrollbar_visible && self.vertical_scrollbar.id().matches(name, uuid) {
+            return Some(&mut self.vertical_scrollbar);
+        }
+
+        if self.view.id().matches(name, uuid) {
+            return Some(&mut self.view);
+        }
+        None
+    }
+
+    fn needs_redraw(&mut self) -> bool {
+        if self.vertical_scrollbar_visible && self.vertical_scrollbar.needs_redraw() {
+            return true;
+        }
+
+        if let Some((editor, ..)) = &mut self.editor {
+            if editor.needs_redraw() {
+                return true;
+            }
+        }
+
+        self.view.needs_redraw() || self.is_dirty
+    }
+
+    fn dim(&self) -> &TheDim {
+        &self.dim
+    }
+
+    fn dim_mut(&mut self) -> &mut TheDim {
+        &mut self.dim
+    }
+
+    fn set_dim(&mut self, dim: TheDim, ctx: &mut TheContext) {
+        if self.dim != dim || ctx.ui.relayout {
+            self.dim = dim;
+            self.place(ctx);
+        }
+    }
+
+    fn limiter(&self) -> &TheSizeLimiter {
+        &self.limiter
+    }
+
+    fn limiter_mut(&mut self) -> &mut TheSizeLimiter {
+        &mut self.limiter
+    }
+
+    fn redirected_widget_value(
+        &mut self,
+        widget_id: &TheId,
+        _value: &TheValue,
+        ctx: &mut TheContext,
+    ) {
+        if self
+            .editor
+            .as_ref()
+            .is_some_and(|(editor, ..)| editor.id() == widget_id)
+        {
+            self.end_edit(true, ctx);
+        }
+    }
+
+    fn draw(
+        &mut self,
+        buffer: &mut TheRGBABuffer,
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+    ) {
+        if !self.dim().is_valid() {
+            return;
+        }
+
+        // Editors commit their value when they lose the focus, e.g. by clicking elsewhere.
+        if self.editor.as_ref().is_some_and(|(editor, ..)| {
+            !ctx.ui.has_focus(editor.id()) && !editor.id().equals(&ctx.ui.overlay)
+        }) {
+            self.end_edit(true, ctx);
+        }
+
+        self.sync_scroll_offset();
+        self.place_editor(ctx);
+
+        self.view.draw(buffer, style, ctx);
+
+        if self.vertical_scrollbar_visible {
+            self.vertical_scrollbar.draw(buffer, style, ctx);
+        }
+
+        if let Some((editor, ..)) = &mut self.editor {
+            editor.draw(buffer, style, ctx);
+        }
+
+        self.is_dirty = false;
+    }
+
+    /// Convert to the table layout trait
+    fn as_table_layout(&mut self) -> Option<&mut dyn TheTableLayoutTrait> {
+        Some(self)
+    }
+}
+
+impl TheTableLayout {
+    fn view(&mut self) -> &mut TheTableView {
+        self.view
+            .as_any()
+            .downcast_mut::<TheTableView>()
+            .expect("the view of a table layout is a TheTableView")
+    }
+
+    /// Places the view and the scrollbar.
+    fn place(&mut self, ctx: &mut TheContext) {
+        let dim = self.dim;
+        let body_height = (dim.height - TABLE_HEADER_HEIGHT).max(0);
+        let view = self.view();
+        let total_height = (view.rows.len() as i32 * view.row_height).max(body_height);
+
+        self.vertical_scrollbar.set_dim(
+            TheDim::new(
+                dim.x + dim.width - 13,
+                dim.y + TABLE_HEADER_HEIGHT,
+                13,
+                body_height,
+            ),
+            ctx,
+        );
+        self.vertical_scrollbar.dim_mut().set_buffer_offset(
+            dim.buffer_x + dim.width - 13,
+            dim.buffer_y + TABLE_HEADER_HEIGHT,
+        );
+
+        if let Some(scroll_bar) = self.vertical_scrollbar.as_vertical_scrollbar() {
+            scroll_bar.set_total_height(total_height);
+            self.vertical_scrollbar_visible = scroll_bar.needs_scrollbar();
+        }
+
+        let mut width = dim.width;
+        if self.vertical_scrollbar_visible {
+            width -= 13;
+        }
+        self.view
+            .set_dim(TheDim::new(dim.x, dim.y, width, dim.height), ctx);
+        self.view
+            .dim_mut()
+            .set_buffer_offset(dim.buffer_x, dim.buffer_y);
+        self.view.set_needs_redraw(true);
+        self.is_dirty = true;
+    }
+
+    /// Passes the scroll offset to the view and scrolls the row it asks for into view.
+    fn sync_scroll_offset(&mut self) {
+        let body_height = self.dim.height - TABLE_HEADER_HEIGHT;
+        let view = self.view();
+        let reveal = view.reveal.take().map(|row| {
+            let top = row as i32 * view.row_height;
+            (top, top + view.row_height)
+        });
+
+        let mut offset = 0;
+        if self.vertical_scrollbar_visible {
+            if let Some(scroll_bar) = self.vertical_scrollbar.as_vertical_scrollbar() {
+                if let Some((top, bottom)) = reveal {
+                    let offset = scroll_bar.scroll_offset();
+                    if top < offset {
+                        scroll_bar.scroll_by(top - offset);
+                    } else if bottom > offset + body_height {
+                        scroll_bar.scroll_by(bottom - offset - body_height);
+                    }
+                }
+                offset = scroll_bar.scroll_offset();
+            }
+        }
+
+        let view = self.view();
+        if view.scroll_offset != offset {
+            view.scroll_offset = offset;
+            view.set_needs_redraw(true);
+        }
+    }
+
+    /// Places the editor over its cell, the edit ends if the cell is not fully visible.
+    fn place_editor(&mut self, ctx: &mut TheContext) {
+        let Some((row, column)) = self.edited_cell() else {
+            return;
+        };
+
+        let view = self.view();
+        let view_dim = *view.dim();
+        let cell = view.cell_rect(row, column).filter(|cell| {
+            cell.y >= TABLE_HEADER_HEIGHT
+                && cell.y + cell.height <= view_dim.height
+                && cell.x + cell.width <= view_dim.width
+        });
+        let Some(cell) = cell else {
+            self.end_edit(true, ctx);
+            return;
+        };
+
+        if let Some((editor, ..)) = &mut self.editor {
+            // Widgets smaller than the cell, like check buttons, are centered.
+            let width = editor.limiter().get_max_width().min(cell.width);
+            let height = editor.limiter().get_max_height().min(cell.height);
+            let x = cell.x + (cell.width - width) / 2;
+            let y = cell.y + (cell.height - height) / 2;
+            editor.set_dim(
+                TheDim::new(view_dim.x + x, view_dim.y + y, width, height),
+                ctx,
+            );
+            editor
+                .dim_mut()
+                .set_buffer_offset(view_dim.buffer_x + x, view_dim.buffer_y + y);
+        }
+    }
+}
+
+/// TheTableLayout specific functions.
+pub trait TheTableLayoutTrait: TheLayout {
+    /// Sets the columns, the display order is reset to the given order.
+    fn set_columns(&mut self, columns: Vec<TheTableColumn>, ctx: &mut TheContext);
+    /// Returns the columns with their current widths.
+    fn columns(&mut self) -> Vec<TheTableColumn>;
+    /// Returns the indices of the columns in display order.
+    fn column_order(&mut self) -> Vec<usize>;
+    /// Sets the display order of the columns, columns missing in the order are hidden.
+    fn set_column_order(&mut self, order: Vec<usize>);
+    /// Sets the rows, each with a value for every column.
+    fn set_rows(&mut self, rows: Vec<Vec<TheValue>>, ctx: &mut TheContext);
+    /// Sets the data source the rows are read from.
+    fn set_data_source(&mut self, source: Box<dyn TheTableDataSource>, ctx: &mut TheContext);
+    /// The rows of the data source changed, reads and sorts them again.
+    fn reload_data(&mut self, ctx: &mut TheContext);
+    /// The number of rows.
+    fn row_count(&mut self) -> usize;
+    /// Returns the value of a cell of the given row of the data source.
+    fn cell(&mut self, row: usize, column: usize) -> TheValue;
+    /// Sorts the rows by the given column, ascending or descending. None shows the rows in the
+    /// order of the data source.
+    fn sort_by(&mut self, column: Option<usize>, ascending: bool);
+    /// Returns the sort column and if the order is ascending.
+    fn sort_column(&mut self) -> Option<(usize, bool)>;
+    /// Returns the rows of the data source in display order.
+    fn display_rows(&mut self) -> Vec<usize>;
+    /// Selects the given row of the data source and scrolls it into view.
+    fn select_row(&mut self, row: Option<usize>);
+    /// Returns the selected row of the data source.
+    fn selected_row(&mut self) -> Option<usize>;
+    /// Sets the height of the rows.
+    fn set_row_height(&mut self, height: i32, ctx: &mut TheContext);
+    /// Edits the given cell of an editable column with a TheTextLineEdit, TheCheckButton or
+    /// TheDropdownMenu depending on the column type. A running edit is committed first.
+    fn edit_cell(&mut self, row: usize, column: usize, ctx: &mut TheContext) -> bool;
+    /// Ends the edit. If committed and the value of the editor differs, the data source is
+    /// updated and TheEvent::TableCellEdited is sent.
+    fn end_edit(&mut self, commit: bool, ctx: &mut TheContext);
+    /// Returns the edited cell (row of the data source, column).
+    fn edited_cell(&self) -> Option<(usize, usize)>;
+    /// Returns the id of the widget editing a cell.
+    fn editor_id(&self) -> Option<&TheId>;
+}
+
+impl TheTableLayoutTrait for TheTableLayout {
+    fn set_columns(&mut self, columns: Vec<TheTableColumn>, ctx: &mut TheContext) {
+        self.end_edit(false, ctx);
+        let view = self.view();
+        view.column_order = (0..columns.len()).collect();
+        view.columns = columns;
+        if view
+            .sort
+            .is_some_and(|(column, _)| column >= view.columns.len())
+        {
+            view.sort = None;
+        }
+        view.sort_rows();
+        self.is_dirty = true;
+    }
+
+    fn columns(&mut self) -> Vec<TheTableColumn> {
+        self.view().columns.clone()
+    }
+
+    fn column_order(&mut self) -> Vec<usize> {
+        self.view().column_order.clone()
+    }
+
+    fn set_column_order(&mut self, order: Vec<usize>) {
+        let view = self.view();
+        let count = view.columns.len();
+        view.column_order = order.into_iter().filter(|column| *column < count).collect();
+        view.set_needs_redraw(true);
+    }
+
+    fn set_rows(&mut self, rows: Vec<Vec<TheValue>>, ctx: &mut TheContext) {
+        self.set_data_source(Box::new(TheTableRows { rows }), ctx);
+    }
+
+    fn set_data_source(&mut self, source: Box<dyn TheTableDataSource>, ctx: &mut TheContext) {
+        self.end_edit(false, ctx);
+        let view = self.view();
+        view.source = source;
+        view.selected = None;
+        view.sort_rows();
+        self.place(ctx);
+    }
+
+    fn reload_data(&mut self, ctx: &mut TheContext) {
+        self.end_edit(false, ctx);
+        self.view().sort_rows();
+        self.place(ctx);
+    }
+
+    fn row_count(&mut self) -> usize {
+        self.view().rows.len()
+    }
+
+    fn cell(&mut self, row: usize, column: usize) -> TheValue {
+        self.view().source.cell(row, column)
+    }
+
+    fn sort_by(&mut self, column: Option<usize>, ascending: bool) {
+        let view = self.view();
+        view.sort = column
+            .filter(|column| *column < view.columns.len())
+            .map(|column| (column, ascending));
+        view.sort_rows();
+    }
+
+    fn sort_column(&mut self) -> Option<(usize, bool)> {
+        self.view().sort
+    }
+
+    fn display_rows(&mut self) -> Vec<usize> {
+        self.view().rows.clone()
+    }
+
+    fn select_row(&mut self, row: Option<usize>) {
+        let view = self.view();
+        view.selected = row.filter(|row| *row < view.rows.len());
+        view.reveal = view.selected.and_then(|row| view.display_row(row));
+        view.set_needs_redraw(true);
+    }
+
+    fn selected_row(&mut self) -> Option<usize> {
+        self.view().selected
+    }
+
+    fn set_row_height(&mut self, height: i32, ctx: &mut TheContext) {
+        self.view().row_height = height.max(1);
+        self.place(ctx);
+    }
+
+    fn edit_cell(&mut self, row: usize, column: usize, ctx: &mut TheContext) -> bool {
+        self.end_edit(true, ctx);
+
+        let view = self.view();
+        let Some(column_type) = view
+            .columns
+            .get(column)
+            .filter(|column| column.editable)
+            .map(|column| column.column_type)
+        else {
+            return false;
+        };
+        if row >= view.rows.len() || !view.column_order.contains(&column) {
+            return false;
+        }
+        let value = view.source.cell(row, column);
+        view.selected = Some(row);
+        view.reveal = view.display_row(row);
+        view.set_needs_redraw(true);
+
+        let id = TheId::named_with_reference("Table Cell Editor", self.id.uuid);
+        let editor: Box<dyn TheWidget> = match column_type {
+            TheTableColumnType::Text | TheTableColumnType::Int | TheTableColumnType::Float => {
+                let mut edit = TheTextLineEdit::new(id);
+                edit.set_text(cell_text(&value));
+                edit.select_all();
+                Box::new(edit)
+            }
+            TheTableColumnType::Bool => {
+                let mut button = TheCheckButton::new(id);
+                button.set_value(value);
+                Box::new(button)
+            }
+            TheTableColumnType::Choice => {
+                let mut menu = TheDropdownMenu::new(id);
+                if let TheValue::TextList(index, options) = value {
+                    for option in options {
+                        menu.add_option(option);
+                    }
+                    menu.set_selected_index(index);
+                }
+                Box::new(menu)
+            }
+        };
+
+        ctx.ui.set_focus(editor.id());
+        self.editor = Some((editor, row, column));
+        self.sync_scroll_offset();
+        self.place_editor(ctx);
+        self.is_dirty = true;
+        self.editor.is_some()
+    }
+
+    fn end_edit(&mut self, commit: bool, ctx: &mut TheContext) {
+        let Some((editor, row, column)) = self.editor.take() else {
+            return;
+        };
+
+        let layout_id = self.id.clone();
+        let view = self.view();
+        if commit && row < view.rows.len() {
+            let current = view.source.cell(row, column);
+            let value = view.columns[column]
+                .column_type
+                .convert(&editor.value(), &current);
+            if let Some(value) = value.filter(|value| *value != current) {
+                view.source.set_cell(row, column, value.clone());
+                if view
+                    .sort
+                    .is_some_and(|(sort_column, _)| sort_column == column)
+                {
+                    view.sort_rows();
+                    view.reveal = view.display_row(row);
+                }
+                ctx.ui
+                    .send(TheEvent::TableCellEdited(layout_id, row, column, value));
+            }
+        }
+        view.set_needs_redraw(true);
+
+        // The table takes the focus back unless it moved on to another widget.
+        if ctx.ui.focus.is_none() || ctx.ui.has_focus(editor.id()) {
+            let view_id = view.id().clone();
+            ctx.ui.set_focus(&view_id);
+        }
+        self.is_dirty = true;
+    }
+
+    fn edited_cell(&self) -> Option<(usize, usize)> {
+        self.editor.as_ref().map(|(_, row, column)| (*row, *column))
+    }
+
+    fn editor_id(&self) -> Option<&TheId> {
+        self.editor.as_ref().map(|(editor, ..)| editor.id())
+    }
+}
@@ -0,0 +1,718 @@
+use crate::prelude::*;
+use crate::theui::thelayout::thetablelayout::TheTableRows;
+use std::cmp::Ordering;
+
+/// The height of the header of a table.
+pub(crate) const TABLE_HEADER_HEIGHT: i32 = 22;
+/// Dragging a header this close to the border of a column resizes the column.
+const TABLE_RESIZE_MARGIN: i32 = 3;
+/// The distance a header has to be dragged before its column moves.
+const TABLE_MOVE_THRESHOLD: i32 = 5;
+const TABLE_CELL_PADDING: i32 = 5;
+
+/// A drag on the header of a table.
+#[derive(Clone, Copy, Debug)]
+enum TheTableHeaderDrag {
+    /// Resizes the column, started at x with the given width.
+    Resize {
+        column: usize,
+        start_x: i32,
+        start_width: i32,
+    },
+    /// The header of the column was pressed, a click sorts and a drag moves the column.
+    Press { column: usize, start_x: i32 },
+    /// Moves the column, the pointer is at x.
+    Move { column: usize, x: i32 },
+}
+
+/// Draws the header and the visible rows of TheTableLayout and handles the input of the table.
+/// The layout owns the view, the scrollbar and the widget editing a cell.
+pub(crate) struct TheTableView {
+    id: TheId,
+    limiter: TheSizeLimiter,
+    dim: TheDim,
+    is_dirty: bool,
+
+    pub layout_id: TheId,
+
+    pub columns: Vec<TheTableColumn>,
+    /// The columns in display order.
+    pub column_order: Vec<usize>,
+    pub source: Box<dyn TheTableDataSource>,
+    /// The rows of the data source in display order.
+    pub rows: Vec<usize>,
+    /// The column the rows are sorted by and if the order is ascending.
+    pub sort: Option<(usize, bool)>,
+    /// The selected row of the data source.
+    pub selected: Option<usize>,
+    pub row_height: i32,
+    pub scroll_offset: i32,
+    /// A row (in display order) the layout scrolls into view.
+    pub reveal: Option<usize>,
+
+    rows_buffer: TheRGBABuffer,
+    header_drag: Option<TheTableHeaderDrag>,
+    resize_hover: bool,
+}
+
+impl TheWidget for TheTableView {
+    fn new(id: TheId) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            id,
+            limiter: TheSizeLimiter::new(),
+            dim: TheDim::zero(),
+            is_dirty: true,
+
+            layout_id: TheId::empty(),
+
+            columns: vec![],
+            column_order: vec![],
+            source: Box::<TheTableRows>::default(),
+            rows: vec![],
+            sort: None,
+            selected: None,
+            row_height: 20,
+            scroll_offset: 0,
+            reveal: None,
+
+            rows_buffer: TheRGBABuffer::empty(),
+            header_drag: None,
+            resize_hover: false,
+        }
+    }
+
+    fn id(&self) -> &TheId {
+        &self.id
+    }
+
+    fn dim(&self) -> &TheDim {
+        &self.dim
+    }
+
+    fn dim_mut(&mut self) -> &mut TheDim {
+        &mut self.dim
+    }
+
+    fn set_dim(&mut self, dim: TheDim, _ctx: &mut TheContext) {
+        if self.dim != dim {
+            self.dim = dim;
+            self.is_dirty = true;
+        }
+    }
+
+    fn limiter(&self) -> &TheSizeLimiter {
+        &self.limiter
+    }
+
+    fn limiter_mut(&mut self) -> &mut TheSizeLimiter {
+        &mut self.limiter
+    }
+
+    fn needs_redraw(&mut self) -> bool {
+        self.is_dirty
+    }
+
+    fn set_needs_redraw(&mut self, redraw: bool) {
+        self.is_dirty = redraw;
+    }
+
+    fn supports_hover(&mut self) -> bool {
+        true
+    }
+
+    fn cursor_icon(&self) -> Option<TheCursorIcon> {
+        let resizing = matches!(self.header_drag, Some(TheTableHeaderDrag::Resize { .. }));
+        if self.resize_hover || resizing {
+            Some(TheCursorIcon::EWResize)
+        } else {
+            None
+        }
+    }
+
+    fn on_event(&mut self, event: &TheEvent, ctx: &mut TheContext) -> bool {
+        let mut redraw = false;
+        match event {
+            TheEvent::MouseDown(coord) => {
+                ctx.ui.set_focus(self.id());
+                if coord.y < TABLE_HEADER_HEIGHT {
+                    if let Some(column) = self.border_at(coord.x) {
+                        self.header_drag = Some(TheTableHeaderDrag::Resize {
+                            column,
+                            start_x: coord.x,
+                            start_width: self.columns[column].width,
+                        });
+                    } else if let Some(column) = self.column_at(coord.x) {
+                        self.header_drag = Some(TheTableHeaderDrag::Press {
+                            column,
+                            start_x: coord.x,
+                        });
+                    }
+                } else if let Some(row) = self.row_at(coord.y) {
+                    let source_row = self.rows[row];
+                    if self.selected == Some(source_row) {
+                        // Clicks into the selected row edit the cell.
+                        if let Some(column) = self
+                            .column_at(coord.x)
+                            .filter(|column| self.columns[*column].editable)
+                        {
+                            ctx.ui.send(TheEvent::TableEditCell(
+                                self.layout_id.clone(),
+                                source_row,
+                                column,
+                            ));
+                        }
+                    } else {
+                        self.select_display_row(row, ctx);
+                    }
+                }
+                self.is_dirty = true;
+                redraw = true;
+            }
+            TheEvent::MouseDragged(coord) => match self.header_drag {
+                Some(TheTableHeaderDrag::Resize {
+                    column,
+                    start_x,
+                    start_width,
+                }) => {
+                    let min_width = self.columns[column].min_width;
+                    self.columns[column].width = (start_width + coord.x - start_x).max(min_width);
+                    self.is_dirty = true;
+                    redraw = true;
+                }
+                Some(TheTableHeaderDrag::Press { column, start_x })
+                    if (coord.x - start_x).abs() >= TABLE_MOVE_THRESHOLD =>
+                {
+                    self.header_drag = Some(TheTableHeaderDrag::Move { column, x: coord.x });
+                    self.is_dirty = true;
+                    redraw = true;
+                }
+                Some(TheTableHeaderDrag::Move { column, .. }) => {
+                    self.header_drag = Some(TheTableHeaderDrag::Move { column, x: coord.x });
+                    self.is_dirty = true;
+                    redraw = true;
+                }
+                _ => {}
+            },
+            TheEvent::MouseUp(_coord) => {
+                match self.header_drag.take() {
+                    Some(TheTableHeaderDrag::Press { column, .. })
+                        if self.columns[column].sortable =>
+                    {
+                        let ascending = self.sort != Some((column, true));
+                        self.sort = Some((column, ascending));
+                        self.sort_rows();
+                    }
+                    Some(TheTableHeaderDrag::Move { column, x }) => {
+                        let index = self.insertion_index(column, x);
+                        self.column_order.retain(|c| *c != column);
+                        self.column_order.insert(index, column);
+                    }
+                    _ => {}
+                }
+                self.is_dirty = true;
+                redraw = true;
+            }
+            TheEvent::Hover(coord) => {
+                let resize_hover =
+                    coord.y < TABLE_HEADER_HEIGHT && self.border_at(coord.x).is_some();
+                if resize_hover != self.resize_hover {
+                    self.resize_hover = resize_hover;
+                    redraw = true;
+                }
+            }
+            TheEvent::KeyCodeDown(key) => match key.to_key_code() {
+                Some(TheKeyCode::Up) | Some(TheKeyCode::Down) if !self.rows.is_empty() => {
+                    let current = self.selected.and_then(|row| self.display_row(row));
+                    let row = match (current, key.to_key_code()) {
+                        (Some(row), Some(TheKeyCode::Up)) => row.saturating_sub(1),
+                        (Some(row), _) => (row + 1).min(self.rows.len() - 1),
+                        (None, _) => 0,
+                    };
+                    if current != Some(row) {
+                        self.select_display_row(row, ctx);
+                        self.is_dirty = true;
+                        redraw = true;
+                    }
+                }
+                Some(TheKeyCode::Return) => {
+                    // Edits the first editable cell of the selected row.
+                    let column = self
+                        .column_order
+                        .iter()
+                        .find(|column| self.columns[**column].editable);
+                    if let (Some(row), Some(column)) = (self.selected, column) {
+                        ctx.ui.send(TheEvent::TableEditCell(
+                            self.layout_id.clone(),
+                            row,
+                            *column,
+                        ));
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        redraw
+    }
+
+    fn draw(
+        &mut self,
+        buffer: &mut TheRGBABuffer,
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+    ) {
+        if !self.dim().is_valid() {
+            return;
+        }
+
+        let stride = buffer.stride();
+        let (x, y) = (self.dim.buffer_x, self.dim.buffer_y);
+        let (width, height) = (self.dim.width, self.dim.height);
+        let columns = self.column_positions();
+
+        // The visible rows are drawn into the rows buffer, which starts at the first visible row.
+        let body_height = height - TABLE_HEADER_HEIGHT;
+        if body_height > 0 {
+            let first = (self.scroll_offset / self.row_height) as usize;
+            let count = (body_height / self.row_height + 2) as usize;
+            let buffer_height = count as i32 * self.row_height;
+            self.rows_buffer.set_scale(ctx.scale_factor);
+            self.rows_buffer
+                .set_dim(TheDim::new(0, 0, width, buffer_height));
+
+            let rows_stride = self.rows_buffer.stride();
+            let pixels = self.rows_buffer.pixels_mut();
+            ctx.draw.rect(
+                pixels,
+                &(0, 0, width as usize, buffer_height as usize),
+                rows_stride,
+                style.theme().color(ListLayoutBackground),
+            );
+
+            let selected_color = if ctx.ui.has_focus(&self.id) {
+                *style.theme().color(ListItemSelected)
+            } else {
+                *style.theme().color(ListItemSelectedNoFocus)
+            };
+            let text_color = *style.theme().color(ListItemText);
+            let separator_color = *style.theme().color(ListLayoutBackground);
+
+            for (index, row) in self.rows.iter().enumerate().skip(first).take(count) {
+                let row_y = ((index - first) as i32 * self.row_height) as usize;
+                let color = if self.selected == Some(*row) {
+                    selected_color
+                } else {
+                    *style.theme().color(ListItemNormal)
+                };
+                let row_height = (self.row_height - 1) as usize;
+                ctx.draw.rect(
+                    pixels,
+                    &(0, row_y, width as usize, row_height),
+                    rows_stride,
+                    &color,
+                );
+
+                for (column, column_x, column_width) in &columns {
+                    if *column_x >= width {
+                        break;
+                    }
+                    let column_width = (*column_width).min(width - column_x);
+                    let rect = (*column_x as usize, row_y, column_width as usize, row_height);
+                    draw_cell(
+                        &ctx.draw,
+                        pixels,
+                        rows_stride,
+                        &rect,
+                        self.columns[*column].column_type,
+                        &self.source.cell(*row, *column),
+                        &text_color,
+                    );
+                    ctx.draw.rect(
+                        pixels,
+                        &(rect.0 + rect.2 - 1, row_y, 1, row_height),
+                        rows_stride,
+                        &separator_color,
+                    );
+                }
+            }
+
+            let start = self.scroll_offset - first as i32 * self.row_height;
+            buffer.copy_vertical_range_into(
+                x,
+                y + TABLE_HEADER_HEIGHT,
+                &self.rows_buffer,
+                start..start + body_height,
+            );
+        }
+
+        // Header
+        let header_height = TABLE_HEADER_HEIGHT.min(height) as usize;
+        let (ux, uy) = (x as usize, y as usize);
+        ctx.draw.rect(
+            buffer.pixels_mut(),
+            &(ux, uy, width as usize, header_height),
+            stride,
+            style.theme().color(SectionbarBackground),
+        );
+
+        let text_color = *style.theme().color(SectionbarNormalTextColor);
+        let border_color = *style.theme().color(SectionbarHeaderBorder);
+        for (column, column_x, column_width) in &columns {
+            if *column_x >= width {
+                break;
+            }
+            let column_width = (*column_width).min(width - column_x);
+            let mut text_width = column_width - 2 * TABLE_CELL_PADDING;
+
+            if let Some((sort_column, ascending)) = self.sort {
+                if sort_column == *column && text_width > 12 {
+                    // A small triangle pointing up or down.
+                    let arrow_x = ux + (column_x + column_width - TABLE_CELL_PADDING - 7) as usize;
+                    let arrow_y = uy + header_height / 2 - 2;
+                    for line in 0..4 {
+                        let top = if ascending { line } else { 3 - line };
+                        ctx.draw.rect(
+                            buffer.pixels_mut(),
+                            &(arrow_x + 3 - line, arrow_y + top, 2 * line + 1, 1),
+                            stride,
+                            &text_color,
+                        );
+                    }
+                    text_width -= 12;
+                }
+            }
+
+            if text_width > 16 {
+                ctx.draw.text_rect_blend(
+                    buffer.pixels_mut(),
+                    &(
+                        ux + (column_x + TABLE_CELL_PADDING) as usize,
+                        uy,
+                        text_width as usize,
+                        header_height,
+                    ),
+                    stride,
+                    &self.columns[*column].name,
+                    TheFontSettings {
+                        size: 12.5,
+                        ..Default::default()
+                    },
+                    &text_color,
+                    TheHorizontalAlign::Left,
+                    TheVerticalAlign::Center,
+                );
+            }
+
+            ctx.draw.rect(
+                buffer.pixels_mut(),
+                &(
+                    ux + (column_x + column_width - 1) as usize,
+                    uy + 3,
+                    1,
+                    header_height.saturating_sub(6),
+                ),
+                stride,
+                &border_color,
+            );
+        }
+        ctx.draw.rect(
+            buffer.pixels_mut(),
+            &(ux, uy + header_height - 1, width as usize, 1),
+            stride,
+            &border_color,
+        );
+
+        // The position a moved column is dropped at.
+        if let Some(TheTableHeaderDrag::Move { column, x: pointer }) = self.header_drag {
+            let index = self.insertion_index(column, pointer);
+            let line_x = columns
+                .iter()
+                .filter(|(c, ..)| *c != column)
+                .map(|(_, _, w)| *w)
+                .take(index)
+                .sum::<i32>();
+            let line_x = (line_x - 1).clamp(0, (width - 2).max(0));
+            ctx.draw.rect(
+                buffer.pixels_mut(),
+                &(ux + line_x as usize, uy, 2, height as usize),
+                stride,
+                style.theme().color(DefaultSelection),
+            );
+        }
+
+        self.is_dirty = false;
+    }
+
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::Table)
+    }
+
+    fn as_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl TheTableView {
+    /// The columns in display order with their position and width.
+    pub fn column_positions(&self) -> Vec<(usize, i32, i32)> {
+        let mut x = 0;
+        self.column_order
+            .iter()
+            .map(|column| {
+                let width = self.columns[*column].width;
+                x += width;
+                (*column, x - width, width)
+            })
+            .collect()
+    }
+
+    /// The column at the given x position.
+    fn column_at(&self, x: i32) -> Option<usize> {
+        self.column_positions()
+            .into_iter()
+            .find(|(_, column_x, width)| x >= *column_x && x < column_x + width)
+            .map(|(column, ..)| column)
+    }
+
+    /// The column whose right border is at the given x position.
+    fn border_at(&self, x: i32) -> Option<usize> {
+        self.column_positions()
+            .into_iter()
+            .find(|(_, column_x, width)| (x - (column_x + width)).abs() <= TABLE_RESIZE_MARGIN)
+            .map(|(column, ..)| column)
+    }
+
+    /// The position in the column order a column dragged to x is moved to.
+    fn insertion_index(&self, column: usize, x: i32) -> usize {
+        let mut index = 0;
+        let mut column_x = 0;
+        for other in &self.column_order {
+            if *other == column {
+                continue;
+            }
+            let width = self.columns[*other].width;
+            if column_x + width / 2 < x {
+                index += 1;
+            }
+            column_x += width;
+        }
+        index
+    }
+
+    /// The row (in display order) at the given y position.
+    fn row_at(&self, y: i32) -> Option<usize> {
+        if y < TABLE_HEADER_HEIGHT {
+            return None;
+        }
+        let row = ((y - TABLE_HEADER_HEIGHT + self.scroll_offset) / self.row_height) as usize;
+        (row < self.rows.len()).then_some(row)
+    }
+
+    /// The display position of the given row of the data source.
+    pub fn display_row(&self, row: usize) -> Option<usize> {
+        self.rows.iter().position(|r| *r == row)
+    }
+
+    /// The rectangle of a cell relative to the view.
+    pub fn cell_rect(&self, row: usize, column: usize) -> Option<TheDim> {
+        let display_row = self.display_row(row)?;
+        let (_, x, width) = self
+            .column_positions()
+            .into_iter()
+            .find(|(c, ..)| *c == column)?;
+        let y = TABLE_HEADER_HEIGHT + display_row as i32 * self.row_height - self.scroll_offset;
+        Some(TheDim::new(x, y, width, self.row_height))
+    }
+
+    /// Selects the row at the given display position and sends TheEvent::TableRowSelected.
+    fn select_display_row(&mut self, row: usize, ctx: &mut TheContext) {
+        let source_row = self.rows[row];
+        self.selected = Some(source_row);
+        self.reveal = Some(row);
+        ctx.ui.send(TheEvent::TableRowSelected(
+            self.layout_id.clone(),
+            source_row,
+        ));
+    }
+
+    /// Reads the rows of the data source and sorts them by the sort column.
+    pub fn sort_rows(&mut self) {
+        let count = self.source.row_count();
+        self.rows = (0..count).collect();
+        if self.selected.is_some_and(|row| row >= count) {
+            self.selected = None;
+        }
+
+        if let Some((column, ascending)) = self.sort {
+            let Some(column_type) = self.columns.get(column).map(|c| c.column_type) else {
+                return;
+            };
+            let keys: Vec<TheValue> = (0..count)
+                .map(|row| sort_key(column_type, self.source.cell(row, column)))
+                .collect();
+            // The sort is stable, rows with equal values keep their order.
+            self.rows.sort_by(|a, b| {
+                let ordering = compare(column_type, &keys[*a], &keys[*b]);
+                if ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+        self.is_dirty = true;
+    }
+
+    /// The header and the visible rows with their cells for accessibility.
+    #[allow(clippy::type_complexity)]
+    pub fn access_rows(&self) -> Vec<(TheAccessInfo, TheDim, Vec<(TheAccessInfo, TheDim)>)> {
+        let columns = self.column_positions();
+        let cell_dim = |x: i32, y: i32, width: i32, height: i32| {
+            TheDim::new(self.dim.x + x, self.dim.y + y, width, height)
+        };
+
+        let headers = columns
+            .iter()
+            .map(|(column, x, width)| {
+                (
+                    TheAccessInfo::named(TheAccessRole::ColumnHeader, &self.columns[*column].name),
+                    cell_dim(*x, 0, *width, TABLE_HEADER_HEIGHT),
+                )
+            })
+            .collect();
+        let mut rows = vec![(
+            TheAccessInfo::new(TheAccessRole::Row),
+            cell_dim(0, 0, self.dim.width, TABLE_HEADER_HEIGHT),
+            headers,
+        )];
+
+        let body_height = self.dim.height - TABLE_HEADER_HEIGHT;
+        if body_height <= 0 {
+            return rows;
+        }
+        let first = (self.scroll_offset / self.row_height) as usize;
+        let count = ((body_height + self.row_height - 1) / self.row_height + 1) as usize;
+        for (index, row) in self.rows.iter().enumerate().skip(first).take(count) {
+            let y = TABLE_HEADER_HEIGHT + index as i32 * self.row_height - self.scroll_offset;
+            let cells = columns
+                .iter()
+                .map(|(column, x, width)| {
+                    let value = self.source.cell(*row, *column);
+                    let checked = match value {
+                        TheValue::Bool(checked) => Some(checked),
+                        _ => None,
+                    };
+                    (
+                        TheAccessInfo {
+                            checked,
+                            ..TheAccessInfo::named(TheAccessRole::Cell, &cell_text(&value))
+                        },
+                        cell_dim(*x, y, *width, self.row_height),
+                    )
+                })
+                .collect();
+            rows.push((
+                TheAccessInfo {
+                    selected: Some(self.selected == Some(*row)),
+                    ..TheAccessInfo::new(TheAccessRole::Row)
+                },
+                cell_dim(0, y, self.dim.width, self.row_height),
+                cells,
+            ));
+        }
+        rows
+    }
+}
+
+/// The text shown for a cell value.
+pub(crate) fn cell_text(value: &TheValue) -> String {
+    match value {
+        TheValue::Empty => String::new(),
+        TheValue::TextList(index, options) => {
+            options.get(*index as usize).cloned().unwrap_or_default()
+        }
+        _ => value.describe(),
+    }
+}
+
+/// Text is sorted case insensitively.
+fn sort_key(column_type: TheTableColumnType, value: TheValue) -> TheValue {
+    match column_type {
+        TheTableColumnType::Text => TheValue::Text(cell_text(&value).to_lowercase()),
+        _ => value,
+    }
+}
+
+fn compare(column_type: TheTableColumnType, a: &TheValue, b: &TheValue) -> Ordering {
+    match column_type {
+        TheTableColumnType::Text => cell_text(a).cmp(&cell_text(b)),
+        TheTableColumnType::Int | TheTableColumnType::Choice => a.to_i32().cmp(&b.to_i32()),
+        TheTableColumnType::Float => a
+            .to_f32()
+            .partial_cmp(&b.to_f32())
+            .unwrap_or(Ordering::Equal),
+        TheTableColumnType::Bool => {
+            matches!(a, TheValue::Bool(true)).cmp(&matches!(b, TheValue::Bool(true)))
+        }
+    }
+}
+
+/// Draws the value of a cell, numbers are aligned to the right and booleans drawn as a box.
+fn draw_cell(
+    draw: &TheDraw2D,
+    pixels: &mut [u8],
+    stride: usize,
+    rect: &(usize, usize, usize, usize),
+    column_type: TheTableColumnType,
+    value: &TheValue,
+    color: &RGBA,
+) {
+    let padding = TABLE_CELL_PADDING as usize;
+    match column_type {
+        TheTableColumnType::Bool => {
+            if rect.2 < 12 || rect.3 < 10 {
+                return;
+            }
+            let box_rect = (
+                rect.0 + (rect.2 - 10) / 2,
+                rect.1 + (rect.3 - 10) / 2,
+                10,
+                10,
+            );
+            draw.rect_outline(pixels, &box_rect, stride, color);
+            if matches!(value, TheValue::Bool(true)) {
+                draw.rect(
+                    pixels,
+                    &(box_rect.0 + 3, box_rect.1 + 3, 4, 4),
+                    stride,
+                    color,
+                );
+            }
+        }
+        _ => {
+            if rect.2 <= 2 * padding + 16 {
+                return;
+            }
+            let align = match column_type {
+                TheTableColumnType::Int | TheTableColumnType::Float => TheHorizontalAlign::Right,
+                _ => TheHorizontalAlign::Left,
+            };
+            draw.text_rect_blend(
+                pixels,
+                &(rect.0 + padding, rect.1, rect.2 - 2 * padding, rect.3),
+                stride,
+                &cell_text(value),
+                TheFontSettings {
+                    size: 12.5,
+                    ..Default::default()
+                },
+                color,
+                align,
+                TheVerticalAlign::Center,
+            );
+        }
+    }
+}
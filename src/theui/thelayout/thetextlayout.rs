@@ -82,6 +82,18 @@ impl TheLayout for TheTextLayout {
         &mut self.widgets
     }
 
+    fn collect_access_nodes(&mut self, tree: &mut TheAccessTree, parent: u64, ctx: &TheContext) {
+        let id = tree.add(parent, &self.id, self.accessibility(), self.dim, ctx);
+        for (text, widget) in self.text.iter().zip(&self.widgets) {
+            // The text of a pair is the label of its widget.
+            let mut info = widget.accessibility();
+            if info.name.is_none() && !text.is_empty() {
+                info.name = Some(text.clone());
+            }
+            tree.add(id, widget.id(), info, *widget.dim(), ctx);
+        }
+    }
+
     fn get_widget_at_coord(&mut self, coord: Vec2<i32>) -> Option<&mut Box<dyn TheWidget>> {
         if !self.dim.contains(coord) {
             return None;
@@ -267,6 +279,7 @@ impl TheLayout for TheTextLayout {
                 width -= 13;
             }
 
+            self.list_buffer.set_scale(ctx.scale_factor);
             self.list_buffer
                 .set_dim(TheDim::new(0, 0, width, total_height));
 
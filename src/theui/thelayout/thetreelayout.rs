@@ -1,14 +1,64 @@
 use crate::prelude::*;
+use crate::theui::thelayout::theselection::TheSelection;
+use std::collections::BTreeSet;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc,
 };
+use web_time::{Duration, Instant};
 
 const TREE_INDENT: i32 = 20;
 const TREE_VERTICAL_SPACING: i32 = 2;
 const TREE_RIGHT_MARGIN: i32 = 2;
 const TREE_BOTTOM_MARGIN: i32 = 2;
 
+/// How long a closed node has to be hovered while dragging before it opens.
+const TREE_DRAG_EXPAND_DELAY: Duration = Duration::from_millis(600);
+/// The distance from the top and bottom edges in which dragging scrolls the tree.
+const TREE_DRAG_SCROLL_MARGIN: i32 = 16;
+
+/// A move of an item or node of a TheTreeLayout by drag and drop, see
+/// TheEvent::TreeMoveRequested.
+#[derive(PartialEq, Clone, Debug)]
+pub struct TheTreeMove {
+    /// The moved item or node.
+    pub id: TheId,
+    /// True if a node is moved, false for an item.
+    pub node: bool,
+    /// The node the item or node is moved into.
+    pub parent: TheId,
+    /// The index in the items (or child nodes) of the parent, not counting the moved item.
+    pub index: usize,
+}
+
+/// Where a dragged item or node would be dropped.
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum TheTreeDropTarget {
+    Before(Uuid),
+    After(Uuid),
+    Into(Uuid),
+}
+
+/// A drag and drop move inside the tree.
+struct TheTreeDrag {
+    id: TheId,
+    node: bool,
+    /// The last pointer position.
+    coord: Vec2<i32>,
+    /// The drop position under the pointer and the resulting move.
+    target: Option<(TheTreeDropTarget, TheTreeMove)>,
+    /// The closed node under the pointer and since when it is hovered.
+    hovered: Option<(Uuid, Instant)>,
+}
+
+/// A shown node or item of the tree.
+#[derive(Clone, Copy)]
+struct TheTreeRow {
+    id: Uuid,
+    node: bool,
+    dim: TheDim,
+}
+
 pub struct TheTreeNode {
     pub id: TheId,
 
@@ -117,7 +167,11 @@ impl TheTreeNode {
         self.mark_layout_dirty();
     }
 
-    pub fn add_widget(&mut self, mut widget: Box<dyn TheWidget>) {
+    pub fn add_widget(&mut self, widget: Box<dyn TheWidget>) {
+        self.add_widget_at(self.widgets.len(), widget);
+    }
+
+    pub fn add_widget_at(&mut self, index: usize, mut widget: Box<dyn TheWidget>) {
         if let Some(layout_id) = &self.layout_id {
             if let Some(tree_item) = widget.as_tree_item() {
                 tree_item.set_associated_layout(layout_id.clone());
@@ -127,7 +181,7 @@ impl TheTreeNode {
                 tree_text.set_associated_layout(layout_id.clone());
             }
         }
-        self.widgets.push(widget);
+        self.widgets.insert(index, widget);
         self.mark_layout_dirty();
     }
 
@@ -182,6 +236,52 @@ impl TheTreeNode {
         false
     }
 
+    /// Selects the items of the given uuids in this node and its children, deselects all others.
+    pub fn set_selection(&mut self, selection: &FxHashSet<Uuid>) {
+        self.selected_widget = None;
+        for widget in &mut self.widgets {
+            let state = if selection.contains(&widget.id().uuid) {
+                if self.selected_widget.is_none() {
+                    self.selected_widget = Some(widget.id().uuid);
+                }
+                TheWidgetState::Selected
+            } else {
+                TheWidgetState::None
+            };
+            if widget.state() != state {
+                widget.set_state(state);
+            }
+        }
+        for child in &mut self.childs {
+            child.set_selection(selection);
+        }
+    }
+
+    /// Adds the ids of the selected items of this node and its children, in tree order.
+    pub fn collect_selected(&self, selected: &mut Vec<TheId>) {
+        for widget in &self.widgets {
+            if widget.state() == TheWidgetState::Selected {
+                selected.push(widget.id().clone());
+            }
+        }
+        for child in &self.childs {
+            child.collect_selected(selected);
+        }
+    }
+
+    /// Adds the ids of the items which are shown, the items of open nodes, in tree order.
+    fn collect_visible(&self, visible: &mut Vec<TheId>) {
+        if !self.open {
+            return;
+        }
+        for widget in &self.widgets {
+            visible.push(widget.id().clone());
+        }
+        for child in &self.childs {
+            child.collect_visible(visible);
+        }
+    }
+
     pub fn remove_child_by_uuid(&mut self, uuid: &Uuid) -> Option<TheTreeNode> {
         if let Some(index) = self.childs.iter().position(|child| child.id.uuid == *uuid) {
             self.mark_layout_dirty();
@@ -250,6 +350,49 @@ impl TheTreeNode {
         None
     }
 
+    /// Returns the node which holds the item or child node of the given uuid.
+    fn parent_of(&self, uuid: &Uuid) -> Option<&TheTreeNode> {
+        if self.widgets.iter().any(|widget| widget.id().uuid == *uuid)
+            || self.childs.iter().any(|child| child.id.uuid == *uuid)
+        {
+            return Some(self);
+        }
+        self.childs.iter().find_map(|child| child.parent_of(uuid))
+    }
+
+    /// Returns the shown node or item at the given vertical content position.
+    fn row_at(&self, y: i32, include_self: bool) -> Option<TheTreeRow> {
+        let contains = |dim: &TheDim| y >= dim.y && y < dim.y + dim.height;
+        if include_self && contains(self.widget.dim()) {
+            return Some(TheTreeRow {
+                id: self.id.uuid,
+                node: true,
+                dim: *self.widget.dim(),
+            });
+        }
+
+        if !self.open {
+            return None;
+        }
+
+        for widget in &self.widgets {
+            if contains(widget.dim()) {
+                return Some(TheTreeRow {
+                    id: widget.id().uuid,
+                    node: false,
+                    dim: *widget.dim(),
+                });
+            }
+        }
+
+        self.childs.iter().find_map(|child| child.row_at(y, true))
+    }
+
+    /// Returns true if the node has items or child nodes.
+    fn has_content(&self) -> bool {
+        !self.widgets.is_empty() || !self.childs.is_empty()
+    }
+
     pub fn node_state_changed(&mut self, id: TheId, open: bool) {
         self.node_state_changed_internal(&id, open);
     }
@@ -403,6 +546,36 @@ impl TheTreeNode {
         }
     }
 
+    /// Adds the accessibility nodes of the node, its items and its open child nodes.
+    fn collect_access_nodes(
+        &self,
+        tree: &mut TheAccessTree,
+        parent: u64,
+        ctx: &TheContext,
+        include_self: bool,
+    ) {
+        let parent = if include_self {
+            let mut info = self.widget.accessibility();
+            info.role = TheAccessRole::TreeItem;
+            info.expanded = Some(self.open);
+            tree.add(parent, self.widget.id(), info, *self.widget.dim(), ctx)
+        } else {
+            parent
+        };
+
+        if !self.open {
+            return;
+        }
+
+        for widget in &self.widgets {
+            tree.add_widget(parent, widget.as_ref(), ctx);
+        }
+
+        for child in &self.childs {
+            child.collect_access_nodes(tree, parent, ctx, true);
+        }
+    }
+
     fn find_widget_at_coord(
         &mut self,
         coord: Vec2<i32>,
@@ -494,6 +667,11 @@ pub struct TheTreeLayout {
     background: Option<TheThemeColors>,
     headerless: bool,
     layout_dirty_flag: Arc<AtomicBool>,
+
+    selection: TheSelection,
+
+    drag_and_drop: bool,
+    drag: Option<TheTreeDrag>,
 }
 
 impl TheLayout for TheTreeLayout {
@@ -525,6 +703,11 @@ impl TheLayout for TheTreeLayout {
             background: Some(TextLayoutBackground),
             headerless: true,
             layout_dirty_flag,
+
+            selection: TheSelection::default(),
+
+            drag_and_drop: false,
+            drag: None,
         }
     }
 
@@ -540,6 +723,16 @@ impl TheLayout for TheTreeLayout {
         &mut self.widgets
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::Tree)
+    }
+
+    fn collect_access_nodes(&mut self, tree: &mut TheAccessTree, parent: u64, ctx: &TheContext) {
+        let id = tree.add(parent, &self.id, self.accessibility(), self.dim, ctx);
+        self.root
+            .collect_access_nodes(tree, id, ctx, !self.headerless);
+    }
+
     fn supports_mouse_wheel(&self) -> bool {
         true
     }
@@ -702,6 +895,15 @@ impl TheLayout for TheTreeLayout {
         self.root
             .draw_recursive(&mut self.content_buffer, style, ctx, !self.headerless);
 
+        let target = self
+            .drag
+            .as_ref()
+            .and_then(|drag| drag.target.as_ref())
+            .map(|(target, _)| *target);
+        if let Some(target) = target {
+            self.draw_drop_target(target, style, ctx);
+        }
+
         if self.vertical_scrollbar_visible {
             if let Some(scroll_bar) = self.vertical_scrollbar.as_vertical_scrollbar() {
                 let offset = scroll_bar.scroll_offset();
@@ -738,6 +940,17 @@ impl TheLayout for TheTreeLayout {
                 style.theme().color(TextLayoutBorder),
             );
         }
+
+        // Dropping into the root of a headerless tree highlights the whole tree.
+        if target == Some(TheTreeDropTarget::Into(self.root.id.uuid)) && self.headerless {
+            let stride: usize = buffer.stride();
+            ctx.draw.rect_outline(
+                buffer.pixels_mut(),
+                &self.dim.to_buffer_utuple(),
+                stride,
+                style.theme().color(TreeViewDropIndicator),
+            );
+        }
     }
 
     fn as_tree_layout(&mut self) -> Option<&mut dyn TheTreeLayoutTrait> {
@@ -763,6 +976,38 @@ pub trait TheTreeLayoutTrait: TheLayout {
     fn tree_node_state_changed(&mut self, id: TheId, open: bool);
     /// Scroll by the given amount.
     fn scroll_by(&mut self, delta: Vec2<i32>);
+    /// Allows selecting several items, ranges with Shift and single items with Ctrl / Cmd.
+    fn set_multi_selection(&mut self, multi: bool);
+    /// An item was clicked. A range click selects the shown items from the last clicked item, a
+    /// toggle click adds or removes the item. Sends TheEvent::NewListItemSelected and
+    /// TheEvent::SelectionChanged if the selection changed.
+    fn item_clicked(&mut self, item: TheId, range: bool, toggle: bool, ctx: &mut TheContext);
+    /// Moves the selection by the given number of shown items, if extended the range selection
+    /// grows or shrinks. Sends TheEvent::SelectionChanged if the selection changed.
+    fn move_selection(&mut self, delta: i32, extend: bool, ctx: &mut TheContext);
+    /// Selects all shown items if multi selection is enabled.
+    fn select_all(&mut self, ctx: &mut TheContext);
+    /// Returns the ids of all selected items in tree order.
+    fn selected_ids(&self) -> Vec<TheId>;
+    /// Allows moving items and nodes by drag and drop. Dropping sends
+    /// TheEvent::TreeMoveRequested, the move is applied by passing it to apply_move().
+    fn set_drag_and_drop(&mut self, drag_and_drop: bool);
+    /// Starts dragging the item or node of the given id. Returns the drop to show, None if drag
+    /// and drop is disabled or the id is not part of the tree.
+    fn start_drag(&mut self, id: &TheId, text: &str, offset: Vec2<i32>) -> Option<TheDrop>;
+    /// Moves the dragged item to the given screen coordinate. Returns true if the coordinate is
+    /// inside the layout.
+    fn drag_to(&mut self, coord: Vec2<i32>, now: Instant, ctx: &mut TheContext) -> bool;
+    /// Scrolls the tree while the pointer is near its top or bottom edge and opens closed nodes
+    /// hovered for a moment. Returns true if the layout needs to be redrawn.
+    fn update_drag(&mut self, now: Instant, ctx: &mut TheContext) -> bool;
+    /// Ends the drag. If dropped at a valid position TheEvent::TreeMoveRequested is sent.
+    fn end_drag(&mut self, drop: bool, ctx: &mut TheContext);
+    /// Returns true while an item or node is dragged.
+    fn is_dragging(&self) -> bool;
+    /// Applies a move, returns false if the moved item or the parent node do not exist or a
+    /// node would be moved into itself.
+    fn apply_move(&mut self, tree_move: &TheTreeMove) -> bool;
 }
 
 impl TheTreeLayoutTrait for TheTreeLayout {
@@ -782,9 +1027,15 @@ impl TheTreeLayoutTrait for TheTreeLayout {
         self.root.find_node_mut(uuid)
     }
     fn new_item_selected(&mut self, item: TheId) {
+        let index = self
+            .visible_items()
+            .iter()
+            .position(|id| id.uuid == item.uuid);
+        self.selection.reset(index);
         if !self.root.new_item_selected(&item) {
             self.root.clear_selection();
         }
+        self.selection.items = self.selected_ids().iter().map(|id| id.uuid).collect();
     }
     fn get_root(&mut self) -> &mut TheTreeNode {
         &mut self.root
@@ -800,9 +1051,469 @@ impl TheTreeLayoutTrait for TheTreeLayout {
             self.root.apply_scroll_offset(offset);
         }
     }
+    fn set_multi_selection(&mut self, multi: bool) {
+        self.selection.multi = multi;
+        if !multi {
+            let first: FxHashSet<Uuid> = self
+                .selected_ids()
+                .first()
+                .map(|id| id.uuid)
+                .into_iter()
+                .collect();
+            self.root.set_selection(&first);
+            self.selection.items = first;
+        }
+    }
+    fn item_clicked(&mut self, item: TheId, range: bool, toggle: bool, ctx: &mut TheContext) {
+        let visible = self.visible_items();
+        let Some(index) = visible.iter().position(|id| id.uuid == item.uuid) else {
+            let previous = self.selected_ids();
+            self.new_item_selected(item.clone());
+            if self.selected_ids() != previous {
+                ctx.ui
+                    .send(TheEvent::NewListItemSelected(item, self.id.clone()));
+            }
+            return;
+        };
+        let current = self.selected_indices(&visible);
+        let selection = self.selection.click(index, &current, range, toggle);
+        if self.apply_selection(&visible, selection, toggle) {
+            ctx.ui
+                .send(TheEvent::NewListItemSelected(item, self.id.clone()));
+            self.send_selection_changed(ctx);
+        }
+    }
+    fn move_selection(&mut self, delta: i32, extend: bool, ctx: &mut TheContext) {
+        let visible = self.visible_items();
+        let current = self.selected_indices(&visible);
+        let Some((lead, selection)) = self.selection.step(delta, visible.len(), &current, extend)
+        else {
+            return;
+        };
+
+        self.scroll_to_item(&visible[lead]);
+        if self.apply_selection(&visible, selection, false) {
+            if !extend {
+                ctx.ui
+                    .send_widget_state_changed(&visible[lead], TheWidgetState::Selected);
+            }
+            ctx.ui.set_focus(&visible[lead]);
+            self.send_selection_changed(ctx);
+        }
+    }
+    fn select_all(&mut self, ctx: &mut TheContext) {
+        let visible = self.visible_items();
+        if let Some(selection) = self.selection.all(visible.len()) {
+            if self.apply_selection(&visible, selection, true) {
+                self.send_selection_changed(ctx);
+            }
+        }
+    }
+    fn selected_ids(&self) -> Vec<TheId> {
+        let mut selected = vec![];
+        self.root.collect_selected(&mut selected);
+        selected
+    }
+    fn set_drag_and_drop(&mut self, drag_and_drop: bool) {
+        self.drag_and_drop = drag_and_drop;
+        if !drag_and_drop {
+            self.drag = None;
+        }
+    }
+    fn start_drag(&mut self, id: &TheId, text: &str, offset: Vec2<i32>) -> Option<TheDrop> {
+        if !self.drag_and_drop || id.uuid == self.root.id.uuid {
+            return None;
+        }
+        let node = if self.root.find_node(&id.uuid).is_some() {
+            true
+        } else if self.root.find_widget(None, Some(&id.uuid), false).is_some() {
+            false
+        } else {
+            return None;
+        };
+
+        self.drag = Some(TheTreeDrag {
+            id: id.clone(),
+            node,
+            coord: Vec2::zero(),
+            target: None,
+            hovered: None,
+        });
+
+        let mut drop = TheDrop::new(id.clone());
+        drop.set_title(text.to_string());
+        drop.set_offset(offset);
+        Some(drop)
+    }
+    fn drag_to(&mut self, coord: Vec2<i32>, now: Instant, ctx: &mut TheContext) -> bool {
+        let Some(drag) = &mut self.drag else {
+            return false;
+        };
+        drag.coord = coord;
+        self.update_drag(now, ctx);
+        self.dim.contains(coord)
+    }
+    fn update_drag(&mut self, now: Instant, ctx: &mut TheContext) -> bool {
+        let Some(mut drag) = self.drag.take() else {
+            return false;
+        };
+
+        let mut redraw = self.auto_scroll(drag.coord);
+        if self.layout_dirty_flag.load(Ordering::Relaxed) {
+            self.recalculate_layout(ctx);
+        }
+
+        let row = self.row_at(drag.coord);
+
+        // Closed nodes open when hovered for a moment.
+        let closed = row
+            .filter(|row| row.node)
+            .and_then(|row| self.root.find_node(&row.id))
+            .filter(|node| !node.open && node.has_content())
+            .map(|node| node.id.clone());
+        drag.hovered = match (closed, drag.hovered) {
+            (Some(id), Some((uuid, since))) if id.uuid == uuid => {
+                if now.duration_since(since) >= TREE_DRAG_EXPAND_DELAY {
+                    if let Some(node) = self.root.find_node_mut(&uuid) {
+                        node.set_open(true);
+                    }
+                    ctx.ui
+                        .send(TheEvent::SnapperStateChanged(id, self.id.clone(), true));
+                    self.recalculate_layout(ctx);
+                    redraw = true;
+                    None
+                } else {
+                    Some((uuid, since))
+                }
+            }
+            (Some(id), _) => Some((id.uuid, now)),
+            (None, _) => None,
+        };
+
+        let row = self.row_at(drag.coord);
+        let target = self.drop_target(&drag, row);
+        if target != drag.target {
+            drag.target = target;
+            redraw = true;
+        }
+
+        self.drag = Some(drag);
+        redraw
+    }
+    fn end_drag(&mut self, drop: bool, ctx: &mut TheContext) {
+        if let Some(drag) = self.drag.take() {
+            if let Some((_, tree_move)) = drag.target.filter(|_| drop) {
+                ctx.ui
+                    .send(TheEvent::TreeMoveRequested(self.id.clone(), tree_move));
+            }
+        }
+    }
+    fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+    fn apply_move(&mut self, tree_move: &TheTreeMove) -> bool {
+        let Some(parent) = self.root.find_node(&tree_move.parent.uuid) else {
+            return false;
+        };
+        let parent = parent.id.uuid;
+
+        if tree_move.node {
+            // A node can not be moved into itself or its children.
+            match self.root.find_node(&tree_move.id.uuid) {
+                Some(node) if node.find_node(&parent).is_none() => {}
+                _ => return false,
+            }
+            let Some(node) = self.root.remove_child_by_uuid(&tree_move.id.uuid) else {
+                return false;
+            };
+            if let Some(parent) = self.root.find_node_mut(&parent) {
+                parent.add_child_at(tree_move.index.min(parent.childs.len()), node);
+            }
+        } else {
+            let Some(widget) = self.root.remove_widget_by_uuid(&tree_move.id.uuid) else {
+                return false;
+            };
+            if let Some(parent) = self.root.find_node_mut(&parent) {
+                parent.add_widget_at(tree_move.index.min(parent.widgets.len()), widget);
+            }
+        }
+        true
+    }
 }
 
 impl TheTreeLayout {
+    /// The ids of the shown items in tree order.
+    fn visible_items(&self) -> Vec<TheId> {
+        let mut visible = vec![];
+        self.root.collect_visible(&mut visible);
+        visible
+    }
+
+    /// The indices of the items selected by the layout among the shown items, see
+    /// TheSelection::items.
+    fn selected_indices(&self, visible: &[TheId]) -> BTreeSet<usize> {
+        visible
+            .iter()
+            .enumerate()
+            .filter(|(_, id)| self.selection.items.contains(&id.uuid))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Selects the shown items at the given indices. Selected items of closed nodes stay selected
+    /// if requested. Returns true if the selection changed.
+    fn apply_selection(
+        &mut self,
+        visible: &[TheId],
+        selection: BTreeSet<usize>,
+        keep_hidden: bool,
+    ) -> bool {
+        let before = self.selection.items.clone();
+        let mut after: FxHashSet<Uuid> = selection
+            .iter()
+            .filter_map(|index| visible.get(*index))
+            .map(|id| id.uuid)
+            .collect();
+        if keep_hidden && self.selection.multi {
+            after.extend(
+                before
+                    .iter()
+                    .filter(|uuid| !visible.iter().any(|id| id.uuid == **uuid)),
+            );
+        }
+
+        self.root.set_selection(&after);
+        self.selection.items = after;
+        before != self.selection.items
+    }
+
+    fn send_selection_changed(&self, ctx: &mut TheContext) {
+        ctx.ui.send(TheEvent::SelectionChanged(
+            self.id.clone(),
+            self.selected_ids(),
+        ));
+    }
+
+    /// Scrolls the given item into view.
+    fn scroll_to_item(&mut self, id: &TheId) {
+        if !self.vertical_scrollbar_visible {
+            return;
+        }
+        let Some(widget) = self.root.find_widget(None, Some(&id.uuid), true) else {
+            return;
+        };
+        let top = widget.dim().y - self.dim.y;
+        let bottom = top + widget.dim().height;
+        if let Some(scroll_bar) = self.vertical_scrollbar.as_vertical_scrollbar() {
+            let offset = scroll_bar.scroll_offset();
+            if top < offset {
+                scroll_bar.scroll_by(top - offset);
+            } else if bottom > offset + self.dim.height {
+                scroll_bar.scroll_by(bottom - offset - self.dim.height);
+            }
+            let offset = scroll_bar.scroll_offset();
+            self.root.apply_scroll_offset(offset);
+        }
+    }
+
+    fn scroll_offset(&mut self) -> i32 {
+        self.vertical_scrollbar
+            .as_vertical_scrollbar()
+            .map(|scroll_bar| scroll_bar.scroll_offset())
+            .unwrap_or(0)
+    }
+
+    /// Returns the shown node or item at the given screen coordinate. Returns the header of the
+    /// root node for the empty area below the rows.
+    fn row_at(&mut self, coord: Vec2<i32>) -> Option<TheTreeRow> {
+        if !self.dim.contains(coord)
+            || (self.vertical_scrollbar_visible && self.vertical_scrollbar.dim().contains(coord))
+        {
+            return None;
+        }
+        let y = coord.y + self.scroll_offset();
+        Some(self.root.row_at(y, !self.headerless).unwrap_or(TheTreeRow {
+            id: self.root.id.uuid,
+            node: true,
+            dim: *self.root.widget.dim(),
+        }))
+    }
+
+    /// Returns where the dragged item would be dropped on the given row and the resulting move.
+    /// The upper and lower quarters of node rows place nodes before or after the node, the rest
+    /// drops into the node. Items are placed before or after items and dropped into nodes.
+    fn drop_target(
+        &mut self,
+        drag: &TheTreeDrag,
+        row: Option<TheTreeRow>,
+    ) -> Option<(TheTreeDropTarget, TheTreeMove)> {
+        let row = row?;
+        let y = drag.coord.y + self.scroll_offset() - row.dim.y;
+        let height = row.dim.height;
+
+        let target = if row.id == self.root.id.uuid {
+            TheTreeDropTarget::Into(row.id)
+        } else if row.node {
+            let node = self.root.find_node(&row.id)?;
+            if !drag.node {
+                TheTreeDropTarget::Into(row.id)
+            } else if y < height / 4 {
+                TheTreeDropTarget::Before(row.id)
+            } else if y >= height - height / 4 && !(node.open && node.has_content()) {
+                TheTreeDropTarget::After(row.id)
+            } else {
+                TheTreeDropTarget::Into(row.id)
+            }
+        } else if drag.node {
+            return None;
+        } else if y < height / 2 {
+            TheTreeDropTarget::Before(row.id)
+        } else {
+            TheTreeDropTarget::After(row.id)
+        };
+
+        let ids = |node: &TheTreeNode| -> Vec<Uuid> {
+            if drag.node {
+                node.childs.iter().map(|child| child.id.uuid).collect()
+            } else {
+                node.widgets.iter().map(|widget| widget.id().uuid).collect()
+            }
+        };
+
+        let (parent, mut index) = match target {
+            TheTreeDropTarget::Into(uuid) => {
+                let parent = self.root.find_node(&uuid)?;
+                (parent, ids(parent).len())
+            }
+            TheTreeDropTarget::Before(uuid) | TheTreeDropTarget::After(uuid) => {
+                let parent = self.root.parent_of(&uuid)?;
+                let index = ids(parent).iter().position(|id| *id == uuid)?;
+                let after = matches!(target, TheTreeDropTarget::After(_));
+                (parent, index + after as usize)
+            }
+        };
+
+        // A node can not be moved into itself or its children.
+        if drag.node {
+            let dragged = self.root.find_node(&drag.id.uuid)?;
+            if dragged.find_node(&parent.id.uuid).is_some() {
+                return None;
+            }
+        }
+
+        // Indices do not count the moved item, moves to its current position are ignored.
+        if let Some(current) = ids(parent).iter().position(|id| *id == drag.id.uuid) {
+            if current < index {
+                index -= 1;
+            }
+            if current == index {
+                return None;
+            }
+        }
+
+        Some((
+            target,
+            TheTreeMove {
+                id: drag.id.clone(),
+                node: drag.node,
+                parent: parent.id.clone(),
+                index,
+            },
+        ))
+    }
+
+    /// Scrolls while the pointer is near the top or bottom edge, faster the closer it gets.
+    /// Returns true if the tree scrolled.
+    fn auto_scroll(&mut self, coord: Vec2<i32>) -> bool {
+        if !self.vertical_scrollbar_visible
+            || coord.x < self.dim.x
+            || coord.x >= self.dim.x + self.dim.width
+        {
+            return false;
+        }
+
+        let top = coord.y - self.dim.y;
+        let bottom = self.dim.y + self.dim.height - coord.y;
+        let delta = if top < TREE_DRAG_SCROLL_MARGIN {
+            -((TREE_DRAG_SCROLL_MARGIN - top).min(TREE_DRAG_SCROLL_MARGIN * 2) / 4 + 1)
+        } else if bottom < TREE_DRAG_SCROLL_MARGIN {
+            (TREE_DRAG_SCROLL_MARGIN - bottom).min(TREE_DRAG_SCROLL_MARGIN * 2) / 4 + 1
+        } else {
+            return false;
+        };
+
+        let Some(scroll_bar) = self.vertical_scrollbar.as_vertical_scrollbar() else {
+            return false;
+        };
+        let offset = scroll_bar.scroll_offset();
+        scroll_bar.scroll_by(delta);
+        let new_offset = scroll_bar.scroll_offset();
+        if new_offset == offset {
+            return false;
+        }
+        self.root.apply_scroll_offset(new_offset);
+        true
+    }
+
+    /// Draws the insertion line or the highlight of the node into the content buffer.
+    fn draw_drop_target(
+        &mut self,
+        target: TheTreeDropTarget,
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+    ) {
+        let (TheTreeDropTarget::Before(uuid)
+        | TheTreeDropTarget::After(uuid)
+        | TheTreeDropTarget::Into(uuid)) = target;
+        if uuid == self.root.id.uuid && self.headerless {
+            return;
+        }
+        let Some(dim) = self
+            .root
+            .find_widget(None, Some(&uuid), true)
+            .map(|widget| *widget.dim())
+        else {
+            return;
+        };
+
+        let color = *style.theme().color(TreeViewDropIndicator);
+        let safe_rect = self.content_buffer.dim().to_buffer_utuple();
+        let stride = self.content_buffer.stride();
+        let x = dim.x - self.dim.x;
+        let y = dim.y - self.dim.y;
+        let width = (safe_rect.2 as i32 - x - TREE_RIGHT_MARGIN).max(0) as usize;
+
+        // Lines are centered on the border between two rows.
+        let line_y = match target {
+            TheTreeDropTarget::Before(_) => y - 1,
+            TheTreeDropTarget::After(_) => y + dim.height - 1,
+            TheTreeDropTarget::Into(_) => {
+                let (x, y, height) = (x as usize, y as usize, dim.height as usize);
+                for inset in 0..2 {
+                    ctx.draw.rect_outline(
+                        self.content_buffer.pixels_mut(),
+                        &(
+                            x + inset,
+                            y + inset,
+                            width.saturating_sub(inset * 2),
+                            height.saturating_sub(inset * 2),
+                        ),
+                        stride,
+                        &color,
+                    );
+                }
+                return;
+            }
+        };
+        ctx.draw.rect_safe(
+            self.content_buffer.pixels_mut(),
+            &(x as isize, line_y as isize, width, 2),
+            stride,
+            &color,
+            &safe_rect,
+        );
+    }
+
     fn recalculate_layout(&mut self, ctx: &mut TheContext) {
         if self.dim.width <= 0 || self.dim.height <= 0 {
             self.layout_dirty_flag.store(false, Ordering::Relaxed);
@@ -871,6 +1582,7 @@ impl TheTreeLayout {
                 }
             }
 
+            self.content_buffer.set_scale(ctx.scale_factor);
             self.content_buffer
                 .set_dim(TheDim::new(0, 0, available_width, total_height));
 
@@ -46,8 +46,10 @@ impl TheSDFCanvas {
 
     /// Renders the sdfs into the given buffer.
     pub fn render(&self, buffer: &mut TheRGBABuffer) {
-        let width = buffer.dim().width as usize;
-        let height = buffer.dim().height;
+        // The SDFs are evaluated in logical units for every physical pixel
+        let scale = buffer.scale();
+        let width = buffer.physical_width() as usize;
+        let height = buffer.physical_height();
 
         let pixels = buffer.pixels_mut();
 
@@ -64,7 +66,7 @@ impl TheSDFCanvas {
                     let mut color = self.background.clone();
                     let mut distance = f32::MAX;
 
-                    let p = Vec2::new(x as f32, y as f32);
+                    let p = Vec2::new(x as f32, y as f32) / scale;
                     let mut i: Option<usize> = None;
 
                     for (index, sdf) in self.sdfs.iter().enumerate() {
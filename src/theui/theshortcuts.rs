@@ -0,0 +1,270 @@
+use crate::prelude::*;
+
+/// A keyboard shortcut, a single accelerator or a chord of several accelerators which have to be
+/// pressed one after another (like Ctrl+K Ctrl+C).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(transparent)]
+pub struct TheShortcut {
+    pub sequence: Vec<TheAccelerator>,
+}
+
+impl TheShortcut {
+    pub fn new(accel: TheAccelerator) -> Self {
+        Self {
+            sequence: vec![accel],
+        }
+    }
+
+    pub fn chord(sequence: Vec<TheAccelerator>) -> Self {
+        Self { sequence }
+    }
+
+    /// Converts the shortcut to a string, the keys of a chord are separated by a comma.
+    pub fn description(&self) -> String {
+        self.sequence
+            .iter()
+            .map(|accel| accel.description())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Test if both shortcuts can be triggered by the same key presses. This is the case if they
+    /// are equal or if one is the start of the other, which would make the longer one
+    /// unreachable.
+    pub fn conflicts_with(&self, other: &TheShortcut) -> bool {
+        self.sequence
+            .iter()
+            .zip(other.sequence.iter())
+            .all(|(a, b)| a.overlaps(b))
+    }
+}
+
+/// A pressed key with its modifiers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TheShortcutKey {
+    shift: bool,
+    ctrl: bool,
+    alt: bool,
+    logo: bool,
+    char: Option<char>,
+    key_code: Option<TheKeyCode>,
+}
+
+impl TheShortcutKey {
+    fn matches(&self, accel: &TheAccelerator) -> bool {
+        if let Some(key_code) = self.key_code {
+            accel.matches_key_code(self.shift, self.ctrl, self.alt, self.logo, key_code)
+        } else if let Some(char) = self.char {
+            accel.matches(self.shift, self.ctrl, self.alt, self.logo, char)
+        } else {
+            false
+        }
+    }
+}
+
+/// The result of passing a key press to TheShortcutRegistry.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TheShortcutMatch {
+    /// The key press is not part of a shortcut.
+    None,
+    /// The key press started or continued a chord, the next key press decides.
+    Pending,
+    /// The shortcut of the given id was completed.
+    Matched(TheId),
+}
+
+/// The shortcut registry of TheUI. It maps shortcuts to the TheIds which are sent in a
+/// TheEvent::ShortcutTriggered event when the shortcut is pressed.
+#[derive(Clone, Debug, Default)]
+pub struct TheShortcutRegistry {
+    shortcuts: Vec<(TheId, TheShortcut)>,
+    /// The keys of a chord pressed so far.
+    pending: Vec<TheShortcutKey>,
+}
+
+impl TheShortcutRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the shortcut for the given id, an existing shortcut of the id is replaced. If
+    /// the shortcut conflicts with the shortcut of another id nothing is registered and the ids
+    /// of the conflicting shortcuts are returned.
+    pub fn register(&mut self, id: TheId, shortcut: TheShortcut) -> Result<(), Vec<TheId>> {
+        if shortcut.sequence.is_empty() {
+            return Err(vec![]);
+        }
+
+        let conflicts: Vec<TheId> = self
+            .conflicts(&shortcut)
+            .into_iter()
+            .filter(|other| other.uuid != id.uuid)
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+
+        self.unregister(&id);
+        self.shortcuts.push((id, shortcut));
+        Ok(())
+    }
+
+    /// Removes the shortcut of the given id.
+    pub fn unregister(&mut self, id: &TheId) {
+        self.shortcuts.retain(|(other, _)| other.uuid != id.uuid);
+        self.pending.clear();
+    }
+
+    /// Removes all shortcuts.
+    pub fn clear(&mut self) {
+        self.shortcuts.clear();
+        self.pending.clear();
+    }
+
+    /// Returns the ids of the registered shortcuts which conflict with the given shortcut.
+    pub fn conflicts(&self, shortcut: &TheShortcut) -> Vec<TheId> {
+        self.shortcuts
+            .iter()
+            .filter(|(_, other)| other.conflicts_with(shortcut))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Returns the shortcut of the given id.
+    pub fn shortcut(&self, id: &TheId) -> Option<&TheShortcut> {
+        self.shortcuts
+            .iter()
+            .find(|(other, _)| other.uuid == id.uuid)
+            .map(|(_, shortcut)| shortcut)
+    }
+
+    /// Returns all registered shortcuts.
+    pub fn shortcuts(&self) -> &Vec<(TheId, TheShortcut)> {
+        &self.shortcuts
+    }
+
+    /// Returns true if a chord was started and waits for its next key.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Aborts a started chord.
+    pub fn cancel_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Returns the effective shortcuts with the bindings of the keymap applied. Registered
+    /// shortcuts are overridden by the keymap binding of the same name, the remaining keymap
+    /// bindings are returned under new ids of their action name.
+    pub fn bindings(&self, keymap: &TheKeymap) -> Vec<(TheId, TheShortcut)> {
+        let mut bindings = vec![];
+
+        for (id, shortcut) in &self.shortcuts {
+            if keymap.contains(&id.name) {
+                if let Some(shortcut) = keymap.shortcut(&id.name) {
+                    bindings.push((id.clone(), shortcut.clone()));
+                }
+            } else {
+                bindings.push((id.clone(), shortcut.clone()));
+            }
+        }
+
+        for action in keymap.bindings.keys() {
+            if !self.shortcuts.iter().any(|(id, _)| id.name == *action) {
+                if let Some(shortcut) = keymap.shortcut(action) {
+                    bindings.push((TheId::named(action), shortcut.clone()));
+                }
+            }
+        }
+
+        bindings
+    }
+
+    /// Returns the pairs of effective shortcuts which conflict with each other.
+    pub fn keymap_conflicts(&self, keymap: &TheKeymap) -> Vec<(TheId, TheId)> {
+        let bindings = self.bindings(keymap);
+        let mut conflicts = vec![];
+
+        for (index, (id, shortcut)) in bindings.iter().enumerate() {
+            for (other_id, other) in bindings.iter().skip(index + 1) {
+                if shortcut.conflicts_with(other) {
+                    conflicts.push((id.clone(), other_id.clone()));
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// Processes a key press against the registered shortcuts and the keymap. A key press which
+    /// does not continue a started chord aborts it and is then tested as the first key of a
+    /// shortcut.
+    #[allow(clippy::too_many_arguments)]
+    pub fn process(
+        &mut self,
+        keymap: &TheKeymap,
+        shift: bool,
+        ctrl: bool,
+        alt: bool,
+        logo: bool,
+        char: Option<char>,
+        key_code: Option<TheKeyCode>,
+    ) -> TheShortcutMatch {
+        if char.is_none() && key_code.is_none() {
+            return TheShortcutMatch::None;
+        }
+
+        let key = TheShortcutKey {
+            shift,
+            ctrl,
+            alt,
+            logo,
+            char,
+            key_code,
+        };
+
+        let bindings = self.bindings(keymap);
+
+        self.pending.push(key);
+        let rc = self.match_pending(&bindings);
+        if rc == TheShortcutMatch::None && self.pending.len() > 1 {
+            self.pending.clear();
+            self.pending.push(key);
+            let rc = self.match_pending(&bindings);
+            if rc != TheShortcutMatch::Pending {
+                self.pending.clear();
+            }
+            return rc;
+        }
+        if rc != TheShortcutMatch::Pending {
+            self.pending.clear();
+        }
+        rc
+    }
+
+    /// Matches the pending keys against the shortcuts.
+    fn match_pending(&self, bindings: &[(TheId, TheShortcut)]) -> TheShortcutMatch {
+        let mut rc = TheShortcutMatch::None;
+
+        for (id, shortcut) in bindings {
+            if shortcut.sequence.len() < self.pending.len() {
+                continue;
+            }
+
+            let matches = self
+                .pending
+                .iter()
+                .zip(shortcut.sequence.iter())
+                .all(|(key, accel)| key.matches(accel));
+
+            if matches {
+                if shortcut.sequence.len() == self.pending.len() {
+                    return TheShortcutMatch::Matched(id.clone());
+                }
+                rc = TheShortcutMatch::Pending;
+            }
+        }
+
+        rc
+    }
+}
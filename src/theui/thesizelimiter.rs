@@ -54,6 +54,16 @@ impl TheSizeLimiter {
         self.max_size.y
     }
 
+    /// Returns the min width.
+    pub fn get_min_width(&self) -> i32 {
+        self.min_size.x
+    }
+
+    /// Returns the min height.
+    pub fn get_min_height(&self) -> i32 {
+        self.min_size.y
+    }
+
     /// Sets the maximum height of the limiter.
     pub fn set_max_height(&mut self, height: i32) {
         self.max_size.y = height;
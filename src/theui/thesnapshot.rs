@@ -0,0 +1,251 @@
+use crate::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// If this environment variable is set, TheSnapshot writes the rendered buffers as the new golden images.
+pub const THE_UPDATE_SNAPSHOTS: &str = "THE_UPDATE_SNAPSHOTS";
+
+/// The result of a failed snapshot comparison.
+#[derive(Debug)]
+pub enum TheSnapshotError {
+    /// Reading or writing an image failed.
+    Io(PathBuf, String),
+    /// The golden image does not exist. The rendered buffer was written to the given path.
+    Missing(PathBuf),
+    /// The golden image has a different size than the rendered buffer.
+    SizeMismatch {
+        expected: (i32, i32),
+        actual: (i32, i32),
+    },
+    /// Pixels differ by more than the tolerance. The diff image was written to the given path.
+    Mismatch {
+        pixels: usize,
+        max_delta: u8,
+        diff: PathBuf,
+    },
+}
+
+impl std::fmt::Display for TheSnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            Self::Missing(path) => write!(
+                f,
+                "golden image missing, rendered buffer written to {} (set {} to accept)",
+                path.display(),
+                THE_UPDATE_SNAPSHOTS
+            ),
+            Self::SizeMismatch { expected, actual } => write!(
+                f,
+                "size mismatch, expected {}x{}, got {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            Self::Mismatch {
+                pixels,
+                max_delta,
+                diff,
+            } => write!(
+                f,
+                "{} pixels differ (max channel delta {}), diff written to {}",
+                pixels,
+                max_delta,
+                diff.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TheSnapshotError {}
+
+/// Renders canvases, layouts and widgets at a fixed size and compares the result against golden PNG images.
+pub struct TheSnapshot {
+    pub ctx: TheContext,
+    pub style: Box<dyn TheStyle>,
+
+    width: i32,
+    height: i32,
+
+    /// The maximum allowed difference per color channel.
+    tolerance: u8,
+}
+
+impl TheSnapshot {
+    /// Creates a snapshot renderer of the given size using TheClassicStyle (with TheDarkTheme).
+    pub fn new(width: i32, height: i32) -> Self {
+        Self::with_style(width, height, Box::new(TheClassicStyle::new()))
+    }
+
+    /// Creates a snapshot renderer of the given size using the given style.
+    pub fn with_style(width: i32, height: i32, style: Box<dyn TheStyle>) -> Self {
+        Self {
+            ctx: TheContext::new(width as usize, height as usize, 1.0),
+            style,
+
+            width,
+            height,
+
+            tolerance: 2,
+        }
+    }
+
+    /// Renders at the given scale factor, golden images then have the physical size.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.ctx.set_scale_factor(scale_factor);
+    }
+
+    /// Sets the maximum allowed difference per color channel.
+    pub fn set_tolerance(&mut self, tolerance: u8) {
+        self.tolerance = tolerance;
+    }
+
+    /// Lays out and draws the canvas and returns its buffer.
+    pub fn render_canvas(&mut self, canvas: &mut TheCanvas) -> TheRGBABuffer {
+        canvas.root = true;
+        canvas.set_dim(TheDim::new(0, 0, self.width, self.height), &mut self.ctx);
+        canvas.layout(self.width, self.height, &mut self.ctx);
+
+        self.ctx.ui.redraw_all = true;
+        canvas.draw(&mut self.style, &mut self.ctx);
+        self.ctx.ui.redraw_all = false;
+
+        canvas.buffer().clone()
+    }
+
+    /// Renders a canvas containing only the given widget.
+    pub fn render_widget<T: TheWidget + 'static>(&mut self, widget: T) -> TheRGBABuffer {
+        let mut canvas = TheCanvas::new();
+        canvas.set_widget(widget);
+        self.render_canvas(&mut canvas)
+    }
+
+    /// Renders a canvas containing only the given layout.
+    pub fn render_layout<T: TheLayout + 'static>(&mut self, layout: T) -> TheRGBABuffer {
+        let mut canvas = TheCanvas::new();
+        canvas.set_layout(layout);
+        self.render_canvas(&mut canvas)
+    }
+
+    /// Compares the buffer against the golden PNG at the given path. On mismatch a diff image
+    /// (`name.diff.png`) and the rendered buffer (`name.actual.png`) are written next to the golden image.
+    pub fn compare(
+        &self,
+        buffer: &TheRGBABuffer,
+        golden: impl AsRef<Path>,
+    ) -> Result<(), TheSnapshotError> {
+        let golden = golden.as_ref();
+
+        if std::env::var_os(THE_UPDATE_SNAPSHOTS).is_some() {
+            return write_png(buffer, golden);
+        }
+
+        if !golden.exists() {
+            let path = sibling(golden, "new");
+            write_png(buffer, &path)?;
+            return Err(TheSnapshotError::Missing(path));
+        }
+
+        let expected = read_png(golden)?;
+
+        if expected.physical_width() != buffer.physical_width()
+            || expected.physical_height() != buffer.physical_height()
+        {
+            write_png(buffer, &sibling(golden, "actual"))?;
+            return Err(TheSnapshotError::SizeMismatch {
+                expected: (expected.physical_width(), expected.physical_height()),
+                actual: (buffer.physical_width(), buffer.physical_height()),
+            });
+        }
+
+        let (diff, pixels, max_delta) = diff_buffers(&expected, buffer, self.tolerance);
+
+        if pixels > 0 {
+            let diff_path = sibling(golden, "diff");
+            write_png(&diff, &diff_path)?;
+            write_png(buffer, &sibling(golden, "actual"))?;
+            return Err(TheSnapshotError::Mismatch {
+                pixels,
+                max_delta,
+                diff: diff_path,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Compares the buffer against the golden PNG and panics on mismatch. Intended for tests.
+    pub fn assert_matches(&self, buffer: &TheRGBABuffer, golden: impl AsRef<Path>) {
+        let golden = golden.as_ref();
+        if let Err(err) = self.compare(buffer, golden) {
+            panic!("snapshot {} failed: {}", golden.display(), err);
+        }
+    }
+}
+
+/// Compares two buffers of equal size. Returns the diff image, the number of pixels exceeding
+/// the tolerance and the largest channel delta. Matching pixels are drawn as a faded grayscale
+/// version of the expected image, differing pixels in red.
+fn diff_buffers(
+    expected: &TheRGBABuffer,
+    actual: &TheRGBABuffer,
+    tolerance: u8,
+) -> (TheRGBABuffer, usize, u8) {
+    let mut diff = TheRGBABuffer::new(*expected.dim());
+    let mut pixels = 0;
+    let mut max_delta = 0;
+
+    for ((e, a), d) in expected
+        .pixels()
+        .chunks_exact(4)
+        .zip(actual.pixels().chunks_exact(4))
+        .zip(diff.pixels_mut().chunks_exact_mut(4))
+    {
+        let delta = e
+            .iter()
+            .zip(a.iter())
+            .map(|(e, a)| e.abs_diff(*a))
+            .max()
+            .unwrap_or(0);
+
+        max_delta = max_delta.max(delta);
+
+        if delta > tolerance {
+            pixels += 1;
+            d.copy_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let gray = ((e[0] as u32 + e[1] as u32 + e[2] as u32) / 3 / 4 + 32) as u8;
+            d.copy_from_slice(&[gray, gray, gray, 255]);
+        }
+    }
+
+    (diff, pixels, max_delta)
+}
+
+/// Returns the path of a sibling file, i.e. `button.png` -> `button.diff.png`.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    path.with_file_name(format!("{}.{}.png", stem, suffix))
+}
+
+fn read_png(path: &Path) -> Result<TheRGBABuffer, TheSnapshotError> {
+    match image::open(path) {
+        Ok(img) => {
+            let rgba = img.to_rgba8();
+            let (width, height) = rgba.dimensions();
+            Ok(TheRGBABuffer::from(rgba.into_vec(), width, height))
+        }
+        Err(err) => Err(TheSnapshotError::Io(path.to_path_buf(), err.to_string())),
+    }
+}
+
+fn write_png(buffer: &TheRGBABuffer, path: &Path) -> Result<(), TheSnapshotError> {
+    let data = buffer
+        .to_png()
+        .map_err(|err| TheSnapshotError::Io(path.to_path_buf(), err.to_string()))?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| TheSnapshotError::Io(path.to_path_buf(), err.to_string()))?;
+    }
+
+    std::fs::write(path, data)
+        .map_err(|err| TheSnapshotError::Io(path.to_path_buf(), err.to_string()))
+}
@@ -16,6 +16,11 @@ pub trait TheStyle: Send {
     /// Returns the current theme of the style
     fn theme(&mut self) -> &mut Box<dyn TheTheme>;
 
+    /// Replaces the theme of the style, by default the one returned by theme().
+    fn set_theme(&mut self, theme: Box<dyn TheTheme>) {
+        *self.theme() = theme;
+    }
+
     /// Draw the widget border
     fn draw_widget_border(
         &mut self,
@@ -50,6 +55,43 @@ pub trait TheStyle: Send {
     ) {
     }
 
+    /// Draws a tooltip into the screen rectangle, the words were laid out by TheTooltip::layout().
+    fn draw_tooltip(
+        &mut self,
+        pixels: &mut [u8],
+        stride: usize,
+        dim: &TheDim,
+        words: &[TheTooltipWord],
+        ctx: &mut TheContext,
+    ) {
+        let rect = dim.to_buffer_utuple();
+        ctx.draw
+            .rect(pixels, &rect, stride, self.theme().color(TooltipBackground));
+        ctx.draw
+            .rect_outline(pixels, &rect, stride, self.theme().color(TooltipBorder));
+
+        let padding = TheTooltipOverlay::padding();
+        for word in words {
+            let color = match word.style {
+                TheTooltipTextStyle::Text => TooltipText,
+                TheTooltipTextStyle::Strong | TheTooltipTextStyle::Heading => TooltipStrong,
+                TheTooltipTextStyle::Emphasis => TooltipEmphasis,
+                TheTooltipTextStyle::Code => TooltipCode,
+            };
+            ctx.draw.text_blend(
+                pixels,
+                &(
+                    (dim.x + padding + word.x) as usize,
+                    (dim.y + padding + word.y) as usize,
+                ),
+                stride,
+                &word.text,
+                word.style.font_settings(),
+                self.theme().color(color),
+            );
+        }
+    }
+
     /// Creates a preview image for the drop.
     fn create_drop_image(&mut self, drop: &mut TheDrop, ctx: &mut TheContext) {
         let mut width: i32 = 120;
@@ -67,7 +109,7 @@ pub trait TheStyle: Send {
             drop.offset.x = width - 10;
         }
 
-        let mut buffer = TheRGBABuffer::new(TheDim::new(0, 0, width, 24));
+        let mut buffer = TheRGBABuffer::new_scaled(TheDim::new(0, 0, width, 24), ctx.scale_factor);
 
         let mut shrinker = TheDimShrinker::zero();
         shrinker.shrink(2);
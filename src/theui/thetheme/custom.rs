@@ -0,0 +1,151 @@
+use crate::prelude::*;
+use crate::Embedded;
+use ::serde::de::IntoDeserializer;
+use indexmap::IndexMap;
+use std::path::Path;
+
+use super::TheThemeColors;
+
+/// The file format of TheCustomTheme. Colors are stored by their TheThemeColors name as hex
+/// strings, "#RRGGBB" or "#RRGGBBAA".
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+struct TheThemeFile {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    colors: IndexMap<String, String>,
+}
+
+/// A theme which reads its colors from a JSON file. Colors missing in the file use the value of
+/// TheDarkTheme.
+pub struct TheCustomTheme {
+    pub name: String,
+
+    temp_color: RGBA,
+    colors: FxHashMap<TheThemeColors, RGBA>,
+}
+
+impl TheCustomTheme {
+    /// Parses a theme from its JSON representation. Unknown color names and invalid colors are
+    /// reported as errors.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let file: TheThemeFile = serde_json::from_str(json).map_err(|err| err.to_string())?;
+
+        let mut theme = Self::new();
+        theme.name = file.name;
+
+        for (name, hex) in &file.colors {
+            let of: Result<TheThemeColors, ::serde::de::value::Error> =
+                TheThemeColors::deserialize(name.as_str().into_deserializer());
+            let Ok(of) = of else {
+                return Err(format!("Unknown theme color \"{}\"", name));
+            };
+            let Some(color) = Self::parse_hex(hex) else {
+                return Err(format!("Invalid color \"{}\" for \"{}\"", hex, name));
+            };
+            theme.colors.insert(of, color);
+        }
+
+        Ok(theme)
+    }
+
+    /// Loads a theme from the given JSON file.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Self::parse(&json).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+
+    /// Saves the theme, including all default colors, as a JSON file.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    /// Loads one of the themes shipped with the framework, "light" or "high_contrast".
+    pub fn embedded(name: &str) -> Option<Self> {
+        let file = Embedded::get(&format!("themes/{}.json", name))?;
+        let json = std::str::from_utf8(&file.data).ok()?;
+        Self::parse(json).ok()
+    }
+
+    /// Create an instance from json, falls back to the dark theme if the json is invalid.
+    pub fn from_json(json: &str) -> Self {
+        Self::parse(json).unwrap_or_else(|_| Self::new())
+    }
+
+    /// Convert the instance to json.
+    pub fn to_json(&self) -> String {
+        let mut colors: Vec<(String, String)> = self
+            .colors
+            .iter()
+            .map(|(of, color)| (format!("{:?}", of), TheColor::from(*color).to_hex()))
+            .collect();
+        colors.sort();
+
+        let file = TheThemeFile {
+            name: self.name.clone(),
+            colors: colors.into_iter().collect(),
+        };
+        serde_json::to_string_pretty(&file).unwrap_or_default()
+    }
+
+    /// Sets the given theme color.
+    pub fn set_color(&mut self, of: TheThemeColors, color: RGBA) {
+        self.colors.insert(of, color);
+    }
+
+    fn parse_hex(hex: &str) -> Option<RGBA> {
+        let hex = hex.strip_prefix('#')?;
+        if (hex.len() != 6 && hex.len() != 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+        let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+        Some([channel(0)?, channel(2)?, channel(4)?, alpha])
+    }
+}
+
+/// Implements TheCustomTheme
+impl TheTheme for TheCustomTheme {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            name: "Dark".to_string(),
+            temp_color: BLACK,
+            colors: TheDarkTheme::colors(),
+        }
+    }
+
+    /// Return the given theme color.
+    fn color(&self, of: TheThemeColors) -> &RGBA {
+        self.colors.get(&of).unwrap_or(&[0, 0, 0, 255])
+    }
+
+    fn color_disabled_switch(&mut self, of: TheThemeColors, disabled: bool) -> &RGBA {
+        if disabled {
+            self.color_disabled(of)
+        } else {
+            self.color(of)
+        }
+    }
+
+    /// Returns the disabled color value for the given color
+    fn color_disabled(&mut self, of: TheThemeColors) -> &RGBA {
+        let mut d = *self.color(of);
+        d[0] = (d[0] as f32 * 0.75) as u8;
+        d[1] = (d[1] as f32 * 0.75) as u8;
+        d[2] = (d[2] as f32 * 0.75) as u8;
+        self.temp_color = d;
+        &self.temp_color
+    }
+
+    /// Returns the disabled color value for the given color
+    fn color_disabled_t(&mut self, of: TheThemeColors) -> &RGBA {
+        let mut d = *self.color(of);
+        d[3] = (d[3] as f32 * 0.75) as u8;
+        self.temp_color = d;
+        &self.temp_color
+    }
+}
@@ -7,12 +7,9 @@ pub struct TheDarkTheme {
     colors: FxHashMap<TheThemeColors, RGBA>,
 }
 
-/// Implements TheDarkTheme
-impl TheTheme for TheDarkTheme {
-    fn new() -> Self
-    where
-        Self: Sized,
-    {
+impl TheDarkTheme {
+    /// Returns the colors of the dark theme. These are also the defaults of TheCustomTheme.
+    pub fn colors() -> FxHashMap<TheThemeColors, RGBA> {
         let mut colors = FxHashMap::default();
 
         colors.insert(Green, [10, 245, 5, 255]);
@@ -20,6 +17,7 @@ impl TheTheme for TheDarkTheme {
 
         colors.insert(DefaultWidgetBackground, [116, 116, 116, 255]);
         colors.insert(DefaultWidgetDarkBackground, [82, 82, 82, 255]);
+        colors.insert(DefaultTextColor, [255, 255, 255, 255]);
         colors.insert(DefaultWidgetBorder, [146, 146, 146, 255]);
         colors.insert(DefaultSelection, [187, 122, 208, 255]);
 
@@ -132,6 +130,22 @@ impl TheTheme for TheDarkTheme {
         colors.insert(ContextMenuTextDisabled, [100, 100, 100, 255]);
         colors.insert(ContextMenuTextHighlight, [82, 82, 82, 255]);
 
+        colors.insert(ToastBackground, [62, 62, 62, 240]);
+        colors.insert(ToastBorder, [130, 130, 130, 255]);
+        colors.insert(ToastText, [230, 230, 230, 255]);
+        colors.insert(ToastInfo, [187, 122, 208, 255]);
+        colors.insert(ToastWarning, [230, 170, 60, 255]);
+        colors.insert(ToastError, [209, 42, 42, 255]);
+        colors.insert(ToastButton, [90, 90, 90, 255]);
+        colors.insert(ToastButtonHover, [120, 120, 120, 255]);
+
+        colors.insert(TooltipBackground, [230, 230, 230, 255]);
+        colors.insert(TooltipBorder, [130, 130, 130, 255]);
+        colors.insert(TooltipText, [40, 40, 40, 255]);
+        colors.insert(TooltipStrong, [10, 10, 10, 255]);
+        colors.insert(TooltipEmphasis, [130, 70, 150, 255]);
+        colors.insert(TooltipCode, [70, 70, 120, 255]);
+
         colors.insert(WindowBorderOuter, [147, 147, 147, 255]);
         colors.insert(WindowBorderInner, [197, 197, 197, 255]);
         colors.insert(WindowHeaderBackground, [148, 148, 148, 255]);
@@ -174,6 +188,21 @@ impl TheTheme for TheDarkTheme {
         colors.insert(TreeViewNodeText, [52, 52, 52, 255]);
 
         colors.insert(TreeViewNodePlusMinus, [227, 227, 227, 255]);
+        colors.insert(TreeViewDropIndicator, [202, 113, 230, 255]);
+
+        colors.insert(DockDropIndicator, [202, 113, 230, 255]);
+
+        colors
+    }
+}
+
+/// Implements TheDarkTheme
+impl TheTheme for TheDarkTheme {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        let colors = Self::colors();
 
         Self {
             temp_color: BLACK,
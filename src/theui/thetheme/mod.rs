@@ -1,8 +1,10 @@
 use crate::prelude::*;
 
+pub mod custom;
 pub mod dark;
 
 pub mod prelude {
+    pub use crate::theui::thetheme::custom::TheCustomTheme;
     pub use crate::theui::thetheme::dark::TheDarkTheme;
 }
 
@@ -26,8 +28,8 @@ pub trait TheTheme: Send {
     fn color_disabled_t(&mut self, of: TheThemeColors) -> &RGBA;
 }
 
-/// The
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+/// The colors of a theme. Theme files refer to them by name.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum TheThemeColors {
     Green,
     Red,
@@ -36,6 +38,7 @@ pub enum TheThemeColors {
     DefaultSelection,
     DefaultWidgetBackground,
     DefaultWidgetDarkBackground,
+    DefaultTextColor,
 
     SwitchbarBorder,
 
@@ -146,6 +149,22 @@ pub enum TheThemeColors {
     ContextMenuTextHighlight,
     ContextMenuSeparator,
 
+    ToastBackground,
+    ToastBorder,
+    ToastText,
+    ToastInfo,
+    ToastWarning,
+    ToastError,
+    ToastButton,
+    ToastButtonHover,
+
+    TooltipBackground,
+    TooltipBorder,
+    TooltipText,
+    TooltipStrong,
+    TooltipEmphasis,
+    TooltipCode,
+
     WindowBorderOuter,
     WindowBorderInner,
     WindowHeaderBackground,
@@ -184,4 +203,7 @@ pub enum TheThemeColors {
     TreeViewNodeSelected,
     TreeViewNodeText,
     TreeViewNodePlusMinus,
+    TreeViewDropIndicator,
+
+    DockDropIndicator,
 }
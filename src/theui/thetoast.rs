@@ -0,0 +1,436 @@
+use crate::prelude::*;
+use web_time::{Duration, Instant};
+
+/// The logical height of a toast.
+pub const THE_TOAST_HEIGHT: i32 = 36;
+
+/// The distance of the toasts to the screen corner and to each other.
+const MARGIN: i32 = 10;
+const SPACING: i32 = 6;
+
+/// The time a toast takes to fade out at the end of its timeout.
+const FADE_DURATION: Duration = Duration::from_millis(400);
+
+/// The severity of a toast.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TheToastLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl TheToastLevel {
+    /// The default time a toast of this level stays visible.
+    pub fn timeout(&self) -> Duration {
+        match self {
+            TheToastLevel::Info => Duration::from_secs(4),
+            TheToastLevel::Warning => Duration::from_secs(6),
+            TheToastLevel::Error => Duration::from_secs(10),
+        }
+    }
+
+    /// The theme color of the level marker.
+    pub fn color(&self) -> TheThemeColors {
+        match self {
+            TheToastLevel::Info => TheThemeColors::ToastInfo,
+            TheToastLevel::Warning => TheThemeColors::ToastWarning,
+            TheToastLevel::Error => TheThemeColors::ToastError,
+        }
+    }
+}
+
+/// The screen corner the toasts stack in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TheToastCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// A button of a toast. Clicking it sends TheEvent::Custom with the id and value of the action
+/// and dismisses the toast.
+#[derive(Clone, Debug)]
+pub struct TheToastAction {
+    pub text: String,
+    pub id: TheId,
+    pub value: TheValue,
+}
+
+/// A transient notification, shown via TheUI::show_toast() or TheEvent::ShowToast. Toasts do not
+/// block input, they fade out after their timeout or are dismissed by a click.
+#[derive(Clone, Debug)]
+pub struct TheToast {
+    pub id: TheId,
+    pub level: TheToastLevel,
+    pub text: String,
+    /// The time the toast stays visible, None keeps it until it is clicked.
+    pub timeout: Option<Duration>,
+    pub actions: Vec<TheToastAction>,
+
+    /// The time the toast was shown.
+    shown: Option<Instant>,
+
+    dim: TheDim,
+    action_dims: Vec<TheDim>,
+}
+
+impl TheToast {
+    pub fn new(level: TheToastLevel, text: &str) -> Self {
+        Self {
+            id: TheId::named("Toast"),
+            level,
+            text: text.to_string(),
+            timeout: Some(level.timeout()),
+            actions: vec![],
+
+            shown: None,
+
+            dim: TheDim::zero(),
+            action_dims: vec![],
+        }
+    }
+
+    pub fn info(text: &str) -> Self {
+        Self::new(TheToastLevel::Info, text)
+    }
+
+    pub fn warning(text: &str) -> Self {
+        Self::new(TheToastLevel::Warning, text)
+    }
+
+    pub fn error(text: &str) -> Self {
+        Self::new(TheToastLevel::Error, text)
+    }
+
+    /// Sets the timeout, None keeps the toast until it is clicked.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Adds an action button, buttons are shown from left to right in the order they were added.
+    pub fn add_action(&mut self, text: &str, id: TheId, value: TheValue) {
+        self.actions.push(TheToastAction {
+            text: text.to_string(),
+            id,
+            value,
+        });
+    }
+
+    /// The screen rectangle of the toast in the last frame.
+    pub fn dim(&self) -> &TheDim {
+        &self.dim
+    }
+
+    /// The opacity at the given time, the toast fades out at the end of its timeout.
+    pub fn opacity(&self, now: Instant) -> f32 {
+        match (self.timeout, self.shown) {
+            (Some(timeout), Some(shown)) => {
+                let left = timeout.saturating_sub(now.saturating_duration_since(shown));
+                (left.as_secs_f32() / FADE_DURATION.as_secs_f32()).min(1.0)
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// Returns true if the timeout of the toast passed.
+    pub fn is_expired(&self, now: Instant) -> bool {
+        match (self.timeout, self.shown) {
+            (Some(timeout), Some(shown)) => now.saturating_duration_since(shown) >= timeout,
+            _ => false,
+        }
+    }
+}
+
+/// The toasts of TheUI. They stack in a screen corner, the newest toast is closest to the corner,
+/// and are drawn on top of the canvas, dialogs and overlays.
+pub struct TheToasts {
+    pub corner: TheToastCorner,
+    /// The maximum number of toasts, the oldest ones are dropped.
+    pub max_visible: usize,
+    /// The logical width of a toast.
+    pub width: i32,
+
+    toasts: Vec<TheToast>,
+    /// The hovered toast and action.
+    hovered: Option<(Uuid, Option<usize>)>,
+}
+
+impl Default for TheToasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TheToasts {
+    pub fn new() -> Self {
+        Self {
+            corner: TheToastCorner::BottomRight,
+            max_visible: 5,
+            width: 320,
+
+            toasts: vec![],
+            hovered: None,
+        }
+    }
+
+    /// Shows the toast, its timeout starts now.
+    pub fn push(&mut self, mut toast: TheToast) {
+        toast.shown = Some(Instant::now());
+        self.toasts.push(toast);
+        if self.toasts.len() > self.max_visible {
+            let excess = self.toasts.len() - self.max_visible;
+            self.toasts.drain(..excess);
+        }
+    }
+
+    /// Dismisses the toast of the given id.
+    pub fn dismiss(&mut self, id: &Uuid) -> bool {
+        let len = self.toasts.len();
+        self.toasts.retain(|toast| toast.id.uuid != *id);
+        if self.hovered.is_some_and(|(uuid, _)| uuid == *id) {
+            self.hovered = None;
+        }
+        len != self.toasts.len()
+    }
+
+    /// Dismisses all toasts.
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+        self.hovered = None;
+    }
+
+    /// The shown toasts, oldest first.
+    pub fn toasts(&self) -> &Vec<TheToast> {
+        &self.toasts
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    /// Removes expired toasts. Hovering a toast restarts its timeout so that it can be read.
+    /// Returns true while toasts are shown or if one was removed, i.e. if a redraw is needed.
+    pub(crate) fn update(&mut self, now: Instant) -> bool {
+        if self.toasts.is_empty() {
+            return false;
+        }
+        if let Some((uuid, _)) = self.hovered {
+            if let Some(toast) = self.toasts.iter_mut().find(|t| t.id.uuid == uuid) {
+                toast.shown = Some(now);
+            }
+        }
+        self.toasts.retain(|toast| !toast.is_expired(now));
+        if let Some((uuid, _)) = self.hovered {
+            if !self.toasts.iter().any(|toast| toast.id.uuid == uuid) {
+                self.hovered = None;
+            }
+        }
+        true
+    }
+
+    /// Positions the toasts and their action buttons in the corner of the screen.
+    pub(crate) fn layout(&mut self, ctx: &TheContext) {
+        let (width, height) = (ctx.width as i32, ctx.height as i32);
+        let toast_width = self.width.min(width - 2 * MARGIN).max(0);
+        let font = Self::font_settings();
+
+        let mut offset = MARGIN;
+        for toast in self.toasts.iter_mut().rev() {
+            let x = match self.corner {
+                TheToastCorner::TopLeft | TheToastCorner::BottomLeft => MARGIN,
+                TheToastCorner::TopRight | TheToastCorner::BottomRight => {
+                    width - MARGIN - toast_width
+                }
+            };
+            let y = match self.corner {
+                TheToastCorner::TopLeft | TheToastCorner::TopRight => offset,
+                TheToastCorner::BottomLeft | TheToastCorner::BottomRight => {
+                    height - offset - THE_TOAST_HEIGHT
+                }
+            };
+            toast.dim = TheDim::rect(x, y, toast_width, THE_TOAST_HEIGHT);
+            offset += THE_TOAST_HEIGHT + SPACING;
+
+            // The action buttons are right aligned.
+            toast.action_dims.clear();
+            let mut right = x + toast_width - 6;
+            for action in toast.actions.iter().rev() {
+                let text_width = ctx.draw.get_text_size(&action.text, &font).0 as i32;
+                let button_width = text_width + 16;
+                right -= button_width;
+                toast
+                    .action_dims
+                    .insert(0, TheDim::rect(right, y + 7, button_width, 22));
+                right -= 4;
+            }
+        }
+    }
+
+    /// Returns the screen regions of the visible toasts.
+    pub(crate) fn regions(&self, ctx: &TheContext) -> Vec<TheDim> {
+        self.toasts
+            .iter()
+            .map(|toast| toast.dim)
+            .filter(|dim| self.is_on_screen(dim, ctx))
+            .collect()
+    }
+
+    /// Draws the toasts into the presented frame. They are not drawn into the canvas buffer, so
+    /// the frame is restored from the canvas where a toast was in the last frame.
+    pub(crate) fn draw(
+        &mut self,
+        pixels: &mut [u8],
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+        now: Instant,
+    ) {
+        let stride = ctx.width;
+
+        for toast in &self.toasts {
+            if !self.is_on_screen(&toast.dim, ctx) {
+                continue;
+            }
+            let opacity = toast.opacity(now);
+            let fade = |color: &RGBA| {
+                let mut color = *color;
+                color[3] = (color[3] as f32 * opacity) as u8;
+                color
+            };
+
+            let background = *style.theme().color(TheThemeColors::ToastBackground);
+            let rect = toast.dim.to_buffer_utuple();
+            ctx.draw
+                .blend_rect(pixels, &rect, stride, &fade(&background));
+
+            let border = fade(style.theme().color(TheThemeColors::ToastBorder));
+            for edge in [
+                (rect.0, rect.1, rect.2, 1),
+                (rect.0, rect.1 + rect.3 - 1, rect.2, 1),
+                (rect.0, rect.1 + 1, 1, rect.3 - 2),
+                (rect.0 + rect.2 - 1, rect.1 + 1, 1, rect.3 - 2),
+            ] {
+                ctx.draw.blend_rect(pixels, &edge, stride, &border);
+            }
+
+            let marker = fade(style.theme().color(toast.level.color()));
+            ctx.draw.blend_rect(
+                pixels,
+                &(rect.0 + 1, rect.1 + 1, 4, rect.3 - 2),
+                stride,
+                &marker,
+            );
+
+            let text_color = fade(style.theme().color(TheThemeColors::ToastText));
+
+            let text_right = toast
+                .action_dims
+                .first()
+                .map(|dim| dim.x - 6)
+                .unwrap_or(toast.dim.x + toast.dim.width - 8);
+            let text_width = (text_right - toast.dim.x - 14).max(0) as usize;
+            ctx.draw.text_rect_blend(
+                pixels,
+                &(rect.0 + 14, rect.1, text_width, rect.3),
+                stride,
+                &toast.text,
+                Self::font_settings(),
+                &text_color,
+                TheHorizontalAlign::Left,
+                TheVerticalAlign::Center,
+            );
+
+            for (index, (action, dim)) in toast.actions.iter().zip(&toast.action_dims).enumerate() {
+                let color = if self.hovered == Some((toast.id.uuid, Some(index))) {
+                    TheThemeColors::ToastButtonHover
+                } else {
+                    TheThemeColors::ToastButton
+                };
+                let button = dim.to_buffer_utuple();
+                ctx.draw
+                    .blend_rect(pixels, &button, stride, &fade(style.theme().color(color)));
+                ctx.draw.text_rect_blend(
+                    pixels,
+                    &button,
+                    stride,
+                    &action.text,
+                    Self::font_settings(),
+                    &text_color,
+                    TheHorizontalAlign::Center,
+                    TheVerticalAlign::Center,
+                );
+            }
+        }
+    }
+
+    /// Handles a click, returns true if it hit a toast.
+    pub(crate) fn touch_down(&mut self, coord: Vec2<i32>, ctx: &mut TheContext) -> bool {
+        let Some((uuid, action)) = self.at_coord(coord, ctx) else {
+            return false;
+        };
+        if let Some(toast) = self.toasts.iter().find(|t| t.id.uuid == uuid) {
+            if let Some(action) = action.and_then(|index| toast.actions.get(index)) {
+                ctx.ui
+                    .send(TheEvent::Custom(action.id.clone(), action.value.clone()));
+            }
+        }
+        self.dismiss(&uuid);
+        true
+    }
+
+    /// Updates the hovered toast, returns true if the hover state changed.
+    pub(crate) fn hover(&mut self, coord: Vec2<i32>, ctx: &TheContext) -> bool {
+        let hovered = self.at_coord(coord, ctx);
+        let changed = hovered != self.hovered;
+        self.hovered = hovered;
+        changed
+    }
+
+    /// Adds the toasts as alerts, with their actions as buttons, to the accessibility tree.
+    pub(crate) fn collect_access_nodes(
+        &self,
+        tree: &mut TheAccessTree,
+        parent: u64,
+        ctx: &TheContext,
+    ) {
+        for toast in &self.toasts {
+            let info = TheAccessInfo::named(TheAccessRole::Alert, &toast.text);
+            let id = tree.add(parent, &toast.id, info, toast.dim, ctx);
+            for (action, dim) in toast.actions.iter().zip(&toast.action_dims) {
+                let info = TheAccessInfo::named(TheAccessRole::Button, &action.text);
+                tree.add(id, &action.id, info, *dim, ctx);
+            }
+        }
+    }
+
+    /// Returns true if the coordinate is on a toast.
+    pub(crate) fn contains(&self, coord: Vec2<i32>, ctx: &TheContext) -> bool {
+        self.at_coord(coord, ctx).is_some()
+    }
+
+    /// The toast and action at the given coordinate.
+    fn at_coord(&self, coord: Vec2<i32>, ctx: &TheContext) -> Option<(Uuid, Option<usize>)> {
+        let toast = self
+            .toasts
+            .iter()
+            .find(|toast| self.is_on_screen(&toast.dim, ctx) && toast.dim.contains(coord))?;
+        let action = toast.action_dims.iter().position(|dim| dim.contains(coord));
+        Some((toast.id.uuid, action))
+    }
+
+    /// Toasts which do not fit on the screen are not drawn.
+    fn is_on_screen(&self, dim: &TheDim, ctx: &TheContext) -> bool {
+        dim.width > 0
+            && dim.x >= 0
+            && dim.y >= 0
+            && dim.x + dim.width <= ctx.width as i32
+            && dim.y + dim.height <= ctx.height as i32
+    }
+
+    fn font_settings() -> TheFontSettings {
+        TheFontSettings {
+            size: 13.0,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use crate::prelude::*;
+use markdown::{mdast::Node, ParseOptions};
+use web_time::{Duration, Instant};
+
+/// The distance between the words of a tooltip.
+const WORD_SPACING: i32 = 4;
+/// The distance of the text to the border of the tooltip.
+const PADDING: i32 = 6;
+
+/// The content of a tooltip, see TheUIContext::set_tooltip() and TheWidget::tooltip().
+#[derive(Clone, Debug, PartialEq)]
+pub enum TheTooltip {
+    Text(String),
+    /// Markdown text, supporting headings, strong and emphasized text, inline code, code blocks
+    /// and lists.
+    Markdown(String),
+}
+
+/// The style of a word in a tooltip.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TheTooltipTextStyle {
+    Text,
+    Strong,
+    Emphasis,
+    Heading,
+    Code,
+}
+
+impl TheTooltipTextStyle {
+    pub fn font_settings(&self) -> TheFontSettings {
+        match self {
+            TheTooltipTextStyle::Heading => TheFontSettings {
+                size: 14.5,
+                ..Default::default()
+            },
+            TheTooltipTextStyle::Code => TheFontSettings {
+                preference: TheFontPreference::Code,
+                size: 12.0,
+            },
+            _ => TheFontSettings {
+                size: 12.5,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// The height of a line of this style.
+    pub fn line_height(&self) -> i32 {
+        match self {
+            TheTooltipTextStyle::Heading => 20,
+            _ => 16,
+        }
+    }
+}
+
+/// A positioned word of a tooltip, relative to the top left of the tooltip text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TheTooltipWord {
+    pub text: String,
+    pub style: TheTooltipTextStyle,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl TheTooltip {
+    /// Lays out the words of the tooltip, wrapped at the given width. Returns the words and the
+    /// size of the text.
+    pub fn layout(&self, max_width: i32, draw: &TheDraw2D) -> (Vec<TheTooltipWord>, Vec2<i32>) {
+        let mut words = vec![];
+        let mut size = Vec2::<i32>::zero();
+        let mut y = 0;
+
+        for (index, line) in self.lines().iter().enumerate() {
+            if index > 0 {
+                y += 2;
+            }
+            let mut x = 0;
+            let mut line_height = 0;
+            for (text, style) in line {
+                let width = draw.get_text_size(text, &style.font_settings()).0 as i32;
+                if x > 0 && x + width > max_width {
+                    x = 0;
+                    y += line_height;
+                }
+                line_height = line_height.max(style.line_height());
+                words.push(TheTooltipWord {
+                    text: text.clone(),
+                    style: *style,
+                    x,
+                    y,
+                });
+                x += width;
+                size.x = size.x.max(x);
+                x += WORD_SPACING;
+            }
+            y += line_height;
+        }
+        size.y = y;
+
+        (words, size)
+    }
+
+    /// The lines of the tooltip as words with their style.
+    fn lines(&self) -> Vec<Vec<(String, TheTooltipTextStyle)>> {
+        match self {
+            TheTooltip::Text(text) => text
+                .lines()
+                .map(|line| {
+                    line.split_whitespace()
+                        .map(|word| (word.to_string(), TheTooltipTextStyle::Text))
+                        .collect()
+                })
+                .collect(),
+            TheTooltip::Markdown(text) => {
+                let mut lines = vec![];
+                if let Ok(root) = markdown::to_mdast(text, &ParseOptions::default()) {
+                    Self::block_lines(&root, &mut lines);
+                }
+                lines
+            }
+        }
+    }
+
+    /// Collects the lines of a markdown block.
+    fn block_lines(node: &Node, lines: &mut Vec<Vec<(String, TheTooltipTextStyle)>>) {
+        match node {
+            Node::Heading(_) => {
+                let mut line = vec![];
+                Self::inline_words(node, TheTooltipTextStyle::Heading, &mut line);
+                lines.push(line);
+            }
+            Node::Paragraph(_) => {
+                let mut line = vec![];
+                Self::inline_words(node, TheTooltipTextStyle::Text, &mut line);
+                // Hard breaks split the paragraph.
+                for part in line.split(|(text, _)| text == "\n") {
+                    lines.push(part.to_vec());
+                }
+            }
+            Node::ListItem(item) => {
+                for (index, child) in item.children.iter().enumerate() {
+                    let start = lines.len();
+                    Self::block_lines(child, lines);
+                    if index == 0 && start < lines.len() {
+                        lines[start].insert(0, ("•".to_string(), TheTooltipTextStyle::Text));
+                    }
+                }
+            }
+            Node::Code(code) => {
+                for line in code.value.lines() {
+                    lines.push(vec![(line.to_string(), TheTooltipTextStyle::Code)]);
+                }
+            }
+            _ => {
+                if let Some(children) = node.children() {
+                    for child in children {
+                        Self::block_lines(child, lines);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Collects the words of inline markdown.
+    fn inline_words(
+        node: &Node,
+        style: TheTooltipTextStyle,
+        words: &mut Vec<(String, TheTooltipTextStyle)>,
+    ) {
+        match node {
+            Node::Text(text) => {
+                for word in text.value.split_whitespace() {
+                    words.push((word.to_string(), style));
+                }
+            }
+            Node::InlineCode(code) => {
+                words.push((code.value.clone(), TheTooltipTextStyle::Code));
+            }
+            Node::Break(_) => words.push(("\n".to_string(), style)),
+            _ => {
+                let style = match node {
+                    Node::Strong(_) if style == TheTooltipTextStyle::Text => {
+                        TheTooltipTextStyle::Strong
+                    }
+                    Node::Emphasis(_) if style == TheTooltipTextStyle::Text => {
+                        TheTooltipTextStyle::Emphasis
+                    }
+                    _ => style,
+                };
+                if let Some(children) = node.children() {
+                    for child in children {
+                        Self::inline_words(child, style, words);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Shows the tooltip of the hovered widget after the hover delay. The tooltip is placed below
+/// the mouse, or above it if there is no room, and kept on screen. It is hidden when the mouse
+/// leaves the widget and on clicks, key presses and scrolling.
+pub struct TheTooltipOverlay {
+    /// The time the mouse has to rest on a widget until its tooltip is shown.
+    pub delay: Duration,
+    /// The width at which tooltips are wrapped.
+    pub max_width: i32,
+    pub enabled: bool,
+
+    /// The hovered widget and its tooltip.
+    hovered: Option<(TheId, TheTooltip)>,
+    /// The time the current widget was hovered.
+    since: Instant,
+    /// The mouse position, the tooltip is placed relative to it.
+    coord: Vec2<i32>,
+    /// True if the tooltip was dismissed, it stays hidden until another widget is hovered.
+    dismissed: bool,
+
+    /// The words and the screen rectangle of the visible tooltip.
+    visible: Option<(Vec<TheTooltipWord>, TheDim)>,
+}
+
+impl Default for TheTooltipOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TheTooltipOverlay {
+    pub fn new() -> Self {
+        Self {
+            delay: Duration::from_millis(600),
+            max_width: 280,
+            enabled: true,
+
+            hovered: None,
+            since: Instant::now(),
+            coord: Vec2::zero(),
+            dismissed: false,
+
+            visible: None,
+        }
+    }
+
+    /// The screen rectangle of the visible tooltip.
+    pub fn visible(&self) -> Option<TheDim> {
+        self.visible.as_ref().map(|(_, dim)| *dim)
+    }
+
+    /// Sets the hovered widget and its tooltip, None if no widget with a tooltip is hovered.
+    /// Returns true if a visible tooltip was hidden.
+    pub(crate) fn hover(&mut self, hovered: Option<(TheId, TheTooltip)>, coord: Vec2<i32>) -> bool {
+        let same = match (&self.hovered, &hovered) {
+            (Some((a, a_tooltip)), Some((b, b_tooltip))) => a == b && a_tooltip == b_tooltip,
+            (None, None) => true,
+            _ => false,
+        };
+        if same {
+            // The tooltip stays where it was shown.
+            if self.visible.is_none() {
+                self.coord = coord;
+            }
+            return false;
+        }
+
+        self.hovered = hovered;
+        self.since = Instant::now();
+        self.coord = coord;
+        self.dismissed = false;
+        self.visible.take().is_some()
+    }
+
+    /// Hides the tooltip until another widget is hovered. Returns true if it was visible.
+    pub(crate) fn dismiss(&mut self) -> bool {
+        self.dismissed = true;
+        self.visible.take().is_some()
+    }
+
+    /// Returns true if the tooltip has to be shown, i.e. if the delay passed and it is not
+    /// visible yet.
+    pub(crate) fn update(&mut self, now: Instant) -> bool {
+        self.enabled
+            && self.hovered.is_some()
+            && !self.dismissed
+            && self.visible.is_none()
+            && now.saturating_duration_since(self.since) >= self.delay
+    }
+
+    /// Draws the tooltip if it is due and returns its screen rectangle.
+    pub(crate) fn draw(
+        &mut self,
+        pixels: &mut [u8],
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+    ) -> Option<TheDim> {
+        if self.visible.is_none() && self.update(Instant::now()) {
+            let (_, tooltip) = self.hovered.as_ref()?;
+            let (words, size) = tooltip.layout(self.max_width, &ctx.draw);
+            let dim = self.place(size + Vec2::broadcast(2 * PADDING), ctx);
+            self.visible = Some((words, dim));
+        }
+
+        let (words, dim) = self.visible.as_ref()?;
+        let (width, height) = (ctx.width as i32, ctx.height as i32);
+        if dim.x < 0 || dim.y < 0 || dim.x + dim.width > width || dim.y + dim.height > height {
+            return None;
+        }
+
+        let stride = ctx.width;
+        style.draw_tooltip(pixels, stride, dim, words, ctx);
+        Some(*dim)
+    }
+
+    /// Places a tooltip of the given size below the mouse, or above it if it does not fit, and
+    /// keeps it on screen.
+    fn place(&self, size: Vec2<i32>, ctx: &TheContext) -> TheDim {
+        let (width, height) = (ctx.width as i32, ctx.height as i32);
+
+        let mut y = self.coord.y + 20;
+        if y + size.y > height {
+            y = self.coord.y - 8 - size.y;
+        }
+        let x = self.coord.x.min(width - size.x - 2).max(2);
+        let y = y.min(height - size.y - 2).max(2);
+
+        TheDim::rect(x, y, size.x, size.y)
+    }
+
+    /// Adds the visible tooltip to the accessibility tree, named by its text.
+    pub(crate) fn collect_access_nodes(
+        &self,
+        tree: &mut TheAccessTree,
+        parent: u64,
+        ctx: &TheContext,
+    ) {
+        if let (Some((id, _)), Some((words, dim))) = (&self.hovered, &self.visible) {
+            let text = words
+                .iter()
+                .map(|word| word.text.as_str())
+                .collect::<Vec<&str>>()
+                .join(" ");
+            let info = TheAccessInfo::named(TheAccessRole::Tooltip, &text);
+            tree.add(parent, id, info, *dim, ctx);
+        }
+    }
+
+    /// Swaps the hover state with the one of another window, the settings are kept.
+    pub(crate) fn swap_state(&mut self, other: &mut TheTooltipOverlay) {
+        std::mem::swap(&mut self.hovered, &mut other.hovered);
+        std::mem::swap(&mut self.since, &mut other.since);
+        std::mem::swap(&mut self.coord, &mut other.coord);
+        std::mem::swap(&mut self.dismissed, &mut other.dismissed);
+        std::mem::swap(&mut self.visible, &mut other.visible);
+    }
+
+    /// The distance of the text to the border of the tooltip.
+    pub fn padding() -> i32 {
+        PADDING
+    }
+}
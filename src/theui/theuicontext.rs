@@ -23,9 +23,21 @@ impl TheFileExtension {
 pub struct TheUIContext {
     pub font: Option<Font>,
     icons: FxHashMap<String, TheRGBABuffer>,
+    /// The @2x variants of the icons, stored under the name of the icon.
+    icons_2x: FxHashMap<String, TheRGBABuffer>,
+
+    /// The scale factor of the display, set via TheContext::set_scale_factor.
+    pub scale_factor: f32,
+    /// The additional window whose events are currently processed, None for the main window.
+    pub window: Option<TheId>,
 
     pub focus: Option<TheId>,
+    /// The focus widget if it was focused from the keyboard (Tab / Shift+Tab).
     pub keyboard_focus: Option<TheId>,
+    /// Explicit tab indices of widgets, see set_tab_index().
+    pub tab_indices: FxHashMap<Uuid, i32>,
+    /// The tooltips set via set_tooltip().
+    pub tooltips: FxHashMap<Uuid, TheTooltip>,
     pub hover: Option<TheId>,
     pub overlay: Option<TheId>,
     pub context_menu: Option<TheContextMenu>,
@@ -44,6 +56,8 @@ pub struct TheUIContext {
     pub file_requester_receiver: Option<(TheId, Receiver<Vec<PathBuf>>)>,
 
     pub accelerators: FxHashMap<TheId, TheAccelerator>,
+    /// The user keymap, overrides the accelerators and shortcuts of the named actions.
+    pub keymap: TheKeymap,
 
     pub clipboard: Option<TheValue>,
     pub clipboard_app_type: Option<String>,
@@ -59,6 +73,7 @@ impl TheUIContext {
     pub fn new() -> Self {
         let mut font: Option<Font> = None;
         let mut icons: FxHashMap<String, TheRGBABuffer> = FxHashMap::default();
+        let mut icons_2x: FxHashMap<String, TheRGBABuffer> = FxHashMap::default();
 
         for file in Embedded::iter() {
             let name = file.as_ref();
@@ -100,10 +115,22 @@ impl TheUIContext {
 
                         let mut cut_name = name.replace("icons/", "");
                         cut_name = cut_name.replace(".png", "");
-                        icons.insert(
-                            cut_name.to_string(),
-                            TheRGBABuffer::from(rgba_bytes, info.width, info.height),
-                        );
+                        if let Some(cut_name) = cut_name.strip_suffix("@2x") {
+                            icons_2x.insert(
+                                cut_name.to_string(),
+                                TheRGBABuffer::from_scaled(
+                                    rgba_bytes,
+                                    info.width,
+                                    info.height,
+                                    2.0,
+                                ),
+                            );
+                        } else {
+                            icons.insert(
+                                cut_name.to_string(),
+                                TheRGBABuffer::from(rgba_bytes, info.width, info.height),
+                            );
+                        }
                     }
                 }
             }
@@ -112,12 +139,18 @@ impl TheUIContext {
         Self {
             focus: None,
             keyboard_focus: None,
+            tab_indices: FxHashMap::default(),
+            tooltips: FxHashMap::default(),
             hover: None,
             overlay: None,
             context_menu: None,
 
             font,
             icons,
+            icons_2x,
+
+            scale_factor: 1.0,
+            window: None,
 
             disabled_ids: FxHashSet::default(),
             state_events_sender: None,
@@ -132,6 +165,7 @@ impl TheUIContext {
             file_requester_receiver: None,
 
             accelerators: FxHashMap::default(),
+            keymap: TheKeymap::default(),
 
             clipboard: None,
             clipboard_app_type: None,
@@ -155,17 +189,36 @@ impl TheUIContext {
         self.set_widget_state(id.to_string(), TheWidgetState::None);
     }
 
-    /// Adds an icon to the library.
+    /// Adds an icon to the library. Icons with a name ending in "@2x" are the high resolution
+    /// variants of the icon without the suffix, they are only used if the 1x icon exists.
     pub fn add_icon(&mut self, name: String, icon: TheRGBABuffer) {
-        self.icons.insert(name, icon);
+        if let Some(name) = name.strip_suffix("@2x") {
+            let icon = if icon.scale() == 1.0 {
+                TheRGBABuffer::from_scaled(
+                    icon.pixels().to_vec(),
+                    icon.dim().width as u32,
+                    icon.dim().height as u32,
+                    2.0,
+                )
+            } else {
+                icon
+            };
+            self.icons_2x.insert(name.to_string(), icon);
+        } else {
+            self.icons.insert(name, icon);
+        }
     }
 
-    /// Returns an icon of the given name from the embedded style icons
+    /// Returns an icon of the given name from the embedded style icons. On HiDPI displays the @2x
+    /// variant of the icon is returned if available.
     pub fn icon(&self, name: &str) -> Option<&TheRGBABuffer> {
-        if let Some(icon) = self.icons.get(name) {
-            return Some(icon);
+        let icon = self.icons.get(name)?;
+        if self.scale_factor > 1.0 {
+            if let Some(icon) = self.icons_2x.get(name) {
+                return Some(icon);
+            }
         }
-        None
+        Some(icon)
     }
 
     /// Sets the focus to the given widget
@@ -176,12 +229,41 @@ impl TheUIContext {
             }
             self.send(TheEvent::GainedFocus(id.clone()));
             self.focus = Some(id.clone());
+            self.keyboard_focus = None;
         }
     }
 
+    /// Sets the focus to the given widget and marks it as focused from the keyboard.
+    pub fn set_keyboard_focus(&mut self, id: &TheId) {
+        self.set_focus(id);
+        self.keyboard_focus = Some(id.clone());
+    }
+
     /// Clears the focus state.
     pub fn clear_focus(&mut self) {
         self.focus = None;
+        self.keyboard_focus = None;
+    }
+
+    /// Sets the tab index of the given widget. Widgets with a tab index are focused first, in
+    /// ascending order, followed by all other widgets in layout order. A negative index removes
+    /// the widget from the focus order, None restores the layout order.
+    pub fn set_tab_index(&mut self, id: &TheId, index: Option<i32>) {
+        if let Some(index) = index {
+            self.tab_indices.insert(id.uuid, index);
+        } else {
+            self.tab_indices.remove(&id.uuid);
+        }
+    }
+
+    /// Sets the tooltip of the given widget, overriding the tooltip of the widget itself. None
+    /// restores the tooltip of the widget.
+    pub fn set_tooltip(&mut self, id: &TheId, tooltip: Option<TheTooltip>) {
+        if let Some(tooltip) = tooltip {
+            self.tooltips.insert(id.uuid, tooltip);
+        } else {
+            self.tooltips.remove(&id.uuid);
+        }
     }
 
     /// Checks if the given id has focus
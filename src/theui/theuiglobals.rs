@@ -102,23 +102,7 @@ pub fn open_text_dialog(
 
 /// Opens a new info dialog.
 pub fn open_info_dialog(window_title: &str, text: &str, ui: &mut TheUI, ctx: &mut TheContext) {
-    let width = 300;
-    let height = 100;
-
-    let mut canvas = TheCanvas::new();
-    canvas.limiter_mut().set_max_size(Vec2::new(width, height));
-
-    let mut hlayout: TheHLayout = TheHLayout::new(TheId::empty());
-    // text_layout.set_margin(vec4i(20, 20, 20, 20));
-
-    hlayout.limiter_mut().set_max_width(width);
-    let mut text_widget = TheText::new(TheId::empty());
-    text_widget.set_text(text.to_string());
-    text_widget.limiter_mut().set_max_width(200);
-    hlayout.add_widget(Box::new(text_widget));
-
-    canvas.set_layout(hlayout);
-    ui.show_dialog(window_title, canvas, vec![TheDialogButtonRole::Accept], ctx);
+    ui.push_dialog(TheDialog::message(TheId::empty(), window_title, text), ctx);
 }
 
 /// Opens a new dialog with a deletion confirmation text.
@@ -129,26 +113,12 @@ pub fn open_delete_confirmation_dialog(
     ui: &mut TheUI,
     ctx: &mut TheContext,
 ) {
-    let width = 300;
-    let height = 100;
-
-    let mut canvas = TheCanvas::new();
-    canvas.limiter_mut().set_max_size(Vec2::new(width, height));
-
-    let mut hlayout: TheHLayout = TheHLayout::new(TheId::empty());
-    // text_layout.set_margin(vec4i(20, 20, 20, 20));
-
-    hlayout.limiter_mut().set_max_width(width);
-    let mut text_widget = TheText::new(TheId::named_with_id("Dialog Value", uuid));
-    text_widget.set_text(text.to_string());
-    text_widget.limiter_mut().set_max_width(200);
-    hlayout.add_widget(Box::new(text_widget));
-
-    canvas.set_layout(hlayout);
-    ui.show_dialog(
+    let mut dialog = TheDialog::new(
+        TheId::named_with_id("Dialog Value", uuid),
         window_title,
-        canvas,
-        vec![TheDialogButtonRole::Delete, TheDialogButtonRole::Reject],
-        ctx,
+        TheDialog::text_canvas(text, Some(uuid)),
     );
+    dialog.add_button(TheDialogButtonRole::Delete.into());
+    dialog.add_button(TheDialogButtonRole::Reject.into());
+    ui.push_dialog(dialog, ctx);
 }
@@ -102,6 +102,8 @@ pub enum TheValue {
     TimeDuration(TheTime, TheTime),
     TileMask(TheTileMask),
     Image(TheRGBABuffer),
+    #[cfg(feature = "code")]
+    CodeObject(TheCodeObject),
 }
 
 use TheValue::*;
@@ -167,7 +169,7 @@ impl TheValue {
 
     pub fn to_key_code(&self) -> Option<TheKeyCode> {
         match self {
-            KeyCode(v) => Some(v.clone()),
+            KeyCode(v) => Some(*v),
             _ => None,
         }
     }
@@ -278,6 +280,8 @@ impl TheValue {
             TimeDuration(s, e) => format!("Time Duration: {:?} {:?}", s.to_time24(), e.to_time24()),
             TileMask(_) => str!("Pixels in a tile"),
             Image(b) => format!("Image ({}, {})", b.dim().width, b.dim().height),
+            #[cfg(feature = "code")]
+            CodeObject(_) => "Object".to_string(),
         }
     }
 
@@ -325,6 +329,8 @@ impl TheValue {
             TimeDuration(s, e) => format!("{} - {}", s.to_time24(), e.to_time24()),
             TileMask(_) => str!("Pixels"),
             Image(b) => format!("Image ({}, {})", b.dim().width, b.dim().height),
+            #[cfg(feature = "code")]
+            CodeObject(_) => "Object".to_string(),
         }
     }
 }
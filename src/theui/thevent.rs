@@ -41,6 +41,8 @@ pub enum TheEvent {
     RedirectWidgetValueToLayout(TheId, TheId, TheValue),
 
     SetStatusText(TheId, String),
+    /// Shows a transient notification, see TheUI::show_toast().
+    ShowToast(TheToast),
 
     // Tabbar, Groupbutton
     IndexChanged(TheId, usize),
@@ -76,12 +78,25 @@ pub enum TheEvent {
     // SDF
     SDFIndexChanged(TheId, u32),
 
+    // Code
+    #[cfg(feature = "code")]
+    CodeEditorChanged(TheId, TheCodeGrid),
+    #[cfg(feature = "code")]
+    CodeEditorSelectionChanged(TheId, Option<(u16, u16)>),
+    #[cfg(feature = "code")]
+    CodeEditorApply(TheId),
+    #[cfg(feature = "code")]
+    CodeBundleChanged(TheCodeBundle, bool),
+
     // Show the given context menu at the given (global) coordinates.
     ShowContextMenu(TheId, Vec2<i32>, TheContextMenu),
     ShowMenu(TheId, Vec2<i32>, TheContextMenu),
     ContextMenuSelected(TheId, TheId),
     ContextMenuClosed(TheId),
 
+    // A shortcut of the TheUI shortcut registry was pressed.
+    ShortcutTriggered(TheId),
+
     // Nodes
     NodeSelectedIndexChanged(TheId, Option<usize>),
     NodeDragged(TheId, usize, Vec2<i32>),
@@ -96,9 +111,32 @@ pub enum TheEvent {
     // These events define layout states.
     SetStackIndex(TheId, usize),
     NewListItemSelected(TheId, TheId),
+    // A list or tree item was clicked (item, layout). The layout applies the click to its
+    // selection and sends NewListItemSelected if the selection changed.
+    ListItemClicked(TheId, TheId),
+    // The selected items of a list or tree layout changed (layout, selected items).
+    SelectionChanged(TheId, Vec<TheId>),
+    // A key was pressed while a list or tree item had the focus (item, layout, key).
+    ListItemKeyDown(TheId, TheId, TheValue),
     ScrollLayout(TheId, Vec2<i32>),
     SnapperStateChanged(TheId, TheId, bool),
+    // An item or node of a tree layout was dragged (item or node, layout, text, offset).
+    TreeItemDragStarted(TheId, TheId, String, Vec2<i32>),
+    // An item or node was dropped at a new position of a tree layout (layout, move). Nothing is
+    // moved until the app passes the move to TheTreeLayoutTrait::apply_move(), ignoring the
+    // event vetoes it.
+    TreeMoveRequested(TheId, TheTreeMove),
     TreeOpenStateChanged(TheId, bool),
+    // A row of a table layout was selected (layout, row of the data source).
+    TableRowSelected(TheId, usize),
+    // A cell of a table layout is to be edited (layout, row, column), opens the cell editor.
+    TableEditCell(TheId, usize, usize),
+    // A cell of a table layout was edited (layout, row, column, value). The value was already
+    // passed to TheTableDataSource::set_cell().
+    TableCellEdited(TheId, usize, usize, TheValue),
+    // The panels of a dock layout were moved, tabbed, resized or brought to the front (layout).
+    // Apps save TheDockLayoutTrait::arrangement() to restore it on the next launch.
+    DockArrangementChanged(TheId),
 
     // Utility
     FileRequesterResult(TheId, Vec<std::path::PathBuf>),
@@ -107,6 +145,8 @@ pub enum TheEvent {
 
     // The top canvas has been resized.
     Resize,
+    // An additional window was closed, see TheUI::open_window().
+    WindowClosed(TheId),
     // A widget has been resized,
     WidgetResized(TheId, TheDim),
 
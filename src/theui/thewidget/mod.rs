@@ -1,6 +1,8 @@
 use crate::prelude::*;
 
 pub mod thecheckbutton;
+#[cfg(feature = "code")]
+pub mod thecodeview;
 pub mod thecolorbutton;
 pub mod thecolorpicker;
 pub mod thedirectionpicker;
@@ -54,6 +56,8 @@ pub mod prelude {
     pub use crate::theui::thewidget::thetreeitem::{TheTreeItem, TheTreeItemTrait};
     pub use crate::theui::thewidget::thetreetext::{TheTreeText, TheTreeTextTrait};
 
+    #[cfg(feature = "code")]
+    pub use crate::theui::thewidget::thecodeview::{TheCodeView, TheCodeViewTrait};
     pub use crate::theui::thewidget::thecolorbutton::TheColorButton;
 
     pub use crate::theui::thewidget::thedropdownmenu::TheDropdownMenu;
@@ -215,6 +219,23 @@ pub trait TheWidget: Send {
     /// Sets the status text for the widget.
     fn set_status_text(&mut self, text: &str) {}
 
+    /// The tooltip of the widget, by default its status text. Tooltips set via
+    /// TheUIContext::set_tooltip() take precedence.
+    fn tooltip(&self) -> Option<TheTooltip> {
+        self.status_text()
+            .filter(|text| !text.is_empty())
+            .map(TheTooltip::Text)
+    }
+
+    /// Describes the widget for assistive technologies. By default the role is unknown and the
+    /// status text is used as the description.
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            description: self.status_text(),
+            ..Default::default()
+        }
+    }
+
     /// Draw the widget in the given style
     fn draw(
         &mut self,
@@ -248,6 +269,17 @@ pub trait TheWidget: Send {
         false
     }
 
+    /// Widgets who can be focused with Tab / Shift+Tab return true
+    fn accepts_focus(&self) -> bool {
+        self.supports_text_input()
+    }
+
+    /// Widgets who use the Tab key themselves (like for indentation) return true. Focus is then
+    /// moved with Ctrl+Tab / Ctrl+Shift+Tab instead.
+    fn supports_tab_input(&self) -> bool {
+        false
+    }
+
     /// If this function returns true it indicates that the widget needs a redraw.
     fn needs_redraw(&mut self) -> bool {
         false
@@ -314,6 +346,12 @@ pub trait TheWidget: Send {
         None
     }
 
+    /// Attempts to cast to TheCodeViewTrait. Only valid for TheCodeView.
+    #[cfg(feature = "code")]
+    fn as_code_view(&mut self) -> Option<&mut dyn TheCodeViewTrait> {
+        None
+    }
+
     /// Attempts to cast to TheRenderViewTrait. Only valid for TheRenderView.
     fn as_render_view(&mut self) -> Option<&mut dyn TheRenderViewTrait> {
         None
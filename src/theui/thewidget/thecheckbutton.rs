@@ -193,13 +193,26 @@ impl TheWidget for TheCheckButton {
                 icon.dim().width as usize,
                 icon.dim().height as usize,
             );
-            ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+            ctx.draw.blend_slice_scaled(
+                buffer.pixels_mut(),
+                icon.pixels(),
+                icon.scale(),
+                &r,
+                stride,
+            );
         }
 
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            description: self.status.clone(),
+            checked: Some(self.state == TheWidgetState::Selected),
+            ..TheAccessInfo::new(TheAccessRole::CheckBox)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -0,0 +1,641 @@
+use crate::prelude::*;
+
+/// Displays and edits a TheCodeGrid. Atoms are drawn as SDF shapes into a buffer at zoom 1.0
+/// which is scaled into the widget by the zoom factor. Drop an atom (a TheDrop named
+/// "Code Editor Atom" containing the atom as JSON) onto a cell to insert it.
+pub struct TheCodeView {
+    id: TheId,
+    limiter: TheSizeLimiter,
+
+    state: TheWidgetState,
+
+    codegrid: TheCodeGrid,
+    debug_module: TheDebugModule,
+    breakpoints: Vec<(u16, u16)>,
+    debug_location: Option<(u16, u16)>,
+
+    buffer: TheRGBABuffer,
+    grid_size: i32,
+
+    scroll_offset: Vec2<i32>,
+    zoom: f32,
+
+    selected: Option<(u16, u16)>,
+    hover: Option<(u16, u16)>,
+    drop: Option<(u16, u16)>,
+
+    hscrollbar: TheId,
+    vscrollbar: TheId,
+
+    dim: TheDim,
+    is_dirty: bool,
+    buffer_is_dirty: bool,
+}
+
+impl TheWidget for TheCodeView {
+    fn new(id: TheId) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            id,
+            limiter: TheSizeLimiter::new(),
+
+            state: TheWidgetState::None,
+
+            codegrid: TheCodeGrid::new(),
+            debug_module: TheDebugModule::default(),
+            breakpoints: vec![],
+            debug_location: None,
+
+            buffer: TheRGBABuffer::empty(),
+            grid_size: 50,
+
+            scroll_offset: Vec2::zero(),
+            zoom: 1.0,
+
+            selected: None,
+            hover: None,
+            drop: None,
+
+            hscrollbar: TheId::empty(),
+            vscrollbar: TheId::empty(),
+
+            dim: TheDim::zero(),
+            is_dirty: true,
+            buffer_is_dirty: true,
+        }
+    }
+
+    fn id(&self) -> &TheId {
+        &self.id
+    }
+
+    fn on_event(&mut self, event: &TheEvent, ctx: &mut TheContext) -> bool {
+        let mut redraw = false;
+
+        match event {
+            TheEvent::MouseDown(coord) => {
+                ctx.ui.set_focus(self.id());
+                let loc = self.get_grid_location(*coord);
+                if loc != self.selected {
+                    self.selected = loc;
+                    ctx.ui.send(TheEvent::CodeEditorSelectionChanged(
+                        self.id.clone(),
+                        self.selected,
+                    ));
+                    self.status_message(ctx);
+                    self.buffer_is_dirty = true;
+                    self.is_dirty = true;
+                    redraw = true;
+                }
+            }
+            TheEvent::Hover(coord) => {
+                if !self.id().equals(&ctx.ui.hover) {
+                    ctx.ui.set_hover(self.id());
+                }
+                let loc = self.get_grid_location(*coord);
+                if loc != self.hover {
+                    self.hover = loc;
+                    self.buffer_is_dirty = true;
+                    self.is_dirty = true;
+                    redraw = true;
+                }
+            }
+            TheEvent::LostHover(_id) if self.hover.is_some() || self.drop.is_some() => {
+                self.hover = None;
+                self.drop = None;
+                self.buffer_is_dirty = true;
+                self.is_dirty = true;
+                redraw = true;
+            }
+            TheEvent::DropPreview(coord, drop) if drop.id.name == "Code Editor Atom" => {
+                let loc = self.get_grid_location(*coord);
+                if loc != self.drop {
+                    self.drop = loc;
+                    self.buffer_is_dirty = true;
+                    self.is_dirty = true;
+                    redraw = true;
+                }
+            }
+            TheEvent::Drop(coord, drop) => {
+                if drop.id.name == "Code Editor Atom" {
+                    if let Some(loc) = self.get_grid_location(*coord) {
+                        let atom = TheCodeAtom::from_json(&drop.data);
+                        self.codegrid.insert_atom(loc, atom);
+                        self.selected = Some(loc);
+                        ctx.ui.send(TheEvent::CodeEditorChanged(
+                            self.id.clone(),
+                            self.codegrid.clone(),
+                        ));
+                        ctx.ui.send(TheEvent::CodeEditorSelectionChanged(
+                            self.id.clone(),
+                            self.selected,
+                        ));
+                    }
+                }
+                self.drop = None;
+                self.buffer_is_dirty = true;
+                self.is_dirty = true;
+                redraw = true;
+            }
+            TheEvent::KeyCodeDown(TheValue::KeyCode(key)) => {
+                if let Some(selected) = self.selected {
+                    let mut changed = false;
+                    let mut new_selection = selected;
+
+                    match key {
+                        TheKeyCode::Delete | TheKeyCode::Backspace => {
+                            new_selection = self.codegrid.delete(selected);
+                            changed = true;
+                        }
+                        TheKeyCode::Return => {
+                            self.codegrid.move_one_line_down(selected);
+                            new_selection = (0, selected.1 + 2);
+                            changed = true;
+                        }
+                        TheKeyCode::Space => {
+                            self.codegrid.insert_space(selected);
+                            changed = true;
+                        }
+                        TheKeyCode::Left => {
+                            new_selection.0 = selected.0.saturating_sub(1);
+                        }
+                        TheKeyCode::Right => {
+                            new_selection.0 = selected.0 + 1;
+                        }
+                        TheKeyCode::Up => {
+                            new_selection.1 = selected.1.saturating_sub(2);
+                        }
+                        TheKeyCode::Down => {
+                            new_selection.1 = selected.1 + 2;
+                        }
+                        _ => {}
+                    }
+
+                    if changed {
+                        ctx.ui.send(TheEvent::CodeEditorChanged(
+                            self.id.clone(),
+                            self.codegrid.clone(),
+                        ));
+                    }
+
+                    if new_selection != selected {
+                        self.selected = Some(new_selection);
+                        ctx.ui.send(TheEvent::CodeEditorSelectionChanged(
+                            self.id.clone(),
+                            self.selected,
+                        ));
+                        self.status_message(ctx);
+                    }
+
+                    if changed || new_selection != selected {
+                        self.buffer_is_dirty = true;
+                        self.is_dirty = true;
+                        redraw = true;
+                    }
+                }
+            }
+            TheEvent::MouseWheel(delta) => {
+                let d = Vec2::new(-delta.x / 4, -delta.y / 4);
+                ctx.ui.send(TheEvent::ScrollBy(self.hscrollbar.clone(), d));
+                ctx.ui.send(TheEvent::ScrollBy(self.vscrollbar.clone(), d));
+            }
+            _ => {}
+        }
+        redraw
+    }
+
+    fn dim(&self) -> &TheDim {
+        &self.dim
+    }
+
+    fn dim_mut(&mut self) -> &mut TheDim {
+        &mut self.dim
+    }
+
+    fn set_dim(&mut self, dim: TheDim, _ctx: &mut TheContext) {
+        if self.dim != dim {
+            self.dim = dim;
+            self.is_dirty = true;
+        }
+    }
+
+    fn limiter(&self) -> &TheSizeLimiter {
+        &self.limiter
+    }
+
+    fn limiter_mut(&mut self) -> &mut TheSizeLimiter {
+        &mut self.limiter
+    }
+
+    fn needs_redraw(&mut self) -> bool {
+        self.is_dirty
+    }
+
+    fn set_needs_redraw(&mut self, redraw: bool) {
+        self.is_dirty = redraw;
+    }
+
+    fn state(&self) -> TheWidgetState {
+        self.state
+    }
+
+    fn set_state(&mut self, state: TheWidgetState) {
+        self.state = state;
+        self.is_dirty = true;
+    }
+
+    fn supports_hover(&mut self) -> bool {
+        true
+    }
+
+    fn draw(
+        &mut self,
+        buffer: &mut TheRGBABuffer,
+        style: &mut Box<dyn TheStyle>,
+        ctx: &mut TheContext,
+    ) {
+        if !self.dim().is_valid() {
+            return;
+        }
+
+        if self.buffer.scale() != ctx.scale_factor {
+            self.buffer = TheRGBABuffer::new_scaled(*self.buffer.dim(), ctx.scale_factor);
+            self.buffer_is_dirty = true;
+        }
+
+        if self.adjust_buffer_to_grid() || self.buffer_is_dirty {
+            self.render_grid(style, ctx);
+        }
+
+        // The grid buffer is rendered at the scale of the target, blit the physical pixels
+        let scale = buffer.scale();
+        let physical = |v: i32| (v as f32 * scale).round() as i32;
+
+        let stride = buffer.physical_width();
+        let background = *style.theme().color(CodeGridBackground);
+
+        let target_len = buffer.pixels().len();
+        let src_width = self.buffer.physical_width() as f32;
+        let src_height = self.buffer.physical_height() as f32;
+        let src_stride = self.buffer.physical_width() as usize;
+
+        let (scroll_x, scroll_y) = (
+            physical(self.scroll_offset.x),
+            physical(self.scroll_offset.y),
+        );
+        let (buffer_x, buffer_y) = (physical(self.dim.buffer_x), physical(self.dim.buffer_y));
+
+        for target_y in 0..physical(self.dim.height) {
+            for target_x in 0..physical(self.dim.width) {
+                let src_x = (target_x + scroll_x) as f32 / self.zoom;
+                let src_y = (target_y + scroll_y) as f32 / self.zoom;
+
+                let target_index =
+                    ((buffer_y + target_y) * stride + target_x + buffer_x) as usize * 4;
+
+                if target_index + 4 > target_len {
+                    continue;
+                }
+
+                if src_x >= 0.0 && src_x < src_width && src_y >= 0.0 && src_y < src_height {
+                    let src_index = (src_y as usize * src_stride + src_x as usize) * 4;
+                    buffer.pixels_mut()[target_index..target_index + 4]
+                        .copy_from_slice(&self.buffer.pixels()[src_index..src_index + 4]);
+                } else {
+                    buffer.pixels_mut()[target_index..target_index + 4]
+                        .copy_from_slice(&background);
+                }
+            }
+        }
+
+        self.is_dirty = false;
+    }
+
+    fn as_code_view(&mut self) -> Option<&mut dyn TheCodeViewTrait> {
+        Some(self)
+    }
+
+    fn as_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+impl TheCodeView {
+    /// Returns the grid location for the given widget coordinate.
+    fn get_grid_location(&self, coord: Vec2<i32>) -> Option<(u16, u16)> {
+        let x = ((coord.x + self.scroll_offset.x) as f32 / self.zoom) as i32 / self.grid_size;
+        let y = ((coord.y + self.scroll_offset.y) as f32 / self.zoom) as i32 / self.grid_size;
+
+        if x >= 0 && y >= 0 && x <= u16::MAX as i32 && y <= u16::MAX as i32 {
+            Some((x as u16, y as u16))
+        } else {
+            None
+        }
+    }
+
+    /// Shows the help text of the selected atom (or the message at its location) in the statusbar.
+    fn status_message(&self, ctx: &mut TheContext) {
+        let mut text = String::new();
+        if let Some(selected) = self.selected {
+            if let Some(message) = self.codegrid.message(selected) {
+                text = message.message;
+            } else if let Some(atom) = self.codegrid.code.get(&selected) {
+                text = atom.help();
+            }
+        }
+        ctx.ui.send(TheEvent::SetStatusText(self.id.clone(), text));
+    }
+
+    /// Renders the codegrid into the buffer at zoom 1.0.
+    fn render_grid(&mut self, style: &mut Box<dyn TheStyle>, ctx: &mut TheContext) {
+        let grid_size = self.grid_size;
+        let stride = self.buffer.stride();
+
+        let mut sdf_canvas = TheSDFCanvas::new();
+        sdf_canvas.background = TheColor::from_u8_array(*style.theme().color(CodeGridBackground));
+        sdf_canvas.highlight = TheColor::from_u8_array(*style.theme().color(CodeGridSelected));
+        sdf_canvas.hover_highlight = TheColor::from_u8_array(*style.theme().color(CodeGridHover));
+
+        let border_color = TheColor::from_u8_array(*style.theme().color(CodeGridDark));
+
+        let mut cells = vec![];
+        for (pos, atom) in self.codegrid.code.iter() {
+            let dim = TheDim::new(
+                pos.0 as i32 * grid_size + 4,
+                pos.1 as i32 * grid_size + 4,
+                grid_size - 8,
+                grid_size - 8,
+            );
+
+            let index = sdf_canvas.sdfs.len();
+            if Some(*pos) == self.selected {
+                sdf_canvas.selected = Some(index);
+            }
+            if Some(*pos) == self.hover || Some(*pos) == self.drop {
+                sdf_canvas.hover = Some(index);
+            }
+            if let Some(message) = self.codegrid.message(*pos) {
+                if message.message_type == TheCodeGridMessageType::Error {
+                    sdf_canvas.error = Some(index);
+                }
+            }
+
+            sdf_canvas.add(
+                atom.to_sdf(dim, 1.0),
+                ThePattern::SolidWithBorder(
+                    TheColor::from_u8_array(atom.to_color()),
+                    border_color.clone(),
+                    1.5,
+                ),
+            );
+            cells.push((*pos, atom.describe()));
+        }
+
+        sdf_canvas.render(&mut self.buffer);
+
+        // Outline empty selected, hovered or drop target cells.
+        for (pos, color) in [
+            (self.drop, CodeGridHover),
+            (self.hover, CodeGridHover),
+            (self.selected, CodeGridSelected),
+        ] {
+            if let Some(pos) = pos {
+                if !self.codegrid.code.contains_key(&pos) {
+                    let rect = (
+                        pos.0 as usize * grid_size as usize + 4,
+                        pos.1 as usize * grid_size as usize + 4,
+                        grid_size as usize - 8,
+                        grid_size as usize - 8,
+                    );
+                    if rect.0 + rect.2 <= stride
+                        && (rect.1 + rect.3) as i32 <= self.buffer.dim().height
+                    {
+                        ctx.draw.rect_outline(
+                            self.buffer.pixels_mut(),
+                            &rect,
+                            stride,
+                            style.theme().color(color),
+                        );
+                    }
+                }
+            }
+        }
+
+        let text_color = *style.theme().color(CodeGridText);
+        for (pos, text) in cells {
+            let rect = (
+                pos.0 as usize * grid_size as usize + 6,
+                pos.1 as usize * grid_size as usize + 4,
+                grid_size as usize - 12,
+                grid_size as usize - 8,
+            );
+            ctx.draw.text_rect_blend(
+                self.buffer.pixels_mut(),
+                &rect,
+                stride,
+                &text,
+                TheFontSettings {
+                    size: 12.0,
+                    ..Default::default()
+                },
+                &text_color,
+                TheHorizontalAlign::Center,
+                TheVerticalAlign::Center,
+            );
+        }
+
+        // Debug values are shown in the cell below their location.
+        if self.debug_module.codegrid_id == self.codegrid.id {
+            let executed_color = *style.theme().color(CodeGridSelected);
+            for pos in self.debug_module.executed.iter() {
+                let rect = (
+                    pos.0 as usize * grid_size as usize + 4,
+                    pos.1 as usize * grid_size as usize + grid_size as usize - 6,
+                    grid_size as usize - 8,
+                    2,
+                );
+                if rect.0 + rect.2 <= stride && (rect.1 + rect.3) as i32 <= self.buffer.dim().height
+                {
+                    ctx.draw
+                        .rect(self.buffer.pixels_mut(), &rect, stride, &executed_color);
+                }
+            }
+
+            for (pos, (_, value)) in self.debug_module.values.iter() {
+                let rect = (
+                    pos.0 as usize * grid_size as usize + 2,
+                    (pos.1 as usize + 1) * grid_size as usize,
+                    grid_size as usize - 4,
+                    grid_size as usize / 2,
+                );
+                if rect.0 + rect.2 <= stride && (rect.1 + rect.3) as i32 <= self.buffer.dim().height
+                {
+                    ctx.draw.text_rect_blend(
+                        self.buffer.pixels_mut(),
+                        &rect,
+                        stride,
+                        &value.describe(),
+                        TheFontSettings {
+                            size: 11.0,
+                            ..Default::default()
+                        },
+                        &text_color,
+                        TheHorizontalAlign::Center,
+                        TheVerticalAlign::Center,
+                    );
+                }
+            }
+        }
+
+        // Breakpoints are shown as a dot in the top left corner, the location the debugger
+        // paused at is outlined.
+        let red = *style.theme().color(Red);
+        for pos in &self.breakpoints {
+            let rect = (
+                pos.0 as usize * grid_size as usize + 1,
+                pos.1 as usize * grid_size as usize + 1,
+                10,
+                10,
+            );
+            if rect.0 + rect.2 <= stride && (rect.1 + rect.3) as i32 <= self.buffer.dim().height {
+                ctx.draw
+                    .circle(self.buffer.pixels_mut(), &rect, stride, &red, 5.0);
+            }
+        }
+
+        if let Some(pos) = self.debug_location {
+            let rect = (
+                pos.0 as usize * grid_size as usize + 1,
+                pos.1 as usize * grid_size as usize + 1,
+                grid_size as usize - 2,
+                grid_size as usize - 2,
+            );
+            if rect.0 + rect.2 <= stride && (rect.1 + rect.3) as i32 <= self.buffer.dim().height {
+                let color = *style.theme().color(Green);
+                ctx.draw
+                    .rect_outline(self.buffer.pixels_mut(), &rect, stride, &color);
+            }
+        }
+
+        self.buffer_is_dirty = false;
+    }
+}
+
+/// TheCodeView specific functions.
+pub trait TheCodeViewTrait: TheWidget {
+    /// Returns a reference to the codegrid.
+    fn codegrid(&self) -> &TheCodeGrid;
+    /// Returns a mutable reference to the codegrid.
+    fn codegrid_mut(&mut self) -> &mut TheCodeGrid;
+    /// Sets the codegrid and clears the selection.
+    fn set_codegrid(&mut self, codegrid: TheCodeGrid);
+    /// Sets the atom at the given grid location.
+    fn set_grid_atom(&mut self, location: (u16, u16), atom: TheCodeAtom);
+    /// Sets the debug module, its values are displayed if it belongs to the codegrid.
+    fn set_debug_module(&mut self, debug_module: TheDebugModule);
+    /// Sets the grid locations which show a breakpoint.
+    fn set_breakpoints(&mut self, breakpoints: Vec<(u16, u16)>);
+    /// Sets the location the debugger paused at, None if the debugger is not paused.
+    fn set_debug_location(&mut self, location: Option<(u16, u16)>);
+    /// Returns the current selection.
+    fn selection(&self) -> Option<(u16, u16)>;
+    /// Sets the current selection.
+    fn set_selection(&mut self, selection: Option<(u16, u16)>);
+    /// Resizes the buffer to fit the codegrid (plus room to add code). Returns true if the size changed.
+    fn adjust_buffer_to_grid(&mut self) -> bool;
+    /// Returns the rendered grid at zoom 1.0.
+    fn buffer(&self) -> &TheRGBABuffer;
+    fn zoom(&self) -> f32;
+    fn set_zoom(&mut self, zoom: f32);
+    fn set_scroll_offset(&mut self, offset: Vec2<i32>);
+    fn set_scrollbar_ids(&mut self, hscrollbar: TheId, vscrollbar: TheId);
+}
+
+impl TheCodeViewTrait for TheCodeView {
+    fn codegrid(&self) -> &TheCodeGrid {
+        &self.codegrid
+    }
+    fn codegrid_mut(&mut self) -> &mut TheCodeGrid {
+        self.buffer_is_dirty = true;
+        self.is_dirty = true;
+        &mut self.codegrid
+    }
+    fn set_codegrid(&mut self, codegrid: TheCodeGrid) {
+        self.codegrid = codegrid;
+        self.selected = None;
+        self.hover = None;
+        self.buffer_is_dirty = true;
+        self.is_dirty = true;
+    }
+    fn set_grid_atom(&mut self, location: (u16, u16), atom: TheCodeAtom) {
+        self.codegrid.insert_atom(location, atom);
+        self.buffer_is_dirty = true;
+        self.is_dirty = true;
+    }
+    fn set_debug_module(&mut self, debug_module: TheDebugModule) {
+        self.debug_module = debug_module;
+        self.buffer_is_dirty = true;
+        self.is_dirty = true;
+    }
+    fn set_breakpoints(&mut self, breakpoints: Vec<(u16, u16)>) {
+        if self.breakpoints != breakpoints {
+            self.breakpoints = breakpoints;
+            self.buffer_is_dirty = true;
+            self.is_dirty = true;
+        }
+    }
+    fn set_debug_location(&mut self, location: Option<(u16, u16)>) {
+        if self.debug_location != location {
+            self.debug_location = location;
+            self.buffer_is_dirty = true;
+            self.is_dirty = true;
+        }
+    }
+    fn selection(&self) -> Option<(u16, u16)> {
+        self.selected
+    }
+    fn set_selection(&mut self, selection: Option<(u16, u16)>) {
+        self.selected = selection;
+        self.buffer_is_dirty = true;
+        self.is_dirty = true;
+    }
+    fn adjust_buffer_to_grid(&mut self) -> bool {
+        let (max_x, max_y) = self.codegrid.max_xy().unwrap_or((0, 0));
+
+        // Leave room for one more expression and two more lines.
+        let width = (max_x as i32 + 3) * self.grid_size;
+        let height = (max_y as i32 + 4) * self.grid_size;
+
+        if self.buffer.dim().width != width || self.buffer.dim().height != height {
+            self.buffer =
+                TheRGBABuffer::new_scaled(TheDim::new(0, 0, width, height), self.buffer.scale());
+            self.buffer_is_dirty = true;
+            self.is_dirty = true;
+            true
+        } else {
+            false
+        }
+    }
+    fn buffer(&self) -> &TheRGBABuffer {
+        &self.buffer
+    }
+    fn zoom(&self) -> f32 {
+        self.zoom
+    }
+    fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.clamp(0.25, 4.0);
+        self.is_dirty = true;
+    }
+    fn set_scroll_offset(&mut self, offset: Vec2<i32>) {
+        if self.scroll_offset != offset {
+            self.scroll_offset = offset;
+            self.is_dirty = true;
+        }
+    }
+    fn set_scrollbar_ids(&mut self, hscrollbar: TheId, vscrollbar: TheId) {
+        self.hscrollbar = hscrollbar;
+        self.vscrollbar = vscrollbar;
+    }
+}
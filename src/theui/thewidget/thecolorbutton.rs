@@ -176,6 +176,14 @@ impl TheWidget for TheColorButton {
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: Some(TheColor::from(self.color).to_hex()),
+            description: self.status.clone(),
+            ..TheAccessInfo::new(TheAccessRole::ColorWell)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -264,8 +264,13 @@ impl TheWidget for TheDropdownMenu {
                 icon.dim().width as usize,
                 icon.dim().height as usize,
             );
-            ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+            ctx.draw.blend_slice_scaled(
+                buffer.pixels_mut(),
+                icon.pixels(),
+                icon.scale(),
+                &r,
+                stride,
+            );
         }
 
         if let Some(icon) = ctx.ui.icon("dark_dropdown_marker") {
@@ -275,8 +280,13 @@ impl TheWidget for TheDropdownMenu {
                 icon.dim().width as usize,
                 icon.dim().height as usize,
             );
-            ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+            ctx.draw.blend_slice_scaled(
+                buffer.pixels_mut(),
+                icon.pixels(),
+                icon.scale(),
+                &r,
+                stride,
+            );
         }
 
         shrinker.shrink_by(8, 0, 12, 0);
@@ -332,7 +342,7 @@ impl TheWidget for TheDropdownMenu {
             dim.y -= self.safety_offset.y;
         }
 
-        let mut buffer = TheRGBABuffer::new(dim);
+        let mut buffer = TheRGBABuffer::new_scaled(dim, ctx.scale_factor);
         ctx.draw.rect(
             buffer.pixels_mut(),
             &(0, 0, width, height),
@@ -377,7 +387,7 @@ impl TheWidget for TheDropdownMenu {
                         size: 12.5,
                         ..Default::default()
                     },
-                    &WHITE,
+                    style.theme().color(DefaultTextColor),
                     TheHorizontalAlign::Left,
                     TheVerticalAlign::Center,
                 );
@@ -393,6 +403,15 @@ impl TheWidget for TheDropdownMenu {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: self.options.get(self.selected as usize).cloned(),
+            description: self.status.clone(),
+            expanded: Some(self.state == TheWidgetState::Clicked),
+            ..TheAccessInfo::new(TheAccessRole::ComboBox)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -295,7 +295,7 @@ impl TheWidget for TheGroupButton {
                         &(icon.dim().width as usize, icon.dim().height as usize),
                     );
                     // ctx.draw
-                    // .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                    // .blend_slice_scaled(buffer.pixels_mut(), icon.pixels(), icon.scale(), &r, stride);
                     has_icon = true;
                     offset = self.icon_size.x as usize + 5 + 2;
                 }
@@ -310,7 +310,7 @@ impl TheWidget for TheGroupButton {
                     size: 12.5,
                     ..Default::default()
                 },
-                &WHITE,
+                style.theme().color(DefaultTextColor),
                 if has_icon {
                     TheHorizontalAlign::Left
                 } else {
@@ -332,6 +332,15 @@ impl TheWidget for TheGroupButton {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: self
+                .selected_index
+                .and_then(|index| self.text.get(index).cloned()),
+            ..TheAccessInfo::new(TheAccessRole::RadioGroup)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -206,8 +206,13 @@ impl TheWidget for TheHorizontalScrollbar {
                     icon.dim().width as usize,
                     icon.dim().height as usize,
                 );
-                ctx.draw
-                    .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.blend_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
             }
         }
 
@@ -220,8 +225,13 @@ impl TheWidget for TheHorizontalScrollbar {
                     icon.dim().height as usize,
                 );
                 for _ in 0..scroll_bar_width - 10 {
-                    ctx.draw
-                        .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                    ctx.draw.copy_slice_scaled(
+                        buffer.pixels_mut(),
+                        icon.pixels(),
+                        icon.scale(),
+                        &r,
+                        stride,
+                    );
                     r.0 += 1;
                 }
             }
@@ -235,8 +245,13 @@ impl TheWidget for TheHorizontalScrollbar {
                     icon.dim().width as usize,
                     icon.dim().height as usize,
                 );
-                ctx.draw
-                    .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.blend_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
             }
         }
 
@@ -10,7 +10,7 @@ pub struct TheIconView {
 
     text: Option<String>,
     text_size: f32,
-    text_color: RGBA,
+    text_color: Option<RGBA>,
 
     border_color: Option<RGBA>,
 
@@ -37,7 +37,7 @@ impl TheWidget for TheIconView {
 
             text: None,
             text_size: 12.0,
-            text_color: WHITE,
+            text_color: None,
 
             border_color: None,
 
@@ -95,7 +95,7 @@ impl TheWidget for TheIconView {
     fn draw(
         &mut self,
         buffer: &mut TheRGBABuffer,
-        _style: &mut Box<dyn TheStyle>,
+        style: &mut Box<dyn TheStyle>,
         ctx: &mut TheContext,
     ) {
         let stride: usize = buffer.stride();
@@ -153,7 +153,9 @@ impl TheWidget for TheIconView {
                     size: self.text_size,
                     ..Default::default()
                 },
-                &self.text_color,
+                &self
+                    .text_color
+                    .unwrap_or(*style.theme().color(DefaultTextColor)),
                 TheHorizontalAlign::Center,
                 TheVerticalAlign::Center,
             );
@@ -171,6 +173,10 @@ impl TheWidget for TheIconView {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::Image)
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -209,7 +215,7 @@ impl TheIconViewTrait for TheIconView {
         self.is_dirty = true;
     }
     fn set_text_color(&mut self, color: RGBA) {
-        self.text_color = color;
+        self.text_color = Some(color);
         self.is_dirty = true;
     }
     fn set_text(&mut self, text: Option<String>) {
@@ -92,12 +92,13 @@ impl TheWidget for TheListItem {
                     self.is_dirty = true;
                     self.state = TheWidgetState::Selected;
                     ctx.ui.send_widget_state_changed(self.id(), self.state);
-                    ctx.ui.send(TheEvent::NewListItemSelected(
-                        self.id().clone(),
-                        self.layout_id.clone(),
-                    ));
                     redraw = true;
                 }
+                // Also sent for selected items, the layout handles clicks with modifiers.
+                ctx.ui.send(TheEvent::ListItemClicked(
+                    self.id().clone(),
+                    self.layout_id.clone(),
+                ));
                 self.mouse_down_pos = Vec2::new(coord.x, coord.y + self.scroll_offset);
                 ctx.ui.set_focus(self.id());
             }
@@ -115,6 +116,14 @@ impl TheWidget for TheListItem {
                     ));
                 }
             }
+            TheEvent::KeyDown(key) | TheEvent::KeyCodeDown(key) => {
+                // The layout moves the selection with the keyboard.
+                ctx.ui.send(TheEvent::ListItemKeyDown(
+                    self.id().clone(),
+                    self.layout_id.clone(),
+                    key.clone(),
+                ));
+            }
             TheEvent::Hover(_coord) => {
                 if self.state != TheWidgetState::Selected && !self.id().equals(&ctx.ui.hover) {
                     self.is_dirty = true;
@@ -259,9 +268,10 @@ impl TheWidget for TheListItem {
                 style.theme().color(ListItemIconBorder),
                 1,
             );
-            ctx.draw.copy_slice(
+            ctx.draw.copy_slice_scaled(
                 buffer.pixels_mut(),
                 icon.pixels(),
+                icon.scale(),
                 &(ut.0 + 2, ut.1 + 2, 36, 36),
                 stride,
             );
@@ -382,6 +392,15 @@ impl TheWidget for TheListItem {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: Some(self.sub_text.clone()).filter(|text| !text.is_empty()),
+            description: self.status.clone(),
+            selected: Some(self.state == TheWidgetState::Selected),
+            ..TheAccessInfo::named(TheAccessRole::ListBoxOption, &self.text)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -395,6 +414,8 @@ pub trait TheListItemTrait {
     fn set_size(&mut self, size: i32);
     fn set_icon(&mut self, icon: TheRGBABuffer);
     fn set_scroll_offset(&mut self, offset: i32);
+    /// Changes the id, used when a virtualized list recycles the widget for another item.
+    fn set_id(&mut self, id: TheId);
     fn add_value_column(&mut self, width: i32, value: TheValue);
 }
 
@@ -424,6 +445,10 @@ impl TheListItemTrait for TheListItem {
     fn set_scroll_offset(&mut self, offset: i32) {
         self.scroll_offset = offset;
     }
+    fn set_id(&mut self, id: TheId) {
+        self.id = id;
+        self.is_dirty = true;
+    }
     fn add_value_column(&mut self, width: i32, value: TheValue) {
         self.values.push((width, value));
     }
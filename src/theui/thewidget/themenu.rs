@@ -177,8 +177,13 @@ impl TheWidget for TheMenu {
             if let Some(icon) = ctx.ui.icon("dark_menu") {
                 for x in 0..utuple.2 {
                     let r = (utuple.0 + x, utuple.1, 1, icon.dim().height as usize);
-                    ctx.draw
-                        .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                    ctx.draw.copy_slice_scaled(
+                        buffer.pixels_mut(),
+                        icon.pixels(),
+                        icon.scale(),
+                        &r,
+                        stride,
+                    );
                 }
             }
         }
@@ -79,14 +79,23 @@ impl TheWidget for TheMenubar {
         if let Some(icon) = ctx.ui.icon("dark_menubar_large") {
             for x in 0..utuple.2 {
                 let r = (utuple.0 + x, utuple.1, 1, icon.dim().height as usize);
-                ctx.draw
-                    .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.copy_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
             }
         }
 
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::MenuBar)
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
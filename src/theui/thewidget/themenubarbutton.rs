@@ -266,8 +266,14 @@ impl TheWidget for TheMenubarButton {
                     icon.dim().width as usize,
                     icon.dim().height as usize,
                 );
-                ctx.draw
-                    .blend_slice_alpha(buffer.pixels_mut(), icon.pixels(), &r, stride, alpha);
+                ctx.draw.blend_slice_alpha_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                    alpha,
+                );
             }
         }
 
@@ -278,6 +284,17 @@ impl TheWidget for TheMenubarButton {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        let mut info = TheAccessInfo::named(
+            TheAccessRole::Button,
+            self.status.as_deref().unwrap_or(&self.icon_name),
+        );
+        if self.has_state {
+            info.checked = Some(self.state == TheWidgetState::Selected);
+        }
+        info
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -353,7 +353,7 @@ impl TheWidget for TheNodeCanvasView {
                 }
             }
             TheEvent::KeyCodeDown(code) => {
-                if code.to_key_code().unwrap() == TheKeyCode::Delete {
+                if code.to_key_code().unwrap().is_delete() {
                     if let Some(deleted_node_index) = self.canvas.selected_node {
                         if self.canvas.nodes[deleted_node_index].can_be_deleted {
                             self.canvas.nodes.remove(deleted_node_index);
@@ -505,9 +505,6 @@ impl TheWidget for TheNodeCanvasView {
                 }
             });
 
-        let rbw = self.render_buffer.dim().width as usize;
-        let rbh = self.render_buffer.dim().height as usize;
-
         let node_width = self.canvas.node_width;
         let node_rects = Arc::new(Mutex::new(Vec::new()));
 
@@ -850,14 +847,23 @@ impl TheWidget for TheNodeCanvasView {
                 .style(Stroke::new(1.5))
                 .render_into(&mut line_mask, None);
 
-            ctx.draw.blend_mask(
-                self.render_buffer.pixels_mut(),
-                &(0, 0, rbw, rbh),
-                rbw,
-                &line_mask[..],
-                &(rbw, rbh),
-                &[90, 90, 90, 255],
-            );
+            // The render buffer is at logical resolution, blend the mask pixel by pixel
+            let color = [90, 90, 90, 255];
+            for (pixel, m) in self
+                .render_buffer
+                .pixels_mut()
+                .chunks_exact_mut(4)
+                .zip(line_mask.iter())
+            {
+                if *m > 0 {
+                    let background = [pixel[0], pixel[1], pixel[2], pixel[3]];
+                    pixel.copy_from_slice(&ctx.draw.mix_color(
+                        &background,
+                        &color,
+                        *m as f32 / 255.0,
+                    ));
+                }
+            }
         }
 
         // Draw ongoing cut connection attempt
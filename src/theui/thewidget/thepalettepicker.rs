@@ -74,7 +74,7 @@ impl TheWidget for ThePalettePicker {
                 }
             }
             TheEvent::KeyCodeDown(TheValue::KeyCode(code)) => match code {
-                TheKeyCode::Delete => {
+                TheKeyCode::Delete | TheKeyCode::Backspace => {
                     self.palette.colors[self.index] = None;
                     self.is_dirty = true;
                     redraw = true;
@@ -240,6 +240,10 @@ impl TheWidget for TheRenderView {
         true
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::Canvas)
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -490,7 +490,9 @@ impl TheWidget for TheRGBAView {
                     ctx.ui.send(TheEvent::ScrollBy(self.vscrollbar.clone(), d));
                 }
             }
-            TheEvent::KeyCodeDown(TheValue::KeyCode(TheKeyCode::Delete)) => {
+            TheEvent::KeyCodeDown(TheValue::KeyCode(
+                TheKeyCode::Delete | TheKeyCode::Backspace,
+            )) => {
                 if !self.selected.is_empty() && self.mode == TheRGBAViewMode::TilePicker {
                     ctx.ui.send(TheEvent::TileEditorDelete(
                         self.id.clone(),
@@ -591,8 +593,9 @@ impl TheWidget for TheRGBAView {
             return;
         }
 
-        let target = buffer;
-        let target_len = target.pixels().len();
+        // The view is rendered at logical resolution and copied into the buffer, which scales it
+        // to the physical resolution of the buffer.
+        let mut target = vec![0; (self.dim.width * self.dim.height * 4) as usize];
 
         let src_width = self.buffer.dim().width as f32;
         let src_height = self.buffer.dim().height as f32;
@@ -624,14 +627,7 @@ impl TheWidget for TheRGBAView {
                 let src_y = (target_y as f32 - offset_y) / self.zoom;
 
                 // Calculate the index for the destination pixel
-                let target_index = ((self.dim.buffer_y + target_y) * target.dim().width
-                    + target_x
-                    + self.dim.buffer_x) as usize
-                    * 4;
-
-                if target_index + 4 > target_len {
-                    continue;
-                }
+                let target_index = (target_y * self.dim.width + target_x) as usize * 4;
 
                 // TileSelection mode - use original grid drawing logic
                 if !self.dont_show_grid && self.mode == TheRGBAViewMode::TileSelection {
@@ -648,12 +644,12 @@ impl TheWidget for TheRGBAView {
                                     && source_y >= 0
                                     && source_y < self.buffer.dim().height
                                 {
-                                    target.pixels_mut()[target_index..target_index + 4]
+                                    target[target_index..target_index + 4]
                                         .copy_from_slice(&self.grid_color);
                                     continue;
                                 }
                             } else {
-                                target.pixels_mut()[target_index..target_index + 4]
+                                target[target_index..target_index + 4]
                                     .copy_from_slice(&self.grid_color);
                                 continue;
                             }
@@ -709,8 +705,7 @@ impl TheWidget for TheRGBAView {
                     }
 
                     if draw_grid_line {
-                        target.pixels_mut()[target_index..target_index + 4]
-                            .copy_from_slice(&self.grid_color);
+                        target[target_index..target_index + 4].copy_from_slice(&self.grid_color);
                         continue;
                     }
 
@@ -720,8 +715,7 @@ impl TheWidget for TheRGBAView {
                         if self.mode == TheRGBAViewMode::TileEditor
                             && self.drop == Some((src_x / grid, src_y / grid))
                         {
-                            target.pixels_mut()[target_index..target_index + 4]
-                                .copy_from_slice(&WHITE);
+                            target[target_index..target_index + 4].copy_from_slice(&WHITE);
                             copy = false;
                         } else if self.icon_mode
                             && !self.selected.contains(&(src_x / grid, src_y / grid))
@@ -733,7 +727,7 @@ impl TheWidget for TheRGBAView {
                                 s[src_index + 2] / 2,
                                 s[src_index + 3],
                             ];
-                            target.pixels_mut()[target_index..target_index + 4].copy_from_slice(c);
+                            target[target_index..target_index + 4].copy_from_slice(c);
                             copy = false;
                         } else
                         // Selected
@@ -752,7 +746,7 @@ impl TheWidget for TheRGBAView {
                                 &self.selection_color,
                                 self.selection_color[3] as f32 / 255.0,
                             );
-                            target.pixels_mut()[target_index..target_index + 4].copy_from_slice(&m);
+                            target[target_index..target_index + 4].copy_from_slice(&m);
                             copy = false;
                         } else
                         // Used
@@ -768,7 +762,7 @@ impl TheWidget for TheRGBAView {
                             ];
                             let m =
                                 mix_color(c, &self.used_color, self.used_color[3] as f32 / 255.0);
-                            target.pixels_mut()[target_index..target_index + 4].copy_from_slice(&m);
+                            target[target_index..target_index + 4].copy_from_slice(&m);
                             copy = false;
                         }
                         // Hover
@@ -784,8 +778,7 @@ impl TheWidget for TheRGBAView {
                                     ];
                                     let m =
                                         mix_color(c, &hover_color, hover_color[3] as f32 / 255.0);
-                                    target.pixels_mut()[target_index..target_index + 4]
-                                        .copy_from_slice(&m);
+                                    target[target_index..target_index + 4].copy_from_slice(&m);
                                     copy = false;
                                 }
                             }
@@ -802,26 +795,30 @@ impl TheWidget for TheRGBAView {
                                 &[src_pixel[0], src_pixel[1], src_pixel[2], src_pixel[3]],
                                 &self.transparency_color,
                             );
-                            target.pixels_mut()[target_index..target_index + 4]
-                                .copy_from_slice(&blended);
+                            target[target_index..target_index + 4].copy_from_slice(&blended);
                         } else {
-                            target.pixels_mut()[target_index..target_index + 4]
-                                .copy_from_slice(src_pixel);
+                            target[target_index..target_index + 4].copy_from_slice(src_pixel);
                         }
                     }
                 } else {
                     // Set the pixel to black if it's out of the source bounds
-                    // target.pixels_mut()[target_index..target_index + 4].fill(0);
-                    target.pixels_mut()[target_index..target_index + 4]
-                        .copy_from_slice(&self.background);
+                    // target[target_index..target_index + 4].fill(0);
+                    target[target_index..target_index + 4].copy_from_slice(&self.background);
                 }
             }
         }
 
+        ctx.draw.copy_slice(
+            buffer.pixels_mut(),
+            &target,
+            &self.dim.to_buffer_utuple(),
+            stride,
+        );
+
         if Some(self.id.clone()) == ctx.ui.focus {
             let tuple = self.dim().to_buffer_utuple();
             ctx.draw.rect_outline(
-                target.pixels_mut(),
+                buffer.pixels_mut(),
                 &tuple,
                 stride,
                 style.theme().color(DefaultSelection),
@@ -835,6 +832,10 @@ impl TheWidget for TheRGBAView {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::Canvas)
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
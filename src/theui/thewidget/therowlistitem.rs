@@ -327,6 +327,15 @@ impl TheWidget for TheRowListItem {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: Some(self.sub_text.clone()).filter(|text| !text.is_empty()),
+            description: self.status.clone(),
+            selected: Some(self.state == TheWidgetState::Selected),
+            ..TheAccessInfo::named(TheAccessRole::ListBoxOption, &self.text)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -340,6 +349,8 @@ pub trait TheRowListItemTrait {
     fn set_size(&mut self, size: i32);
     fn set_icon(&mut self, icon: TheRGBABuffer);
     fn set_scroll_offset(&mut self, offset: i32);
+    /// Changes the id, used when a virtualized list recycles the widget for another item.
+    fn set_id(&mut self, id: TheId);
 }
 
 impl TheRowListItemTrait for TheRowListItem {
@@ -368,4 +379,8 @@ impl TheRowListItemTrait for TheRowListItem {
     fn set_scroll_offset(&mut self, offset: i32) {
         self.scroll_offset = offset;
     }
+    fn set_id(&mut self, id: TheId) {
+        self.id = id;
+        self.is_dirty = true;
+    }
 }
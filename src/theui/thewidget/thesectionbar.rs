@@ -83,8 +83,13 @@ impl TheWidget for TheSectionbar {
         if let Some(icon) = ctx.ui.icon("dark_sectionbar") {
             for x in 1..utuple.2 - 1 {
                 let r = (utuple.0 + x, utuple.1, 1, icon.dim().height as usize);
-                ctx.draw
-                    .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.copy_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
             }
         }
 
@@ -114,6 +119,10 @@ impl TheWidget for TheSectionbar {
         // }
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::TabList)
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -152,8 +152,13 @@ impl TheWidget for TheSectionbarButton {
                 icon.dim().width as usize,
                 icon.dim().height as usize,
             );
-            ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+            ctx.draw.blend_slice_scaled(
+                buffer.pixels_mut(),
+                icon.pixels(),
+                icon.scale(),
+                &r,
+                stride,
+            );
         }
 
         ctx.draw.text_rect_blend(
@@ -173,6 +178,14 @@ impl TheWidget for TheSectionbarButton {
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            description: self.status.clone(),
+            selected: Some(self.state == TheWidgetState::Selected),
+            ..TheAccessInfo::named(TheAccessRole::Tab, &self.text)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
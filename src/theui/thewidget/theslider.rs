@@ -375,8 +375,13 @@ impl TheWidget for TheSlider {
                 icon.dim().width as usize,
                 icon.dim().height as usize,
             );
-            ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+            ctx.draw.blend_slice_scaled(
+                buffer.pixels_mut(),
+                icon.pixels(),
+                icon.scale(),
+                &r,
+                stride,
+            );
         }
 
         shrinker.reset();
@@ -391,7 +396,7 @@ impl TheWidget for TheSlider {
                 size: 13.0,
                 ..Default::default()
             },
-            &WHITE,
+            style.theme().color(DefaultTextColor),
             TheHorizontalAlign::Left,
             TheVerticalAlign::Center,
         );
@@ -399,6 +404,23 @@ impl TheWidget for TheSlider {
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        let mut info = TheAccessInfo {
+            value: Some(self.value.describe()),
+            description: self.status.clone(),
+            numeric_value: self.value.to_f32().map(|value| value as f64),
+            ..TheAccessInfo::new(TheAccessRole::Slider)
+        };
+        if let Some(range) = self.range.to_range_f32() {
+            info.min_numeric_value = Some(*range.start() as f64);
+            info.max_numeric_value = Some(*range.end() as f64);
+        } else if let Some(range) = self.range.to_range_i32() {
+            info.min_numeric_value = Some(*range.start() as f64);
+            info.max_numeric_value = Some(*range.end() as f64);
+        }
+        info
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
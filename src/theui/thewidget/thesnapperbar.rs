@@ -16,6 +16,7 @@ pub struct TheSnapperbar {
     is_dirty: bool,
 
     layout_id: TheId,
+    mouse_down_pos: Vec2<i32>,
 
     root_mode: bool,
 }
@@ -43,6 +44,7 @@ impl TheWidget for TheSnapperbar {
             is_dirty: false,
 
             layout_id: TheId::empty(),
+            mouse_down_pos: Vec2::zero(),
 
             root_mode: true,
         }
@@ -111,8 +113,9 @@ impl TheWidget for TheSnapperbar {
         let mut redraw = false;
         // println!("event ({}): {:?}", self.widget_id.name, event);
         match event {
-            TheEvent::MouseDown(_coord) => {
+            TheEvent::MouseDown(coord) => {
                 self.is_dirty = true;
+                self.mouse_down_pos = *coord;
                 if self.state != TheWidgetState::Clicked {
                     self.state = TheWidgetState::Clicked;
                     ctx.ui.send_widget_state_changed(self.id(), self.state);
@@ -125,16 +128,34 @@ impl TheWidget for TheSnapperbar {
                 if self.state == TheWidgetState::Clicked {
                     self.state = TheWidgetState::None;
                     ctx.ui.send_widget_state_changed(self.id(), self.state);
-                    self.open = !self.open;
 
-                    ctx.ui.send(TheEvent::SnapperStateChanged(
-                        self.id().clone(),
-                        self.layout_id.clone(),
-                        self.open,
-                    ));
+                    // Nodes of a tree which were dragged do not toggle.
+                    if ctx.ui.drop.is_none() {
+                        self.open = !self.open;
+
+                        ctx.ui.send(TheEvent::SnapperStateChanged(
+                            self.id().clone(),
+                            self.layout_id.clone(),
+                            self.open,
+                        ));
+                    }
                 }
                 redraw = true;
             }
+            TheEvent::MouseDragged(coord)
+                if self.state == TheWidgetState::Clicked
+                    && ctx.ui.drop.is_none()
+                    && Vec2::new(self.mouse_down_pos.x as f32, self.mouse_down_pos.y as f32)
+                        .distance(Vec2::new(coord.x as f32, coord.y as f32))
+                        >= 5.0 =>
+            {
+                ctx.ui.send(TheEvent::TreeItemDragStarted(
+                    self.id().clone(),
+                    self.layout_id.clone(),
+                    self.text.clone(),
+                    *coord,
+                ));
+            }
             TheEvent::Hover(_coord) => {
                 if self.state != TheWidgetState::Clicked && !self.id().equals(&ctx.ui.hover) {
                     self.is_dirty = true;
@@ -186,8 +207,13 @@ impl TheWidget for TheSnapperbar {
                 }
 
                 let r = (utuple.0, utuple.1 + 1, 1, icon.dim().height as usize);
-                ctx.draw
-                    .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.copy_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
 
                 let r = (
                     utuple.0 + utuple.2 - 1,
@@ -195,8 +221,13 @@ impl TheWidget for TheSnapperbar {
                     1,
                     icon.dim().height as usize,
                 );
-                ctx.draw
-                    .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.copy_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
             }
 
             if let Some(mut icon) = ctx
@@ -211,8 +242,13 @@ impl TheWidget for TheSnapperbar {
 
                 for x in 1..utuple.2 - 1 {
                     let r = (utuple.0 + x, utuple.1, 1, icon.dim().height as usize);
-                    ctx.draw
-                        .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                    ctx.draw.copy_slice_scaled(
+                        buffer.pixels_mut(),
+                        icon.pixels(),
+                        icon.scale(),
+                        &r,
+                        stride,
+                    );
                 }
             }
         } else {
@@ -251,8 +287,13 @@ impl TheWidget for TheSnapperbar {
                     icon.dim().width as usize,
                     icon.dim().height as usize,
                 );
-                ctx.draw
-                    .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.blend_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
             }
         } else if let Some(icon) = ctx.ui.icon("dark_snapperbar_closed") {
             let r = (
@@ -261,8 +302,13 @@ impl TheWidget for TheSnapperbar {
                 icon.dim().width as usize,
                 icon.dim().height as usize,
             );
-            ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+            ctx.draw.blend_slice_scaled(
+                buffer.pixels_mut(),
+                icon.pixels(),
+                icon.scale(),
+                &r,
+                stride,
+            );
         }
 
         let mut shrinker = TheDimShrinker::zero();
@@ -277,7 +323,7 @@ impl TheWidget for TheSnapperbar {
                 size: 13.5,
                 ..Default::default()
             },
-            &WHITE,
+            style.theme().color(DefaultTextColor),
             TheHorizontalAlign::Left,
             TheVerticalAlign::Center,
         );
@@ -285,6 +331,14 @@ impl TheWidget for TheSnapperbar {
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            expanded: Some(self.open),
+            selected: Some(self.selected),
+            ..TheAccessInfo::named(TheAccessRole::Button, &self.text)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
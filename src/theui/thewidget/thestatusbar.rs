@@ -86,8 +86,13 @@ impl TheWidget for TheStatusbar {
         if let Some(icon) = ctx.ui.icon("dark_statusbar") {
             for x in 1..utuple.2 {
                 let r = (utuple.0 + x, utuple.1, 1, icon.dim().height as usize);
-                ctx.draw
-                    .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.copy_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
             }
         }
 
@@ -103,7 +108,7 @@ impl TheWidget for TheStatusbar {
                 size: 13.5,
                 ..Default::default()
             },
-            &WHITE,
+            style.theme().color(DefaultTextColor),
             TheHorizontalAlign::Left,
             TheVerticalAlign::Center,
         );
@@ -111,6 +116,13 @@ impl TheWidget for TheStatusbar {
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: Some(self.text.clone()),
+            ..TheAccessInfo::new(TheAccessRole::Status)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
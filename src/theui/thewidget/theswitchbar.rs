@@ -93,8 +93,13 @@ impl TheWidget for TheSwitchbar {
         if let Some(icon) = ctx.ui.icon("dark_switchbar") {
             for x in 1..utuple.2 - 1 {
                 let r = (utuple.0 + x, utuple.1, 1, icon.dim().height as usize);
-                ctx.draw
-                    .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.copy_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
             }
         }
 
@@ -105,8 +110,13 @@ impl TheWidget for TheSwitchbar {
                 icon.dim().width as usize,
                 icon.dim().height as usize,
             );
-            ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+            ctx.draw.blend_slice_scaled(
+                buffer.pixels_mut(),
+                icon.pixels(),
+                icon.scale(),
+                &r,
+                stride,
+            );
         }
 
         let mut shrinker = TheDimShrinker::zero();
@@ -123,7 +133,7 @@ impl TheWidget for TheSwitchbar {
                 size: 13.0,
                 ..Default::default()
             },
-            &WHITE,
+            style.theme().color(DefaultTextColor),
             TheHorizontalAlign::Left,
             TheVerticalAlign::Center,
         );
@@ -131,6 +141,10 @@ impl TheWidget for TheSwitchbar {
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::named(TheAccessRole::Label, &self.text)
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
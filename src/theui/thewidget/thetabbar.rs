@@ -189,8 +189,13 @@ impl TheWidget for TheTabbar {
                     icon.dim().width as usize,
                     icon.dim().height as usize,
                 );
-                ctx.draw
-                    .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.copy_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
 
                 ctx.draw.text_rect_blend(
                     buffer.pixels_mut(),
@@ -231,6 +236,13 @@ impl TheWidget for TheTabbar {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: self.tabs.get(self.selected as usize).cloned(),
+            ..TheAccessInfo::new(TheAccessRole::TabList)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
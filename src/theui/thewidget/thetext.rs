@@ -8,7 +8,8 @@ pub struct TheText {
     dim: TheDim,
     text: String,
     text_size: f32,
-    text_color: RGBA,
+    /// A custom text color, by default the DefaultTextColor of the theme is used.
+    text_color: Option<RGBA>,
 
     fixed_size_text: String,
 
@@ -30,7 +31,7 @@ impl TheWidget for TheText {
             dim: TheDim::zero(),
             text: "".to_string(),
             text_size: 13.0,
-            text_color: WHITE,
+            text_color: None,
 
             fixed_size_text: String::default(),
 
@@ -106,7 +107,7 @@ impl TheWidget for TheText {
     fn draw(
         &mut self,
         buffer: &mut TheRGBABuffer,
-        _style: &mut Box<dyn TheStyle>,
+        style: &mut Box<dyn TheStyle>,
         ctx: &mut TheContext,
     ) {
         if !self.dim().is_valid() {
@@ -127,7 +128,9 @@ impl TheWidget for TheText {
                 size: self.text_size,
                 ..Default::default()
             },
-            &self.text_color,
+            &self
+                .text_color
+                .unwrap_or(*style.theme().color(DefaultTextColor)),
             TheHorizontalAlign::Left,
             TheVerticalAlign::Center,
         );
@@ -139,6 +142,10 @@ impl TheWidget for TheText {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::named(TheAccessRole::Label, &self.text)
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -166,7 +173,7 @@ impl TheTextTrait for TheText {
         self.is_dirty = true;
     }
     fn set_text_color(&mut self, color: RGBA) {
-        self.text_color = color;
+        self.text_color = Some(color);
         self.is_dirty = true;
     }
     fn set_fixed_size_text(&mut self, fixed_size_text: String) {
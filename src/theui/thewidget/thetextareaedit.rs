@@ -225,6 +225,10 @@ impl TheWidget for TheTextAreaEdit {
         true
     }
 
+    fn supports_tab_input(&self) -> bool {
+        !self.readonly
+    }
+
     fn supports_clipboard(&mut self) -> bool {
         true
     }
@@ -757,8 +761,13 @@ impl TheWidget for TheTextAreaEdit {
                                     self.emit_value_changed(ctx);
                                 }
                             }
-                            TheKeyCode::Delete => {
-                                if self.state.delete_text() {
+                            TheKeyCode::Backspace | TheKeyCode::Delete => {
+                                let deleted = if key == TheKeyCode::Delete {
+                                    self.state.delete_text_forward()
+                                } else {
+                                    self.state.delete_text()
+                                };
+                                if deleted {
                                     self.modified_since_last_tick = true;
                                     self.is_dirty = true;
                                     redraw = true;
@@ -769,6 +778,53 @@ impl TheWidget for TheTextAreaEdit {
                                     }
                                 }
                             }
+                            TheKeyCode::Home
+                            | TheKeyCode::End
+                            | TheKeyCode::PageUp
+                            | TheKeyCode::PageDown => {
+                                let cursor_index = self.state.find_cursor_index();
+                                let page = self
+                                    .renderer
+                                    .visible_rows()
+                                    .map(|(start, end)| end - start)
+                                    .unwrap_or(1)
+                                    .max(1);
+                                let to_text_bound = self.modifier_ctrl || self.modifier_logo;
+
+                                let mut updated = match key {
+                                    TheKeyCode::Home if to_text_bound => {
+                                        self.state.move_cursor_to_text_start()
+                                    }
+                                    TheKeyCode::Home => self.state.move_cursor_to_line_start(),
+                                    TheKeyCode::End if to_text_bound => {
+                                        self.state.move_cursor_to_text_end()
+                                    }
+                                    TheKeyCode::End => self.state.move_cursor_to_line_end(),
+                                    TheKeyCode::PageUp => self.state.move_cursor_up_by(page),
+                                    _ => self.state.move_cursor_down_by(page),
+                                };
+
+                                if self.modifier_shift {
+                                    if updated {
+                                        self.state.select_to_cursor(cursor_index);
+                                    }
+                                } else if !self.state.selection.is_none() {
+                                    self.state.reset_selection();
+                                    updated = true;
+                                }
+
+                                if updated {
+                                    if self.auto_scroll_to_cursor {
+                                        self.renderer.scroll_to_cursor(
+                                            self.state.find_cursor_index(),
+                                            self.state.cursor.row,
+                                        );
+                                    }
+                                    self.is_dirty = true;
+                                    redraw = true;
+                                    update_status = true;
+                                }
+                            }
                             TheKeyCode::Up => {
                                 if self.modifier_alt {
                                     if self.state.move_lines_up() {
@@ -1558,6 +1614,15 @@ impl TheWidget for TheTextAreaEdit {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: Some(self.state.to_text()),
+            description: self.status.clone(),
+            read_only: self.readonly,
+            ..TheAccessInfo::new(TheAccessRole::MultilineTextInput)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
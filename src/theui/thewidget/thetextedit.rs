@@ -139,6 +139,25 @@ impl TheTextEditState {
         deleted
     }
 
+    // Deletes the selection or the char after the cursor
+    pub fn delete_text_forward(&mut self) -> bool {
+        if !self.selection.is_none() {
+            return self.delete_text();
+        }
+
+        let glyphs_count = self.glyphs_in_row(self.cursor.row);
+        if self.cursor.column < glyphs_count {
+            self.delete_range_of_row(self.cursor.row, self.cursor.column, self.cursor.column + 1)
+        } else if !self.is_last_row(self.cursor.row) {
+            // Concat the next row
+            let text = self.rows.remove(self.cursor.row + 1);
+            self.rows[self.cursor.row].push_str(&text);
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn find_beginning_spaces_of_row(&self, row_number: usize) -> usize {
         self.rows[row_number]
             .chars()
@@ -540,6 +559,48 @@ impl TheTextEditState {
         true
     }
 
+    pub fn move_cursor_to_text_start(&mut self) -> bool {
+        if self.cursor.is_zero() {
+            return false;
+        }
+
+        self.cursor.reset();
+        true
+    }
+
+    pub fn move_cursor_to_text_end(&mut self) -> bool {
+        let row = self.row_count() - 1;
+        let column = self.glyphs_in_row(row);
+        if self.cursor.row == row && self.cursor.column == column {
+            return false;
+        }
+
+        self.cursor = TheCursor::new(row, column);
+        true
+    }
+
+    // Moves the cursor up by the given amount of rows, used for page up
+    pub fn move_cursor_up_by(&mut self, rows: usize) -> bool {
+        if self.cursor.row == 0 {
+            return self.move_cursor_to_line_start();
+        }
+
+        self.cursor.row = self.cursor.row.saturating_sub(rows);
+        self.cursor.column = self.cursor.column.min(self.glyphs_in_row(self.cursor.row));
+        true
+    }
+
+    // Moves the cursor down by the given amount of rows, used for page down
+    pub fn move_cursor_down_by(&mut self, rows: usize) -> bool {
+        if self.is_last_row(self.cursor.row) {
+            return self.move_cursor_to_line_end();
+        }
+
+        self.cursor.row = (self.cursor.row + rows).min(self.row_count() - 1);
+        self.cursor.column = self.cursor.column.min(self.glyphs_in_row(self.cursor.row));
+        true
+    }
+
     pub fn move_cursor_up(&mut self) -> bool {
         if self.cursor.row == 0 {
             return false;
@@ -761,6 +822,24 @@ impl TheTextEditState {
         self.selection.end = end;
     }
 
+    // Extends the selection to the cursor after it moved away from the given cursor index
+    pub fn select_to_cursor(&mut self, previous_cursor_index: usize) {
+        let anchor = if self.selection.is_none() {
+            previous_cursor_index
+        } else if previous_cursor_index == self.selection.start {
+            self.selection.end
+        } else {
+            self.selection.start
+        };
+
+        let cursor_index = self.find_cursor_index();
+        if anchor == cursor_index {
+            self.reset_selection();
+        } else {
+            self.select(anchor.min(cursor_index), anchor.max(cursor_index));
+        }
+    }
+
     pub fn select_all(&mut self) {
         self.selection.start = 0;
         self.selection.end = self
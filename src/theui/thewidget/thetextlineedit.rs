@@ -443,13 +443,49 @@ impl TheWidget for TheTextLineEdit {
                                 }
                             }
                         }
-                        TheKeyCode::Delete => {
-                            if self.state.delete_text() {
+                        TheKeyCode::Backspace | TheKeyCode::Delete => {
+                            let deleted = if key == TheKeyCode::Delete {
+                                self.state.delete_text_forward()
+                            } else {
+                                self.state.delete_text()
+                            };
+                            if deleted {
                                 self.modified_since_last_tick = true;
                                 self.is_dirty = true;
                                 redraw = true;
                             }
                         }
+                        TheKeyCode::Home
+                        | TheKeyCode::End
+                        | TheKeyCode::PageUp
+                        | TheKeyCode::PageDown => {
+                            // A single line, PageUp and PageDown work like Home and End.
+                            let cursor_index = self.state.find_cursor_index();
+                            let mut updated =
+                                if key == TheKeyCode::Home || key == TheKeyCode::PageUp {
+                                    self.state.move_cursor_to_text_start()
+                                } else {
+                                    self.state.move_cursor_to_text_end()
+                                };
+
+                            if self.modifier_shift {
+                                if updated {
+                                    self.state.select_to_cursor(cursor_index);
+                                }
+                            } else if !self.state.selection.is_none() {
+                                self.state.reset_selection();
+                                updated = true;
+                            }
+
+                            if updated {
+                                self.renderer.scroll_to_cursor(
+                                    self.state.find_cursor_index(),
+                                    self.state.cursor.row,
+                                );
+                                self.is_dirty = true;
+                                redraw = true;
+                            }
+                        }
                         TheKeyCode::Right => {
                             if self.modifier_ctrl || self.modifier_logo {
                                 if self.state.quick_move_cursor_right()
@@ -995,6 +1031,14 @@ impl TheWidget for TheTextLineEdit {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: Some(self.state.to_text()),
+            description: self.status.clone(),
+            ..TheAccessInfo::new(TheAccessRole::TextInput)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -1009,6 +1053,8 @@ pub trait TheTextLineEditTrait: TheWidget {
     fn set_associated_layout(&mut self, id: TheId);
     fn set_continuous(&mut self, continuous: bool);
     fn set_palette(&mut self, palette: ThePalette);
+    /// Selects the whole text, typing replaces it.
+    fn select_all(&mut self);
 }
 
 impl TheTextLineEditTrait for TheTextLineEdit {
@@ -1073,6 +1119,10 @@ impl TheTextLineEditTrait for TheTextLineEdit {
         self.palette = Some(palette);
         self.is_dirty = true;
     }
+    fn select_all(&mut self) {
+        self.state.select_all();
+        self.is_dirty = true;
+    }
 }
 
 impl TheTextLineEdit {
@@ -606,6 +606,15 @@ impl TheWidget for TheTextView {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: Some(self.state.to_text()),
+            description: self.status.clone(),
+            read_only: true,
+            ..TheAccessInfo::new(TheAccessRole::Document)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
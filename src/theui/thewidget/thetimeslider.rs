@@ -358,6 +358,14 @@ impl TheWidget for TheTimeSlider {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: Some(self.value.describe()),
+            description: self.status.clone(),
+            ..TheAccessInfo::new(TheAccessRole::Slider)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -215,8 +215,13 @@ impl TheWidget for TheToolbarButton {
                 icon.dim().width as usize,
                 icon.dim().height as usize,
             );
-            ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+            ctx.draw.blend_slice_scaled(
+                buffer.pixels_mut(),
+                icon.pixels(),
+                icon.scale(),
+                &r,
+                stride,
+            );
         }
 
         if !self.text.is_empty() {
@@ -229,7 +234,7 @@ impl TheWidget for TheToolbarButton {
                     size: self.text_size,
                     ..Default::default()
                 },
-                &WHITE,
+                style.theme().color(DefaultTextColor),
                 TheHorizontalAlign::Center,
                 TheVerticalAlign::Center,
             );
@@ -238,6 +243,10 @@ impl TheWidget for TheToolbarButton {
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::named(TheAccessRole::Button, &self.text)
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
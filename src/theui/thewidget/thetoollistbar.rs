@@ -94,8 +94,13 @@ impl TheWidget for TheToolListBar {
         if let Some(icon) = ctx.ui.icon("dark_toollistbar") {
             for x in 0..utuple.2 - 1 {
                 let r = (utuple.0 + x, utuple.1 + 1, 1, icon.dim().height as usize);
-                ctx.draw
-                    .copy_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                ctx.draw.copy_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &r,
+                    stride,
+                );
             }
         }
 
@@ -108,7 +113,7 @@ impl TheWidget for TheToolListBar {
                 size: 11.5,
                 ..Default::default()
             },
-            &WHITE,
+            style.theme().color(DefaultTextColor),
             TheHorizontalAlign::Center,
             TheVerticalAlign::Center,
         );
@@ -122,6 +127,10 @@ impl TheWidget for TheToolListBar {
         }
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::Toolbar)
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -172,9 +172,10 @@ impl TheWidget for TheToolListButton {
         };
 
         if let Some(icon) = ctx.ui.icon(&self.icon_name) {
-            ctx.draw.blend_slice(
+            ctx.draw.blend_slice_scaled(
                 buffer.pixels_mut(),
                 icon.pixels(),
+                icon.scale(),
                 &(
                     utuple.0 + (utuple.2 - icon.dim().width as usize) / 2,
                     utuple.1 + (utuple.3 - icon.dim().height as usize) / 2,
@@ -204,7 +205,7 @@ impl TheWidget for TheToolListButton {
                 icon.dim().height as usize,
             );
             ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+                .blend_slice_scaled(buffer.pixels_mut(), icon.pixels(), icon.scale(), &r, stride);
         }
 
         if let Some(font) = &ctx.ui.font {
@@ -224,6 +225,16 @@ impl TheWidget for TheToolListButton {
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            selected: Some(self.state == TheWidgetState::Selected),
+            ..TheAccessInfo::named(
+                TheAccessRole::Button,
+                self.status.as_deref().unwrap_or_default(),
+            )
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -98,6 +98,10 @@ impl TheWidget for TheTraybar {
         self.is_dirty = false;
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo::new(TheAccessRole::Toolbar)
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -298,8 +298,13 @@ impl TheWidget for TheTraybarButton {
                 icon.dim().width as usize,
                 icon.dim().height as usize,
             );
-            ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+            ctx.draw.blend_slice_scaled(
+                buffer.pixels_mut(),
+                icon.pixels(),
+                icon.scale(),
+                &r,
+                stride,
+            );
         } else if let Some(icon) = ctx.ui.icon(&self.icon_name) {
             let utuple = self.dim.to_buffer_shrunk_utuple(&shrinker);
             let r = (
@@ -310,15 +315,20 @@ impl TheWidget for TheTraybarButton {
                 icon.dim().width as usize,
                 icon.dim().height as usize,
             );
-            ctx.draw
-                .blend_slice(buffer.pixels_mut(), icon.pixels(), &r, stride);
+            ctx.draw.blend_slice_scaled(
+                buffer.pixels_mut(),
+                icon.pixels(),
+                icon.scale(),
+                &r,
+                stride,
+            );
         }
 
         if !self.text.is_empty() {
             let color = if let Some(custom) = &self.custom_color {
                 &custom.to_u8_array()
             } else {
-                &WHITE
+                style.theme().color(DefaultTextColor)
             };
             ctx.draw.text_rect_blend(
                 buffer.pixels_mut(),
@@ -345,6 +355,13 @@ impl TheWidget for TheTraybarButton {
         }
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            description: self.status.clone(),
+            ..TheAccessInfo::named(TheAccessRole::Button, &self.text)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -126,15 +126,24 @@ impl TheWidget for TheTreeIcons {
                         self.is_dirty = true;
                         self.state = TheWidgetState::Selected;
                         ctx.ui.send_widget_state_changed(self.id(), self.state);
-                        ctx.ui.send(TheEvent::NewListItemSelected(
-                            self.id().clone(),
-                            self.layout_id.clone(),
-                        ));
                         redraw = true;
                     }
+                    // Also sent for selected items, the layout handles clicks with modifiers.
+                    ctx.ui.send(TheEvent::ListItemClicked(
+                        self.id().clone(),
+                        self.layout_id.clone(),
+                    ));
                     ctx.ui.set_focus(self.id());
                 }
             }
+            TheEvent::KeyDown(key) | TheEvent::KeyCodeDown(key) => {
+                // The layout moves the selection with the keyboard.
+                ctx.ui.send(TheEvent::ListItemKeyDown(
+                    self.id().clone(),
+                    self.layout_id.clone(),
+                    key.clone(),
+                ));
+            }
             TheEvent::Hover(coord) => {
                 // Adjust coordinates for scroll offset from layout
                 let adjusted_coord = Vec2::new(coord.x, coord.y + self.scroll_offset);
@@ -380,9 +389,10 @@ impl TheWidget for TheTreeIcons {
                             (self.icon_size - 4) as usize,
                             (self.icon_size - 4) as usize,
                         );
-                        ctx.draw.copy_slice(
+                        ctx.draw.copy_slice_scaled(
                             buffer.pixels_mut(),
                             icon.pixels(),
+                            icon.scale(),
                             &content_rect,
                             stride,
                         );
@@ -96,12 +96,13 @@ impl TheWidget for TheTreeItem {
                     self.is_dirty = true;
                     self.state = TheWidgetState::Selected;
                     ctx.ui.send_widget_state_changed(self.id(), self.state);
-                    ctx.ui.send(TheEvent::NewListItemSelected(
-                        self.id().clone(),
-                        self.layout_id.clone(),
-                    ));
                     redraw = true;
                 }
+                // Also sent for selected items, the layout handles clicks with modifiers.
+                ctx.ui.send(TheEvent::ListItemClicked(
+                    self.id().clone(),
+                    self.layout_id.clone(),
+                ));
 
                 ctx.ui.set_focus(self.id());
 
@@ -196,8 +197,17 @@ impl TheWidget for TheTreeItem {
                                 text = t.clone();
                             }
                         }
-                        ctx.ui
-                            .send(TheEvent::DragStarted(self.id().clone(), text, coord));
+                        ctx.ui.send(TheEvent::DragStarted(
+                            self.id().clone(),
+                            text.clone(),
+                            coord,
+                        ));
+                        ctx.ui.send(TheEvent::TreeItemDragStarted(
+                            self.id().clone(),
+                            self.layout_id.clone(),
+                            text,
+                            coord,
+                        ));
                     }
                 }
             }
@@ -242,6 +252,16 @@ impl TheWidget for TheTreeItem {
                 // Only pass specific events to embedded widget that don't depend on mouse position
                 // This prevents embedded widgets from receiving events that should only go to the tree item
                 let has_focus = ctx.ui.has_focus(self.id());
+                if self.widget_column.is_none() {
+                    // Without an embedded widget the layout moves the selection with the keyboard.
+                    if let TheEvent::KeyDown(key) | TheEvent::KeyCodeDown(key) = event {
+                        ctx.ui.send(TheEvent::ListItemKeyDown(
+                            self.id().clone(),
+                            self.layout_id.clone(),
+                            key.clone(),
+                        ));
+                    }
+                }
                 if let Some((_, w)) = &mut self.widget_column {
                     match event {
                         // Pass focus events to embedded widget
@@ -513,8 +533,13 @@ impl TheWidget for TheTreeItem {
                 && icon_copy_rect.0 + icon_copy_rect.2 <= buffer_width
                 && icon_copy_rect.1 + icon_copy_rect.3 <= buffer_height
             {
-                ctx.draw
-                    .copy_slice(buffer.pixels_mut(), icon.pixels(), &icon_copy_rect, stride);
+                ctx.draw.copy_slice_scaled(
+                    buffer.pixels_mut(),
+                    icon.pixels(),
+                    icon.scale(),
+                    &icon_copy_rect,
+                    stride,
+                );
             }
 
             let text_rect = (
@@ -722,6 +747,15 @@ impl TheWidget for TheTreeItem {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            value: Some(self.sub_text.clone()).filter(|text| !text.is_empty()),
+            description: self.status.clone(),
+            selected: Some(self.state == TheWidgetState::Selected),
+            ..TheAccessInfo::named(TheAccessRole::TreeItem, &self.text)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -11,6 +11,7 @@ pub struct TheTreeText {
 
     layout_id: TheId,
     scroll_offset: i32,
+    mouse_down_pos: Vec2<i32>,
 
     text: String,
     font_size: f32,
@@ -42,6 +43,7 @@ impl TheWidget for TheTreeText {
             is_dirty: true,
 
             layout_id: TheId::empty(),
+            mouse_down_pos: Vec2::zero(),
             scroll_offset: 0,
 
             text: String::new(),
@@ -93,19 +95,42 @@ impl TheWidget for TheTreeText {
                     self.is_dirty = true;
                 }
             }
-            TheEvent::MouseDown(_coord) => {
+            TheEvent::MouseDown(coord) => {
+                self.mouse_down_pos = *coord;
                 if self.state != TheWidgetState::Selected || !self.id().equals(&ctx.ui.focus) {
                     self.is_dirty = true;
                     self.state = TheWidgetState::Selected;
                     ctx.ui.send_widget_state_changed(self.id(), self.state);
-                    ctx.ui.send(TheEvent::NewListItemSelected(
-                        self.id().clone(),
-                        self.layout_id.clone(),
-                    ));
                     redraw = true;
                 }
+                // Also sent for selected items, the layout handles clicks with modifiers.
+                ctx.ui.send(TheEvent::ListItemClicked(
+                    self.id().clone(),
+                    self.layout_id.clone(),
+                ));
                 ctx.ui.set_focus(self.id());
             }
+            TheEvent::MouseDragged(coord)
+                if ctx.ui.drop.is_none()
+                    && Vec2::new(self.mouse_down_pos.x as f32, self.mouse_down_pos.y as f32)
+                        .distance(Vec2::new(coord.x as f32, coord.y as f32))
+                        >= 5.0 =>
+            {
+                ctx.ui.send(TheEvent::TreeItemDragStarted(
+                    self.id().clone(),
+                    self.layout_id.clone(),
+                    self.text.clone(),
+                    *coord,
+                ));
+            }
+            TheEvent::KeyDown(key) | TheEvent::KeyCodeDown(key) => {
+                // The layout moves the selection with the keyboard.
+                ctx.ui.send(TheEvent::ListItemKeyDown(
+                    self.id().clone(),
+                    self.layout_id.clone(),
+                    key.clone(),
+                ));
+            }
             TheEvent::Hover(_coord) => {
                 if self.state != TheWidgetState::Selected && !self.id().equals(&ctx.ui.hover) {
                     self.is_dirty = true;
@@ -335,6 +360,13 @@ impl TheWidget for TheTreeText {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            selected: Some(self.state == TheWidgetState::Selected),
+            ..TheAccessInfo::named(TheAccessRole::TreeItem, &self.text)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -208,9 +208,10 @@ impl TheWidget for TheVerticalScrollbar {
                     icon.dim().width as usize,
                     icon.dim().height as usize,
                 );
-                ctx.draw.blend_slice_safe(
+                ctx.draw.blend_slice_safe_scaled(
                     buffer.pixels_mut(),
                     icon.pixels(),
+                    icon.scale(),
                     &r,
                     stride,
                     &safe_utuple,
@@ -227,9 +228,10 @@ impl TheWidget for TheVerticalScrollbar {
                     icon.dim().height as usize,
                 );
                 for _ in 0..scroll_bar_height - 10 {
-                    ctx.draw.blend_slice_safe(
+                    ctx.draw.blend_slice_safe_scaled(
                         buffer.pixels_mut(),
                         icon.pixels(),
+                        icon.scale(),
                         &r,
                         stride,
                         &safe_utuple,
@@ -247,9 +249,10 @@ impl TheWidget for TheVerticalScrollbar {
                     icon.dim().width as usize,
                     icon.dim().height as usize,
                 );
-                ctx.draw.blend_slice_safe(
+                ctx.draw.blend_slice_safe_scaled(
                     buffer.pixels_mut(),
                     icon.pixels(),
+                    icon.scale(),
                     &r,
                     stride,
                     &safe_utuple,
@@ -264,6 +267,15 @@ impl TheWidget for TheVerticalScrollbar {
         Some(self)
     }
 
+    fn accessibility(&self) -> TheAccessInfo {
+        TheAccessInfo {
+            numeric_value: Some(self.scroll_offset as f64),
+            min_numeric_value: Some(0.0),
+            max_numeric_value: Some((self.total_height - self.dim.height).max(0) as f64),
+            ..TheAccessInfo::new(TheAccessRole::ScrollBar)
+        }
+    }
+
     fn as_any(&mut self) -> &mut dyn std::any::Any {
         self
     }
@@ -0,0 +1,91 @@
+use crate::prelude::*;
+
+/// The settings of an additional window, see TheUI::open_window().
+#[derive(Clone, Debug, PartialEq)]
+pub struct TheWindowSettings {
+    pub title: String,
+    /// The logical width of the window.
+    pub width: usize,
+    /// The logical height of the window.
+    pub height: usize,
+    pub resizable: bool,
+}
+
+impl TheWindowSettings {
+    pub fn new(title: &str, width: usize, height: usize) -> Self {
+        Self {
+            title: title.to_string(),
+            width,
+            height,
+            resizable: true,
+        }
+    }
+}
+
+/// An additional OS window of TheUI with its own canvas. All windows share TheUIContext, i.e. the
+/// clipboard, the undo stacks and the focus.
+///
+/// While the backend processes the events of a window or draws it, the window is activated: its
+/// canvas, size and damage are swapped with the ones of TheUI and TheContext. Otherwise the
+/// window holds its own state.
+pub struct TheWindow {
+    pub id: TheId,
+    pub settings: TheWindowSettings,
+    pub canvas: TheCanvas,
+
+    /// True once the backend created the OS window.
+    created: bool,
+
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) scale_factor: f32,
+    pub(crate) damage: TheDamage,
+    pub(crate) redraw_all: bool,
+    pub(crate) relayout: bool,
+
+    pub(crate) context_menu: Option<TheContextMenu>,
+    pub(crate) menu_widget_id: Option<TheId>,
+    pub(crate) overlay_regions: Vec<TheDim>,
+    pub(crate) tooltip: TheTooltipOverlay,
+    pub(crate) access_tree: Option<TheAccessTree>,
+}
+
+impl TheWindow {
+    pub fn new(id: TheId, settings: TheWindowSettings, mut canvas: TheCanvas) -> Self {
+        canvas.root = true;
+        Self {
+            id,
+            width: settings.width,
+            height: settings.height,
+            settings,
+            canvas,
+
+            created: false,
+
+            scale_factor: 1.0,
+            damage: TheDamage::Full,
+            redraw_all: true,
+            relayout: true,
+
+            context_menu: None,
+            menu_widget_id: None,
+            overlay_regions: vec![],
+            tooltip: TheTooltipOverlay::default(),
+            access_tree: None,
+        }
+    }
+
+    /// The logical size of the window.
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Returns true once the backend created the OS window.
+    pub fn is_created(&self) -> bool {
+        self.created
+    }
+
+    pub(crate) fn set_created(&mut self) {
+        self.created = true;
+    }
+}
@@ -1,4 +1,4 @@
-use std::{num::NonZeroU32, sync::Arc};
+use std::{collections::VecDeque, num::NonZeroU32, sync::Arc};
 
 #[cfg(feature = "ui")]
 use rfd::MessageDialog;
@@ -8,6 +8,9 @@ use crate::thecontext::TheCursorIcon;
 #[cfg(target_os = "macos")]
 use winit::platform::macos::WindowExtMacOS;
 
+#[cfg(feature = "accessibility")]
+use winit::event_loop::EventLoopProxy;
+
 use crate::prelude::*;
 use softbuffer::Surface;
 use web_time::{Duration, Instant};
@@ -19,7 +22,7 @@ use winit::{
         TouchPhase, WindowEvent,
     },
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
-    keyboard::{Key, KeyCode, ModifiersState, NamedKey},
+    keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey, PhysicalKey},
     window::{Icon, Window, WindowAttributes, WindowId},
 };
 
@@ -99,68 +102,153 @@ fn accel_physical_to_ascii(code: KeyCode, shift: bool) -> Option<char> {
     Some(if shift { shifted } else { base })
 }
 
+// Translates the named keys which have a TheKeyCode.
+fn named_key_code(key: &NamedKey) -> Option<TheKeyCode> {
+    Some(match key {
+        NamedKey::Escape => TheKeyCode::Escape,
+        NamedKey::Enter => TheKeyCode::Return,
+        NamedKey::Delete => TheKeyCode::Delete,
+        NamedKey::Backspace => TheKeyCode::Backspace,
+        NamedKey::Insert => TheKeyCode::Insert,
+        NamedKey::ArrowUp => TheKeyCode::Up,
+        NamedKey::ArrowRight => TheKeyCode::Right,
+        NamedKey::ArrowDown => TheKeyCode::Down,
+        NamedKey::ArrowLeft => TheKeyCode::Left,
+        NamedKey::Home => TheKeyCode::Home,
+        NamedKey::End => TheKeyCode::End,
+        NamedKey::PageUp => TheKeyCode::PageUp,
+        NamedKey::PageDown => TheKeyCode::PageDown,
+        NamedKey::Space => TheKeyCode::Space,
+        NamedKey::Tab => TheKeyCode::Tab,
+        NamedKey::F1 => TheKeyCode::F1,
+        NamedKey::F2 => TheKeyCode::F2,
+        NamedKey::F3 => TheKeyCode::F3,
+        NamedKey::F4 => TheKeyCode::F4,
+        NamedKey::F5 => TheKeyCode::F5,
+        NamedKey::F6 => TheKeyCode::F6,
+        NamedKey::F7 => TheKeyCode::F7,
+        NamedKey::F8 => TheKeyCode::F8,
+        NamedKey::F9 => TheKeyCode::F9,
+        NamedKey::F10 => TheKeyCode::F10,
+        NamedKey::F11 => TheKeyCode::F11,
+        NamedKey::F12 => TheKeyCode::F12,
+        _ => return None,
+    })
+}
+
+// The numpad key code of a key which produced a character or Enter. With NumLock off the numpad
+// produces named keys (arrows, Home, ...), these are not reported as numpad keys.
+fn numpad_key_code(key_event: &winit::event::KeyEvent) -> Option<TheKeyCode> {
+    if key_event.location != KeyLocation::Numpad {
+        return None;
+    }
+    if !matches!(
+        key_event.logical_key,
+        Key::Character(_) | Key::Named(NamedKey::Enter)
+    ) {
+        return None;
+    }
+    let PhysicalKey::Code(code) = key_event.physical_key else {
+        return None;
+    };
+    Some(match code {
+        KeyCode::Numpad0 => TheKeyCode::Numpad0,
+        KeyCode::Numpad1 => TheKeyCode::Numpad1,
+        KeyCode::Numpad2 => TheKeyCode::Numpad2,
+        KeyCode::Numpad3 => TheKeyCode::Numpad3,
+        KeyCode::Numpad4 => TheKeyCode::Numpad4,
+        KeyCode::Numpad5 => TheKeyCode::Numpad5,
+        KeyCode::Numpad6 => TheKeyCode::Numpad6,
+        KeyCode::Numpad7 => TheKeyCode::Numpad7,
+        KeyCode::Numpad8 => TheKeyCode::Numpad8,
+        KeyCode::Numpad9 => TheKeyCode::Numpad9,
+        KeyCode::NumpadAdd => TheKeyCode::NumpadAdd,
+        KeyCode::NumpadSubtract => TheKeyCode::NumpadSubtract,
+        KeyCode::NumpadMultiply => TheKeyCode::NumpadMultiply,
+        KeyCode::NumpadDivide => TheKeyCode::NumpadDivide,
+        KeyCode::NumpadDecimal | KeyCode::NumpadComma => TheKeyCode::NumpadDecimal,
+        KeyCode::NumpadEnter => TheKeyCode::NumpadEnter,
+        _ => return None,
+    })
+}
+
+/// Copies the given rectangles of the physical resolution UI frame into the surface. Both have
+/// the same size except for a possible rounding difference at fractional scale factors, which is
+/// clipped.
 fn blit_rgba_into_softbuffer(
     ui_frame: &[u8],
-    scale_factor: f32,
     width: usize,
     height: usize,
     dest: &mut [u32],
+    dest_width: usize,
+    dest_height: usize,
+    rects: &[(usize, usize, usize, usize)],
 ) {
-    // Round to match the resized surface at fractional DPI across platforms
-    let dest_width = (width as f32 * scale_factor).round() as usize;
-    let dest_height = (height as f32 * scale_factor).round() as usize;
-
-    if scale_factor == 1.0 {
-        // Direct copy without extra allocation.
-        for (dst, rgba) in dest.iter_mut().zip(ui_frame.chunks_exact(4)) {
-            *dst = (rgba[2] as u32) | ((rgba[1] as u32) << 8) | ((rgba[0] as u32) << 16);
+    let copy_width = width.min(dest_width);
+    let copy_height = height.min(dest_height);
+
+    for &(x, y, w, h) in rects {
+        let x1 = (x + w).min(copy_width);
+        let y1 = (y + h).min(copy_height);
+        if x >= x1 {
+            continue;
         }
-    } else {
-        // Nearest-neighbor upscaling with fractional scale factors
-        for dest_y in 0..dest_height {
-            let src_y = (dest_y as f32 / scale_factor) as usize;
-            if src_y >= height {
-                continue;
-            }
-
-            for dest_x in 0..dest_width {
-                let src_x = (dest_x as f32 / scale_factor) as usize;
-                if src_x >= width {
-                    continue;
-                }
-
-                let src_offset = (src_y * width + src_x) * 4;
-                let r = ui_frame[src_offset] as u32;
-                let g = ui_frame[src_offset + 1] as u32;
-                let b = ui_frame[src_offset + 2] as u32;
-                let color = b | (g << 8) | (r << 16);
-
-                let dest_offset = dest_y * dest_width + dest_x;
-                if dest_offset < dest.len() {
-                    dest[dest_offset] = color;
-                }
+        for y in y..y1 {
+            let src = &ui_frame[(y * width + x) * 4..(y * width + x1) * 4];
+            let dst = &mut dest[y * dest_width + x..y * dest_width + x1];
+            for (dst, rgba) in dst.iter_mut().zip(src.chunks_exact(4)) {
+                *dst = (rgba[2] as u32) | ((rgba[1] as u32) << 8) | ((rgba[0] as u32) << 16);
             }
         }
     }
 }
 
+/// Converts physical frame rectangles to the damage rectangles of softbuffer.
+fn softbuffer_damage(rects: &[(usize, usize, usize, usize)]) -> Vec<softbuffer::Rect> {
+    rects
+        .iter()
+        .filter_map(|&(x, y, width, height)| {
+            Some(softbuffer::Rect {
+                x: x as u32,
+                y: y as u32,
+                width: NonZeroU32::new(width as u32)?,
+                height: NonZeroU32::new(height as u32)?,
+            })
+        })
+        .collect()
+}
+
 fn translate_coord_to_local(x: f32, y: f32, scale_factor: f32) -> (f32, f32) {
     (x / scale_factor, y / scale_factor)
 }
 
+/// The user events of the event loop, the requests of the AccessKit adapter with the
+/// "accessibility" feature.
+#[cfg(feature = "accessibility")]
+type TheUserEvent = accesskit_winit::Event;
+#[cfg(not(feature = "accessibility"))]
+type TheUserEvent = ();
+
+/// The number of presented frames whose damage is kept, for surfaces which cycle through several
+/// buffers.
+const DAMAGE_HISTORY: usize = 3;
+
 struct TheWinitContext {
     window: Arc<Window>,
     ctx: TheContext,
     ui_frame: Vec<u8>,
     surface: Surface<Arc<Window>, Arc<Window>>,
+    /// The damaged rectangles of the last presented frames, the most recent first.
+    damage_history: VecDeque<Vec<(usize, usize, usize, usize)>>,
 }
 
 impl TheWinitContext {
     fn from_window(window: Arc<Window>) -> Self {
-        #[cfg(not(target_os = "macos"))]
+        // The UI is laid out in logical units and rendered at physical resolution. On WASM the
+        // surface uses the logical size, so the UI is rendered at a scale factor of 1.
+        #[cfg(target_arch = "wasm32")]
         let scale_factor = 1.0;
-        // Make sure to set the initial scale factor on macOS
-        #[cfg(target_os = "macos")]
+        #[cfg(not(target_arch = "wasm32"))]
         let scale_factor = window.scale_factor() as f32;
 
         let size = window.inner_size();
@@ -176,43 +264,23 @@ impl TheWinitContext {
             )
         };
         #[cfg(not(target_arch = "wasm32"))]
-        let (width, height) = (size.width as usize, size.height as usize);
-
-        // println!("=== from_window DEBUG ===");
-        // println!(
-        //     "Window scale_factor (from winit): {}",
-        //     window.scale_factor()
-        // );
-        // println!("Using scale_factor: {}", scale_factor);
-        // println!("Physical size (inner_size): {}x{}", size.width, size.height);
-        // println!("Context size: {}x{}", width, height);
-        // println!("ui_frame size: {} bytes", width * height * 4);
+        let (width, height) = (
+            (size.width as f32 / scale_factor).round() as usize,
+            (size.height as f32 / scale_factor).round() as usize,
+        );
 
         let ctx = TheContext::new(width, height, scale_factor);
 
-        let ui_frame = vec![0; (width * height * 4) as usize];
+        let ui_frame = vec![0; ctx.physical_width() * ctx.physical_height() * 4];
 
         let context = softbuffer::Context::new(window.clone()).unwrap();
         let mut surface = softbuffer::Surface::new(&context, window.clone()).unwrap();
 
-        // On Windows/Linux, don't scale the surface (use 1.0)
-        // On macOS, scale by scale_factor
-        // On WASM, use logical size directly
+        // The surface has the physical size of the window, on WASM the logical size
         #[cfg(target_arch = "wasm32")]
         let (surface_width, surface_height) = (width as u32, height as u32);
         #[cfg(not(target_arch = "wasm32"))]
-        let (surface_width, surface_height) = {
-            #[cfg(target_os = "macos")]
-            let surface_scale = scale_factor;
-            #[cfg(not(target_os = "macos"))]
-            let surface_scale = 1.0;
-
-            (
-                size.width * surface_scale as u32,
-                size.height * surface_scale as u32,
-            )
-        };
-        // println!("Surface size: {}x{}", surface_width, surface_height);
+        let (surface_width, surface_height) = (size.width, size.height);
 
         if let (Some(width), Some(height)) = (
             NonZeroU32::new(surface_width),
@@ -220,17 +288,38 @@ impl TheWinitContext {
         ) {
             surface.resize(width, height).unwrap();
         }
-        // println!("========================\n");
 
         TheWinitContext {
             window,
             ctx,
             ui_frame,
             surface,
+            damage_history: VecDeque::new(),
         }
     }
 }
 
+/// An additional window of TheUI, see TheUI::open_window(). While the window is active its OS
+/// window, frame and surface are swapped with the ones of TheWinitContext.
+#[cfg(feature = "ui")]
+struct TheWinitWindow {
+    id: TheId,
+    window: Arc<Window>,
+    ui_frame: Vec<u8>,
+    surface: Surface<Arc<Window>, Arc<Window>>,
+    damage_history: VecDeque<Vec<(usize, usize, usize, usize)>>,
+}
+
+#[cfg(feature = "ui")]
+impl TheWinitWindow {
+    fn swap(&mut self, ctx: &mut TheWinitContext) {
+        std::mem::swap(&mut self.window, &mut ctx.window);
+        std::mem::swap(&mut self.ui_frame, &mut ctx.ui_frame);
+        std::mem::swap(&mut self.surface, &mut ctx.surface);
+        std::mem::swap(&mut self.damage_history, &mut ctx.damage_history);
+    }
+}
+
 struct TheWinitApp {
     args: Option<Vec<String>>,
     ctx: Option<TheWinitContext>,
@@ -245,6 +334,21 @@ struct TheWinitApp {
 
     #[cfg(feature = "ui")]
     ui: TheUI,
+    /// The additional windows.
+    #[cfg(feature = "ui")]
+    windows: FxHashMap<WindowId, TheWinitWindow>,
+    /// The additional window which is currently active, None for the main window.
+    #[cfg(feature = "ui")]
+    active_window: Option<WindowId>,
+
+    /// The AccessKit adapter of the main window, the proxy it sends its requests with and if an
+    /// assistive technology is connected.
+    #[cfg(feature = "accessibility")]
+    proxy: Option<EventLoopProxy<TheUserEvent>>,
+    #[cfg(feature = "accessibility")]
+    access_adapter: Option<accesskit_winit::Adapter>,
+    #[cfg(feature = "accessibility")]
+    access_active: bool,
 }
 
 impl TheWinitApp {
@@ -263,6 +367,16 @@ impl TheWinitApp {
             has_changes: false,
             #[cfg(feature = "ui")]
             ui: TheUI::new(),
+            #[cfg(feature = "ui")]
+            windows: FxHashMap::default(),
+            #[cfg(feature = "ui")]
+            active_window: None,
+            #[cfg(feature = "accessibility")]
+            proxy: None,
+            #[cfg(feature = "accessibility")]
+            access_adapter: None,
+            #[cfg(feature = "accessibility")]
+            access_active: false,
         }
     }
 
@@ -289,8 +403,24 @@ impl TheWinitApp {
             window_attributes.with_append(true)
         };
 
+        // The AccessKit adapter has to be created before the window is shown.
+        #[cfg(feature = "accessibility")]
+        let window_attributes = window_attributes.with_visible(false);
+
         let window = event_loop.create_window(window_attributes).unwrap();
 
+        #[cfg(feature = "accessibility")]
+        {
+            if let Some(proxy) = &self.proxy {
+                self.access_adapter = Some(accesskit_winit::Adapter::with_event_loop_proxy(
+                    event_loop,
+                    &window,
+                    proxy.clone(),
+                ));
+            }
+            window.set_visible(true);
+        }
+
         Some(Arc::new(window))
     }
 
@@ -335,56 +465,131 @@ impl TheWinitApp {
             return;
         }
 
+        // Additional windows only show their TheUI canvas.
+        #[cfg(feature = "ui")]
+        let main_window = self.ui.active_window().is_none();
+        #[cfg(not(feature = "ui"))]
+        let main_window = true;
+
         #[cfg(feature = "ui")]
-        self.app.pre_ui(&mut ctx.ctx);
+        if main_window {
+            self.app.pre_ui(&mut ctx.ctx);
+        }
 
         #[cfg(feature = "ui")]
         self.ui.draw(&mut ctx.ui_frame, &mut ctx.ctx);
 
         // We always call this for apps who use the "ui" feature
         // but do not use the UI API
-        self.app.draw(&mut ctx.ui_frame, &mut ctx.ctx);
-
-        // On Windows/Linux, try to use the actual scale_factor, but verify the dest buffer is large enough
-        // On macOS, use the actual scale_factor for Retina displays
-        #[cfg(target_os = "macos")]
-        let blit_scale_factor = ctx.ctx.scale_factor;
-        #[cfg(not(target_os = "macos"))]
-        let blit_scale_factor = {
-            let buffer = ctx.surface.buffer_mut().unwrap();
+        if main_window {
+            self.app.draw(&mut ctx.ui_frame, &mut ctx.ctx);
+            if !self.app.reports_damage() {
+                ctx.ctx.damage_all();
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        let (dest_width, dest_height) = (ctx.ctx.width, ctx.ctx.height);
+        #[cfg(not(target_arch = "wasm32"))]
+        let (dest_width, dest_height) = {
             let inner_size = ctx.window.inner_size();
-            // Derive scale from the actual surface size to avoid double rounding (Windows fractional DPI)
-            let desired_scale = inner_size.width as f32 / ctx.ctx.width as f32;
+            (inner_size.width as usize, inner_size.height as usize)
+        };
 
-            let dest_width = inner_size.width as usize;
-            let dest_height = inner_size.height as usize;
-            let required_size = dest_width * dest_height;
+        let (width, height) = (ctx.ctx.physical_width(), ctx.ctx.physical_height());
+
+        // Only the regions changed by the UI and the app are presented.
+        let damage = ctx.ctx.take_damage();
+        if !damage.is_empty() {
+            let rects = damage.physical_rects(ctx.ctx.scale_factor, width, height);
+
+            let mut buffer = ctx.surface.buffer_mut().unwrap();
+            if buffer.len() >= dest_width * dest_height {
+                // A buffer which was presented several frames ago also needs the changes of
+                // the frames in between, a new buffer needs everything.
+                let age = buffer.age() as usize;
+                let mut blit_rects = rects.clone();
+                if age == 0 || age > ctx.damage_history.len() + 1 {
+                    blit_rects = vec![(0, 0, width, height)];
+                } else {
+                    for history in ctx.damage_history.iter().take(age - 1) {
+                        blit_rects.extend(history);
+                    }
+                }
 
-            // Check if the destination buffer is large enough for the upscaled blit
-            // If not, fall back to scale_factor = 1.0 to avoid crashes/panics
-            if buffer.len() >= required_size {
-                desired_scale
-            } else {
-                println!(
-                    "Warning: Buffer too small for scale_factor {}. Required: {}, Available: {}. Falling back to scale_factor = 1.0",
-                    desired_scale, required_size, buffer.len()
+                blit_rgba_into_softbuffer(
+                    &ctx.ui_frame,
+                    width,
+                    height,
+                    &mut buffer,
+                    dest_width,
+                    dest_height,
+                    &blit_rects,
                 );
-                1.0
-            }
-        };
+                buffer
+                    .present_with_damage(&softbuffer_damage(&rects))
+                    .unwrap();
 
-        let mut buffer = ctx.surface.buffer_mut().unwrap();
-        blit_rgba_into_softbuffer(
-            &ctx.ui_frame,
-            blit_scale_factor,
-            ctx.ctx.width,
-            ctx.ctx.height,
-            &mut *buffer,
-        );
-        buffer.present().unwrap();
+                ctx.damage_history.push_front(rects);
+                ctx.damage_history.truncate(DAMAGE_HISTORY);
+            }
+        }
 
         #[cfg(feature = "ui")]
-        self.app.post_ui(&mut ctx.ctx);
+        if main_window {
+            self.app.post_ui(&mut ctx.ctx);
+        }
+
+        #[cfg(feature = "accessibility")]
+        if main_window {
+            self.update_accessibility();
+        }
+    }
+
+    /// Passes the changes of the accessibility tree of the main window on to the AccessKit
+    /// adapter while an assistive technology is connected.
+    #[cfg(feature = "accessibility")]
+    fn update_accessibility(&mut self) {
+        if !self.access_active {
+            return;
+        }
+        let (Some(adapter), Some(ctx)) = (&mut self.access_adapter, &self.ctx) else {
+            return;
+        };
+        if let Some(update) = self.ui.accessibility_update(&ctx.ctx) {
+            let scale_factor = ctx.ctx.scale_factor as f64;
+            adapter.update_if_active(|| update.to_accesskit(scale_factor));
+        }
+    }
+
+    /// Handles the requests of the AccessKit adapter of the main window.
+    #[cfg(feature = "accessibility")]
+    fn access_event(&mut self, event: accesskit_winit::WindowEvent) {
+        match event {
+            accesskit_winit::WindowEvent::InitialTreeRequested => {
+                self.access_active = true;
+                self.ui.reset_accessibility();
+                self.update_accessibility();
+            }
+            accesskit_winit::WindowEvent::ActionRequested(request) => {
+                let action = match request.action {
+                    accesskit::Action::Focus => TheAccessAction::Focus,
+                    accesskit::Action::Click => TheAccessAction::Click,
+                    _ => return,
+                };
+                if let Some(ctx) = &mut self.ctx {
+                    if self
+                        .ui
+                        .accessibility_action(request.target.0, action, &mut ctx.ctx)
+                    {
+                        ctx.window.request_redraw();
+                    }
+                }
+            }
+            accesskit_winit::WindowEvent::AccessibilityDeactivated => {
+                self.access_active = false;
+            }
+        }
     }
 
     fn resize(&mut self, size: PhysicalSize<u32>) {
@@ -396,66 +601,51 @@ impl TheWinitApp {
             // println!("=== resize DEBUG ===");
             // println!("New physical size: {}x{}", size.width, size.height);
 
-            let scale_factor = ctx.window.scale_factor() as f32;
-
-            // On non-macOS, if DPI scale is fractional, render at physical resolution with scale_factor forced to 1.0
-            #[cfg(all(not(target_os = "macos"), not(target_arch = "wasm32")))]
-            let (effective_scale, width, height) = if scale_factor.fract() != 0.0 {
-                (1.0_f32, size.width, size.height)
-            } else {
+            #[cfg(target_arch = "wasm32")]
+            let (scale_factor, width, height) = {
+                let wasm_scale = ctx.window.scale_factor() as f32;
+                (
+                    1.0,
+                    (size.width as f32 / wasm_scale).round() as u32,
+                    (size.height as f32 / wasm_scale).round() as u32,
+                )
+            };
+            #[cfg(not(target_arch = "wasm32"))]
+            let (scale_factor, width, height) = {
+                let scale_factor = ctx.window.scale_factor() as f32;
                 (
                     scale_factor,
                     (size.width as f32 / scale_factor).round() as u32,
                     (size.height as f32 / scale_factor).round() as u32,
                 )
             };
-            // macOS and WASM: keep logical sizing based on scale_factor
-            #[cfg(any(target_os = "macos", target_arch = "wasm32"))]
-            let (effective_scale, width, height) = (
-                scale_factor,
-                (size.width as f32 / scale_factor).round() as u32,
-                (size.height as f32 / scale_factor).round() as u32,
-            );
-
-            ctx.ctx.scale_factor = effective_scale;
 
             // WASM-specific: surface should use logical size
             #[cfg(target_arch = "wasm32")]
             ctx.surface
                 .resize(
-                    NonZeroU32::new(width as u32).unwrap(),
-                    NonZeroU32::new(height as u32).unwrap(),
+                    NonZeroU32::new(width).unwrap(),
+                    NonZeroU32::new(height).unwrap(),
                 )
                 .unwrap();
 
             // Desktop: surface uses physical size
             #[cfg(not(target_arch = "wasm32"))]
-            {
-                #[cfg(not(target_os = "macos"))]
-                ctx.surface
-                    .resize(
-                        NonZeroU32::new(size.width).unwrap(),
-                        NonZeroU32::new(size.height).unwrap(),
-                    )
-                    .unwrap();
-                #[cfg(target_os = "macos")]
-                ctx.surface
-                    .resize(
-                        NonZeroU32::new(size.width).unwrap(),
-                        NonZeroU32::new(size.height).unwrap(),
-                    )
-                    .unwrap();
-            }
-
-            // println!("Window scale_factor: {}", scale_factor);
-            // println!("New logical size: {}x{}", width, height);
-            // println!("New ui_frame size: {} bytes", width * height * 4);
+            ctx.surface
+                .resize(
+                    NonZeroU32::new(size.width).unwrap(),
+                    NonZeroU32::new(size.height).unwrap(),
+                )
+                .unwrap();
 
             ctx.ctx.width = width as usize;
             ctx.ctx.height = height as usize;
+            ctx.ctx.set_scale_factor(scale_factor);
+            ctx.ctx.damage_all();
+            ctx.damage_history.clear();
 
-            ctx.ui_frame.resize((width * height * 4) as usize, 0);
-            // println!("===================\n");
+            ctx.ui_frame
+                .resize(ctx.ctx.physical_width() * ctx.ctx.physical_height() * 4, 0);
 
             #[cfg(feature = "ui")]
             self.ui
@@ -467,30 +657,101 @@ impl TheWinitApp {
             ctx.window.request_redraw();
         }
     }
-}
 
-impl ApplicationHandler for TheWinitApp {
-    fn new_events(&mut self, _: &ActiveEventLoop, _: StartCause) {}
+    /// Makes the given additional window, or the main window for None, the active one. The OS
+    /// window, frame and surface of the window are swapped into TheWinitContext and TheUI
+    /// switches to the canvas of the window, so that all event processing and rendering applies
+    /// to it.
+    #[cfg(feature = "ui")]
+    fn activate_window(&mut self, window_id: Option<WindowId>) {
+        if self.active_window == window_id {
+            return;
+        }
+        let Some(ctx) = &mut self.ctx else {
+            return;
+        };
 
-    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        if self.ctx.is_none() {
-            if let Some(window) = self.create_window(event_loop) {
-                self.ctx = Some(self.init_context(window));
-                // Set initial cursor to default and ensure it's visible
-                if let Some(ctx) = &mut self.ctx {
-                    ctx.ctx.set_cursor_icon(TheCursorIcon::Default);
-                    ctx.window.set_cursor_visible(true);
-                }
+        if let Some(active) = self.active_window.take() {
+            if let Some(window) = self.windows.get_mut(&active) {
+                window.swap(ctx);
+            }
+        }
+
+        let mut id = None;
+        if let Some(window_id) = window_id {
+            if let Some(window) = self.windows.get_mut(&window_id) {
+                window.swap(ctx);
+                id = Some(window.id.uuid);
+                self.active_window = Some(window_id);
             }
         }
+        self.ui.activate_window(id.as_ref(), &mut ctx.ctx);
     }
 
-    fn window_event(
-        &mut self,
-        event_loop: &ActiveEventLoop,
-        _window_id: WindowId,
-        event: WindowEvent,
-    ) {
+    /// The user wants to close an additional window, the app decides.
+    #[cfg(feature = "ui")]
+    fn close_requested(&mut self, window_id: &WindowId) {
+        let Some(ctx) = &mut self.ctx else {
+            return;
+        };
+        let Some(window) = self.windows.get(window_id) else {
+            return;
+        };
+
+        let id = window.id.clone();
+        if !self.app.window_closing(&id, &mut ctx.ctx) {
+            self.ui.close_window(&id.uuid, &mut ctx.ctx);
+            self.windows.remove(window_id);
+            ctx.window.request_redraw();
+        }
+    }
+
+    /// Creates the OS windows of newly opened windows and destroys the ones of closed windows.
+    #[cfg(feature = "ui")]
+    fn sync_windows(&mut self, event_loop: &ActiveEventLoop) {
+        for id in self.ui.take_closed_windows() {
+            self.windows.retain(|_, window| window.id.uuid != id.uuid);
+        }
+
+        for (id, settings) in self.ui.take_new_windows() {
+            let window_attributes = WindowAttributes::default()
+                .with_title(settings.title.clone())
+                .with_inner_size(LogicalSize::new(
+                    settings.width as f64,
+                    settings.height as f64,
+                ))
+                .with_resizable(settings.resizable);
+
+            let Ok(window) = event_loop.create_window(window_attributes) else {
+                continue;
+            };
+            let window = Arc::new(window);
+
+            let context = softbuffer::Context::new(window.clone()).unwrap();
+            let surface = softbuffer::Surface::new(&context, window.clone()).unwrap();
+
+            let window_id = window.id();
+            let size = window.inner_size();
+            self.windows.insert(
+                window_id,
+                TheWinitWindow {
+                    id,
+                    window,
+                    ui_frame: vec![],
+                    surface,
+                    damage_history: VecDeque::new(),
+                },
+            );
+
+            // Sizes the surface, the frame and the canvas of the window.
+            self.activate_window(Some(window_id));
+            self.resize(size);
+            self.activate_window(None);
+        }
+    }
+
+    /// Processes an event of the active window.
+    fn process_window_event(&mut self, event_loop: &ActiveEventLoop, event: WindowEvent) {
         match event {
             WindowEvent::RedrawRequested => {
                 self.render();
@@ -524,13 +785,20 @@ impl ApplicationHandler for TheWinitApp {
             WindowEvent::Resized(size) => {
                 self.resize(size);
             }
-            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+            WindowEvent::ScaleFactorChanged { .. } => {
                 if let Some(ctx) = &mut self.ctx {
-                    ctx.ctx.scale_factor = scale_factor as f32;
                     let size = ctx.window.inner_size();
                     self.resize(size);
                 }
             }
+            WindowEvent::Occluded(false) => {
+                // The window contents may have been lost while hidden.
+                if let Some(ctx) = &mut self.ctx {
+                    ctx.ctx.damage_all();
+                    ctx.damage_history.clear();
+                    ctx.window.request_redraw();
+                }
+            }
             event => {
                 let Some(ctx) = &mut self.ctx else {
                     return;
@@ -542,17 +810,7 @@ impl ApplicationHandler for TheWinitApp {
                     } => {
                         if key_event.state == ElementState::Pressed {
                             let key = match &key_event.logical_key {
-                                Key::Named(NamedKey::Delete) | Key::Named(NamedKey::Backspace) => {
-                                    Some(TheKeyCode::Delete)
-                                }
-                                Key::Named(NamedKey::ArrowUp) => Some(TheKeyCode::Up),
-                                Key::Named(NamedKey::ArrowRight) => Some(TheKeyCode::Right),
-                                Key::Named(NamedKey::ArrowDown) => Some(TheKeyCode::Down),
-                                Key::Named(NamedKey::ArrowLeft) => Some(TheKeyCode::Left),
-                                Key::Named(NamedKey::Space) => Some(TheKeyCode::Space),
-                                Key::Named(NamedKey::Tab) => Some(TheKeyCode::Tab),
-                                Key::Named(NamedKey::Enter) => Some(TheKeyCode::Return),
-                                Key::Named(NamedKey::Escape) => Some(TheKeyCode::Escape),
+                                Key::Named(named) => named_key_code(named),
                                 Key::Character(str) => {
                                     // Accelerator: use physical key with modifiers (ignore composed text like "å")
                                     if is_accel_mods(&self.mods) {
@@ -588,12 +846,13 @@ impl ApplicationHandler for TheWinitApp {
                                 }
                                 _ => None,
                             };
-                            if key.is_some() {
+                            // Numpad keys follow the character or Return they produced.
+                            for key in [key, numpad_key_code(&key_event)].into_iter().flatten() {
                                 #[cfg(feature = "ui")]
-                                if self.ui.key_down(None, key.clone(), &mut ctx.ctx) {
+                                if self.ui.key_down(None, Some(key), &mut ctx.ctx) {
                                     ctx.window.request_redraw();
                                 }
-                                if self.app.key_down(None, key, &mut ctx.ctx) {
+                                if self.app.key_down(None, Some(key), &mut ctx.ctx) {
                                     ctx.window.request_redraw();
                                 }
                             }
@@ -665,17 +924,7 @@ impl ApplicationHandler for TheWinitApp {
                         }
                         if key_event.state == ElementState::Released {
                             let key = match &key_event.logical_key {
-                                Key::Named(NamedKey::Delete) | Key::Named(NamedKey::Backspace) => {
-                                    Some(TheKeyCode::Delete)
-                                }
-                                Key::Named(NamedKey::ArrowUp) => Some(TheKeyCode::Up),
-                                Key::Named(NamedKey::ArrowRight) => Some(TheKeyCode::Right),
-                                Key::Named(NamedKey::ArrowDown) => Some(TheKeyCode::Down),
-                                Key::Named(NamedKey::ArrowLeft) => Some(TheKeyCode::Left),
-                                Key::Named(NamedKey::Space) => Some(TheKeyCode::Space),
-                                Key::Named(NamedKey::Tab) => Some(TheKeyCode::Tab),
-                                Key::Named(NamedKey::Enter) => Some(TheKeyCode::Return),
-                                Key::Named(NamedKey::Escape) => Some(TheKeyCode::Escape),
+                                Key::Named(named) => named_key_code(named),
                                 Key::Character(str) => {
                                     // Accelerator release: use physical key with modifiers (ignore composed text)
                                     if is_accel_mods(&self.mods) {
@@ -711,12 +960,13 @@ impl ApplicationHandler for TheWinitApp {
                                 }
                                 _ => None,
                             };
-                            if key.is_some() {
+                            // Numpad keys follow the character or Return they produced.
+                            for key in [key, numpad_key_code(&key_event)].into_iter().flatten() {
                                 #[cfg(feature = "ui")]
-                                if self.ui.key_up(None, key.clone(), &mut ctx.ctx) {
+                                if self.ui.key_up(None, Some(key), &mut ctx.ctx) {
                                     ctx.window.request_redraw();
                                 }
-                                if self.app.key_up(None, key, &mut ctx.ctx) {
+                                if self.app.key_up(None, Some(key), &mut ctx.ctx) {
                                     ctx.window.request_redraw();
                                 }
                             }
@@ -1108,6 +1358,60 @@ impl ApplicationHandler for TheWinitApp {
             }
         }
     }
+}
+
+impl ApplicationHandler<TheUserEvent> for TheWinitApp {
+    fn new_events(&mut self, _: &ActiveEventLoop, _: StartCause) {}
+
+    #[cfg(feature = "accessibility")]
+    fn user_event(&mut self, _: &ActiveEventLoop, event: TheUserEvent) {
+        let main_window = self.ctx.as_ref().map(|ctx| ctx.window.id());
+        if main_window == Some(event.window_id) {
+            self.access_event(event.window_event);
+        }
+    }
+
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.ctx.is_none() {
+            if let Some(window) = self.create_window(event_loop) {
+                self.ctx = Some(self.init_context(window));
+                // Set initial cursor to default and ensure it's visible
+                if let Some(ctx) = &mut self.ctx {
+                    ctx.ctx.set_cursor_icon(TheCursorIcon::Default);
+                    ctx.window.set_cursor_visible(true);
+                }
+            }
+        }
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        #[cfg(not(feature = "ui"))]
+        let _ = window_id;
+
+        #[cfg(feature = "ui")]
+        if self.windows.contains_key(&window_id) {
+            if event == WindowEvent::CloseRequested {
+                self.close_requested(&window_id);
+            } else {
+                self.activate_window(Some(window_id));
+                self.process_window_event(event_loop, event);
+                self.activate_window(None);
+            }
+            return;
+        }
+
+        #[cfg(feature = "accessibility")]
+        if let (Some(adapter), Some(ctx)) = (&mut self.access_adapter, &self.ctx) {
+            adapter.process_event(&ctx.window, &event);
+        }
+
+        self.process_window_event(event_loop, event);
+    }
 
     fn device_event(&mut self, _: &ActiveEventLoop, _: DeviceId, _: DeviceEvent) {}
 
@@ -1118,9 +1422,16 @@ impl ApplicationHandler for TheWinitApp {
             if let Some(ctx) = &self.ctx {
                 ctx.window.request_redraw();
             }
+            #[cfg(feature = "ui")]
+            for window in self.windows.values() {
+                window.window.request_redraw();
+            }
             self.next_frame_time = now + self.target_frame_time;
         }
 
+        #[cfg(feature = "ui")]
+        self.sync_windows(event_loop);
+
         // #[cfg(target_arch = "wasm32")]
         // {
         //     // Avoid WaitUntil on wasm to sidestep duration underflow; simple Wait keeps CPU low.
@@ -1166,6 +1477,12 @@ pub fn run_winit_app(args: Option<Vec<String>>, app: Box<dyn TheTrait>) {
 
     let mut winit_app = TheWinitApp::new(args, app);
 
-    let event_loop = EventLoop::new().unwrap();
+    let event_loop = EventLoop::<TheUserEvent>::with_user_event()
+        .build()
+        .unwrap();
+    #[cfg(feature = "accessibility")]
+    {
+        winit_app.proxy = Some(event_loop.create_proxy());
+    }
     event_loop.run_app(&mut winit_app).unwrap();
 }
@@ -0,0 +1,172 @@
+//! Tests for the accessibility tree of TheUI, driven by the headless runner.
+
+use theframework::prelude::*;
+
+struct Form;
+
+impl TheTrait for Form {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, _ctx: &mut TheContext) {
+        let mut canvas = TheCanvas::new();
+
+        let mut toolbar = TheHLayout::new(TheId::named("Toolbar"));
+        let mut save = TheTraybarButton::new(TheId::named("Save"));
+        save.set_text("Save".to_string());
+        save.set_status_text("Saves the document.");
+        toolbar.add_widget(Box::new(save));
+        toolbar.limiter_mut().set_max_height(30);
+        let mut toolbar_canvas = TheCanvas::new();
+        toolbar_canvas.set_layout(toolbar);
+        canvas.set_top(toolbar_canvas);
+
+        let mut form = TheTextLayout::new(TheId::named("Form"));
+        let mut name = TheTextLineEdit::new(TheId::named("Name"));
+        name.set_text("Alice".to_string());
+        form.add_pair("Name".to_string(), Box::new(name));
+        form.add_pair(
+            "Visible".to_string(),
+            Box::new(TheCheckButton::new(TheId::named("Visible"))),
+        );
+        let mut mode = TheDropdownMenu::new(TheId::named("Mode"));
+        mode.add_option("Fast".to_string());
+        mode.add_option("Exact".to_string());
+        form.add_pair("Mode".to_string(), Box::new(mode));
+        canvas.set_layout(form);
+
+        ui.canvas = canvas;
+    }
+}
+
+fn app() -> TheHeadlessApp {
+    let mut app = TheHeadlessApp::new(400, 300, Box::new(Form));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    app
+}
+
+#[test]
+fn dump_describes_roles_names_and_values() {
+    let mut app = app();
+    assert_eq!(
+        app.accessibility_dump(),
+        "Window\n\
+        \x20 GenericContainer\n\
+        \x20   Button \"Save\"\n\
+        \x20 GenericContainer\n\
+        \x20   TextInput \"Name\" value=\"Alice\"\n\
+        \x20   CheckBox \"Visible\" [unchecked]\n\
+        \x20   ComboBox \"Mode\" value=\"Fast\" [collapsed]\n"
+    );
+
+    let tree = app.ui.accessibility_tree(&app.ctx);
+    let save = tree.find("Save").unwrap();
+    assert_eq!(
+        save.info.description.as_deref(),
+        Some("Saves the document.")
+    );
+    assert!(save.bounds.is_valid());
+}
+
+#[test]
+fn focus_and_disabled_state_are_reported() {
+    let mut app = app();
+    let name = app.ui.get_widget("Name").unwrap().id().clone();
+    app.ctx.ui.set_focus(&name);
+    app.ctx.ui.set_disabled("Mode");
+
+    let tree = app.ui.accessibility_tree(&app.ctx);
+    assert_eq!(tree.node(tree.focus).unwrap().source.name, "Name");
+    assert!(tree.find("Mode").unwrap().disabled);
+    assert!(app
+        .accessibility_dump()
+        .contains("TextInput \"Name\" value=\"Alice\" [focused]"));
+}
+
+#[test]
+fn updates_contain_only_changed_nodes() {
+    let mut app = app();
+
+    let first = app.ui.accessibility_update(&app.ctx).unwrap();
+    assert!(first.root.is_some());
+    assert_eq!(first.nodes.len(), 7);
+    assert!(app.ui.accessibility_update(&app.ctx).is_none());
+
+    let (x, y) = {
+        let dim = app.ui.get_widget("Visible").unwrap().dim();
+        (
+            (dim.x + dim.width / 2) as f32,
+            (dim.y + dim.height / 2) as f32,
+        )
+    };
+    app.run_script(&[
+        TheHeadlessEvent::TouchDown(x, y),
+        TheHeadlessEvent::TouchUp(x, y),
+        TheHeadlessEvent::Frames(1),
+    ]);
+
+    let update = app.ui.accessibility_update(&app.ctx).unwrap();
+    assert!(update.root.is_none());
+    let checkbox = update
+        .nodes
+        .iter()
+        .find(|node| node.source.name == "Visible")
+        .unwrap();
+    assert_eq!(checkbox.info.checked, Some(true));
+    assert!(update.nodes.len() < first.nodes.len());
+}
+
+#[test]
+fn actions_focus_and_click_widgets() {
+    let mut app = app();
+    app.ui.accessibility_update(&app.ctx).unwrap();
+    let tree = app.ui.accessibility_tree(&app.ctx);
+    let name = tree.find("Name").unwrap().id;
+    let visible = tree.find("Visible").unwrap().id;
+
+    assert!(app
+        .ui
+        .accessibility_action(name, TheAccessAction::Focus, &mut app.ctx));
+    assert_eq!(app.ctx.ui.focus.as_ref().unwrap().name, "Name");
+
+    assert!(app
+        .ui
+        .accessibility_action(visible, TheAccessAction::Click, &mut app.ctx));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    let update = app.ui.accessibility_update(&app.ctx).unwrap();
+    let checkbox = update
+        .nodes
+        .iter()
+        .find(|node| node.source.name == "Visible")
+        .unwrap();
+    assert_eq!(checkbox.info.checked, Some(true));
+
+    assert!(!app
+        .ui
+        .accessibility_action(42, TheAccessAction::Click, &mut app.ctx));
+
+    // A newly connected assistive technology gets the whole tree.
+    app.ui.reset_accessibility();
+    let update = app.ui.accessibility_update(&app.ctx).unwrap();
+    assert!(update.root.is_some());
+    assert_eq!(update.nodes.len(), 7);
+}
+
+#[test]
+fn dialogs_are_modal_nodes() {
+    let mut app = app();
+    app.ui.push_dialog(
+        TheDialog::message(TheId::named("Info"), "About", "Version 1"),
+        &mut app.ctx,
+    );
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    let dump = app.accessibility_dump();
+    assert!(dump.contains("  Dialog \"About\" [modal]\n"), "{dump}");
+    assert!(dump.contains("      Label \"Version 1\"\n"), "{dump}");
+    assert!(dump.contains("      Button \"Accept\"\n"), "{dump}");
+}
@@ -0,0 +1,229 @@
+//! Tests for the bytecode VM and the text frontend of TheCode.
+
+use theframework::prelude::*;
+
+const SOURCE: &str = "a = 2 + 3 * 4
+b = a - 4
+
+a > 10:
+    c = a * 2
+== 14:
+    f = \"hit\" + a
+b < 0:
+    g = 1
+b == 10:
+    b == 10:
+        d = 1
+        return d
+        x = 1
+    e = 5
+h = 10 % 4
+return h
+i = 1
+";
+
+fn compile(source: &str) -> TheCodeModule {
+    let mut grid = TheCodeSource::new().compile(source).unwrap();
+    TheCompiler::new().compile(&mut grid).unwrap()
+}
+
+fn run(module: &mut TheCodeModule, vm: bool) -> (TheCodeFunction, TheDebugModule) {
+    let mut sandbox = TheCodeSandbox::new();
+    sandbox.debug_mode = true;
+
+    // Keep the locals of the run by executing inside a frame we own.
+    sandbox.call_stack.push(TheCodeFunction::default());
+    if vm {
+        module.execute_vm(&mut sandbox);
+    } else {
+        module.execute(&mut sandbox);
+    }
+
+    let debug = sandbox.get_module_debug_module(module.id);
+    (sandbox.call_stack.pop().unwrap(), debug)
+}
+
+#[test]
+fn vm_matches_node_execution() {
+    let mut module = compile(SOURCE);
+    assert!(!module.chunk.is_empty());
+
+    let (_, tree) = run(&mut module, false);
+    let (_, vm) = run(&mut module, true);
+
+    let mut tree_values: Vec<_> = tree.values.iter().collect();
+    let mut vm_values: Vec<_> = vm.values.iter().collect();
+    tree_values.sort_by_key(|(loc, _)| (loc.1, loc.0));
+    vm_values.sort_by_key(|(loc, _)| (loc.1, loc.0));
+
+    assert_eq!(tree_values, vm_values);
+    assert_eq!(tree.executed, vm.executed);
+
+    // Returning from a block only leaves the block, returning at the top level ends the module.
+    let value = |loc: (u16, u16)| vm.values.get(&loc).map(|v| v.1.clone());
+    assert_eq!(value((0, 0)), Some(TheValue::Int(14)));
+    assert_eq!(value((0, 2)), Some(TheValue::Int(10)));
+    assert_eq!(value((2, 8)), Some(TheValue::Int(28)));
+    assert_eq!(value((2, 12)), Some(TheValue::Text("hit 14".to_string())));
+    assert_eq!(value((2, 16)), None);
+    assert_eq!(value((4, 22)), Some(TheValue::Int(1)));
+    assert_eq!(value((4, 24)), Some(TheValue::Int(1)));
+    assert_eq!(value((4, 26)), None);
+    assert_eq!(value((2, 28)), Some(TheValue::Int(5)));
+    assert_eq!(value((0, 30)), Some(TheValue::Int(2)));
+    assert_eq!(value((0, 32)), Some(TheValue::Int(2)));
+    assert_eq!(value((0, 34)), None);
+}
+
+#[test]
+fn source_round_trip() {
+    let grid = TheCodeSource::new().compile(SOURCE).unwrap();
+    let text = TheCodeSource::to_source(&grid).unwrap();
+    let again = TheCodeSource::new().compile(&text).unwrap();
+
+    assert_eq!(grid.code, again.code);
+    assert!(text.contains("    b == 10:\n        d = 1\n"));
+}
+
+#[test]
+fn source_errors() {
+    let source = TheCodeSource::new();
+
+    let err = source.compile("a = 1\n  b = 2").unwrap_err();
+    assert_eq!(err.location, (0, 1));
+
+    let err = source.compile("a + 1").unwrap_err();
+    assert_eq!(err.location, (2, 0));
+
+    let err = source.compile("a > 1").unwrap_err();
+    assert_eq!(err.location, (5, 0));
+
+    let err = source.compile("move(1)").unwrap_err();
+    assert_eq!(err.location, (0, 0));
+}
+
+const CALLER: &str = "a = 1
+call(\"Lib\", \"Callee\")
+c = a + 1
+";
+
+const CALLEE: &str = "r = 5
+s = r * 2
+return s
+";
+
+fn debug_setup() -> (TheCodeSandbox, TheCodeModule, TheCodeModule) {
+    let mut callee = compile(CALLEE);
+    callee.name = "Callee".to_string();
+
+    let mut package = TheCodePackage::new();
+    package.name = "Lib".to_string();
+    package.insert_module(callee.name.clone(), callee.clone());
+
+    let mut source = TheCodeSource::new();
+    source.insert_module(package.name.clone(), package.id, &callee);
+
+    let mut grid = source.compile(CALLER).unwrap();
+    let mut compiler = TheCompiler::new();
+    compiler.update_package(package.clone());
+    let caller = compiler.compile(&mut grid).unwrap();
+
+    let mut sandbox = TheCodeSandbox::new();
+    sandbox.debug_mode = true;
+    sandbox.insert_package(package);
+
+    (sandbox, caller, callee)
+}
+
+fn paused(state: TheDebugState) -> TheDebugPause {
+    match state {
+        TheDebugState::Paused(pause) => pause,
+        state => panic!("Expected a pause, got {:?}", state),
+    }
+}
+
+#[test]
+fn debugger_breakpoints_and_steps() {
+    let (mut sandbox, caller, callee) = debug_setup();
+    let mut debugger = TheCodeDebugger::new();
+
+    debugger.set_breakpoint(callee.codegrid_id, (0, 2));
+    debugger.start(caller.clone(), &mut sandbox);
+
+    // The breakpoint in the callee pauses before the assignment, with r already set.
+    let pause = paused(debugger.resume(&mut sandbox));
+    assert_eq!(pause.reason, TheDebugPauseReason::Breakpoint);
+    assert_eq!(pause.codegrid_id, callee.codegrid_id);
+    assert_eq!(pause.depth, 2);
+    let frames = &pause.snapshot.frames;
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].locals, vec![("a".to_string(), TheValue::Int(1))]);
+    assert_eq!(frames[1].module_name, "Callee");
+    assert_eq!(frames[1].locals, vec![("r".to_string(), TheValue::Int(5))]);
+
+    // Step out pauses in the calling module after the call.
+    let pause = paused(debugger.step(TheDebugStep::StepOut, &mut sandbox));
+    assert_eq!(pause.reason, TheDebugPauseReason::Step);
+    assert_eq!((pause.depth, pause.location.1), (1, 4));
+
+    let state = debugger.resume(&mut sandbox);
+    assert!(matches!(state, TheDebugState::Finished(_)));
+    assert!(!debugger.is_active());
+    assert!(sandbox.call_stack.is_empty());
+    assert!(sandbox.module_stack.is_empty());
+    assert_eq!(
+        sandbox
+            .get_module_debug_module(caller.id)
+            .values
+            .get(&(0, 2))
+            .map(|v| v.1.clone()),
+        Some(TheValue::Int(10))
+    );
+
+    // Step over skips the call, step into enters it.
+    debugger.clear_breakpoints();
+    debugger.start(caller.clone(), &mut sandbox);
+    let pause = paused(debugger.step(TheDebugStep::StepOver, &mut sandbox));
+    assert_eq!(pause.location.1, 0);
+    let pause = paused(debugger.step(TheDebugStep::StepOver, &mut sandbox));
+    assert_eq!(pause.location.1, 2);
+    let pause = paused(debugger.step(TheDebugStep::StepOver, &mut sandbox));
+    assert_eq!((pause.depth, pause.location.1), (1, 4));
+
+    debugger.start(caller.clone(), &mut sandbox);
+    debugger.step(TheDebugStep::StepInto, &mut sandbox);
+    debugger.step(TheDebugStep::StepInto, &mut sandbox);
+    let pause = paused(debugger.step(TheDebugStep::StepInto, &mut sandbox));
+    assert_eq!((pause.depth, pause.location.1), (2, 0));
+    assert_eq!(pause.module_id, callee.id);
+
+    debugger.stop(&mut sandbox);
+    assert!(sandbox.call_stack.is_empty());
+}
+
+#[test]
+fn debugger_budget_and_pause() {
+    let (mut sandbox, caller, _) = debug_setup();
+    let mut debugger = TheCodeDebugger::new();
+    debugger.set_instruction_budget(Some(2));
+
+    debugger.start(caller, &mut sandbox);
+    assert!(matches!(
+        debugger.resume(&mut sandbox),
+        TheDebugState::Running
+    ));
+
+    debugger.pause();
+    let pause = paused(debugger.resume(&mut sandbox));
+    assert_eq!(pause.reason, TheDebugPauseReason::Pause);
+
+    let mut tree = TheTreeLayout::new(TheId::empty());
+    pause.snapshot.apply_to_tree_layout(&mut tree);
+    assert_eq!(tree.get_root().childs.len(), 2);
+
+    debugger.set_instruction_budget(None);
+    assert!(matches!(
+        debugger.resume(&mut sandbox),
+        TheDebugState::Finished(_)
+    ));
+}
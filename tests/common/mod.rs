@@ -0,0 +1,49 @@
+//! Fixtures shared by the headless tests, not every test uses all of them.
+#![allow(dead_code)]
+
+use std::sync::mpsc::Receiver;
+use theframework::prelude::*;
+
+/// An app showing a single text widget.
+pub struct Empty;
+
+impl TheTrait for Empty {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, _ctx: &mut TheContext) {
+        let mut canvas = TheCanvas::new();
+        canvas.set_widget(TheText::new(TheId::named("Main")));
+        ui.canvas = canvas;
+    }
+}
+
+/// Runs a single frame.
+pub fn frame(app: &mut TheHeadlessApp) {
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+}
+
+/// Presses the key and runs a frame.
+pub fn key(app: &mut TheHeadlessApp, char: Option<char>, key: Option<TheKeyCode>) {
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(char, key),
+        TheHeadlessEvent::Frames(1),
+    ]);
+}
+
+/// Creates a headless app of the given size, runs its first frame and returns it with a
+/// listener for the events sent from then on.
+pub fn app_with_events(
+    width: usize,
+    height: usize,
+    app: Box<dyn TheTrait>,
+) -> (TheHeadlessApp, Receiver<TheEvent>) {
+    let mut app = TheHeadlessApp::new(width, height, app);
+    frame(&mut app);
+    let events = app.ui.add_state_listener("Test".to_string());
+    (app, events)
+}
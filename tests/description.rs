@@ -0,0 +1,116 @@
+//! Tests for building canvases from declarative descriptions.
+
+use theframework::prelude::*;
+
+const SIDEBAR: &str = r#"{
+  "top": {
+    "limiter": { "max_height": 30 },
+    "widget": { "type": "Switchbar", "text": "Material" }
+  },
+  "layout": {
+    "type": "TextLayout",
+    "name": "Material Layout",
+    "margin": [10, 10, 10, 10],
+    "widgets": [
+      { "type": "TextLineEdit", "name": "Name", "label": "Name", "text": "Gold", "limiter": { "max_width": 120 } },
+      {
+        "type": "Slider",
+        "name": "Metallic",
+        "label": "Metallic",
+        "value": { "Float": 0.75 },
+        "range": { "RangeF32": { "start": 0.0, "end": 2.0 } }
+      },
+      { "type": "DropdownMenu", "name": "Mode", "label": "Mode", "options": ["Fast", "Exact"], "value": { "Int": 1 } },
+      { "type": "CheckButton", "name": "Visible", "label": "Visible", "value": { "Bool": true }, "disabled": true }
+    ]
+  }
+}"#;
+
+struct Described;
+
+impl TheTrait for Described {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
+        let description = TheCanvasDescription::parse(SIDEBAR).unwrap();
+        ui.canvas = description.build(ctx).unwrap();
+    }
+}
+
+#[test]
+fn builds_the_described_hierarchy() {
+    let mut app = TheHeadlessApp::new(400, 300, Box::new(Described));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    assert_eq!(
+        app.accessibility_dump(),
+        "Window\n\
+        \x20 Label \"Material\"\n\
+        \x20 GenericContainer\n\
+        \x20   TextInput \"Name\" value=\"Gold\"\n\
+        \x20   Slider \"Metallic\" value=\"0.75\"\n\
+        \x20   ComboBox \"Mode\" value=\"Exact\" [collapsed]\n\
+        \x20   CheckBox \"Visible\" [disabled, checked]\n"
+    );
+
+    let tree = app.ui.accessibility_tree(&app.ctx);
+    let metallic = tree.find("Metallic").unwrap();
+    assert_eq!(metallic.info.max_numeric_value, Some(2.0));
+    assert!(app.ctx.ui.is_disabled("Visible"));
+
+    assert!(app.ui.get_layout("Material Layout").is_some());
+    assert_eq!(app.ui.get_widget("Name").unwrap().dim().width, 120);
+}
+
+#[test]
+fn json_round_trips() {
+    let description = TheCanvasDescription::parse(SIDEBAR).unwrap();
+    let json = description.to_json();
+    assert_eq!(TheCanvasDescription::parse(&json).unwrap(), description);
+    assert!(!json.contains("\"left\""));
+}
+
+#[test]
+fn invalid_descriptions_are_errors() {
+    let err = TheCanvasDescription::parse(r#"{ "widget": { "type": "Slider", "colour": 1 } }"#)
+        .unwrap_err();
+    assert!(err.contains("unknown field `colour`"), "{err}");
+
+    let err = TheCanvasDescription::parse(r#"{ "widget": { "type": "Knob" } }"#).unwrap_err();
+    assert!(err.contains("unknown variant `Knob`"), "{err}");
+
+    let mut ctx = TheContext::new(100, 100, 1.0);
+    let shared = TheCanvasDescription::parse(
+        r#"{ "layout": { "type": "SharedHLayout", "name": "Split", "canvases": [{}] } }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        shared.build(&mut ctx).err().as_deref(),
+        Some("SharedHLayout \"Split\" needs two canvases")
+    );
+
+    let list = TheCanvasDescription::parse(
+        r#"{ "layout": { "type": "ListLayout", "widgets": [{ "type": "Text" }] } }"#,
+    )
+    .unwrap();
+    assert!(list.build(&mut ctx).is_err());
+}
+
+#[test]
+fn unnamed_widgets_are_disabled_alone() {
+    let mut ctx = TheContext::new(100, 100, 1.0);
+    let description = TheCanvasDescription::parse(
+        r#"{ "widget": { "type": "TextLineEdit", "disabled": true } }"#,
+    )
+    .unwrap();
+    let mut canvas = description.build(&mut ctx).unwrap();
+    canvas.layout(100, 100, &mut ctx);
+    assert!(!ctx.ui.is_disabled(""));
+    let widget = canvas.get_widget_at_coord(Vec2::new(10, 10)).unwrap();
+    assert!(widget.disabled());
+}
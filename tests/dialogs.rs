@@ -0,0 +1,110 @@
+//! Tests for the modal dialog stack of TheUI, driven by the headless runner.
+
+mod common;
+
+use common::{frame, key, Empty};
+use theframework::prelude::*;
+
+fn closed_dialogs(
+    events: &std::sync::mpsc::Receiver<TheEvent>,
+) -> Vec<(TheDialogButtonRole, String, TheValue)> {
+    events
+        .try_iter()
+        .filter_map(|event| match event {
+            TheEvent::DialogValueOnClose(role, title, _, value) => Some((role, title, value)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn enter_accepts_the_text_input_dialog() {
+    let mut app = TheHeadlessApp::new(500, 300, Box::new(Empty));
+    let events = app.ui.add_state_listener("Test".to_string());
+    frame(&mut app);
+
+    let dialog = TheDialog::text_input(TheId::named("Rename"), "Rename", "Name", "Old");
+    app.ui.push_dialog(dialog, &mut app.ctx);
+    frame(&mut app);
+    assert!(app.ui.is_dialog_open());
+    assert_eq!(
+        app.ctx.ui.focus.as_ref().map(|id| id.name.as_str()),
+        Some("Dialog Value")
+    );
+
+    key(&mut app, None, Some(TheKeyCode::End));
+    key(&mut app, Some('r'), None);
+    key(&mut app, None, Some(TheKeyCode::Return));
+
+    assert!(!app.ui.is_dialog_open());
+    assert_eq!(
+        closed_dialogs(&events),
+        vec![(
+            TheDialogButtonRole::Accept,
+            "Rename".to_string(),
+            TheValue::Text("Oldr".to_string())
+        )]
+    );
+}
+
+#[test]
+fn escape_closes_only_the_topmost_dialog() {
+    let mut app = TheHeadlessApp::new(500, 300, Box::new(Empty));
+    let events = app.ui.add_state_listener("Test".to_string());
+    frame(&mut app);
+
+    let mut question = TheDialog::confirm(TheId::named("Delete"), "Delete", "Sure?", "Delete");
+    question.add_button(TheDialogButton::new(
+        TheDialogButtonRole::Custom(1),
+        "Later",
+    ));
+    app.ui.push_dialog(question, &mut app.ctx);
+    app.ui.push_dialog(
+        TheDialog::message(TheId::named("Info"), "Info", "Done"),
+        &mut app.ctx,
+    );
+    frame(&mut app);
+
+    // The only button of the message box is its escape button.
+    key(&mut app, None, Some(TheKeyCode::Escape));
+    assert_eq!(app.ui.dialogs.len(), 1);
+    assert_eq!(closed_dialogs(&events)[0].0, TheDialogButtonRole::Accept);
+
+    // Custom buttons are labeled with their text and close the dialog with their role.
+    let dialog = app.ui.dialogs.last().unwrap();
+    assert_eq!(dialog.buttons[0].text, "Delete");
+    assert_eq!(dialog.buttons[2].text, "Later");
+    assert_eq!(dialog.escape_button(), Some(TheDialogButtonRole::Reject));
+    app.ui
+        .close_dialog(TheDialogButtonRole::Custom(1), &mut app.ctx);
+    frame(&mut app);
+    assert!(!app.ui.is_dialog_open());
+    assert_eq!(closed_dialogs(&events)[0].0, TheDialogButtonRole::Custom(1));
+
+    assert_eq!(TheDialogButtonRole::Rename.to_string(), "Rename");
+}
+
+#[test]
+fn draggable_dialogs_move_with_their_title_bar() {
+    let mut app = TheHeadlessApp::new(500, 300, Box::new(Empty));
+    frame(&mut app);
+
+    let mut dialog = TheDialog::message(TheId::named("Info"), "Info", "Done");
+    dialog.set_draggable(true);
+    app.ui.push_dialog(dialog, &mut app.ctx);
+    frame(&mut app);
+
+    let before = app.ui.dialogs[0].canvas.dim;
+    let bar = app.ui.dialogs[0].title_bar();
+    let (x, y) = ((bar.x + 20) as f32, (bar.y + 10) as f32);
+    app.run_script(&[
+        TheHeadlessEvent::TouchDown(x, y),
+        TheHeadlessEvent::TouchDragged(x - 50.0, y + 30.0),
+        TheHeadlessEvent::TouchUp(x - 50.0, y + 30.0),
+        TheHeadlessEvent::Frames(1),
+    ]);
+
+    let after = app.ui.dialogs[0].canvas.dim;
+    assert_eq!((after.x, after.y), (before.x - 50, before.y + 30));
+    assert!(app.ui.is_dialog_open());
+}
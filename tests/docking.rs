@@ -0,0 +1,290 @@
+//! Tests for docking, tabbing and resizing the panels of TheDockLayout.
+
+mod common;
+
+use common::app_with_events;
+use std::sync::mpsc::Receiver;
+use theframework::prelude::*;
+
+const PANELS: [&str; 3] = ["Scene", "Assets", "Console"];
+
+/// Scene and Assets tabbed on the left, Console on the right.
+fn default_arrangement() -> TheDockNode {
+    TheDockNode::split(
+        TheDockAxis::Horizontal,
+        0.5,
+        TheDockNode::tabs(&["Scene", "Assets"]),
+        TheDockNode::tabs(&["Console"]),
+    )
+}
+
+struct Editor {
+    arrangement: Option<TheDockNode>,
+}
+
+impl TheTrait for Editor {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self { arrangement: None }
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, _ctx: &mut TheContext) {
+        let mut canvas = TheCanvas::new();
+
+        let mut dock = TheDockLayout::new(TheId::named("Dock"));
+        for name in PANELS {
+            let mut panel = TheCanvas::new();
+            panel.set_widget(TheTextLineEdit::new(TheId::named(&format!("{name} Edit"))));
+            dock.add_panel(name, panel);
+        }
+        dock.set_arrangement(self.arrangement.take().unwrap_or_else(default_arrangement));
+        canvas.set_layout(dock);
+
+        ui.canvas = canvas;
+    }
+}
+
+fn app_with(arrangement: Option<TheDockNode>) -> (TheHeadlessApp, Receiver<TheEvent>) {
+    app_with_events(600, 400, Box::new(Editor { arrangement }))
+}
+
+fn app() -> (TheHeadlessApp, Receiver<TheEvent>) {
+    app_with(None)
+}
+
+fn dock(app: &mut TheHeadlessApp) -> &mut dyn TheDockLayoutTrait {
+    app.ui.get_dock_layout("Dock").unwrap()
+}
+
+fn drag(from: (f32, f32), to: (f32, f32)) -> [TheHeadlessEvent; 5] {
+    [
+        TheHeadlessEvent::TouchDown(from.0, from.1),
+        TheHeadlessEvent::TouchDragged(from.0 + 6.0, from.1),
+        TheHeadlessEvent::TouchDragged(to.0, to.1),
+        TheHeadlessEvent::TouchUp(to.0, to.1),
+        TheHeadlessEvent::Frames(1),
+    ]
+}
+
+fn changes(events: &Receiver<TheEvent>) -> usize {
+    events
+        .try_iter()
+        .filter(|event| matches!(event, TheEvent::DockArrangementChanged(id) if id.name == "Dock"))
+        .count()
+}
+
+#[test]
+fn dragging_a_tab_docks_the_panel_to_a_side() {
+    let (mut app, events) = app();
+
+    // Both areas are 298 pixels wide, tabs are 120 pixels wide. Assets is dropped close to the
+    // bottom border of the Console area.
+    app.run_script(&drag((180.0, 11.0), (450.0, 380.0)));
+    assert_eq!(
+        dock(&mut app).arrangement(),
+        Some(TheDockNode::split(
+            TheDockAxis::Horizontal,
+            0.5,
+            TheDockNode::tabs(&["Scene"]),
+            TheDockNode::split(
+                TheDockAxis::Vertical,
+                0.5,
+                TheDockNode::tabs(&["Console"]),
+                TheDockNode::tabs(&["Assets"]),
+            ),
+        ))
+    );
+    assert_eq!(
+        dock(&mut app).visible_panels(),
+        ["Scene", "Console", "Assets"]
+    );
+    assert_eq!(changes(&events), 2);
+
+    // The panels are placed in their areas.
+    let edit = app.ui.get_widget("Assets Edit").unwrap();
+    assert_eq!((edit.dim().x, edit.dim().y), (302, 224));
+
+    // Scene goes to the left of Console, its old area collapses.
+    app.run_script(&drag((60.0, 11.0), (310.0, 100.0)));
+    assert_eq!(
+        dock(&mut app).arrangement(),
+        Some(TheDockNode::split(
+            TheDockAxis::Vertical,
+            0.5,
+            TheDockNode::split(
+                TheDockAxis::Horizontal,
+                0.5,
+                TheDockNode::tabs(&["Scene"]),
+                TheDockNode::tabs(&["Console"]),
+            ),
+            TheDockNode::tabs(&["Assets"]),
+        ))
+    );
+}
+
+#[test]
+fn panels_are_tabbed_together() {
+    let (mut app, events) = app();
+
+    // Clicking a tab shows its panel.
+    app.run_script(&[
+        TheHeadlessEvent::TouchDown(180.0, 11.0),
+        TheHeadlessEvent::TouchUp(180.0, 11.0),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert_eq!(dock(&mut app).visible_panels(), ["Assets", "Console"]);
+    assert_eq!(changes(&events), 1);
+
+    // A panel alone in its area can not dock to its own area.
+    app.run_script(&drag((362.0, 11.0), (450.0, 380.0)));
+    assert_eq!(
+        dock(&mut app).arrangement(),
+        Some(TheDockNode::split(
+            TheDockAxis::Horizontal,
+            0.5,
+            TheDockNode::Tabs {
+                panels: vec!["Scene".to_string(), "Assets".to_string()],
+                index: 1,
+            },
+            TheDockNode::tabs(&["Console"]),
+        ))
+    );
+    assert_eq!(changes(&events), 0);
+
+    // Dropped into the middle of an area, the panel becomes its last tab.
+    app.run_script(&drag((60.0, 11.0), (450.0, 200.0)));
+    assert_eq!(
+        dock(&mut app).arrangement(),
+        Some(TheDockNode::split(
+            TheDockAxis::Horizontal,
+            0.5,
+            TheDockNode::tabs(&["Assets"]),
+            TheDockNode::Tabs {
+                panels: vec!["Console".to_string(), "Scene".to_string()],
+                index: 1,
+            },
+        ))
+    );
+
+    // Dropped into a tab strip, the tab is inserted at the pointer, behind the Console tab.
+    app.run_script(&drag((60.0, 11.0), (430.0, 11.0)));
+    assert_eq!(
+        dock(&mut app).arrangement(),
+        Some(TheDockNode::Tabs {
+            panels: vec![
+                "Console".to_string(),
+                "Assets".to_string(),
+                "Scene".to_string()
+            ],
+            index: 1,
+        })
+    );
+}
+
+#[test]
+fn dragging_a_splitter_changes_the_ratio() {
+    let (mut app, events) = app();
+
+    app.run_script(&drag((300.0, 200.0), (150.0, 200.0)));
+    let Some(TheDockNode::Split { ratio, .. }) = dock(&mut app).arrangement() else {
+        panic!("the arrangement is split");
+    };
+    assert_eq!(ratio, 148.0 / 596.0);
+    assert_eq!(changes(&events), 1);
+
+    let edit = app.ui.get_widget("Console Edit").unwrap();
+    assert_eq!(edit.dim().x, 152);
+
+    // Areas do not shrink below 40 pixels.
+    app.run_script(&drag((150.0, 200.0), (590.0, 200.0)));
+    let Some(TheDockNode::Split { ratio, .. }) = dock(&mut app).arrangement() else {
+        panic!("the arrangement is split");
+    };
+    assert_eq!(ratio, 556.0 / 596.0);
+}
+
+#[test]
+fn arrangements_restore_from_json() {
+    let (mut app, _events) = app();
+    assert!(dock(&mut app).dock_panel("Console", "Scene", TheDockSide::Top));
+    assert!(!dock(&mut app).dock_panel("Console", "Console", TheDockSide::Left));
+    assert!(!dock(&mut app).dock_panel("Missing", "Scene", TheDockSide::Left));
+    assert!(dock(&mut app).select_panel("Assets"));
+
+    let arrangement = dock(&mut app).arrangement().unwrap();
+    let json = arrangement.to_json();
+
+    let (mut restored, _events) = app_with(TheDockNode::from_json(&json));
+    assert_eq!(dock(&mut restored).arrangement(), Some(arrangement));
+    assert_eq!(dock(&mut restored).visible_panels(), ["Console", "Assets"]);
+
+    // Panels which were never added are left out, panels missing from the arrangement are
+    // tabbed into the first area.
+    let outdated = TheDockNode::split(
+        TheDockAxis::Vertical,
+        0.3,
+        TheDockNode::tabs(&["Assets", "Removed"]),
+        TheDockNode::tabs(&["Scene", "Assets"]),
+    );
+    let (mut restored, _events) = app_with(Some(outdated));
+    assert_eq!(
+        dock(&mut restored).arrangement(),
+        Some(TheDockNode::split(
+            TheDockAxis::Vertical,
+            0.3,
+            TheDockNode::Tabs {
+                panels: vec!["Assets".to_string(), "Console".to_string()],
+                index: 1,
+            },
+            TheDockNode::tabs(&["Scene"]),
+        ))
+    );
+
+    assert_eq!(TheDockNode::from_json("not json"), None);
+}
+
+#[test]
+fn arrangements_place_panels_added_later() {
+    let mut dock = TheDockLayout::new(TheId::named("Dock"));
+    dock.add_panel("Console", TheCanvas::new());
+    dock.set_arrangement(default_arrangement());
+    assert_eq!(dock.arrangement(), Some(TheDockNode::tabs(&["Console"])));
+
+    // The Inspector is not part of the arrangement and is tabbed into the first area.
+    dock.add_panel("Assets", TheCanvas::new());
+    dock.add_panel("Inspector", TheCanvas::new());
+    dock.add_panel("Scene", TheCanvas::new());
+    assert_eq!(
+        dock.arrangement(),
+        Some(TheDockNode::split(
+            TheDockAxis::Horizontal,
+            0.5,
+            TheDockNode::Tabs {
+                panels: vec!["Scene".into(), "Assets".into(), "Inspector".into()],
+                index: 2,
+            },
+            TheDockNode::tabs(&["Console"]),
+        ))
+    );
+
+    // Once all panels were added, the arrangement is not restored again.
+    assert!(dock.dock_panel("Assets", "Console", TheDockSide::Center));
+    dock.add_panel("Scene", TheCanvas::new());
+    assert_eq!(
+        dock.arrangement(),
+        Some(TheDockNode::split(
+            TheDockAxis::Horizontal,
+            0.5,
+            TheDockNode::Tabs {
+                panels: vec!["Scene".into(), "Inspector".into()],
+                index: 1,
+            },
+            TheDockNode::Tabs {
+                panels: vec!["Console".into(), "Assets".into()],
+                index: 1,
+            },
+        ))
+    );
+}
@@ -0,0 +1,133 @@
+//! Tests for rebuilding canvases when their description files change.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use theframework::prelude::*;
+
+const FORM: &str = r#"{
+  "layout": {
+    "type": "VLayout",
+    "name": "Form",
+    "widgets": [
+      { "type": "TextLineEdit", "name": "Name", "text": "Gold" },
+      { "type": "Slider", "name": "Metallic", "value": { "Float": 0.5 } }
+    ]
+  }
+}"#;
+
+const FORM_WITH_HINT: &str = r#"{
+  "layout": {
+    "type": "VLayout",
+    "name": "Form",
+    "widgets": [
+      { "type": "Text", "name": "Hint", "text": "The name of the material." },
+      { "type": "TextLineEdit", "name": "Name", "text": "Gold" },
+      { "type": "TextLineEdit", "name": "Metallic", "text": "0.5" }
+    ]
+  }
+}"#;
+
+struct Watched {
+    path: PathBuf,
+}
+
+impl TheTrait for Watched {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        unreachable!()
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
+        let description = TheCanvasDescription::load(&self.path).unwrap();
+        ui.canvas = description.build(ctx).unwrap();
+        ui.watch_canvas_file(&self.path, ui.canvas.uuid);
+        ui.hot_reload.interval = Duration::ZERO;
+    }
+}
+
+/// Writes the file with a modification time distinct from the previous write.
+fn write(path: &Path, json: &str, generation: u64) {
+    std::fs::write(path, json).unwrap();
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 + generation);
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(time)
+        .unwrap();
+}
+
+fn app(name: &str) -> (TheHeadlessApp, PathBuf) {
+    let path = std::env::temp_dir().join(format!("theframework-{}-{}.json", name, Uuid::new_v4()));
+    write(&path, FORM, 0);
+    let mut app = TheHeadlessApp::new(400, 300, Box::new(Watched { path: path.clone() }));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    (app, path)
+}
+
+#[test]
+fn changed_files_rebuild_the_canvas_and_keep_values() {
+    let (mut app, path) = app("reload");
+    let uuid = app.ui.canvas.uuid;
+
+    app.ui
+        .get_widget("Name")
+        .unwrap()
+        .set_value(TheValue::Text("Silver".to_string()));
+
+    write(&path, FORM_WITH_HINT, 1);
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    assert!(app.ui.get_widget("Hint").is_some());
+    assert_eq!(app.ui.canvas.uuid, uuid);
+    assert_eq!(
+        app.ui.get_widget("Name").unwrap().value(),
+        TheValue::Text("Silver".to_string())
+    );
+    // The slider became a text edit, its value is kept where the new widget accepts it.
+    assert_eq!(app.ui.get_text_line_edit("Metallic").unwrap().text(), "0.5");
+    assert!(app.ui.get_widget("Hint").unwrap().dim().is_valid());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn parse_errors_show_a_toast_until_fixed() {
+    let (mut app, path) = app("errors");
+
+    write(&path, "{ \"layout\": ", 1);
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    // The canvas stays as it was.
+    assert!(app.ui.get_widget("Name").is_some());
+    let toasts = app.ui.toasts.toasts();
+    assert_eq!(toasts.len(), 1);
+    assert_eq!(toasts[0].level, TheToastLevel::Error);
+    assert!(
+        toasts[0].text.contains("EOF while parsing"),
+        "{}",
+        toasts[0].text
+    );
+    assert_eq!(toasts[0].timeout, None);
+
+    // A second error replaces the toast.
+    write(&path, "{ \"layot\": {} }", 2);
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    let toasts = app.ui.toasts.toasts();
+    assert_eq!(toasts.len(), 1);
+    assert!(
+        toasts[0].text.contains("unknown field `layot`"),
+        "{}",
+        toasts[0].text
+    );
+
+    write(&path, FORM_WITH_HINT, 3);
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    assert!(app.ui.toasts.is_empty());
+    assert!(app.ui.get_widget("Hint").is_some());
+
+    std::fs::remove_file(path).unwrap();
+}
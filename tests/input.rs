@@ -0,0 +1,376 @@
+//! Tests for keyboard input handling of TheUI, driven by the headless runner.
+
+use theframework::prelude::*;
+
+/// A form with three text fields next to a read-only area.
+struct Form;
+
+impl TheTrait for Form {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self
+    }
+
+    fn reports_damage(&self) -> bool {
+        true
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, _ctx: &mut TheContext) {
+        let mut canvas = TheCanvas::new();
+
+        let mut text_layout = TheTextLayout::new(TheId::named("Form"));
+        for name in ["Name", "Width", "Height"] {
+            let edit = TheTextLineEdit::new(TheId::named(name));
+            text_layout.add_pair(name.to_string(), Box::new(edit));
+        }
+        canvas.set_layout(text_layout);
+
+        let mut notes_canvas = TheCanvas::new();
+        let mut notes = TheTextAreaEdit::new(TheId::named("Notes"));
+        notes.limiter_mut().set_max_width(150);
+        notes_canvas.set_widget(notes);
+        canvas.set_right(notes_canvas);
+
+        ui.canvas = canvas;
+    }
+}
+
+fn focus_name(app: &TheHeadlessApp) -> Option<String> {
+    app.ctx.ui.focus.as_ref().map(|id| id.name.clone())
+}
+
+fn tab(app: &mut TheHeadlessApp) {
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(None, Some(TheKeyCode::Tab)),
+        TheHeadlessEvent::Frames(1),
+    ]);
+}
+
+fn modifiers(app: &mut TheHeadlessApp, shift: bool, ctrl: bool) {
+    app.run_script(&[TheHeadlessEvent::ModifierChanged(shift, ctrl, false, false)]);
+}
+
+#[test]
+fn tab_cycles_focus_in_layout_order() {
+    let mut app = TheHeadlessApp::new(500, 200, Box::new(Form));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    tab(&mut app);
+    assert_eq!(focus_name(&app).as_deref(), Some("Name"));
+    assert!(app.ctx.ui.keyboard_focus.is_some());
+    tab(&mut app);
+    assert_eq!(focus_name(&app).as_deref(), Some("Width"));
+
+    modifiers(&mut app, true, false);
+    tab(&mut app);
+    assert_eq!(focus_name(&app).as_deref(), Some("Name"));
+    tab(&mut app);
+    assert_eq!(focus_name(&app).as_deref(), Some("Notes"));
+
+    // The text area uses Tab for indentation, Ctrl+Tab leaves it.
+    modifiers(&mut app, false, false);
+    tab(&mut app);
+    assert_eq!(focus_name(&app).as_deref(), Some("Notes"));
+    modifiers(&mut app, false, true);
+    tab(&mut app);
+    assert_eq!(focus_name(&app).as_deref(), Some("Name"));
+}
+
+#[test]
+fn tab_indices_come_first() {
+    let mut app = TheHeadlessApp::new(500, 200, Box::new(Form));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    let height = app.ui.get_widget("Height").unwrap().id().clone();
+    let width = app.ui.get_widget("Width").unwrap().id().clone();
+    app.ctx.ui.set_tab_index(&height, Some(1));
+    app.ctx.ui.set_tab_index(&width, Some(-1));
+
+    let order: Vec<String> = app
+        .ui
+        .focus_order(&app.ctx)
+        .into_iter()
+        .map(|id| id.name)
+        .collect();
+    assert_eq!(order, vec!["Height", "Name", "Notes"]);
+}
+
+fn triggered(receiver: &std::sync::mpsc::Receiver<TheEvent>) -> Vec<String> {
+    receiver
+        .try_iter()
+        .filter_map(|event| match event {
+            TheEvent::ShortcutTriggered(id) => Some(id.name),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn shortcuts_named_keys_and_chords() {
+    let mut app = TheHeadlessApp::new(500, 200, Box::new(Form));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    let receiver = app.ui.add_state_listener("Test".into());
+
+    let run = TheShortcut::new(TheAccelerator::new_key_code(
+        TheAcceleratorKey::empty(),
+        TheKeyCode::F5,
+    ));
+    let comment = TheShortcut::chord(vec![
+        TheAccelerator::new(TheAcceleratorKey::CTRLCMD, 'k'),
+        TheAccelerator::new(TheAcceleratorKey::CTRLCMD, 'c'),
+    ]);
+    assert!(app.ui.shortcuts.register(TheId::named("Run"), run).is_ok());
+    assert!(app
+        .ui
+        .shortcuts
+        .register(TheId::named("Comment"), comment.clone())
+        .is_ok());
+    assert_eq!(
+        comment.description(),
+        format!(
+            "{}, {}",
+            comment.sequence[0].description(),
+            comment.sequence[1].description()
+        )
+    );
+
+    // F5 triggers even while a text field has the focus.
+    tab(&mut app);
+    assert_eq!(focus_name(&app).as_deref(), Some("Name"));
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(None, Some(TheKeyCode::F5)),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert_eq!(triggered(&receiver), vec!["Run".to_string()]);
+
+    modifiers(&mut app, false, true);
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(Some('k'), None),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert!(app.ui.shortcuts.is_pending());
+    assert!(triggered(&receiver).is_empty());
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(Some('c'), None),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert!(!app.ui.shortcuts.is_pending());
+    assert_eq!(triggered(&receiver), vec!["Comment".to_string()]);
+
+    // A key which does not continue the chord aborts it.
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(Some('k'), None),
+        TheHeadlessEvent::KeyDown(Some('x'), None),
+        TheHeadlessEvent::KeyDown(Some('c'), None),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert!(triggered(&receiver).is_empty());
+}
+
+#[test]
+fn shortcut_conflicts() {
+    let mut registry = TheShortcutRegistry::default();
+    let save = TheShortcut::new(TheAccelerator::new(TheAcceleratorKey::CTRLCMD, 's'));
+    let save_id = TheId::named("Save");
+    assert!(registry.register(save_id.clone(), save.clone()).is_ok());
+
+    // Same keys, and a chord which starts with the shortcut.
+    let other = TheShortcut::new(TheAccelerator::new(TheAcceleratorKey::CTRL, 'S'));
+    assert_eq!(
+        registry.register(TheId::named("Other"), other),
+        Err(vec![save_id.clone()])
+    );
+    let chord = TheShortcut::chord(vec![
+        TheAccelerator::new(TheAcceleratorKey::CTRLCMD, 's'),
+        TheAccelerator::new(TheAcceleratorKey::CTRLCMD, 'a'),
+    ]);
+    assert_eq!(registry.conflicts(&chord), vec![save_id.clone()]);
+
+    // Different modifiers do not conflict, re-registering the same id replaces it.
+    let save_as = TheShortcut::new(TheAccelerator::new(
+        TheAcceleratorKey::CTRLCMD | TheAcceleratorKey::SHIFT,
+        's',
+    ));
+    assert!(registry.register(TheId::named("Save As"), save_as).is_ok());
+    assert!(registry.register(save_id.clone(), save).is_ok());
+    assert_eq!(registry.shortcuts().len(), 2);
+
+    registry.unregister(&save_id);
+    assert!(registry.shortcut(&save_id).is_none());
+}
+
+#[test]
+fn accelerator_literals_and_modifiers() {
+    let copy = TheAccelerator {
+        accel: TheAcceleratorKey::CTRL,
+        key: 'c',
+        ..Default::default()
+    };
+    assert_eq!(copy, TheAccelerator::new(TheAcceleratorKey::CTRL, 'c'));
+    assert!(copy.matches(false, true, false, false, 'C'));
+
+    // Modifiers which are not part of the accelerator do not match.
+    assert!(!copy.matches(true, true, false, false, 'c'));
+    assert!(!copy.matches(false, true, true, false, 'c'));
+}
+
+#[test]
+fn keymap_notation_and_json() {
+    let save_as = TheAccelerator::from_keymap_str("CtrlCmd+Shift+S").unwrap();
+    assert_eq!(
+        save_as,
+        TheAccelerator::new(TheAcceleratorKey::CTRLCMD | TheAcceleratorKey::SHIFT, 's')
+    );
+    assert_eq!(save_as.to_keymap_string(), "CtrlCmd+Shift+S");
+    assert_eq!(
+        TheAccelerator::from_keymap_str("Alt+F5"),
+        Some(TheAccelerator::new_key_code(
+            TheAcceleratorKey::ALT,
+            TheKeyCode::F5
+        ))
+    );
+    assert_eq!(
+        TheAccelerator::from_keymap_str("Ctrl++"),
+        Some(TheAccelerator::new(TheAcceleratorKey::CTRL, '+'))
+    );
+    assert!(TheAccelerator::from_keymap_str("Hyper+S").is_none());
+    assert!(TheAccelerator::from_keymap_str("Ctrl+F13").is_none());
+
+    let mut keymap = TheKeymap::new();
+    keymap.bind(
+        "Comment",
+        TheShortcut::chord(vec![
+            TheAccelerator::from_keymap_str("CtrlCmd+K").unwrap(),
+            TheAccelerator::from_keymap_str("CtrlCmd+C").unwrap(),
+        ]),
+    );
+    keymap.unbind("Save");
+
+    let json = keymap.to_json();
+    assert!(json.contains("\"CtrlCmd+K\""));
+    assert_eq!(TheKeymap::from_json(&json), keymap);
+    assert!(keymap.contains("Save"));
+    assert!(keymap.shortcut("Save").is_none());
+
+    let path = std::env::temp_dir().join(format!("keymap-{}.json", Uuid::new_v4()));
+    keymap.save(&path).unwrap();
+    assert_eq!(TheKeymap::load(&path).unwrap(), keymap);
+    std::fs::write(&path, r#"{"bindings": {"Save": ["Ctrl+Nope"]}}"#).unwrap();
+    assert!(TheKeymap::load(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn keymap_rebinds_menu_accelerators() {
+    let mut app = TheHeadlessApp::new(500, 200, Box::new(Form));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    let receiver = app.ui.add_state_listener("Test".into());
+
+    let mut menu = TheContextMenu::new();
+    menu.add(TheContextMenuItem::new_with_accel(
+        "Save".into(),
+        TheId::named("Save"),
+        TheAccelerator::new(TheAcceleratorKey::CTRLCMD, 's'),
+    ));
+    menu.register_accel(&mut app.ctx);
+
+    let selected = |receiver: &std::sync::mpsc::Receiver<TheEvent>| {
+        receiver
+            .try_iter()
+            .filter_map(|event| match event {
+                TheEvent::ContextMenuSelected(_, id) => Some(id.name),
+                _ => None,
+            })
+            .collect::<Vec<String>>()
+    };
+
+    modifiers(&mut app, false, true);
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(Some('s'), None),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert_eq!(selected(&receiver), vec!["Save".to_string()]);
+
+    // Rebind Save to F2, the old accelerator no longer triggers.
+    let f2 = TheShortcut::new(TheAccelerator::from_keymap_str("F2").unwrap());
+    app.ctx.ui.keymap.bind("Save", f2.clone());
+    assert_eq!(
+        app.ctx.ui.keymap.description("Save"),
+        Some(f2.description())
+    );
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(Some('s'), None),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert!(selected(&receiver).is_empty());
+    modifiers(&mut app, false, false);
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(None, Some(TheKeyCode::F2)),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert_eq!(selected(&receiver), vec!["Save".to_string()]);
+
+    // Keymap bindings are checked for conflicts with registered shortcuts.
+    app.ui
+        .shortcuts
+        .register(
+            TheId::named("Rename"),
+            TheShortcut::new(TheAccelerator::from_keymap_str("F2").unwrap()),
+        )
+        .unwrap();
+    let conflicts = app.ui.shortcuts.keymap_conflicts(&app.ctx.ui.keymap);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].0.name, "Rename");
+    assert_eq!(conflicts[0].1.name, "Save");
+}
+
+#[test]
+fn typing_damages_only_the_edited_widget() {
+    let mut app = TheHeadlessApp::new(500, 200, Box::new(Form));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    assert_eq!(app.damage(), &TheDamage::Full);
+
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    assert!(app.damage().is_empty());
+
+    app.run_script(&[
+        TheHeadlessEvent::TouchDown(420.0, 100.0),
+        TheHeadlessEvent::TouchUp(420.0, 100.0),
+        TheHeadlessEvent::KeyDown(Some('a'), None),
+    ]);
+    let TheDamage::Regions(regions) = app.damage() else {
+        panic!("expected partial damage, got {:?}", app.damage());
+    };
+    assert!(!regions.is_empty());
+    assert!(regions.iter().all(|dim| dim.x >= 350));
+
+    // The partially updated frame equals the fully drawn UI.
+    let frame = app.pixels().to_vec();
+    assert_eq!(frame, app.ui.canvas.buffer().pixels());
+}
+
+struct Painter;
+
+impl TheTrait for Painter {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self
+    }
+
+    fn draw(&mut self, pixels: &mut [u8], _ctx: &mut TheContext) {
+        pixels.fill(255);
+    }
+}
+
+#[test]
+fn apps_without_damage_reports_present_every_frame() {
+    let mut app = TheHeadlessApp::new(100, 100, Box::new(Painter));
+    for _ in 0..3 {
+        app.run_script(&[TheHeadlessEvent::Frames(1)]);
+        assert_eq!(app.damage(), &TheDamage::Full);
+    }
+}
@@ -0,0 +1,149 @@
+//! Tests for the grid and flex layouts.
+
+use theframework::prelude::*;
+
+/// A spacer with the given maximum size.
+fn spacer(name: &str, width: i32, height: i32) -> Box<dyn TheWidget> {
+    let mut spacer = TheSpacer::new(TheId::named(name));
+    spacer.limiter_mut().set_max_size(Vec2::new(width, height));
+    Box::new(spacer)
+}
+
+fn rect(layout: &mut dyn TheLayout, name: &str) -> (i32, i32, i32, i32) {
+    let dim = *layout
+        .get_widget(Some(&name.to_string()), None)
+        .unwrap()
+        .dim();
+    (dim.x, dim.y, dim.width, dim.height)
+}
+
+#[test]
+fn grid_tracks_spans_and_alignment() {
+    let mut ctx = TheContext::new(400, 400, 1.0);
+
+    let mut grid = TheGridLayout::new(TheId::empty());
+    grid.set_margin(Vec4::zero());
+    grid.set_columns(vec![
+        TheGridTrack::Fixed(50),
+        TheGridTrack::Auto,
+        TheGridTrack::Fraction(1.0),
+    ]);
+    grid.add_widget(spacer("Fixed", 1000, 14), TheGridPlacement::new(0, 0));
+    grid.add_widget(spacer("Auto", 40, 14), TheGridPlacement::new(1, 0));
+    grid.add_widget(spacer("Fraction", 1000, 14), TheGridPlacement::new(2, 0));
+    grid.add_widget(
+        spacer("Span", 1000, 20),
+        TheGridPlacement::new(0, 1).span(3, 1),
+    );
+    grid.add_widget(
+        spacer("Centered", 20, 10),
+        TheGridPlacement::new(0, 2).align(TheLayoutAlign::Center, TheLayoutAlign::End),
+    );
+    grid.set_dim(TheDim::new(0, 0, 300, 200), &mut ctx);
+
+    assert_eq!(rect(&mut grid, "Fixed"), (0, 0, 50, 14));
+    assert_eq!(rect(&mut grid, "Auto"), (55, 0, 40, 14));
+    assert_eq!(rect(&mut grid, "Fraction"), (100, 0, 200, 14));
+    assert_eq!(rect(&mut grid, "Span"), (0, 19, 300, 20));
+    assert_eq!(rect(&mut grid, "Centered"), (15, 44, 20, 10));
+}
+
+#[test]
+fn grid_hides_widgets_in_the_margin() {
+    let mut ctx = TheContext::new(400, 400, 1.0);
+
+    let mut grid = TheGridLayout::new(TheId::empty());
+    grid.set_margin(Vec4::new(10, 10, 10, 10));
+    grid.set_columns(vec![TheGridTrack::Fixed(60), TheGridTrack::Fixed(60)]);
+    grid.add_widget(spacer("Inside", 1000, 14), TheGridPlacement::new(0, 0));
+    grid.add_widget(spacer("Overflow", 1000, 14), TheGridPlacement::new(1, 0));
+    grid.set_dim(TheDim::new(0, 0, 135, 100), &mut ctx);
+
+    // The second column ends at 135, inside the layout but in its right margin.
+    assert_eq!(rect(&mut grid, "Inside"), (10, 10, 60, 14));
+    assert_eq!(rect(&mut grid, "Overflow"), (0, 0, 0, 0));
+}
+
+#[test]
+fn flex_grows_within_limits() {
+    let mut ctx = TheContext::new(400, 400, 1.0);
+
+    let mut flex = TheFlexLayout::new(TheId::empty());
+    flex.set_margin(Vec4::zero());
+    flex.set_gap(10);
+    flex.add_widget(spacer("A", 1000, 14), TheFlexItem::grow(1.0).with_basis(40));
+    flex.add_widget(spacer("B", 60, 14), TheFlexItem::grow(1.0).with_basis(40));
+    flex.add_widget(spacer("C", 50, 14), TheFlexItem::default());
+    flex.set_dim(TheDim::new(0, 0, 300, 30), &mut ctx);
+
+    // B stops at its maximum width, A takes the rest of the free space.
+    assert_eq!(rect(&mut flex, "A"), (0, 0, 170, 14));
+    assert_eq!(rect(&mut flex, "B"), (180, 0, 60, 14));
+    assert_eq!(rect(&mut flex, "C"), (250, 0, 50, 14));
+}
+
+#[test]
+fn flex_distributes_rounding_pixels() {
+    let mut ctx = TheContext::new(400, 400, 1.0);
+
+    let mut flex = TheFlexLayout::new(TheId::empty());
+    flex.set_margin(Vec4::zero());
+    flex.set_gap(0);
+    for name in ["A", "B", "C"] {
+        flex.add_widget(spacer(name, 1000, 14), TheFlexItem::grow(1.0).with_basis(0));
+    }
+    flex.set_dim(TheDim::new(0, 0, 131, 30), &mut ctx);
+
+    // The three items share 131 pixels exactly, the pixel left by rounding goes to the first.
+    assert_eq!(rect(&mut flex, "A"), (0, 0, 44, 14));
+    assert_eq!(rect(&mut flex, "B"), (44, 0, 44, 14));
+    assert_eq!(rect(&mut flex, "C"), (88, 0, 43, 14));
+}
+
+#[test]
+fn flex_shrinks_to_minimum() {
+    let mut ctx = TheContext::new(400, 400, 1.0);
+
+    let mut flex = TheFlexLayout::new(TheId::empty());
+    flex.set_margin(Vec4::zero());
+    flex.set_gap(10);
+    flex.set_alignment(TheLayoutAlign::Start);
+    let mut a = spacer("A", 50, 14);
+    a.limiter_mut().set_min_width(30);
+    flex.add_widget(a, TheFlexItem::default());
+    flex.add_widget(spacer("B", 50, 14), TheFlexItem::default());
+    flex.add_widget(spacer("C", 50, 14), TheFlexItem::default());
+    flex.set_dim(TheDim::new(0, 0, 100, 30), &mut ctx);
+
+    assert_eq!(rect(&mut flex, "A"), (0, 0, 30, 14));
+    assert_eq!(rect(&mut flex, "B"), (40, 0, 25, 14));
+    assert_eq!(rect(&mut flex, "C"), (75, 0, 25, 14));
+}
+
+#[test]
+fn flex_wraps_and_justifies() {
+    let mut ctx = TheContext::new(400, 400, 1.0);
+
+    let mut flex = TheFlexLayout::new(TheId::empty());
+    flex.set_margin(Vec4::new(5, 5, 5, 5));
+    flex.set_gap(10);
+    flex.set_wrap(true);
+    flex.set_justify(TheFlexJustify::SpaceBetween);
+    for name in ["A", "B", "C"] {
+        flex.add_widget(spacer(name, 40, 14), TheFlexItem::default());
+    }
+    flex.set_dim(TheDim::new(0, 0, 110, 100), &mut ctx);
+
+    assert_eq!(rect(&mut flex, "A"), (5, 5, 40, 14));
+    assert_eq!(rect(&mut flex, "B"), (65, 5, 40, 14));
+    assert_eq!(rect(&mut flex, "C"), (5, 29, 40, 14));
+
+    flex.set_direction(TheFlexDirection::Column);
+    flex.set_wrap(false);
+    flex.set_justify(TheFlexJustify::Start);
+    flex.relayout(&mut ctx);
+
+    assert_eq!(rect(&mut flex, "A"), (5, 5, 40, 14));
+    assert_eq!(rect(&mut flex, "B"), (5, 29, 40, 14));
+    assert_eq!(rect(&mut flex, "C"), (5, 53, 40, 14));
+}
@@ -0,0 +1,256 @@
+//! Tests for multi-selection in list and tree layouts.
+
+mod common;
+
+use common::app_with_events;
+use std::sync::mpsc::Receiver;
+use theframework::prelude::*;
+
+struct Lists {
+    multi: bool,
+}
+
+impl TheTrait for Lists {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self { multi: true }
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
+        let mut canvas = TheCanvas::new();
+
+        let mut list = TheListLayout::new(TheId::named("Assets"));
+        for i in 0..10 {
+            let mut item = TheListItem::new(TheId::named(&format!("Asset {}", i)));
+            item.set_text(format!("Asset {}", i));
+            list.add_item(item, ctx);
+        }
+        list.set_multi_selection(self.multi);
+        canvas.set_layout(list);
+
+        ui.canvas = canvas;
+    }
+}
+
+fn app(multi: bool) -> (TheHeadlessApp, Receiver<TheEvent>) {
+    app_with_events(400, 300, Box::new(Lists { multi }))
+}
+
+/// Clicks the list item at the given index.
+fn click(index: i32) -> [TheHeadlessEvent; 2] {
+    let y = (1 + index * 20 + 8) as f32;
+    [
+        TheHeadlessEvent::TouchDown(50.0, y),
+        TheHeadlessEvent::TouchUp(50.0, y),
+    ]
+}
+
+fn modifiers(shift: bool, ctrl: bool) -> TheHeadlessEvent {
+    TheHeadlessEvent::ModifierChanged(shift, ctrl, false, false)
+}
+
+fn selected(app: &mut TheHeadlessApp) -> Vec<String> {
+    app.ui
+        .get_list_layout("Assets")
+        .unwrap()
+        .selected_ids()
+        .into_iter()
+        .map(|id| id.name)
+        .collect()
+}
+
+fn names(indices: &[usize]) -> Vec<String> {
+    indices.iter().map(|i| format!("Asset {}", i)).collect()
+}
+
+/// The selections reported by TheEvent::SelectionChanged.
+fn reported(events: &Receiver<TheEvent>) -> Vec<Vec<String>> {
+    events
+        .try_iter()
+        .filter_map(|event| match event {
+            TheEvent::SelectionChanged(layout, ids) if layout.name == "Assets" => {
+                Some(ids.into_iter().map(|id| id.name).collect())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn shift_selects_ranges_and_ctrl_toggles() {
+    let (mut app, events) = app(true);
+
+    app.run_script(&click(1));
+    assert_eq!(selected(&mut app), names(&[1]));
+
+    app.run_script(&[modifiers(true, false)]);
+    app.run_script(&click(4));
+    assert_eq!(selected(&mut app), names(&[1, 2, 3, 4]));
+
+    // A range from the same anchor shrinks the selection.
+    app.run_script(&click(2));
+    assert_eq!(selected(&mut app), names(&[1, 2]));
+
+    app.run_script(&[modifiers(false, true)]);
+    app.run_script(&click(2));
+    app.run_script(&click(6));
+    assert_eq!(selected(&mut app), names(&[1, 6]));
+    app.run_script(&[modifiers(false, false)]);
+
+    assert_eq!(
+        reported(&events),
+        vec![
+            names(&[1]),
+            names(&[1, 2, 3, 4]),
+            names(&[1, 2]),
+            names(&[1]),
+            names(&[1, 6]),
+        ]
+    );
+
+    // A plain click selects a single item again.
+    app.run_script(&click(3));
+    assert_eq!(selected(&mut app), names(&[3]));
+    let list = app.ui.get_list_layout("Assets").unwrap();
+    assert_eq!(
+        list.selected().map(|id| id.name),
+        Some("Asset 3".to_string())
+    );
+}
+
+#[test]
+fn keyboard_extends_and_selects_all() {
+    let (mut app, events) = app(true);
+
+    app.run_script(&click(5));
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(None, Some(TheKeyCode::Down)),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert_eq!(selected(&mut app), names(&[6]));
+
+    app.run_script(&[
+        modifiers(true, false),
+        TheHeadlessEvent::KeyDown(None, Some(TheKeyCode::Up)),
+        TheHeadlessEvent::KeyDown(None, Some(TheKeyCode::Up)),
+        modifiers(false, false),
+    ]);
+    assert_eq!(selected(&mut app), names(&[4, 5, 6]));
+
+    app.run_script(&[
+        modifiers(false, true),
+        TheHeadlessEvent::KeyDown(Some('a'), None),
+        modifiers(false, false),
+    ]);
+    assert_eq!(selected(&mut app).len(), 10);
+    assert_eq!(reported(&events).last().map(|ids| ids.len()), Some(10));
+}
+
+#[test]
+fn single_selection_ignores_modifiers() {
+    let (mut app, _events) = app(false);
+
+    app.run_script(&click(1));
+    app.run_script(&[modifiers(true, false)]);
+    app.run_script(&click(4));
+    assert_eq!(selected(&mut app), names(&[4]));
+
+    app.run_script(&[
+        modifiers(false, true),
+        TheHeadlessEvent::KeyDown(Some('a'), None),
+    ]);
+    assert_eq!(selected(&mut app), names(&[4]));
+}
+
+fn tree() -> TheTreeLayout {
+    let mut tree = TheTreeLayout::new(TheId::named("Scene"));
+    let root = tree.get_root();
+    for name in ["Camera", "Light"] {
+        root.add_widget(Box::new(TheTreeText::new(TheId::named(name))));
+    }
+    let mut node = TheTreeNode::new(TheId::named("Meshes"));
+    for name in ["Cube", "Sphere", "Plane"] {
+        node.add_widget(Box::new(TheTreeText::new(TheId::named(name))));
+    }
+    node.set_open(true);
+    root.add_child(node);
+    tree.set_multi_selection(true);
+    tree
+}
+
+fn tree_selection(tree: &TheTreeLayout) -> Vec<String> {
+    tree.selected_ids().into_iter().map(|id| id.name).collect()
+}
+
+#[test]
+fn tree_selection_spans_nodes() {
+    let mut ctx = TheContext::new(400, 300, 1.0);
+    let mut tree = tree();
+    tree.set_dim(TheDim::new(0, 0, 200, 300), &mut ctx);
+
+    let light = tree_id(&mut tree, "Light");
+    let sphere = tree_id(&mut tree, "Sphere");
+    let plane = tree_id(&mut tree, "Plane");
+
+    tree.item_clicked(light, false, false, &mut ctx);
+    tree.item_clicked(sphere, true, false, &mut ctx);
+    assert_eq!(tree_selection(&tree), ["Light", "Cube", "Sphere"]);
+
+    tree.move_selection(1, true, &mut ctx);
+    assert_eq!(tree_selection(&tree), ["Light", "Cube", "Sphere", "Plane"]);
+
+    tree.item_clicked(plane, false, true, &mut ctx);
+    assert_eq!(tree_selection(&tree), ["Light", "Cube", "Sphere"]);
+
+    // Selected items of closed nodes are kept by toggles but not by plain clicks.
+    let meshes = tree.get_root().childs[0].id.clone();
+    tree.tree_node_state_changed(meshes, false);
+    let camera = tree_id(&mut tree, "Camera");
+    tree.item_clicked(camera.clone(), false, true, &mut ctx);
+    assert_eq!(tree_selection(&tree), ["Camera", "Light", "Cube", "Sphere"]);
+    tree.item_clicked(camera, false, false, &mut ctx);
+    assert_eq!(tree_selection(&tree), ["Camera"]);
+
+    tree.select_all(&mut ctx);
+    assert_eq!(tree_selection(&tree), ["Camera", "Light"]);
+}
+
+fn tree_id(tree: &mut TheTreeLayout, name: &str) -> TheId {
+    tree.get_widget(Some(&name.to_string()), None)
+        .unwrap()
+        .id()
+        .clone()
+}
+
+#[test]
+fn clicks_on_the_selection_send_no_new_item() {
+    let (mut app, events) = app(true);
+    let new_items = |events: &Receiver<TheEvent>| -> Vec<String> {
+        events
+            .try_iter()
+            .filter_map(|event| match event {
+                TheEvent::NewListItemSelected(id, layout) if layout.name == "Assets" => {
+                    Some(id.name)
+                }
+                _ => None,
+            })
+            .collect()
+    };
+
+    app.run_script(&click(2));
+    assert_eq!(new_items(&events), names(&[2]));
+
+    app.run_script(&click(2));
+    app.run_script(&click(2));
+    assert!(new_items(&events).is_empty());
+
+    // A plain click on an item of a multi selection selects it alone.
+    app.run_script(&[modifiers(false, true)]);
+    app.run_script(&click(5));
+    app.run_script(&[modifiers(false, false)]);
+    app.run_script(&click(5));
+    assert_eq!(new_items(&events), names(&[5, 5]));
+    assert_eq!(selected(&mut app), names(&[5]));
+}
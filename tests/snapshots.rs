@@ -0,0 +1,227 @@
+//! Golden-image tests for widgets and layouts. Run with `THE_UPDATE_SNAPSHOTS=1` to regenerate the
+//! images in `tests/snapshots/` after an intended visual change.
+
+use theframework::prelude::*;
+
+fn golden(name: &str) -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.png", name))
+}
+
+#[test]
+fn text_line_edit() {
+    let mut snapshot = TheSnapshot::new(200, 30);
+
+    let mut edit = TheTextLineEdit::new(TheId::named("Edit"));
+    edit.set_text("Hello World".to_string());
+
+    let buffer = snapshot.render_widget(edit);
+    snapshot.assert_matches(&buffer, golden("text_line_edit"));
+}
+
+/// A small settings form used by the layout and theme snapshots.
+fn settings_layout() -> TheVLayout {
+    let mut vlayout = TheVLayout::new(TheId::named("Layout"));
+
+    let mut text = TheText::new(TheId::empty());
+    text.set_text("Settings".to_string());
+    vlayout.add_widget(Box::new(text));
+
+    let mut button = TheTraybarButton::new(TheId::named("Button"));
+    button.set_text("Apply".to_string());
+    vlayout.add_widget(Box::new(button));
+
+    let mut slider = TheTextLineEdit::new(TheId::named("Slider"));
+    slider.set_value(TheValue::Float(0.5));
+    slider.set_range(TheValue::RangeF32(0.0..=1.0));
+    vlayout.add_widget(Box::new(slider));
+
+    vlayout
+}
+
+#[test]
+fn vlayout_with_widgets() {
+    let mut snapshot = TheSnapshot::new(200, 120);
+
+    let buffer = snapshot.render_layout(settings_layout());
+    snapshot.assert_matches(&buffer, golden("vlayout_with_widgets"));
+}
+
+#[test]
+fn embedded_themes() {
+    for name in ["light", "high_contrast"] {
+        let mut snapshot = TheSnapshot::new(200, 120);
+        let theme = TheCustomTheme::embedded(name).unwrap();
+        snapshot.style.set_theme(Box::new(theme));
+
+        let buffer = snapshot.render_layout(settings_layout());
+        snapshot.assert_matches(&buffer, golden(&format!("vlayout_{}_theme", name)));
+    }
+}
+
+#[test]
+fn custom_theme_defaults_and_errors() {
+    let theme = TheCustomTheme::parse(
+        r##"{ "name": "Mine", "colors": { "TextEditBackground": "#10203040" } }"##,
+    )
+    .unwrap();
+    let dark = TheDarkTheme::new();
+    assert_eq!(theme.name, "Mine");
+    assert_eq!(theme.color(TextEditBackground), &[0x10, 0x20, 0x30, 0x40]);
+    assert_eq!(theme.color(TextEditBorder), dark.color(TextEditBorder));
+
+    // Saved themes contain every color and load back unchanged.
+    let reloaded = TheCustomTheme::parse(&theme.to_json()).unwrap();
+    assert_eq!(
+        reloaded.color(TextEditBackground),
+        theme.color(TextEditBackground)
+    );
+    assert_eq!(reloaded.color(Red), dark.color(Red));
+
+    assert!(TheCustomTheme::parse(r##"{ "colors": { "NoSuchColor": "#FFFFFF" } }"##).is_err());
+    assert!(TheCustomTheme::parse(r##"{ "colors": { "Red": "red" } }"##).is_err());
+    let fallback = TheCustomTheme::from_json("not json");
+    assert_eq!(fallback.color(Red), dark.color(Red));
+}
+
+#[test]
+fn canvas_borders() {
+    let mut snapshot = TheSnapshot::new(240, 160);
+
+    let mut canvas = TheCanvas::new();
+
+    let mut top = TheCanvas::new();
+    top.set_widget(TheMenubar::new(TheId::empty()));
+    canvas.set_top(top);
+
+    let mut left = TheCanvas::new();
+    let mut left_layout = TheVLayout::new(TheId::empty());
+    left_layout.limiter_mut().set_max_width(60);
+    left.set_layout(left_layout);
+    canvas.set_left(left);
+
+    let mut bottom = TheCanvas::new();
+    bottom.set_widget(TheStatusbar::new(TheId::empty()));
+    canvas.set_bottom(bottom);
+
+    let mut center = TheCanvas::new();
+    let mut list = TheListLayout::new(TheId::empty());
+    for name in ["First", "Second", "Third"] {
+        let mut item = TheListItem::new(TheId::named(name));
+        item.set_text(name.to_string());
+        list.add_item(item, &mut snapshot.ctx);
+    }
+    center.set_layout(list);
+    canvas.set_center(center);
+
+    let buffer = snapshot.render_canvas(&mut canvas);
+    snapshot.assert_matches(&buffer, golden("canvas_borders"));
+}
+
+#[test]
+fn hidpi_layouts() {
+    for (scale, name) in [(2.0, "vlayout_2x"), (1.5, "vlayout_1_5x")] {
+        let mut snapshot = TheSnapshot::new(200, 120);
+        snapshot.set_scale_factor(scale);
+
+        let buffer = snapshot.render_layout(settings_layout());
+        assert_eq!(buffer.dim().width, 200);
+        assert_eq!(buffer.physical_width(), (200.0 * scale) as i32);
+        assert_eq!(buffer.physical_height(), (120.0 * scale) as i32);
+        snapshot.assert_matches(&buffer, golden(name));
+    }
+}
+
+#[test]
+fn rgba_view_2x() {
+    let mut snapshot = TheSnapshot::new(64, 48);
+    snapshot.set_scale_factor(2.0);
+
+    let mut image = TheRGBABuffer::new(TheDim::sized(8, 8));
+    for y in 0..8 {
+        for x in 0..8 {
+            let color = if (x + y) % 2 == 0 {
+                [255, 160, 0, 255]
+            } else {
+                [0, 90, 200, 255]
+            };
+            image.set_pixel(x, y, &color);
+        }
+    }
+
+    let mut view = TheRGBAView::new(TheId::named("View"));
+    view.set_buffer(image);
+    view.set_zoom(4.0);
+    view.set_background([20, 20, 20, 255]);
+
+    let buffer = snapshot.render_widget(view);
+    assert_eq!(buffer.physical_width(), 128);
+
+    // The 32x32 image is centered in the 64x48 view, i.e. starts at 16,8 (32,16 physical).
+    assert_eq!(buffer.get_pixel(31, 40), Some([20, 20, 20, 255]));
+    assert_eq!(buffer.get_pixel(33, 17), Some([255, 160, 0, 255]));
+    assert_eq!(buffer.get_pixel(40, 17), Some([0, 90, 200, 255]));
+    assert_eq!(buffer.get_pixel(95, 79), Some([255, 160, 0, 255]));
+    snapshot.assert_matches(&buffer, golden("rgba_view_2x"));
+}
+
+#[test]
+fn hidpi_icons() {
+    let mut ctx = TheContext::new(100, 100, 1.0);
+    let mut icon = TheRGBABuffer::new(TheDim::sized(8, 8));
+    icon.fill([255, 0, 0, 255]);
+    ctx.ui.add_icon("dot".to_string(), icon);
+
+    // A @2x variant without a 1x icon is never used.
+    let mut orphan = TheRGBABuffer::new(TheDim::sized(16, 16));
+    orphan.fill([0, 0, 255, 255]);
+    ctx.ui.add_icon("orphan@2x".to_string(), orphan.clone());
+    assert!(ctx.ui.icon("orphan").is_none());
+
+    let mut icon_2x = orphan.clone();
+    icon_2x.fill([0, 255, 0, 255]);
+    ctx.ui.add_icon("dot@2x".to_string(), icon_2x);
+
+    assert_eq!(ctx.ui.icon("dot").unwrap().scale(), 1.0);
+
+    ctx.set_scale_factor(2.0);
+    let icon = ctx.ui.icon("dot").unwrap();
+    assert_eq!(icon.scale(), 2.0);
+    assert_eq!(icon.dim().width, 8);
+    assert_eq!(icon.physical_width(), 16);
+    assert_eq!(icon.get_pixel(3, 3), Some([0, 255, 0, 255]));
+}
+
+#[test]
+fn mismatch_writes_diff() {
+    if std::env::var_os(theframework::theui::thesnapshot::THE_UPDATE_SNAPSHOTS).is_some() {
+        return;
+    }
+
+    let dir = std::env::temp_dir().join(format!("theframework_snapshot_{}", Uuid::new_v4()));
+    let path = dir.join("fill.png");
+
+    let snapshot = TheSnapshot::new(16, 16);
+
+    let mut expected = TheRGBABuffer::new(TheDim::sized(16, 16));
+    expected.fill([10, 20, 30, 255]);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(&path, expected.to_png().unwrap()).unwrap();
+
+    let mut actual = expected.clone();
+    actual.set_pixel(3, 4, &[11, 21, 31, 255]);
+    assert!(snapshot.compare(&actual, &path).is_ok());
+
+    actual.set_pixel(5, 6, &[200, 20, 30, 255]);
+    match snapshot.compare(&actual, &path) {
+        Err(TheSnapshotError::Mismatch { pixels, diff, .. }) => {
+            assert_eq!(pixels, 1);
+            assert!(diff.exists());
+        }
+        other => panic!("expected a mismatch, got {:?}", other),
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,307 @@
+//! Tests for sorting, resizing, reordering and editing TheTableLayout.
+
+mod common;
+
+use common::app_with_events;
+use std::sync::mpsc::Receiver;
+use theframework::prelude::*;
+
+const HEADER: f32 = 22.0;
+const ROW: f32 = 20.0;
+
+struct Inventory;
+
+impl TheTrait for Inventory {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
+        let mut canvas = TheCanvas::new();
+
+        let mut table = TheTableLayout::new(TheId::named("Inventory"));
+        table.set_columns(
+            vec![
+                TheTableColumn::new("Name", TheTableColumnType::Text)
+                    .with_width(120)
+                    .with_editable(true),
+                TheTableColumn::new("Count", TheTableColumnType::Int)
+                    .with_width(80)
+                    .with_editable(true),
+                TheTableColumn::new("Stocked", TheTableColumnType::Bool)
+                    .with_width(60)
+                    .with_editable(true),
+                TheTableColumn::new("Kind", TheTableColumnType::Choice).with_editable(true),
+            ],
+            ctx,
+        );
+        let kinds = vec!["Tool".to_string(), "Food".to_string()];
+        let rows = [
+            ("Hammer", 3, true, 0),
+            ("apple", 12, false, 1),
+            ("Bread", 7, true, 1),
+        ]
+        .into_iter()
+        .map(|(name, count, stocked, kind)| {
+            vec![
+                TheValue::Text(name.to_string()),
+                TheValue::Int(count),
+                TheValue::Bool(stocked),
+                TheValue::TextList(kind, kinds.clone()),
+            ]
+        })
+        .collect();
+        table.set_rows(rows, ctx);
+        canvas.set_layout(table);
+
+        ui.canvas = canvas;
+    }
+}
+
+fn app() -> (TheHeadlessApp, Receiver<TheEvent>) {
+    app_with_events(400, 300, Box::new(Inventory))
+}
+
+fn table(app: &mut TheHeadlessApp) -> &mut dyn TheTableLayoutTrait {
+    app.ui.get_table_layout("Inventory").unwrap()
+}
+
+fn click(x: f32, y: f32) -> [TheHeadlessEvent; 3] {
+    [
+        TheHeadlessEvent::TouchDown(x, y),
+        TheHeadlessEvent::TouchUp(x, y),
+        TheHeadlessEvent::Frames(1),
+    ]
+}
+
+fn drag(from: (f32, f32), to: (f32, f32)) -> [TheHeadlessEvent; 5] {
+    [
+        TheHeadlessEvent::TouchDown(from.0, from.1),
+        TheHeadlessEvent::TouchDragged(from.0 + 6.0, from.1),
+        TheHeadlessEvent::TouchDragged(to.0, to.1),
+        TheHeadlessEvent::TouchUp(to.0, to.1),
+        TheHeadlessEvent::Frames(1),
+    ]
+}
+
+/// The vertical center of the row at the given display position.
+fn row_y(row: usize) -> f32 {
+    HEADER + row as f32 * ROW + ROW / 2.0
+}
+
+fn edited(events: &Receiver<TheEvent>) -> Vec<(usize, usize, TheValue)> {
+    events
+        .try_iter()
+        .filter_map(|event| match event {
+            TheEvent::TableCellEdited(layout, row, column, value) if layout.name == "Inventory" => {
+                Some((row, column, value))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn clicking_headers_sorts_the_rows() {
+    let (mut app, _events) = app();
+    assert_eq!(table(&mut app).display_rows(), [0, 1, 2]);
+
+    // Count
+    app.run_script(&click(160.0, 10.0));
+    assert_eq!(table(&mut app).sort_column(), Some((1, true)));
+    assert_eq!(table(&mut app).display_rows(), [0, 2, 1]);
+
+    app.run_script(&click(160.0, 10.0));
+    assert_eq!(table(&mut app).sort_column(), Some((1, false)));
+    assert_eq!(table(&mut app).display_rows(), [1, 2, 0]);
+
+    // Text sorts case insensitively.
+    app.run_script(&click(60.0, 10.0));
+    assert_eq!(table(&mut app).display_rows(), [1, 2, 0]);
+    app.run_script(&click(60.0, 10.0));
+    assert_eq!(table(&mut app).display_rows(), [0, 2, 1]);
+
+    // Clicks into rows select the row of the data source.
+    app.run_script(&click(60.0, row_y(1)));
+    assert_eq!(table(&mut app).selected_row(), Some(2));
+}
+
+#[test]
+fn headers_resize_and_reorder_columns() {
+    let (mut app, _events) = app();
+
+    // The border between Name and Count.
+    app.run_script(&drag((120.0, 10.0), (160.0, 10.0)));
+    assert_eq!(table(&mut app).columns()[0].width, 160);
+    assert_eq!(table(&mut app).sort_column(), None);
+
+    // Columns do not shrink below their minimum width.
+    app.run_script(&drag((160.0, 10.0), (0.0, 10.0)));
+    assert_eq!(table(&mut app).columns()[0].width, 30);
+
+    // Count moves in front of Name.
+    app.run_script(&drag((60.0, 10.0), (5.0, 10.0)));
+    assert_eq!(table(&mut app).column_order(), [1, 0, 2, 3]);
+    assert_eq!(table(&mut app).sort_column(), None);
+
+    // Name moves behind Stocked.
+    app.run_script(&drag((95.0, 10.0), (150.0, 10.0)));
+    assert_eq!(table(&mut app).column_order(), [1, 2, 0, 3]);
+}
+
+#[test]
+fn cells_are_edited_with_widgets() {
+    let (mut app, events) = app();
+
+    // The first click selects the row, the second edits the cell.
+    app.run_script(&click(160.0, row_y(0)));
+    assert_eq!(table(&mut app).edited_cell(), None);
+    app.run_script(&click(160.0, row_y(0)));
+    assert_eq!(table(&mut app).edited_cell(), Some((0, 1)));
+
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(Some('4'), None),
+        TheHeadlessEvent::KeyDown(Some('2'), None),
+        TheHeadlessEvent::KeyDown(None, Some(TheKeyCode::Return)),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert_eq!(table(&mut app).edited_cell(), None);
+    assert_eq!(table(&mut app).cell(0, 1), TheValue::Int(42));
+    assert_eq!(edited(&events), [(0, 1, TheValue::Int(42))]);
+
+    // Text which is not a number is not committed to Int columns.
+    app.run_script(&click(160.0, row_y(0)));
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(Some('x'), None),
+        TheHeadlessEvent::KeyDown(None, Some(TheKeyCode::Return)),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert_eq!(table(&mut app).cell(0, 1), TheValue::Int(42));
+    assert!(edited(&events).is_empty());
+
+    // Bools are edited with a check button.
+    app.run_script(&click(230.0, row_y(0)));
+    assert_eq!(table(&mut app).edited_cell(), Some((0, 2)));
+    app.run_script(&click(230.0, row_y(0)));
+    assert_eq!(table(&mut app).edited_cell(), None);
+    assert_eq!(table(&mut app).cell(0, 2), TheValue::Bool(false));
+    assert_eq!(edited(&events), [(0, 2, TheValue::Bool(false))]);
+}
+
+#[test]
+fn return_escape_and_focus_changes_end_edits() {
+    let (mut app, events) = app();
+    app.run_script(&click(160.0, row_y(0)));
+
+    // Return ends the edit even if the text was not changed.
+    app.run_script(&click(160.0, row_y(0)));
+    assert_eq!(table(&mut app).edited_cell(), Some((0, 1)));
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(None, Some(TheKeyCode::Return)),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert_eq!(table(&mut app).edited_cell(), None);
+    assert!(edited(&events).is_empty());
+
+    // Escape ends the edit without committing it.
+    app.run_script(&click(160.0, row_y(0)));
+    app.run_script(&[
+        TheHeadlessEvent::KeyDown(Some('7'), None),
+        TheHeadlessEvent::KeyDown(None, Some(TheKeyCode::Escape)),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    assert_eq!(table(&mut app).edited_cell(), None);
+    assert_eq!(table(&mut app).cell(0, 1), TheValue::Int(3));
+    assert!(edited(&events).is_empty());
+
+    // Moving the focus to another row commits the edit.
+    app.run_script(&click(160.0, row_y(0)));
+    app.run_script(&[TheHeadlessEvent::KeyDown(Some('5'), None)]);
+    app.run_script(&click(160.0, row_y(1)));
+    assert_eq!(table(&mut app).edited_cell(), None);
+    assert_eq!(edited(&events), [(0, 1, TheValue::Int(5))]);
+}
+
+#[test]
+fn choices_are_edited_with_a_dropdown_menu() {
+    let (mut app, events) = app();
+    app.run_script(&click(300.0, row_y(0)));
+
+    let layout = app.ui.get_table_layout("Inventory").unwrap();
+    assert!(layout.edit_cell(0, 3, &mut app.ctx));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    // The menu opens below the cell, the second option is 20 pixels further down.
+    let menu_y = row_y(0) + ROW + 20.0;
+    app.run_script(&[
+        TheHeadlessEvent::TouchDown(300.0, row_y(0)),
+        TheHeadlessEvent::TouchDragged(300.0, menu_y),
+        TheHeadlessEvent::TouchUp(300.0, menu_y),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    let kinds = vec!["Tool".to_string(), "Food".to_string()];
+    assert_eq!(
+        table(&mut app).cell(0, 3),
+        TheValue::TextList(1, kinds.clone())
+    );
+    assert_eq!(edited(&events), [(0, 3, TheValue::TextList(1, kinds))]);
+    assert_eq!(table(&mut app).edited_cell(), None);
+}
+
+/// A data source with many rows, the table only reads the visible ones.
+struct Numbers {
+    count: usize,
+    reads: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl TheTableDataSource for Numbers {
+    fn row_count(&self) -> usize {
+        self.count
+    }
+
+    fn cell(&self, row: usize, _column: usize) -> TheValue {
+        self.reads
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        TheValue::Int(row as i32)
+    }
+}
+
+#[test]
+fn large_data_sources_are_virtualized() {
+    let mut ctx = TheContext::new(400, 300, 1.0);
+    let mut buffer = TheRGBABuffer::new(TheDim::new(0, 0, 400, 300));
+    let mut style: Box<dyn TheStyle> = Box::new(TheClassicStyle::new());
+
+    let reads = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let mut table = TheTableLayout::new(TheId::named("Numbers"));
+    table.set_columns(
+        vec![TheTableColumn::new("Number", TheTableColumnType::Int)],
+        &mut ctx,
+    );
+    table.set_data_source(
+        Box::new(Numbers {
+            count: 100_000,
+            reads: reads.clone(),
+        }),
+        &mut ctx,
+    );
+    table.set_dim(TheDim::new(0, 0, 400, 300), &mut ctx);
+    table.draw(&mut buffer, &mut style, &mut ctx);
+    assert!(reads.load(std::sync::atomic::Ordering::Relaxed) < 20);
+
+    // Scrolled to the end, the last row is clicked.
+    table.mouse_wheel_scroll(Vec2::new(0, -2_000_000));
+    table.draw(&mut buffer, &mut style, &mut ctx);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    ctx.ui.state_events_sender = Some(sender);
+    let view = table.get_widget_at_coord(Vec2::new(50, 290)).unwrap();
+    view.on_event(&TheEvent::MouseDown(Vec2::new(50, 290)), &mut ctx);
+    assert_eq!(table.selected_row(), Some(99_999));
+    assert!(receiver
+        .try_iter()
+        .any(|event| matches!(event, TheEvent::TableRowSelected(_, 99_999))));
+    assert!(reads.load(std::sync::atomic::Ordering::Relaxed) < 40);
+}
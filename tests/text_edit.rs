@@ -0,0 +1,65 @@
+//! Tests for the editing state shared by the text widgets.
+
+use theframework::theui::thewidget::thetextedit::{TheCursor, TheTextEditState};
+
+fn state(text: &str, row: usize, column: usize) -> TheTextEditState {
+    let mut state = TheTextEditState::default();
+    state.set_text(text.to_string());
+    state.set_cursor(TheCursor::new(row, column));
+    state
+}
+
+#[test]
+fn backspace_and_forward_delete() {
+    let mut s = state("abc\ndef", 0, 1);
+    assert!(s.delete_text());
+    assert_eq!(s.to_text(), "bc\ndef");
+
+    assert!(s.delete_text_forward());
+    assert_eq!(s.to_text(), "c\ndef");
+
+    // Forward delete at the end of a row joins the next row.
+    s.set_cursor(TheCursor::new(0, 1));
+    assert!(s.delete_text_forward());
+    assert_eq!(s.to_text(), "cdef");
+
+    s.set_cursor(TheCursor::new(0, 4));
+    assert!(!s.delete_text_forward());
+}
+
+#[test]
+fn home_end_and_paging() {
+    let mut s = state("one\ntwo\nthree\nfour", 2, 2);
+
+    assert!(s.move_cursor_to_line_end());
+    assert_eq!((s.cursor.row, s.cursor.column), (2, 5));
+    assert!(s.move_cursor_to_line_start());
+    assert_eq!((s.cursor.row, s.cursor.column), (2, 0));
+
+    assert!(s.move_cursor_up_by(5));
+    assert_eq!(s.cursor.row, 0);
+    assert!(s.move_cursor_down_by(2));
+    assert_eq!(s.cursor.row, 2);
+
+    assert!(s.move_cursor_to_text_end());
+    assert_eq!((s.cursor.row, s.cursor.column), (3, 4));
+    assert!(!s.move_cursor_to_text_end());
+    assert!(s.move_cursor_to_text_start());
+    assert!(s.cursor.is_zero());
+}
+
+#[test]
+fn shift_selection_to_cursor() {
+    let mut s = state("hello world", 0, 6);
+
+    let index = s.find_cursor_index();
+    s.move_cursor_to_line_end();
+    s.select_to_cursor(index);
+    assert_eq!((s.selection.start, s.selection.end), (6, 11));
+
+    // Moving the other way across the anchor flips the selection.
+    let index = s.find_cursor_index();
+    s.move_cursor_to_line_start();
+    s.select_to_cursor(index);
+    assert_eq!((s.selection.start, s.selection.end), (0, 6));
+}
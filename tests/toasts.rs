@@ -0,0 +1,147 @@
+//! Tests for the toast notifications of TheUI, driven by the headless runner.
+
+mod common;
+
+use common::{frame, Empty};
+use std::time::{Duration, Instant};
+use theframework::prelude::*;
+
+#[test]
+fn toasts_stack_in_the_corner() {
+    let mut app = TheHeadlessApp::new(500, 300, Box::new(Empty));
+    frame(&mut app);
+
+    app.ctx
+        .ui
+        .send(TheEvent::ShowToast(TheToast::info("Saved")));
+    app.ctx
+        .ui
+        .send(TheEvent::ShowToast(TheToast::error("Export failed")));
+    frame(&mut app);
+
+    let toasts = app.ui.toasts.toasts();
+    assert_eq!(toasts.len(), 2);
+    assert_eq!(toasts[0].level, TheToastLevel::Info);
+
+    // The newest toast is closest to the bottom right corner.
+    let (info, error) = (*toasts[0].dim(), *toasts[1].dim());
+    assert_eq!(error.x + error.width, 490);
+    assert_eq!(error.y + error.height, 290);
+    assert!(info.y + info.height <= error.y);
+
+    // The toasts are drawn and damaged in the overlay pass.
+    let frame = app.frame();
+    let marker = frame.get_pixel(error.x + 2, error.y + error.height / 2);
+    let theme = TheDarkTheme::new();
+    assert_eq!(marker, Some(*theme.color(TheThemeColors::ToastError)));
+    match app.damage() {
+        TheDamage::Full => {}
+        TheDamage::Regions(regions) => assert!(regions.contains(&error)),
+    }
+}
+
+#[test]
+fn toast_actions_send_custom_events() {
+    let mut app = TheHeadlessApp::new(500, 300, Box::new(Empty));
+    let events = app.ui.add_state_listener("Test".to_string());
+    frame(&mut app);
+
+    let mut toast = TheToast::warning("File changed on disk");
+    toast.set_timeout(None);
+    toast.add_action("Reload", TheId::named("Reload"), TheValue::Bool(true));
+    app.ui.show_toast(toast);
+    frame(&mut app);
+
+    let button = *app.ui.toasts.toasts()[0].dim();
+    let (x, y) = (
+        (button.x + button.width - 12) as f32,
+        (button.y + button.height / 2) as f32,
+    );
+    app.run_script(&[
+        TheHeadlessEvent::TouchDown(x, y),
+        TheHeadlessEvent::TouchUp(x, y),
+        TheHeadlessEvent::Frames(1),
+    ]);
+
+    assert!(app.ui.toasts.is_empty());
+    let reload = events.try_iter().any(
+        |event| matches!(event, TheEvent::Custom(id, TheValue::Bool(true)) if id.name == "Reload"),
+    );
+    assert!(reload);
+}
+
+#[test]
+fn toasts_fade_out_after_their_timeout() {
+    let mut app = TheHeadlessApp::new(500, 300, Box::new(Empty));
+    frame(&mut app);
+
+    let mut toast = TheToast::info("Copied");
+    toast.set_timeout(Some(Duration::from_millis(50)));
+    app.ui.show_toast(toast);
+    frame(&mut app);
+    assert_eq!(app.ui.toasts.toasts().len(), 1);
+
+    // The toast fades during its last moments.
+    let opacity = app.ui.toasts.toasts()[0].opacity(Instant::now());
+    assert!(opacity < 1.0);
+
+    std::thread::sleep(Duration::from_millis(60));
+    frame(&mut app);
+    assert!(app.ui.toasts.is_empty());
+}
+
+#[test]
+fn toasts_restore_the_frame_below_them() {
+    let mut app = TheHeadlessApp::new(500, 300, Box::new(Empty));
+    frame(&mut app);
+    let background = app.pixels().to_vec();
+
+    let mut toast = TheToast::warning("Low disk space");
+    toast.set_timeout(Some(Duration::from_millis(600)));
+    app.ui.show_toast(toast);
+    frame(&mut app);
+    let shown = app.pixels().to_vec();
+    assert_ne!(shown, background);
+
+    // Redrawing a toast does not blend it onto the toast of the last frame.
+    frame(&mut app);
+    assert_eq!(app.pixels(), &shown[..]);
+
+    std::thread::sleep(Duration::from_millis(610));
+    frame(&mut app);
+    assert!(app.ui.toasts.is_empty());
+    assert_eq!(app.pixels(), &background[..]);
+}
+
+#[test]
+fn faded_toast_text_is_transparent() {
+    // Toasts fade their text through the alpha of the text color.
+    let ctx = TheContext::new(100, 40, 1.0);
+    let rect = (0, 0, 100, 40);
+    let mut pixels = vec![0; 100 * 40 * 4];
+    let text = |pixels: &mut [u8], alpha| {
+        ctx.draw.text_rect_blend(
+            pixels,
+            &rect,
+            100,
+            "Saved",
+            TheFontSettings {
+                size: 13.0,
+                ..Default::default()
+            },
+            &[255, 255, 255, alpha],
+            TheHorizontalAlign::Center,
+            TheVerticalAlign::Center,
+        );
+    };
+
+    text(&mut pixels, 0);
+    assert!(pixels.iter().all(|value| *value == 0));
+
+    text(&mut pixels, 255);
+    let opaque = pixels.iter().map(|value| *value as u32).sum::<u32>();
+    let mut half = vec![0; 100 * 40 * 4];
+    text(&mut half, 128);
+    let half = half.iter().map(|value| *value as u32).sum::<u32>();
+    assert!(half > 0 && half < opaque);
+}
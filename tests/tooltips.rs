@@ -0,0 +1,140 @@
+//! Tests for the tooltips of TheUI, driven by the headless runner.
+
+use std::time::Duration;
+use theframework::prelude::*;
+
+struct Toolbar;
+
+impl TheTrait for Toolbar {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, _ctx: &mut TheContext) {
+        let mut canvas = TheCanvas::new();
+
+        let mut layout = TheHLayout::new(TheId::named("Toolbar"));
+        let mut add = TheTraybarButton::new(TheId::named("Add"));
+        add.set_text("Add".to_string());
+        add.set_status_text("Add new code.");
+        layout.add_widget(Box::new(add));
+
+        let mut label = TheText::new(TheId::named("Label"));
+        label.set_text("Label".to_string());
+        layout.add_widget(Box::new(label));
+        canvas.set_layout(layout);
+
+        ui.canvas = canvas;
+        ui.tooltip.delay = Duration::ZERO;
+    }
+}
+
+fn center(app: &mut TheHeadlessApp, name: &str) -> (f32, f32) {
+    let dim = *app.ui.get_widget(name).unwrap().dim();
+    (
+        (dim.x + dim.width / 2) as f32,
+        (dim.y + dim.height / 2) as f32,
+    )
+}
+
+fn hover(app: &mut TheHeadlessApp, (x, y): (f32, f32)) {
+    app.run_script(&[TheHeadlessEvent::Hover(x, y), TheHeadlessEvent::Frames(1)]);
+}
+
+#[test]
+fn status_text_is_shown_as_tooltip() {
+    let mut app = TheHeadlessApp::new(400, 200, Box::new(Toolbar));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    let add = center(&mut app, "Add");
+    hover(&mut app, add);
+    let dim = app.ui.tooltip.visible().expect("tooltip");
+    assert!(dim.y > add.1 as i32);
+
+    let frame = app.frame();
+    let theme = TheDarkTheme::new();
+    assert_eq!(
+        frame.get_pixel(dim.x + 1, dim.y + 1),
+        Some(*theme.color(TheThemeColors::TooltipBackground))
+    );
+
+    // Widgets without a status text have no tooltip.
+    let label = center(&mut app, "Label");
+    hover(&mut app, label);
+    assert!(app.ui.tooltip.visible().is_none());
+
+    // A click hides the tooltip until another widget is hovered.
+    hover(&mut app, add);
+    assert!(app.ui.tooltip.visible().is_some());
+    app.run_script(&[
+        TheHeadlessEvent::TouchDown(add.0, add.1),
+        TheHeadlessEvent::TouchUp(add.0, add.1),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    hover(&mut app, (add.0 + 1.0, add.1));
+    assert!(app.ui.tooltip.visible().is_none());
+}
+
+#[test]
+fn tooltips_wait_for_the_hover_delay() {
+    let mut app = TheHeadlessApp::new(400, 200, Box::new(Toolbar));
+    app.ui.tooltip.delay = Duration::from_secs(60);
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    let add = center(&mut app, "Add");
+    hover(&mut app, add);
+    assert!(app.ui.tooltip.visible().is_none());
+}
+
+#[test]
+fn tooltips_stay_on_screen() {
+    let mut app = TheHeadlessApp::new(400, 200, Box::new(Toolbar));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    let id = app.ui.get_widget("Label").unwrap().id().clone();
+    app.ctx.ui.set_tooltip(
+        &id,
+        Some(TheTooltip::Markdown(
+            "# Label\nShows **bold** and *emphasized* text.".to_string(),
+        )),
+    );
+
+    let label = *app.ui.get_widget("Label").unwrap().dim();
+    let corner = (
+        (label.x + label.width - 2) as f32,
+        (label.y + label.height - 2) as f32,
+    );
+    hover(&mut app, corner);
+
+    let dim = app.ui.tooltip.visible().expect("tooltip");
+    assert!(dim.x >= 0 && dim.x + dim.width <= 400);
+    assert!(dim.y >= 0 && dim.y + dim.height <= 200);
+}
+
+#[test]
+fn markdown_tooltips_are_styled() {
+    let draw = TheDraw2D::new();
+    let tooltip = TheTooltip::Markdown("# Title\n\n**Bold** and `code`\n\n- Item".to_string());
+    let (words, size) = tooltip.layout(280, &draw);
+
+    let styles: Vec<(&str, TheTooltipTextStyle)> = words
+        .iter()
+        .map(|word| (word.text.as_str(), word.style))
+        .collect();
+    assert_eq!(
+        styles,
+        vec![
+            ("Title", TheTooltipTextStyle::Heading),
+            ("Bold", TheTooltipTextStyle::Strong),
+            ("and", TheTooltipTextStyle::Text),
+            ("code", TheTooltipTextStyle::Code),
+            ("•", TheTooltipTextStyle::Text),
+            ("Item", TheTooltipTextStyle::Text),
+        ]
+    );
+    assert!(words[1].y > words[0].y && words[4].y > words[1].y);
+    assert!(size.x > 0 && size.x <= 280);
+}
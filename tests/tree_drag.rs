@@ -0,0 +1,218 @@
+//! Tests for moving items and nodes of TheTreeLayout by drag and drop.
+
+mod common;
+
+use common::app_with_events;
+use std::sync::mpsc::Receiver;
+use theframework::prelude::*;
+use web_time::{Duration, Instant};
+
+struct Scene;
+
+impl TheTrait for Scene {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
+        let mut canvas = TheCanvas::new();
+        canvas.set_layout(tree());
+        ui.canvas = canvas;
+        ctx.ui.relayout = true;
+    }
+}
+
+fn tree() -> TheTreeLayout {
+    let mut tree = TheTreeLayout::new(TheId::named("Scene"));
+    let root = tree.get_root();
+    for name in ["Camera", "Light"] {
+        root.add_widget(Box::new(TheTreeText::new(TheId::named(name))));
+    }
+    let mut meshes = TheTreeNode::new(TheId::named("Meshes"));
+    meshes.set_open(true);
+    for name in ["Cube", "Sphere", "Plane"] {
+        meshes.add_widget(Box::new(TheTreeText::new(TheId::named(name))));
+    }
+    root.add_child(meshes);
+    let mut lights = TheTreeNode::new(TheId::named("Lights"));
+    lights.add_widget(Box::new(TheTreeText::new(TheId::named("Sun"))));
+    root.add_child(lights);
+    tree.set_drag_and_drop(true);
+    tree
+}
+
+fn app() -> (TheHeadlessApp, Receiver<TheEvent>) {
+    app_with_events(400, 300, Box::new(Scene))
+}
+
+/// The screen position of the given part (0..1) of the height of a node or item.
+fn position(tree: &mut dyn TheTreeLayoutTrait, name: &str, part: f32) -> (f32, f32) {
+    let dim = *tree
+        .get_widget(Some(&name.to_string()), None)
+        .unwrap()
+        .dim();
+    ((dim.x + 30) as f32, dim.y as f32 + dim.height as f32 * part)
+}
+
+/// Drags from the first to the second position and releases.
+fn drag(app: &mut TheHeadlessApp, from: (f32, f32), to: (f32, f32)) {
+    app.run_script(&[
+        TheHeadlessEvent::TouchDown(from.0, from.1),
+        TheHeadlessEvent::TouchDragged(from.0, from.1 + 6.0),
+        TheHeadlessEvent::TouchDragged(to.0, to.1),
+        TheHeadlessEvent::TouchUp(to.0, to.1),
+    ]);
+}
+
+fn requested(events: &Receiver<TheEvent>) -> Vec<TheTreeMove> {
+    events
+        .try_iter()
+        .filter_map(|event| match event {
+            TheEvent::TreeMoveRequested(layout, tree_move) if layout.name == "Scene" => {
+                Some(tree_move)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn names(node: &TheTreeNode) -> Vec<String> {
+    node.widgets.iter().map(|w| w.id().name.clone()).collect()
+}
+
+#[test]
+fn items_are_reordered_when_the_move_is_applied() {
+    let (mut app, events) = app();
+    let tree = app.ui.get_tree_layout("Scene").unwrap();
+    let from = position(tree, "Camera", 0.5);
+    let to = position(tree, "Light", 0.75);
+
+    drag(&mut app, from, to);
+
+    let moves = requested(&events);
+    assert_eq!(moves.len(), 1);
+    assert_eq!(moves[0].id.name, "Camera");
+    assert!(!moves[0].node);
+    assert_eq!(moves[0].parent.name, "Scene");
+    assert_eq!(moves[0].index, 1);
+    assert!(app.ctx.ui.drop.is_none());
+
+    // Nothing moves until the app applies the move.
+    let tree = app.ui.get_tree_layout("Scene").unwrap();
+    assert_eq!(names(tree.get_root()), ["Camera", "Light"]);
+    assert!(tree.apply_move(&moves[0]));
+    assert_eq!(names(tree.get_root()), ["Light", "Camera"]);
+}
+
+#[test]
+fn items_and_nodes_are_dropped_into_nodes() {
+    let (mut app, events) = app();
+    let tree = app.ui.get_tree_layout("Scene").unwrap();
+    let from = position(tree, "Cube", 0.5);
+    let to = position(tree, "Lights", 0.5);
+
+    drag(&mut app, from, to);
+
+    let moves = requested(&events);
+    assert_eq!(moves.len(), 1);
+    assert_eq!(moves[0].parent.name, "Lights");
+    assert_eq!(moves[0].index, 1);
+    let tree = app.ui.get_tree_layout("Scene").unwrap();
+    assert!(tree.apply_move(&moves[0]));
+    let lights = tree.get_root().childs[1].id.uuid;
+    assert_eq!(
+        names(tree.get_node_by_id(&lights).unwrap()),
+        ["Sun", "Cube"]
+    );
+
+    // The Lights node is moved into the Meshes node.
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    let tree = app.ui.get_tree_layout("Scene").unwrap();
+    let from = position(tree, "Lights", 0.5);
+    let to = position(tree, "Meshes", 0.5);
+    drag(&mut app, from, to);
+
+    let moves = requested(&events);
+    assert_eq!(moves.len(), 1);
+    assert!(moves[0].node);
+    assert_eq!(moves[0].parent.name, "Meshes");
+    assert_eq!(moves[0].index, 0);
+    let tree = app.ui.get_tree_layout("Scene").unwrap();
+    assert!(tree.apply_move(&moves[0]));
+    assert_eq!(tree.get_root().childs.len(), 1);
+    assert_eq!(tree.get_root().childs[0].childs[0].id.name, "Lights");
+}
+
+#[test]
+fn nodes_are_not_moved_into_themselves() {
+    let mut ctx = TheContext::new(400, 300, 1.0);
+    let mut tree = tree();
+    tree.set_dim(TheDim::new(0, 0, 200, 300), &mut ctx);
+
+    let meshes = tree.get_root().childs[0].id.clone();
+    let now = Instant::now();
+    assert!(tree.start_drag(&meshes, "Meshes", Vec2::zero()).is_some());
+    let (x, y) = position(&mut tree, "Sphere", 0.5);
+    assert!(tree.drag_to(Vec2::new(x as i32, y as i32), now, &mut ctx));
+    let (x, y) = position(&mut tree, "Meshes", 0.5);
+    assert!(tree.drag_to(Vec2::new(x as i32, y as i32), now, &mut ctx));
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    ctx.ui.state_events_sender = Some(sender);
+    tree.end_drag(true, &mut ctx);
+    assert!(!receiver
+        .try_iter()
+        .any(|event| matches!(event, TheEvent::TreeMoveRequested(..))));
+
+    let lights = tree.get_root().childs[1].id.clone();
+    let into_itself = TheTreeMove {
+        id: meshes.clone(),
+        node: true,
+        parent: meshes,
+        index: 0,
+    };
+    assert!(!tree.apply_move(&into_itself));
+    assert_eq!(tree.get_root().childs[1].id, lights);
+}
+
+#[test]
+fn hovered_nodes_open_and_the_tree_scrolls_at_the_edges() {
+    let mut ctx = TheContext::new(400, 300, 1.0);
+    let mut tree = tree();
+    tree.set_dim(TheDim::new(0, 0, 200, 300), &mut ctx);
+
+    let camera = tree.get_root().widgets[0].id().clone();
+    let lights = tree.get_root().childs[1].id.uuid;
+    let now = Instant::now();
+    assert!(tree.start_drag(&camera, "Camera", Vec2::zero()).is_some());
+
+    let (x, y) = position(&mut tree, "Lights", 0.5);
+    tree.drag_to(Vec2::new(x as i32, y as i32), now, &mut ctx);
+    tree.update_drag(now + Duration::from_millis(100), &mut ctx);
+    assert!(!tree.get_node_by_id(&lights).unwrap().open);
+    tree.update_drag(now + Duration::from_millis(700), &mut ctx);
+    assert!(tree.get_node_by_id(&lights).unwrap().open);
+    tree.end_drag(false, &mut ctx);
+
+    // A short tree scrolls while the pointer is at its bottom edge.
+    tree.set_dim(TheDim::new(0, 0, 200, 60), &mut ctx);
+    let top = tree
+        .get_widget_at_coord(Vec2::new(30, 2))
+        .map(|w| w.id().name.clone());
+    assert!(tree.start_drag(&camera, "Camera", Vec2::zero()).is_some());
+    for frame in 0..20 {
+        tree.drag_to(
+            Vec2::new(30, 58),
+            now + Duration::from_millis(frame),
+            &mut ctx,
+        );
+    }
+    tree.end_drag(false, &mut ctx);
+    let scrolled = tree
+        .get_widget_at_coord(Vec2::new(30, 2))
+        .map(|w| w.id().name.clone());
+    assert_ne!(top, scrolled);
+}
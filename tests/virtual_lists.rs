@@ -0,0 +1,163 @@
+//! Tests for virtualized list layouts driven by a TheListDataSource.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use theframework::prelude::*;
+
+const COUNT: usize = 100_000;
+
+struct Assets {
+    count: usize,
+    /// How often an item widget was filled.
+    filled: Arc<AtomicUsize>,
+}
+
+impl TheListDataSource for Assets {
+    fn count(&self) -> usize {
+        self.count
+    }
+
+    fn item_id(&self, index: usize) -> TheId {
+        TheId::named_with_id("Asset", Uuid::from_u128(index as u128 + 1))
+    }
+
+    fn update_item(&mut self, index: usize, item: &mut dyn TheWidget) {
+        self.filled.fetch_add(1, Ordering::Relaxed);
+        item.set_value(TheValue::Text(format!("Asset {}", index)));
+    }
+}
+
+struct Browser {
+    filled: Arc<AtomicUsize>,
+}
+
+impl TheTrait for Browser {
+    fn new() -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            filled: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn init_ui(&mut self, ui: &mut TheUI, ctx: &mut TheContext) {
+        let mut canvas = TheCanvas::new();
+
+        let mut list = TheListLayout::new(TheId::named("Assets"));
+        list.set_data_source(
+            Some(Box::new(Assets {
+                count: COUNT,
+                filled: self.filled.clone(),
+            })),
+            ctx,
+        );
+        canvas.set_layout(list);
+
+        let mut rows = TheRowListLayout::new(TheId::named("Thumbnails"));
+        rows.limiter_mut().set_max_height(100);
+        rows.set_data_source(
+            Some(Box::new(Assets {
+                count: COUNT,
+                filled: self.filled.clone(),
+            })),
+            ctx,
+        );
+        let mut bottom = TheCanvas::new();
+        bottom.limiter_mut().set_max_height(100);
+        bottom.set_layout(rows);
+        canvas.set_bottom(bottom);
+
+        ui.canvas = canvas;
+    }
+}
+
+fn app() -> (TheHeadlessApp, Arc<AtomicUsize>) {
+    let browser = Browser::new();
+    let filled = browser.filled.clone();
+    let mut app = TheHeadlessApp::new(400, 300, Box::new(browser));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+    (app, filled)
+}
+
+fn visible_names(app: &mut TheHeadlessApp, layout: &str) -> Vec<String> {
+    app.ui
+        .get_layout(layout)
+        .unwrap()
+        .widgets()
+        .iter()
+        .map(|w| w.value().describe())
+        .collect()
+}
+
+#[test]
+fn only_visible_items_get_widgets() {
+    let (mut app, filled) = app();
+
+    let names = visible_names(&mut app, "Assets");
+    assert!(!names.is_empty() && names.len() <= 12, "{}", names.len());
+    assert_eq!(names[0], "Asset 0");
+
+    let thumbnails = visible_names(&mut app, "Thumbnails");
+    assert!(!thumbnails.is_empty() && thumbnails.len() <= 8);
+    assert_eq!(thumbnails[0], "Asset 0");
+
+    let before = filled.load(Ordering::Relaxed);
+    assert!(before < 30);
+
+    // Scrolling recycles the widgets for the items which became visible.
+    app.ui
+        .get_list_layout("Assets")
+        .unwrap()
+        .scroll_by(Vec2::new(0, -2000));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    let names = visible_names(&mut app, "Assets");
+    assert_eq!(names[0], "Asset 99");
+    assert!(names.len() <= 12);
+    assert!(filled.load(Ordering::Relaxed) - before <= names.len());
+}
+
+#[test]
+fn selection_by_click_and_index() {
+    let (mut app, _) = app();
+
+    app.ui
+        .get_list_layout("Assets")
+        .unwrap()
+        .scroll_by(Vec2::new(0, -2000));
+    app.run_script(&[TheHeadlessEvent::Frames(1)]);
+
+    // The item at content position 2015 is the 101st.
+    app.run_script(&[
+        TheHeadlessEvent::TouchDown(50.0, 15.0),
+        TheHeadlessEvent::TouchUp(50.0, 15.0),
+        TheHeadlessEvent::Frames(1),
+    ]);
+    let list = app.ui.get_list_layout("Assets").unwrap();
+    assert_eq!(list.selected_index(), Some(100));
+    assert_eq!(
+        list.selected().map(|id| id.uuid),
+        Some(Uuid::from_u128(101))
+    );
+
+    let mut ctx = TheContext::new(400, 300, 1.0);
+    assert!(list.select_item_at(COUNT as i32 - 1, &mut ctx, true));
+    assert_eq!(list.selected_index(), Some(COUNT - 1));
+    assert!(list.select_item(Uuid::from_u128(6), &mut ctx, false));
+    assert_eq!(list.selected_index(), Some(5));
+    assert!(!list.select_item_at(COUNT as i32, &mut ctx, false));
+    assert_eq!(list.selected(), None);
+
+    // The visible widgets follow the selection.
+    list.select_item_at(101, &mut ctx, false);
+    let states: Vec<TheWidgetState> = list.widgets().iter().map(|w| w.state()).collect();
+    assert_eq!(states[2], TheWidgetState::Selected);
+    assert_eq!(
+        states
+            .iter()
+            .filter(|state| **state == TheWidgetState::Selected)
+            .count(),
+        1
+    );
+}
@@ -0,0 +1,75 @@
+//! Tests for the additional windows of TheUI, driven without a window backend.
+
+use theframework::prelude::*;
+
+fn text_canvas(text: &str) -> TheCanvas {
+    let mut canvas = TheCanvas::new();
+    let mut widget = TheText::new(TheId::named(text));
+    widget.set_text(text.to_string());
+    canvas.set_widget(widget);
+    canvas
+}
+
+#[test]
+fn windows_have_their_own_canvas_and_size() {
+    let mut ctx = TheContext::new(300, 200, 1.0);
+    let mut ui = TheUI::new();
+    ui.init(&mut ctx);
+    let events = ui.add_state_listener("Test".to_string());
+
+    ui.canvas = text_canvas("Main");
+    ui.canvas.root = true;
+    ui.canvas.set_dim(TheDim::new(0, 0, 300, 200), &mut ctx);
+
+    let id = TheId::named("Palette");
+    ui.open_window(
+        id.clone(),
+        TheWindowSettings::new("Palette", 120, 80),
+        text_canvas("Palette Text"),
+    );
+
+    let new_windows = ui.take_new_windows();
+    assert_eq!(new_windows.len(), 1);
+    assert_eq!(new_windows[0].0.uuid, id.uuid);
+    assert!(ui.take_new_windows().is_empty());
+
+    // The palette draws into its own frame at its own size and scale.
+    assert!(ui.activate_window(Some(&id.uuid), &mut ctx));
+    assert_eq!(ctx.ui.window.as_ref().map(|w| w.uuid), Some(id.uuid));
+    assert_eq!((ctx.width, ctx.height), (120, 80));
+    ctx.set_scale_factor(2.0);
+    ui.canvas.set_dim(TheDim::new(0, 0, 120, 80), &mut ctx);
+
+    let mut frame = vec![0; ctx.physical_width() * ctx.physical_height() * 4];
+    ui.draw(&mut frame, &mut ctx);
+    assert_eq!(frame.len(), 240 * 160 * 4);
+    assert!(ui.get_widget("Palette Text").is_some());
+    assert!(ui.get_widget("Main").is_none());
+
+    // Shared state like the clipboard is visible in all windows.
+    ctx.ui.clipboard = Some(TheValue::Text("Copied".to_string()));
+
+    assert!(ui.activate_window(None, &mut ctx));
+    assert_eq!((ctx.width, ctx.height, ctx.scale_factor), (300, 200, 1.0));
+    assert!(ui.get_widget("Main").is_some());
+    assert!(ctx.ui.clipboard.is_some());
+    assert!(ctx.ui.window.is_none());
+
+    let window = ui.window_mut(&id.uuid).unwrap();
+    assert_eq!(window.size(), (120, 80));
+    assert!(window
+        .canvas
+        .get_widget(Some(&"Palette Text".to_string()), None)
+        .is_some());
+
+    // Closing the window reports it to the app and to the backend.
+    assert!(ui.close_window(&id.uuid, &mut ctx));
+    assert!(ui.windows().is_empty());
+    assert_eq!(ui.take_closed_windows().len(), 1);
+
+    ui.process_events(&mut ctx);
+    let closed = events
+        .try_iter()
+        .any(|event| matches!(event, TheEvent::WindowClosed(closed) if closed.uuid == id.uuid));
+    assert!(closed);
+}